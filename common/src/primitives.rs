@@ -592,6 +592,13 @@ impl Display for SymbolName {
     }
 }
 
+impl MaxEncodedLen for SymbolName {
+    /// Valid symbol names are at most `ASSET_SYMBOL_MAX_LENGTH` bytes long, see `is_valid`
+    fn max_encoded_len() -> usize {
+        codec::Compact(ASSET_SYMBOL_MAX_LENGTH as u32).encoded_size() + ASSET_SYMBOL_MAX_LENGTH
+    }
+}
+
 impl IsValid for SymbolName {
     /// Same as for AssetSymbol
     fn is_valid(&self) -> bool {
@@ -708,6 +715,7 @@ pub enum LiquiditySourceType {
     MockPool4,
     XSTPool,
     OrderBook,
+    StableSwapPool,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
@@ -1065,6 +1073,7 @@ mod tests {
 #[derive(
     Encode, Decode, PartialEq, Eq, Copy, Clone, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
 )]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PriceVariant {
     Buy,
     Sell,
@@ -1084,14 +1093,18 @@ impl PriceVariant {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Oracle {
     BandChainFeed,
+    /// Additional oracle feed registered in `oracle-proxy` under the given index
+    Feed(u8),
 }
 
 /// Information about received oracle symbol (price and last update time)
-#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Copy, Clone, PartialEq, Eq)]
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
 pub struct Rate {
     pub value: Balance,
     pub last_updated: u64,
     pub dynamic_fee: Fixed,
+    /// Oracles which contributed to the rate, empty if the rate was taken from a feed directly
+    pub sources: sp_std::vec::Vec<Oracle>,
 }
 
 #[derive(Encode, MaxEncodedLen, Default, TypeInfo)]
@@ -132,6 +132,17 @@ pub trait TradingPairSourceManager<DEXId, AssetId> {
         base_asset_id: AssetId,
         target_asset_id: AssetId,
     ) -> Result<(), DispatchError>;
+
+    /// Register trading pair of two assets, neither of which is a base asset of the DEX.
+    fn register_non_base_pair(
+        dex_id: DEXId,
+        base_asset_id: AssetId,
+        target_asset_id: AssetId,
+    ) -> Result<(), DispatchError>;
+
+    /// List assets which are paired with `asset_id` by trading pairs without the base assets
+    /// of the DEX.
+    fn list_asset_neighbours(dex_id: &DEXId, asset_id: &AssetId) -> Vec<AssetId>;
 }
 
 impl<DEXId, AssetId> TradingPairSourceManager<DEXId, AssetId> for () {
@@ -185,6 +196,18 @@ impl<DEXId, AssetId> TradingPairSourceManager<DEXId, AssetId> for () {
     ) -> Result<(), DispatchError> {
         Err(DispatchError::CannotLookup)
     }
+
+    fn register_non_base_pair(
+        _dex_id: DEXId,
+        _base_asset_id: AssetId,
+        _target_asset_id: AssetId,
+    ) -> Result<(), DispatchError> {
+        Err(DispatchError::CannotLookup)
+    }
+
+    fn list_asset_neighbours(_dex_id: &DEXId, _asset_id: &AssetId) -> Vec<AssetId> {
+        Vec::new()
+    }
 }
 
 /// Indicates that particular object can be used to perform exchanges.
@@ -251,6 +274,16 @@ pub trait LockedLiquiditySourcesManager<LiquiditySourceType> {
     fn append(liquidity_source_type: LiquiditySourceType);
 }
 
+impl<LiquiditySourceType> LockedLiquiditySourcesManager<LiquiditySourceType> for () {
+    fn get() -> Vec<LiquiditySourceType> {
+        Vec::new()
+    }
+
+    fn set(_liquidity_source_types: Vec<LiquiditySourceType>) {}
+
+    fn append(_liquidity_source_type: LiquiditySourceType) {}
+}
+
 /// Implements trading pair EnabledSources storage
 pub trait EnabledSourcesManager<DEXId, AssetId> {
     fn mutate_remove(dex_id: &DEXId, base_asset_id: &AssetId, target_asset_id: &AssetId);
@@ -696,6 +729,28 @@ impl<AssetId> GetPoolReserves<AssetId> for () {
     }
 }
 
+/// Trait to get time-weighted average prices (TWAP) of liquidity pools, which can't be
+/// manipulated within a single block as opposed to the spot prices.
+pub trait GetTwap<AssetId, Moment> {
+    /// Returns the time-weighted average amount of `output_asset` corresponding to a unit (1)
+    /// of `input_asset` over at least the last `period`.
+    fn get_twap(
+        input_asset: &AssetId,
+        output_asset: &AssetId,
+        period: Moment,
+    ) -> Result<Balance, DispatchError>;
+}
+
+impl<AssetId, Moment> GetTwap<AssetId, Moment> for () {
+    fn get_twap(
+        _input_asset: &AssetId,
+        _output_asset: &AssetId,
+        _period: Moment,
+    ) -> Result<Balance, DispatchError> {
+        Err(DispatchError::CannotLookup)
+    }
+}
+
 /// General trait for passing pswap amount burned information to required pallets.
 pub trait OnPswapBurned {
     /// Report amount and fractions of burned pswap at the moment of invocation.
@@ -1575,6 +1575,7 @@ fn testnet_genesis(
                 LiquiditySourceType::MulticollateralBondingCurvePool,
                 LiquiditySourceType::XSTPool,
                 LiquiditySourceType::OrderBook,
+                LiquiditySourceType::StableSwapPool,
             ]
             .into(),
         },
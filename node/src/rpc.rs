@@ -33,8 +33,9 @@
 use common::{ContentSource, Description, TradingPair};
 use framenode_runtime::opaque::Block;
 use framenode_runtime::{
-    eth_bridge, AccountId, AssetId, AssetName, AssetSymbol, Balance, BalancePrecision, DEXId,
-    FilterMode, Index, LiquiditySourceType, ResolveTime, Runtime, SwapVariant, Symbol,
+    eth_bridge, AccountId, AssetId, AssetName, AssetSymbol, Balance, BalancePrecision, BlockNumber,
+    DEXId, FilterMode, Index, LiquiditySourceType, Moment, ResolveTime, Runtime, SwapVariant,
+    Symbol,
 };
 use jsonrpsee::RpcModule;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -82,9 +83,12 @@ where
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
     C: Send + Sync + 'static,
     C::Api: beefy_light_client_rpc::BeefyLightClientRuntimeAPI<Block, beefy_light_client::BitField>,
+    C::Api: kensetsu_rpc::KensetsuRuntimeApi<Block, AccountId, AssetId>,
 {
     use beefy_light_client_rpc::{BeefyLightClientAPIServer, BeefyLightClientClient};
-    rpc.merge(BeefyLightClientClient::new(client).into_rpc())?;
+    use kensetsu_rpc::{KensetsuApiServer, KensetsuClient};
+    rpc.merge(BeefyLightClientClient::new(client.clone()).into_rpc())?;
+    rpc.merge(KensetsuClient::new(client).into_rpc())?;
     Ok(rpc)
 }
 
@@ -120,6 +124,16 @@ where
         SwapVariant,
     >,
     C::Api: oracle_proxy_rpc::OracleProxyRuntimeApi<Block, Symbol, ResolveTime>,
+    C::Api: price_tools_rpc::PriceToolsRuntimeApi<Block, AssetId>,
+    C::Api: xor_fee_rpc::XorFeeRuntimeApi<Block, AssetId>,
+    C::Api: order_book_rpc::OrderBookRuntimeApi<
+        Block,
+        framenode_runtime::order_book::OrderBookId<AssetId, DEXId>,
+        AccountId,
+        u128,
+        Moment,
+        BlockNumber,
+    >,
     C::Api: dex_manager_rpc::DEXManagerRuntimeAPI<Block, DEXId>,
     C::Api: trading_pair_rpc::TradingPairRuntimeAPI<
         Block,
@@ -191,12 +205,15 @@ where
     use liquidity_proxy_rpc::{LiquidityProxyAPIServer, LiquidityProxyClient};
     use mmr_rpc::{Mmr, MmrApiServer};
     use oracle_proxy_rpc::{OracleProxyApiServer, OracleProxyClient};
+    use order_book_rpc::{OrderBookApiServer, OrderBookClient};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+    use price_tools_rpc::{PriceToolsApiServer, PriceToolsClient};
     use pswap_distribution_rpc::{PswapDistributionAPIServer, PswapDistributionClient};
     use rewards_rpc::{RewardsAPIServer, RewardsClient};
     use substrate_frame_rpc_system::{System, SystemApiServer};
     use trading_pair_rpc::{TradingPairAPIServer, TradingPairClient};
     use vested_rewards_rpc::{VestedRewardsApiServer, VestedRewardsClient};
+    use xor_fee_rpc::{XorFeeApiServer, XorFeeClient};
 
     let mut io = RpcModule::new(());
     let FullDeps {
@@ -224,6 +241,9 @@ where
     io.merge(AssetsClient::new(client.clone()).into_rpc())?;
     io.merge(LiquidityProxyClient::new(client.clone()).into_rpc())?;
     io.merge(OracleProxyClient::new(client.clone()).into_rpc())?;
+    io.merge(PriceToolsClient::new(client.clone()).into_rpc())?;
+    io.merge(XorFeeClient::new(client.clone()).into_rpc())?;
+    io.merge(OrderBookClient::new(client.clone()).into_rpc())?;
     io.merge(EthBridgeRpc::new(client.clone()).into_rpc())?;
     io.merge(IrohaMigrationClient::new(client.clone()).into_rpc())?;
     io.merge(PswapDistributionClient::new(client.clone()).into_rpc())?;
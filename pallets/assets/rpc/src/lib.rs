@@ -94,6 +94,15 @@ pub trait AssetsAPI<
 
     #[method(name = "assets_getAssetInfo")]
     fn get_asset_info(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<OptionAssetInfo>;
+
+    #[method(name = "assets_allowance")]
+    fn allowance(
+        &self,
+        asset_id: AssetId,
+        owner: AccountId,
+        spender: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<OptionBalanceInfo>;
 }
 
 pub struct AssetsClient<C, B> {
@@ -256,4 +265,20 @@ where
         api.get_asset_info(&at, asset_id)
             .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
     }
+
+    fn allowance(
+        &self,
+        asset_id: AssetId,
+        owner: AccountId,
+        spender: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BalanceInfo<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.allowance(&at, asset_id, owner, spender)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
 }
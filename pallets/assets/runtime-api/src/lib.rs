@@ -139,6 +139,7 @@ pub struct AssetInfo<AssetId, AssetSymbol, AssetName, Precision, ContentSource,
 }
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait AssetsAPI<AccountId, AssetId, Balance, AssetSymbol, AssetName, Precision, ContentSource, Description> where
         AccountId: Codec,
         AssetId: Codec,
@@ -164,6 +165,10 @@ sp_api::decl_runtime_apis! {
         fn get_asset_info(asset_id: AssetId) -> Option<AssetInfo<AssetId, AssetSymbol, AssetName, Precision, ContentSource, Description>>;
 
         fn get_asset_content_src(asset_id: AssetId) -> Option<ContentSource>;
+
+        /// Amount of the asset `spender` can transfer from `owner` account,
+        /// `None` if there is no allowance or it has expired
+        fn allowance(asset_id: AssetId, owner: AccountId, spender: AccountId) -> Option<BalanceInfo<Balance>>;
     }
 }
 
@@ -76,6 +76,23 @@ fn add_assets<T: Config>(n: u32) -> Result<(), &'static str> {
     Ok(())
 }
 
+// Registers XOR owned by Alice with 1000 initial supply
+fn register_xor<T: Config>() {
+    let owner = alice::<T>();
+    frame_system::Pallet::<T>::inc_providers(&owner);
+    let _ = Assets::<T>::register_asset_id(
+        owner,
+        XOR.into(),
+        AssetSymbol(b"XOR".to_vec()),
+        AssetName(b"XOR".to_vec()),
+        DEFAULT_BALANCE_PRECISION,
+        1000_u32.into(),
+        true,
+        None,
+        None,
+    );
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     let events = frame_system::Pallet::<T>::events();
     let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -294,6 +311,68 @@ benchmarks! {
             )
         );
     }
+
+    approve {
+        register_xor::<T>();
+        let caller = alice::<T>();
+        let spender = bob::<T>();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        XOR.into(),
+        spender.clone(),
+        100_u32.into(),
+        Some(100_u32.into())
+    )
+    verify {
+        assert_last_event::<T>(Event::<T>::Approval(caller, spender, XOR.into(), 100_u32.into(), Some(100_u32.into())).into())
+    }
+
+    increase_allowance {
+        register_xor::<T>();
+        let caller = alice::<T>();
+        let spender = bob::<T>();
+        Assets::<T>::approve(RawOrigin::Signed(caller.clone()).into(), XOR.into(), spender.clone(), 100_u32.into(), Some(100_u32.into()))?;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        XOR.into(),
+        spender.clone(),
+        100_u32.into()
+    )
+    verify {
+        assert_last_event::<T>(Event::<T>::Approval(caller, spender, XOR.into(), 200_u32.into(), Some(100_u32.into())).into())
+    }
+
+    decrease_allowance {
+        register_xor::<T>();
+        let caller = alice::<T>();
+        let spender = bob::<T>();
+        Assets::<T>::approve(RawOrigin::Signed(caller.clone()).into(), XOR.into(), spender.clone(), 100_u32.into(), Some(100_u32.into()))?;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        XOR.into(),
+        spender.clone(),
+        50_u32.into()
+    )
+    verify {
+        assert_last_event::<T>(Event::<T>::Approval(caller, spender, XOR.into(), 50_u32.into(), Some(100_u32.into())).into())
+    }
+
+    transfer_approved {
+        register_xor::<T>();
+        let owner = alice::<T>();
+        let caller = bob::<T>();
+        frame_system::Pallet::<T>::inc_providers(&caller);
+        Assets::<T>::approve(RawOrigin::Signed(owner.clone()).into(), XOR.into(), caller.clone(), 200_u32.into(), Some(100_u32.into()))?;
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        XOR.into(),
+        owner.clone(),
+        caller.clone(),
+        100_u32.into()
+    )
+    verify {
+        assert_last_event::<T>(Event::<T>::ApprovedTransfer(caller.clone(), owner, caller, XOR.into(), 100_u32.into()).into())
+    }
 }
 
 #[cfg(test)]
@@ -313,6 +392,10 @@ mod tests {
             assert_ok!(Pallet::<Runtime>::test_benchmark_burn());
             assert_ok!(Pallet::<Runtime>::test_benchmark_update_balance());
             assert_ok!(Pallet::<Runtime>::test_benchmark_set_non_mintable());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_approve());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_increase_allowance());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_decrease_allowance());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_transfer_approved());
         });
     }
 }
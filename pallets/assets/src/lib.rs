@@ -39,6 +39,8 @@
 //! ### Dispatchable Functions
 //!
 //! - `register` - registers new asset by a given ID.
+//! - `approve` - allows another account to transfer the asset from the caller account.
+//! - `transfer_approved` - transfers the asset from another account within the allowance.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -157,6 +159,21 @@ pub enum AssetRecord<T: Config> {
     ),
 }
 
+/// Amount of the asset the spender is allowed to transfer from the owner account
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, Default, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Allowance<BlockNumber> {
+    pub amount: Balance,
+    /// The allowance can't be spent after this block
+    pub expiry: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> Allowance<BlockNumber> {
+    pub fn is_expired(&self, now: &BlockNumber) -> bool {
+        self.expiry.as_ref().map_or(false, |expiry| now > expiry)
+    }
+}
+
 pub trait GetTotalBalance<T: Config> {
     fn total_balance(asset_id: &T::AssetId, who: &T::AccountId) -> Result<Balance, DispatchError>;
 }
@@ -477,6 +494,130 @@ pub mod pallet {
             Self::deposit_event(Event::<T>::AssetUpdated(asset_id, new_symbol, new_name));
             Ok(().into())
         }
+
+        /// Allow `spender` to transfer up to `amount` of the asset from the caller account,
+        /// replacing the previous allowance. Zero `amount` removes the allowance.
+        ///
+        /// - `origin`: caller Account, owner of the transferred Asset amount,
+        /// - `asset_id`: Id of approved Asset,
+        /// - `spender`: Id of Account allowed to transfer the Asset,
+        /// - `amount`: approved Asset amount,
+        /// - `expiry`: the last block the allowance can be spent in, never expires if `None`.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve())]
+        pub fn approve(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            spender: T::AccountId,
+            amount: Balance,
+            expiry: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_asset_exists(&asset_id)?;
+            ensure!(owner != spender, Error::<T>::InvalidSpender);
+            if let Some(expiry) = expiry {
+                ensure!(
+                    expiry >= frame_system::Pallet::<T>::block_number(),
+                    Error::<T>::InvalidAllowanceExpiry
+                );
+            }
+            Self::set_allowance(&asset_id, &owner, &spender, Allowance { amount, expiry });
+            Ok(().into())
+        }
+
+        /// Increase the allowance of `spender` by `amount`, keeping its expiry.
+        ///
+        /// - `origin`: caller Account, owner of the transferred Asset amount,
+        /// - `asset_id`: Id of approved Asset,
+        /// - `spender`: Id of Account allowed to transfer the Asset,
+        /// - `amount`: Asset amount added to the allowance.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::increase_allowance())]
+        pub fn increase_allowance(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            spender: T::AccountId,
+            amount: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_asset_exists(&asset_id)?;
+            ensure!(owner != spender, Error::<T>::InvalidSpender);
+            let mut allowance = Self::allowance(asset_id, (&owner, &spender)).unwrap_or_default();
+            ensure!(
+                !allowance.is_expired(&frame_system::Pallet::<T>::block_number()),
+                Error::<T>::AllowanceExpired
+            );
+            allowance.amount = allowance
+                .amount
+                .checked_add(amount)
+                .ok_or(Error::<T>::Overflow)?;
+            Self::set_allowance(&asset_id, &owner, &spender, allowance);
+            Ok(().into())
+        }
+
+        /// Decrease the allowance of `spender` by `amount`, keeping its expiry.
+        ///
+        /// - `origin`: caller Account, owner of the transferred Asset amount,
+        /// - `asset_id`: Id of approved Asset,
+        /// - `spender`: Id of Account allowed to transfer the Asset,
+        /// - `amount`: Asset amount subtracted from the allowance.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::decrease_allowance())]
+        pub fn decrease_allowance(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            spender: T::AccountId,
+            amount: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let mut allowance = Self::allowance(asset_id, (&owner, &spender))
+                .ok_or(Error::<T>::InsufficientAllowance)?;
+            allowance.amount = allowance
+                .amount
+                .checked_sub(amount)
+                .ok_or(Error::<T>::InsufficientAllowance)?;
+            Self::set_allowance(&asset_id, &owner, &spender, allowance);
+            Ok(().into())
+        }
+
+        /// Transfer the asset from `owner` account within the allowance given to the caller.
+        ///
+        /// - `origin`: caller Account, spender of the allowance,
+        /// - `asset_id`: Id of transferred Asset,
+        /// - `owner`: Id of Account, from which Asset amount is withdrawn,
+        /// - `to`: Id of Account, to which Asset amount is deposited,
+        /// - `amount`: transferred Asset amount.
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_approved())]
+        pub fn transfer_approved(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            owner: T::AccountId,
+            to: T::AccountId,
+            amount: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let spender = ensure_signed(origin)?;
+            let mut allowance = Self::allowance(asset_id, (&owner, &spender))
+                .ok_or(Error::<T>::InsufficientAllowance)?;
+            ensure!(
+                !allowance.is_expired(&frame_system::Pallet::<T>::block_number()),
+                Error::<T>::AllowanceExpired
+            );
+            allowance.amount = allowance
+                .amount
+                .checked_sub(amount)
+                .ok_or(Error::<T>::InsufficientAllowance)?;
+            Self::transfer_from(&asset_id, &owner, &to, amount)?;
+            if allowance.amount.is_zero() {
+                Allowances::<T>::remove(asset_id, (&owner, &spender));
+            } else {
+                Allowances::<T>::insert(asset_id, (&owner, &spender), allowance);
+            }
+            Self::deposit_event(Event::ApprovedTransfer(
+                spender, owner, to, asset_id, amount,
+            ));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -494,6 +635,22 @@ pub mod pallet {
         AssetSetNonMintable(AssetIdOf<T>),
         /// Asset info has been updated
         AssetUpdated(AssetIdOf<T>, Option<AssetSymbol>, Option<AssetName>),
+        /// Allowance has been changed by the owner. [Owner Account, Spender Account, Asset Id, Allowed Amount, Expiry Block]
+        Approval(
+            AccountIdOf<T>,
+            AccountIdOf<T>,
+            AssetIdOf<T>,
+            Balance,
+            Option<BlockNumberFor<T>>,
+        ),
+        /// Asset amount has been transferred within the allowance. [Spender Account, Owner Account, To Account, Asset Id, Amount Transferred]
+        ApprovedTransfer(
+            AccountIdOf<T>,
+            AccountIdOf<T>,
+            AccountIdOf<T>,
+            AssetIdOf<T>,
+            Balance,
+        ),
     }
 
     #[pallet::error]
@@ -524,6 +681,14 @@ pub mod pallet {
         DeadAsset,
         /// Computation overflow.
         Overflow,
+        /// The allowance is not enough for the transfer or decrease.
+        InsufficientAllowance,
+        /// The allowance can't be spent anymore.
+        AllowanceExpired,
+        /// The expiry block of the allowance is in the past.
+        InvalidAllowanceExpiry,
+        /// Account can't approve its own assets to itself.
+        InvalidSpender,
     }
 
     /// Asset Id -> Owner Account Id
@@ -556,6 +721,19 @@ pub mod pallet {
     pub type AssetRecordAssetId<T: Config> =
         StorageMap<_, Twox64Concat, T::AssetId, AssetRecord<T>>;
 
+    /// Asset Id -> (Owner Account Id, Spender Account Id) -> Allowance
+    #[pallet::storage]
+    #[pallet::getter(fn allowance)]
+    pub type Allowances<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        (T::AccountId, T::AccountId),
+        Allowance<T::BlockNumber>,
+        OptionQuery,
+    >;
+
     #[allow(clippy::type_complexity)]
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -911,6 +1089,38 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    fn set_allowance(
+        asset_id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        allowance: Allowance<T::BlockNumber>,
+    ) {
+        if allowance.amount.is_zero() {
+            Allowances::<T>::remove(asset_id, (owner, spender));
+        } else {
+            Allowances::<T>::insert(asset_id, (owner, spender), allowance);
+        }
+        Self::deposit_event(Event::Approval(
+            owner.clone(),
+            spender.clone(),
+            *asset_id,
+            allowance.amount,
+            allowance.expiry,
+        ));
+    }
+
+    /// Returns the amount `spender` can transfer from `owner` account, `None` if there is
+    /// no allowance or it has expired
+    pub fn spendable_allowance(
+        asset_id: &T::AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+    ) -> Option<Balance> {
+        Self::allowance(asset_id, (owner, spender))
+            .filter(|allowance| !allowance.is_expired(&frame_system::Pallet::<T>::block_number()))
+            .map(|allowance| allowance.amount)
+    }
+
     pub fn list_registered_asset_ids() -> Vec<T::AssetId> {
         AssetInfos::<T>::iter().map(|(key, _)| key).collect()
     }
@@ -31,6 +31,7 @@
 use crate::mock::*;
 use crate::Error;
 use crate::Event;
+use crate::{Allowance, Allowances};
 use common::balance;
 use common::prelude::{AssetName, AssetSymbol, Balance};
 use common::DAI;
@@ -938,3 +939,258 @@ fn test_update_asset_info() {
         );
     });
 }
+
+const CHARLIE: AccountId = 3;
+
+fn register_xor_with_supply() {
+    assert_ok!(Assets::register_asset_id(
+        ALICE,
+        XOR,
+        AssetSymbol(b"XOR".to_vec()),
+        AssetName(b"SORA".to_vec()),
+        DEFAULT_BALANCE_PRECISION,
+        balance!(100),
+        true,
+        None,
+        None,
+    ));
+}
+
+#[test]
+fn should_transfer_approved_amount() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        register_xor_with_supply();
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(10),
+            None
+        ));
+        System::assert_last_event(
+            Event::<Runtime>::Approval(ALICE, BOB, XOR, balance!(10), None).into(),
+        );
+        assert_eq!(
+            Assets::spendable_allowance(&XOR, &ALICE, &BOB),
+            Some(balance!(10))
+        );
+        assert_eq!(Assets::spendable_allowance(&XOR, &BOB, &ALICE), None);
+
+        assert_ok!(Assets::transfer_approved(
+            RuntimeOrigin::signed(BOB),
+            XOR,
+            ALICE,
+            CHARLIE,
+            balance!(4)
+        ));
+        System::assert_last_event(
+            Event::<Runtime>::ApprovedTransfer(BOB, ALICE, CHARLIE, XOR, balance!(4)).into(),
+        );
+        assert_eq!(Assets::free_balance(&XOR, &ALICE).unwrap(), balance!(96));
+        assert_eq!(Assets::free_balance(&XOR, &CHARLIE).unwrap(), balance!(4));
+        assert_eq!(
+            Assets::spendable_allowance(&XOR, &ALICE, &BOB),
+            Some(balance!(6))
+        );
+
+        assert_noop!(
+            Assets::transfer_approved(
+                RuntimeOrigin::signed(CHARLIE),
+                XOR,
+                ALICE,
+                CHARLIE,
+                balance!(1)
+            ),
+            Error::<Runtime>::InsufficientAllowance
+        );
+        assert_noop!(
+            Assets::transfer_approved(RuntimeOrigin::signed(BOB), XOR, ALICE, BOB, balance!(7)),
+            Error::<Runtime>::InsufficientAllowance
+        );
+        assert_ok!(Assets::transfer_approved(
+            RuntimeOrigin::signed(BOB),
+            XOR,
+            ALICE,
+            BOB,
+            balance!(6)
+        ));
+        assert_eq!(Assets::free_balance(&XOR, &BOB).unwrap(), balance!(6));
+        assert!(!Allowances::<Runtime>::contains_key(XOR, (ALICE, BOB)));
+    });
+}
+
+#[test]
+fn should_not_transfer_approved_amount_above_balance() {
+    ExtBuilder::default().build().execute_with(|| {
+        register_xor_with_supply();
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(1000),
+            None
+        ));
+        assert!(Assets::transfer_approved(
+            RuntimeOrigin::signed(BOB),
+            XOR,
+            ALICE,
+            BOB,
+            balance!(200)
+        )
+        .is_err());
+        assert_eq!(Assets::free_balance(&XOR, &ALICE).unwrap(), balance!(100));
+        assert_eq!(
+            Assets::spendable_allowance(&XOR, &ALICE, &BOB),
+            Some(balance!(1000))
+        );
+    });
+}
+
+#[test]
+fn should_change_allowance() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        register_xor_with_supply();
+        assert_noop!(
+            Assets::approve(RuntimeOrigin::signed(ALICE), DAI, BOB, balance!(10), None),
+            Error::<Runtime>::AssetIdNotExists
+        );
+        assert_noop!(
+            Assets::approve(RuntimeOrigin::signed(ALICE), XOR, ALICE, balance!(10), None),
+            Error::<Runtime>::InvalidSpender
+        );
+        assert_ok!(Assets::increase_allowance(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(10)
+        ));
+        assert_ok!(Assets::increase_allowance(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(5)
+        ));
+        System::assert_last_event(
+            Event::<Runtime>::Approval(ALICE, BOB, XOR, balance!(15), None).into(),
+        );
+        assert_noop!(
+            Assets::decrease_allowance(RuntimeOrigin::signed(ALICE), XOR, BOB, balance!(20)),
+            Error::<Runtime>::InsufficientAllowance
+        );
+        assert_noop!(
+            Assets::decrease_allowance(RuntimeOrigin::signed(ALICE), XOR, CHARLIE, balance!(1)),
+            Error::<Runtime>::InsufficientAllowance
+        );
+        assert_ok!(Assets::decrease_allowance(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(5)
+        ));
+        assert_eq!(
+            Assets::allowance(XOR, (ALICE, BOB)),
+            Some(Allowance {
+                amount: balance!(10),
+                expiry: None
+            })
+        );
+        assert_ok!(Assets::decrease_allowance(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(10)
+        ));
+        assert_eq!(Assets::allowance(XOR, (ALICE, BOB)), None);
+
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            Balance::MAX,
+            None
+        ));
+        assert_noop!(
+            Assets::increase_allowance(RuntimeOrigin::signed(ALICE), XOR, BOB, 1),
+            Error::<Runtime>::Overflow
+        );
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            0,
+            None
+        ));
+        assert_eq!(Assets::allowance(XOR, (ALICE, BOB)), None);
+    });
+}
+
+#[test]
+fn should_expire_allowance() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(5);
+        register_xor_with_supply();
+        assert_noop!(
+            Assets::approve(
+                RuntimeOrigin::signed(ALICE),
+                XOR,
+                BOB,
+                balance!(10),
+                Some(4)
+            ),
+            Error::<Runtime>::InvalidAllowanceExpiry
+        );
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(10),
+            Some(6)
+        ));
+
+        System::set_block_number(6);
+        assert_ok!(Assets::transfer_approved(
+            RuntimeOrigin::signed(BOB),
+            XOR,
+            ALICE,
+            BOB,
+            balance!(1)
+        ));
+        assert_ok!(Assets::increase_allowance(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(1)
+        ));
+        assert_eq!(
+            Assets::allowance(XOR, (ALICE, BOB)),
+            Some(Allowance {
+                amount: balance!(10),
+                expiry: Some(6)
+            })
+        );
+
+        System::set_block_number(7);
+        assert_eq!(Assets::spendable_allowance(&XOR, &ALICE, &BOB), None);
+        assert_noop!(
+            Assets::transfer_approved(RuntimeOrigin::signed(BOB), XOR, ALICE, BOB, balance!(1)),
+            Error::<Runtime>::AllowanceExpired
+        );
+        assert_noop!(
+            Assets::increase_allowance(RuntimeOrigin::signed(ALICE), XOR, BOB, balance!(1)),
+            Error::<Runtime>::AllowanceExpired
+        );
+        assert_ok!(Assets::approve(
+            RuntimeOrigin::signed(ALICE),
+            XOR,
+            BOB,
+            balance!(10),
+            None
+        ));
+        assert_eq!(
+            Assets::spendable_allowance(&XOR, &ALICE, &BOB),
+            Some(balance!(10))
+        );
+    });
+}
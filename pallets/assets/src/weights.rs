@@ -68,6 +68,10 @@ pub trait WeightInfo {
 	fn update_balance() -> Weight;
 	fn set_non_mintable() -> Weight;
 	fn update_info() -> Weight;
+	fn approve() -> Weight;
+	fn increase_allowance() -> Weight;
+	fn decrease_allowance() -> Weight;
+	fn transfer_approved() -> Weight;
 }
 
 /// Weights for assets using the Substrate node and recommended hardware.
@@ -191,6 +195,56 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Allowances (r:0 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn approve() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1196`
+		//  Estimated: `3671`
+		// Minimum execution time: 31_274_000 picoseconds.
+		Weight::from_parts(32_108_000, 3671)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn increase_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1380`
+		//  Estimated: `7710`
+		// Minimum execution time: 37_845_000 picoseconds.
+		Weight::from_parts(38_912_000, 7710)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn decrease_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `364`
+		//  Estimated: `2839`
+		// Minimum execution time: 27_116_000 picoseconds.
+		Weight::from_parts(27_893_000, 2839)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn transfer_approved() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `549`
+		//  Estimated: `8045`
+		// Minimum execution time: 68_420_000 picoseconds.
+		Weight::from_parts(70_311_000, 8045)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -313,4 +367,54 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Allowances (r:0 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn approve() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1196`
+		//  Estimated: `3671`
+		// Minimum execution time: 31_274_000 picoseconds.
+		Weight::from_parts(32_108_000, 3671)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn increase_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1380`
+		//  Estimated: `7710`
+		// Minimum execution time: 37_845_000 picoseconds.
+		Weight::from_parts(38_912_000, 7710)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	fn decrease_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `364`
+		//  Estimated: `2839`
+		// Minimum execution time: 27_116_000 picoseconds.
+		Weight::from_parts(27_893_000, 2839)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Allowances (r:1 w:1)
+	/// Proof Skipped: Assets Allowances (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn transfer_approved() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `549`
+		//  Estimated: `8045`
+		// Minimum execution time: 68_420_000 picoseconds.
+		Weight::from_parts(70_311_000, 8045)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
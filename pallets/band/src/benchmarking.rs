@@ -35,64 +35,120 @@
 use super::*;
 
 use crate::Pallet as Band;
-use frame_benchmarking::benchmarks;
+use common::balance;
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
+use frame_support::traits::Currency;
 use frame_system::RawOrigin;
-use hex_literal::hex;
 
-fn relayer<T: Config>() -> T::AccountId {
-    let bytes = hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48");
-    T::AccountId::decode(&mut &bytes[..]).expect("Failed to decode relayer ID")
+fn relayers<T: Config>(n: u32) -> Vec<T::AccountId> {
+    (0..n).map(|i| account("relayer", i, 0)).collect()
 }
 
-fn symbol<T: Config>(sym: &str) -> T::Symbol {
-    let bytes = sym.encode();
+fn symbol<T: Config>(i: u32) -> T::Symbol {
+    let mut name = b"SYMBOL".to_vec();
+    for byte in i.to_be_bytes() {
+        name.push(b'A' + byte / 16);
+        name.push(b'A' + byte % 16);
+    }
+    let bytes = name.encode();
     T::Symbol::decode(&mut &bytes[..]).expect("Failed to decode symbol")
 }
 
+fn rates<T: Config>(n: u32, value: u64) -> BoundedVec<(T::Symbol, u64), T::MaxRelaySymbols> {
+    (0..n)
+        .map(|i| (symbol::<T>(i), value))
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("Failed to bound rates")
+}
+
+fn add_funded_relayers<T: Config>(relayers: &[T::AccountId]) -> Result<(), BenchmarkError> {
+    for relayer in relayers.iter() {
+        T::Currency::make_free_balance_be(relayer, balance!(100));
+    }
+    Band::<T>::add_relayers(RawOrigin::Root.into(), relayers.to_vec())?;
+    Ok(())
+}
+
+/// Add `quorum` relayers and submit `n` symbols by all of them except the returned one.
+///
+/// Submissions differ from each other, so the submission finalising the rounds detects
+/// the outliers and slashes their deposits.
+fn prepare_rounds<T: Config>(n: u32, quorum: u32) -> Result<T::AccountId, BenchmarkError> {
+    let relayers = relayers::<T>(quorum);
+    add_funded_relayers::<T>(&relayers)?;
+    Band::<T>::set_round_parameters(
+        RawOrigin::Root.into(),
+        RoundParameters {
+            quorum,
+            tolerance: fixed!(0),
+            round_period: 5u32.into(),
+            relayer_deposit: balance!(10),
+            outlier_slash: balance!(1),
+        },
+    )?;
+    for (i, relayer) in relayers.iter().enumerate().skip(1) {
+        Band::<T>::relay(
+            RawOrigin::Signed(relayer.clone()).into(),
+            rates::<T>(n, i as u64 + 1),
+            100,
+            1,
+        )?;
+    }
+    Ok(relayers[0].clone())
+}
+
 benchmarks! {
     relay {
-        let relayer = relayer::<T>();
-        let euro = symbol::<T>("EURO");
-        Band::<T>::add_relayers(RawOrigin::Root.into(), vec![relayer.clone()])?;
-    }: _(RawOrigin::Signed(relayer), vec![(euro.clone(), 2)].try_into().unwrap(), 100, 1)
+        let n in 1 .. T::MaxRelaySymbols::get();
+        let q in 1 .. T::MaxRelayers::get();
+        let relayer = prepare_rounds::<T>(n, q)?;
+    }: _(RawOrigin::Signed(relayer), rates::<T>(n, 1), 100, 1)
     verify {
-        assert_eq!(Band::<T>::rates(euro), Some(BandRate {
-            value: Band::<T>::raw_rate_into_balance(2).expect("failed to convert value to Balance"),
-            last_updated: 100,
-            request_id: 1,
-            dynamic_fee: fixed!(0),
-            last_updated_block: 1u32.into(),
-        }));
+        for i in 0..n {
+            assert!(Band::<T>::rates(symbol::<T>(i)).is_some());
+            assert!(Band::<T>::rounds(symbol::<T>(i)).is_none());
+        }
     }
 
     force_relay {
-        let relayer = relayer::<T>();
-        let euro = symbol::<T>("EURO");
-        Band::<T>::add_relayers(RawOrigin::Root.into(), vec![relayer.clone()])?;
-    }: _(RawOrigin::Signed(relayer), vec![(euro.clone(), 2)].try_into().unwrap(), 100, 1)
+        let n in 1 .. T::MaxRelaySymbols::get();
+        let q in 1 .. T::MaxRelayers::get();
+        let relayer = prepare_rounds::<T>(n, q)?;
+    }: _(RawOrigin::Signed(relayer), rates::<T>(n, 1), 100, 1)
     verify {
-        assert_eq!(Band::<T>::rates(euro), Some(BandRate {
-            value: Band::<T>::raw_rate_into_balance(2).expect("failed to convert value to Balance"),
-            last_updated: 100,
-            request_id: 1,
-            dynamic_fee: fixed!(0),
-            last_updated_block: 1u32.into(),
-        }));
+        for i in 0..n {
+            assert!(Band::<T>::rates(symbol::<T>(i)).is_some());
+            assert!(Band::<T>::rounds(symbol::<T>(i)).is_none());
+        }
     }
 
     add_relayers {
-        let relayer = relayer::<T>();
-    }: _(RawOrigin::Root, vec![relayer.clone()])
+        let n in 1 .. T::MaxRelayers::get();
+        let relayers = relayers::<T>(n);
+        for relayer in relayers.iter() {
+            T::Currency::make_free_balance_be(relayer, balance!(100));
+        }
+        Parameters::<T>::mutate(|parameters| parameters.relayer_deposit = balance!(10));
+    }: _(RawOrigin::Root, relayers.clone())
     verify {
-        assert!(Band::<T>::trusted_relayers().unwrap().contains(&relayer));
+        for relayer in relayers.iter() {
+            assert!(Band::<T>::trusted_relayers().unwrap().contains(relayer));
+            assert_eq!(Band::<T>::relayer_deposit(relayer), balance!(10));
+        }
     }
 
     remove_relayers {
-        let relayer = relayer::<T>();
-        Band::<T>::add_relayers(RawOrigin::Root.into(), vec![relayer.clone()])?;
-    }: _(RawOrigin::Root, vec![relayer.clone()])
+        let n in 1 .. T::MaxRelayers::get();
+        let relayers = relayers::<T>(n);
+        Parameters::<T>::mutate(|parameters| parameters.relayer_deposit = balance!(10));
+        add_funded_relayers::<T>(&relayers)?;
+    }: _(RawOrigin::Root, relayers.clone())
     verify {
-        assert!(!Band::<T>::trusted_relayers().unwrap().contains(&relayer));
+        for relayer in relayers.iter() {
+            assert!(!Band::<T>::trusted_relayers().unwrap().contains(relayer));
+            assert_eq!(Band::<T>::relayer_deposit(relayer), 0);
+        }
     }
 
     set_dynamic_fee_parameters {
@@ -100,5 +156,24 @@ benchmarks! {
     }: _(RawOrigin::Root, parameters)
     verify {}
 
+    set_round_parameters {
+        let n in 1 .. T::MaxRelayers::get();
+        let relayers = relayers::<T>(n);
+        add_funded_relayers::<T>(&relayers)?;
+        let parameters = RoundParameters {
+            quorum: n,
+            tolerance: fixed!(0.01),
+            round_period: 5u32.into(),
+            relayer_deposit: balance!(10),
+            outlier_slash: balance!(1),
+        };
+    }: _(RawOrigin::Root, parameters)
+    verify {
+        assert_eq!(Band::<T>::round_parameters(), parameters);
+        for relayer in relayers.iter() {
+            assert_eq!(Band::<T>::relayer_deposit(relayer), balance!(10));
+        }
+    }
+
     impl_benchmark_test_suite!(Band, crate::mock::new_test_ext(), crate::mock::Runtime);
 }
@@ -34,9 +34,10 @@ use common::prelude::FixedWrapper;
 use common::{fixed, fixed_wrapper, Balance, DataFeed, Fixed, OnNewSymbolsRelayed, Oracle, Rate};
 use fallible_iterator::FallibleIterator;
 use frame_support::pallet_prelude::*;
-use frame_support::traits::Time;
+use frame_support::sp_runtime::traits::Saturating;
+use frame_support::traits::{ReservableCurrency, Time};
 use frame_system::pallet_prelude::*;
-use sp_std::collections::btree_set::BTreeSet;
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
@@ -94,6 +95,67 @@ impl FeeCalculationParameters {
     }
 }
 
+/// Parameters of gathering relayers submissions into rounds
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Copy, Clone, PartialEq, Eq)]
+pub struct RoundParameters<BlockNumber> {
+    /// Number of distinct relayers submissions required to finalise a symbol rate.
+    /// Rates are finalised right away if the quorum is `1`.
+    pub quorum: u32,
+    /// Maximal relative deviation from the median for a submission not to be an outlier
+    pub tolerance: Fixed,
+    /// Number of blocks after which an unfinished round is discarded
+    pub round_period: BlockNumber,
+    /// Deposit reserved from a relayer when it's added to the trusted relayers
+    pub relayer_deposit: Balance,
+    /// Amount slashed from the relayer deposit for an outlier submission, zero disables slashing
+    pub outlier_slash: Balance,
+}
+
+impl<BlockNumber: PartialOrd + Default> RoundParameters<BlockNumber> {
+    pub fn validate<T: Config<I>, I: 'static>(&self) -> Result<(), DispatchError> {
+        ensure!(self.quorum > 0, Error::<T, I>::InvalidRoundParameters);
+        ensure!(
+            self.tolerance >= fixed!(0),
+            Error::<T, I>::InvalidRoundParameters
+        );
+        ensure!(
+            self.round_period > BlockNumber::default(),
+            Error::<T, I>::InvalidRoundParameters
+        );
+        ensure!(
+            self.outlier_slash <= self.relayer_deposit,
+            Error::<T, I>::InvalidRoundParameters
+        );
+        let relayers_count = Pallet::<T, I>::trusted_relayers()
+            .map(|relayers| relayers.len() as u32)
+            .unwrap_or_default();
+        // a single relayer is enough to finalise the round while there are no relayers yet
+        ensure!(
+            self.quorum <= relayers_count.max(1),
+            Error::<T, I>::InvalidRoundParameters
+        );
+        Ok(())
+    }
+}
+
+/// Rate submitted by a relayer
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Copy, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Rate value in USD.
+    pub value: Balance,
+    /// Resolve time of the rate on *BandChain*.
+    pub resolve_time: u64,
+    /// Request identifier in the *Band* protocol.
+    pub request_id: u64,
+}
+
+/// Submissions gathered for a symbol since the round start
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct Round<AccountId, BlockNumber> {
+    pub started: BlockNumber,
+    pub submissions: BTreeMap<AccountId, Submission>,
+}
+
 /// Symbol rate
 #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Copy, Clone, PartialEq, Eq)]
 pub struct BandRate<BlockNumber> {
@@ -116,6 +178,7 @@ impl<BlockNumber> From<BandRate<BlockNumber>> for Rate {
             value: value.value,
             last_updated: value.last_updated,
             dynamic_fee: value.dynamic_fee,
+            sources: Vec::new(),
         }
     }
 }
@@ -208,10 +271,17 @@ pub mod pallet {
         /// Maximum number of symbols that can be relayed within a single call.
         #[pallet::constant]
         type MaxRelaySymbols: Get<u32>;
+        /// Maximum number of trusted relayers, bounds the quorum of the rounds.
+        #[pallet::constant]
+        type MaxRelayers: Get<u32>;
         /// Time used for checking if rate expired
         type Time: Time;
         /// Hook which is being executed when some symbol must be disabled
         type OnSymbolDisabledHook: OnSymbolDisabled<Self::Symbol>;
+        /// Oracle variant under which this instance is known to `OnNewSymbolsRelayedHook`
+        type OracleVariant: Get<Oracle>;
+        /// Currency used for relayers deposits
+        type Currency: ReservableCurrency<Self::AccountId, Balance = Balance>;
     }
 
     #[pallet::storage]
@@ -250,6 +320,40 @@ pub mod pallet {
     pub type DynamicFeeParameters<T: Config<I>, I: 'static = ()> =
         StorageValue<_, FeeCalculationParameters, ValueQuery, DefaultDynamicFeeParameters<T, I>>;
 
+    #[pallet::type_value]
+    pub fn DefaultRoundParameters<T: Config<I>, I: 'static>() -> RoundParameters<T::BlockNumber> {
+        RoundParameters {
+            quorum: 1,
+            tolerance: fixed!(0.05),
+            round_period: 10u32.into(),
+            relayer_deposit: 0,
+            outlier_slash: 0,
+        }
+    }
+
+    #[pallet::storage]
+    #[pallet::getter(fn round_parameters)]
+    pub type Parameters<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, RoundParameters<T::BlockNumber>, ValueQuery, DefaultRoundParameters<T, I>>;
+
+    /// Unfinished rounds of the symbols, only used if the quorum is greater than `1`
+    #[pallet::storage]
+    #[pallet::getter(fn rounds)]
+    pub type Rounds<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::Symbol, Round<T::AccountId, T::BlockNumber>>;
+
+    /// Number of outlier submissions of the relayers
+    #[pallet::storage]
+    #[pallet::getter(fn outliers_count)]
+    pub type OutliersCount<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Deposits currently reserved from the relayers
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_deposit)]
+    pub type RelayerDeposits<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -259,6 +363,12 @@ pub mod pallet {
         RelayersAdded(Vec<T::AccountId>),
         /// Relayer accounts were removed from trusted list. [relayers]
         RelayersRemoved(Vec<T::AccountId>),
+        /// Round of the symbol reached the quorum and was finalised at the median rate. [symbol, rate, submissions]
+        RoundFinalized(T::Symbol, Balance, u32),
+        /// Relayer submission deviates from the median more than allowed. [symbol, relayer, submitted rate, median rate, slashed amount]
+        OutlierDetected(T::Symbol, T::AccountId, Balance, Balance, Balance),
+        /// Round parameters were updated. [parameters]
+        RoundParametersUpdated(RoundParameters<T::BlockNumber>),
     }
 
     #[pallet::error]
@@ -279,6 +389,12 @@ pub mod pallet {
         DynamicFeeCalculationError,
         /// Dynamic fee parameters are invalid,
         InvalidDynamicFeeParameters,
+        /// Round parameters are invalid.
+        InvalidRoundParameters,
+        /// Number of trusted relayers would exceed the maximum.
+        TooManyRelayers,
+        /// Number of trusted relayers would be lower than the quorum.
+        NotEnoughRelayers,
     }
 
     #[pallet::hooks]
@@ -309,21 +425,29 @@ pub mod pallet {
         ///
         /// If `rates` contains duplicated symbols, then the last rate will be stored.
         ///
+        /// If the quorum is greater than `1`, rates are gathered into the symbol rounds and stored
+        /// once enough relayers submitted them. The median of the submissions is stored then.
+        ///
         /// - `origin`: the relayer account on whose behalf the transaction is being executed,
         /// - `rates`: symbols with rates in USD represented as fixed point with precision = 9,
         /// - `resolve_time`: symbols which rates are provided,
         /// - `request_id`: id of the request sent to the *BandChain* to retrieve this data.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::relay())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::relay(rates.len() as u32, T::MaxRelayers::get()))]
         pub fn relay(
             origin: OriginFor<T>,
             rates: BoundedVec<(T::Symbol, u64), T::MaxRelaySymbols>,
             resolve_time: u64,
             request_id: u64,
         ) -> DispatchResultWithPostInfo {
-            Self::ensure_relayer(origin)?;
+            let relayer = Self::ensure_relayer(origin)?;
+            let weight = <T as Config<I>>::WeightInfo::relay(
+                rates.len() as u32,
+                Self::round_parameters().quorum,
+            );
 
             let symbol_rates = Self::update_rates(
+                &relayer,
                 rates,
                 resolve_time,
                 request_id,
@@ -343,29 +467,35 @@ pub mod pallet {
             )?;
 
             Self::deposit_event(Event::SymbolsRelayed(symbol_rates));
-            Ok(().into())
+            Ok(Some(weight).into())
         }
 
         /// Similar to [`relay()`] but without the resolve time guard.
         ///
         /// Should be used in emergency situations i.e. then previous value was
-        /// relayed by a faulty/malicious actor.
+        /// relayed by a faulty/malicious actor. Still requires the quorum of submissions, round is
+        /// finalised without the resolve time guard if the quorum is reached by this call.
         ///
         /// - `origin`: the relayer account on whose behalf the transaction is being executed,
         /// - `rates`: symbols with rates in USD represented as fixed point with precision = 9,
         /// - `resolve_time`: symbols which rates are provided,
         /// - `request_id`: id of the request sent to the *BandChain* to retrieve this data.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::force_relay())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_relay(rates.len() as u32, T::MaxRelayers::get()))]
         pub fn force_relay(
             origin: OriginFor<T>,
             rates: BoundedVec<(T::Symbol, u64), T::MaxRelaySymbols>,
             resolve_time: u64,
             request_id: u64,
         ) -> DispatchResultWithPostInfo {
-            Self::ensure_relayer(origin)?;
+            let relayer = Self::ensure_relayer(origin)?;
+            let weight = <T as Config<I>>::WeightInfo::force_relay(
+                rates.len() as u32,
+                Self::round_parameters().quorum,
+            );
 
             let symbol_rates: Vec<_> = Self::update_rates(
+                &relayer,
                 rates,
                 resolve_time,
                 request_id,
@@ -388,19 +518,19 @@ pub mod pallet {
             )?;
 
             Self::deposit_event(Event::SymbolsRelayed(symbol_rates));
-            Ok(().into())
+            Ok(Some(weight).into())
         }
 
         /// Add `account_ids` to the list of trusted relayers.
         ///
         /// Ignores repeated accounts in `account_ids`.
         /// If one of account is already a trusted relayer an [`Error::AlreadyATrustedRelayer`] will
-        /// be returned.
+        /// be returned. The total number of relayers is limited by `MaxRelayers`.
         ///
         /// - `origin`: the sudo account on whose behalf the transaction is being executed,
         /// - `account_ids`: list of new trusted relayers to add.
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::add_relayers())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::add_relayers(account_ids.len() as u32))]
         pub fn add_relayers(
             origin: OriginFor<T>,
             account_ids: Vec<T::AccountId>,
@@ -408,24 +538,42 @@ pub mod pallet {
             ensure_root(origin)?;
 
             let added_accounts =
-                TrustedRelayers::<T, I>::mutate(|option_relayers| match option_relayers {
-                    Some(relayers) => {
-                        let to_add = BTreeSet::from_iter(account_ids);
-
-                        if relayers.is_disjoint(&to_add) {
+                TrustedRelayers::<T, I>::mutate(|option_relayers| -> Result<_, Error<T, I>> {
+                    match option_relayers {
+                        Some(relayers) => {
+                            let to_add = BTreeSet::from_iter(account_ids);
+
+                            ensure!(
+                                relayers.is_disjoint(&to_add),
+                                Error::<T, I>::AlreadyATrustedRelayer
+                            );
+                            ensure!(
+                                relayers.len() + to_add.len() <= T::MaxRelayers::get() as usize,
+                                Error::<T, I>::TooManyRelayers
+                            );
                             relayers.append(&mut to_add.clone());
                             Ok(to_add)
-                        } else {
-                            Err(Error::<T, I>::AlreadyATrustedRelayer)
                         }
-                    }
-                    None => {
-                        let to_add = BTreeSet::from_iter(account_ids);
-                        let _ = option_relayers.insert(to_add.clone());
-                        Ok(to_add)
+                        None => {
+                            let to_add = BTreeSet::from_iter(account_ids);
+                            ensure!(
+                                to_add.len() <= T::MaxRelayers::get() as usize,
+                                Error::<T, I>::TooManyRelayers
+                            );
+                            let _ = option_relayers.insert(to_add.clone());
+                            Ok(to_add)
+                        }
                     }
                 })?;
 
+            let deposit = Self::round_parameters().relayer_deposit;
+            if deposit > 0 {
+                for account in added_accounts.iter() {
+                    T::Currency::reserve(account, deposit)?;
+                    RelayerDeposits::<T, I>::insert(account, deposit);
+                }
+            }
+
             Self::deposit_event(Event::RelayersAdded(added_accounts.into_iter().collect()));
             Ok(().into())
         }
@@ -434,12 +582,12 @@ pub mod pallet {
         ///
         /// Ignores repeated accounts in `account_ids`.
         /// If one of account is not a trusted relayer an [`Error::AlreadyATrustedRelayer`] will
-        /// be returned.
+        /// be returned. Relayers can't be removed if less of them than the quorum would remain.
         ///
         /// - `origin`: the sudo account on whose behalf the transaction is being executed,
         /// - `account_ids`: list of relayers to remove.
         #[pallet::call_index(3)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::remove_relayers())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::remove_relayers(account_ids.len() as u32))]
         pub fn remove_relayers(
             origin: OriginFor<T>,
             account_ids: Vec<T::AccountId>,
@@ -450,14 +598,16 @@ pub mod pallet {
                 TrustedRelayers::<T, I>::mutate(|option_relayers| match option_relayers {
                     Some(relayers) => {
                         let to_remove = BTreeSet::from_iter(account_ids);
-                        if to_remove.is_subset(relayers) {
-                            for account in &to_remove {
-                                relayers.remove(account);
-                            }
-                            Ok(to_remove)
-                        } else {
-                            Err(Error::<T, I>::NoSuchRelayer)
+                        ensure!(to_remove.is_subset(relayers), Error::<T, I>::NoSuchRelayer);
+                        let quorum = Self::round_parameters().quorum as usize;
+                        ensure!(
+                            quorum <= 1 || relayers.len() - to_remove.len() >= quorum,
+                            Error::<T, I>::NotEnoughRelayers
+                        );
+                        for account in &to_remove {
+                            relayers.remove(account);
                         }
+                        Ok(to_remove)
                     }
                     None => {
                         let _ = option_relayers.insert(BTreeSet::new());
@@ -469,6 +619,13 @@ pub mod pallet {
                     }
                 })?;
 
+            for account in removed_accounts.iter() {
+                let deposit = RelayerDeposits::<T, I>::take(account);
+                if deposit > 0 {
+                    T::Currency::unreserve(account, deposit);
+                }
+            }
+
             Self::deposit_event(Event::RelayersRemoved(
                 removed_accounts.into_iter().collect(),
             ));
@@ -486,11 +643,44 @@ pub mod pallet {
             DynamicFeeParameters::<T, I>::put(fee_parameters);
             Ok(().into())
         }
+
+        /// Set parameters of gathering relayers submissions into rounds.
+        ///
+        /// The quorum can't exceed the number of trusted relayers. If the relayer deposit is
+        /// changed, deposits of the current relayers are brought to the new amount by reserving
+        /// or unreserving the difference. Unfinished rounds are finalised using new parameters.
+        ///
+        /// - `origin`: the sudo account on whose behalf the transaction is being executed,
+        /// - `parameters`: new round parameters.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_round_parameters(T::MaxRelayers::get()))]
+        pub fn set_round_parameters(
+            origin: OriginFor<T>,
+            parameters: RoundParameters<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            parameters.validate::<T, I>()?;
+
+            let mut updated_deposits = 0;
+            if parameters.relayer_deposit != Self::round_parameters().relayer_deposit {
+                for account in Self::trusted_relayers().unwrap_or_default() {
+                    Self::update_relayer_deposit(&account, parameters.relayer_deposit)?;
+                    updated_deposits += 1;
+                }
+            }
+
+            Parameters::<T, I>::put(parameters);
+            Self::deposit_event(Event::RoundParametersUpdated(parameters));
+            Ok(Some(<T as Config<I>>::WeightInfo::set_round_parameters(
+                updated_deposits,
+            ))
+            .into())
+        }
     }
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
-    fn ensure_relayer(origin: OriginFor<T>) -> DispatchResult {
+    fn ensure_relayer(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
         let account_id = ensure_signed(origin)?;
 
         Self::trusted_relayers()
@@ -503,13 +693,35 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                     None
                 }
             })
+            .map(|_| account_id)
             .ok_or_else(|| Error::<T, I>::UnauthorizedRelayer.into())
     }
 
+    /// Reserve or unreserve the difference between the current deposit of `relayer` and
+    /// `deposit`.
+    fn update_relayer_deposit(relayer: &T::AccountId, deposit: Balance) -> DispatchResult {
+        let current_deposit = Self::relayer_deposit(relayer);
+        if deposit > current_deposit {
+            T::Currency::reserve(relayer, deposit - current_deposit)?;
+        } else if deposit < current_deposit {
+            T::Currency::unreserve(relayer, current_deposit - deposit);
+        }
+        if deposit > 0 {
+            RelayerDeposits::<T, I>::insert(relayer, deposit);
+        } else {
+            RelayerDeposits::<T, I>::remove(relayer);
+        }
+        Ok(())
+    }
+
     /// Update rates in the storage with the new ones.
     ///
+    /// If the quorum is greater than `1`, rates are submitted to the symbol rounds and only
+    /// the rates of finalised rounds are updated and returned.
+    ///
     /// `f` - mutation function which defines the way values should be updated.
     fn update_rates(
+        relayer: &T::AccountId,
         rates: BoundedVec<(T::Symbol, u64), T::MaxRelaySymbols>,
         resolve_time: u64,
         request_id: u64,
@@ -528,14 +740,41 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             ))
             .collect()?;
         let now = frame_system::Pallet::<T>::block_number();
+        let parameters = Self::round_parameters();
+        let finalized_rates: Vec<(T::Symbol, Submission)> = if parameters.quorum > 1 {
+            let mut finalized_rates = Vec::new();
+            for (symbol, value) in converted_rates {
+                let submission = Submission {
+                    value,
+                    resolve_time,
+                    request_id,
+                };
+                if let Some(median) = Self::submit(relayer, &symbol, submission, &parameters, now) {
+                    finalized_rates.push((symbol, median));
+                }
+            }
+            finalized_rates
+        } else {
+            converted_rates
+                .into_iter()
+                .map(|(symbol, value)| {
+                    let submission = Submission {
+                        value,
+                        resolve_time,
+                        request_id,
+                    };
+                    (symbol, submission)
+                })
+                .collect()
+        };
         let new_symbols =
-            fallible_iterator::convert(converted_rates.iter().map(Ok::<_, DispatchError>)).fold(
+            fallible_iterator::convert(finalized_rates.iter().map(Ok::<_, DispatchError>)).fold(
                 BTreeSet::new(),
-                |mut new_symbols_acc, (symbol, rate_value)| {
+                |mut new_symbols_acc, (symbol, submission)| {
                     let new_rate = BandRate {
-                        value: *rate_value,
-                        last_updated: resolve_time,
-                        request_id,
+                        value: submission.value,
+                        last_updated: submission.resolve_time,
+                        request_id: submission.request_id,
                         dynamic_fee: fixed!(0),
                         last_updated_block: now,
                     };
@@ -549,9 +788,105 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                 },
             )?;
 
-        T::OnNewSymbolsRelayedHook::on_new_symbols_relayed(Oracle::BandChainFeed, new_symbols)?;
+        T::OnNewSymbolsRelayedHook::on_new_symbols_relayed(T::OracleVariant::get(), new_symbols)?;
+
+        Ok(finalized_rates
+            .into_iter()
+            .map(|(symbol, submission)| (symbol, submission.value))
+            .collect())
+    }
+
+    /// Add `submission` of `relayer` to the current round of `symbol`, a new round is started
+    /// if the current one is older than the round period.
+    ///
+    /// Returns the median submission if the round reached the quorum and was finalised.
+    fn submit(
+        relayer: &T::AccountId,
+        symbol: &T::Symbol,
+        submission: Submission,
+        parameters: &RoundParameters<T::BlockNumber>,
+        now: T::BlockNumber,
+    ) -> Option<Submission> {
+        let mut round = Rounds::<T, I>::get(symbol)
+            .filter(|round| now < round.started.saturating_add(parameters.round_period))
+            .unwrap_or_else(|| Round {
+                started: now,
+                submissions: BTreeMap::new(),
+            });
+        round.submissions.insert(relayer.clone(), submission);
+
+        let submissions_count = round.submissions.len() as u32;
+        if submissions_count < parameters.quorum {
+            Rounds::<T, I>::insert(symbol, round);
+            return None;
+        }
+        Rounds::<T, I>::remove(symbol);
+
+        let median = Self::median_submission(round.submissions.values().copied().collect());
+        for (relayer, submission) in round.submissions.iter() {
+            Self::check_outlier(symbol, relayer, submission.value, median.value, parameters);
+        }
+        Self::deposit_event(Event::RoundFinalized(
+            symbol.clone(),
+            median.value,
+            submissions_count,
+        ));
+        Some(median)
+    }
 
-        Ok(converted_rates)
+    /// Median of non-empty `submissions`. Mean of the two middle values is taken as the rate
+    /// value for even number of submissions, resolve time is the lower median of resolve times.
+    fn median_submission(mut submissions: Vec<Submission>) -> Submission {
+        let middle = (submissions.len() - 1) / 2;
+        submissions.sort_by_key(|submission| submission.resolve_time);
+        let resolve_time = submissions[middle].resolve_time;
+        submissions.sort_by_key(|submission| submission.value);
+        let lower = submissions[middle];
+        let value = if submissions.len() % 2 == 0 {
+            let upper = submissions[middle + 1].value;
+            lower.value / 2 + upper / 2 + (lower.value % 2 + upper % 2) / 2
+        } else {
+            lower.value
+        };
+        Submission {
+            value,
+            resolve_time,
+            request_id: lower.request_id,
+        }
+    }
+
+    /// Track `relayer` as an outlier and slash its deposit if `value` deviates from `median`
+    /// more than the tolerance allows.
+    fn check_outlier(
+        symbol: &T::Symbol,
+        relayer: &T::AccountId,
+        value: Balance,
+        median: Balance,
+        parameters: &RoundParameters<T::BlockNumber>,
+    ) {
+        let deviation = FixedWrapper::from(value.max(median) - value.min(median));
+        let max_deviation = FixedWrapper::from(median) * FixedWrapper::from(parameters.tolerance);
+        if deviation <= max_deviation {
+            return;
+        }
+
+        OutliersCount::<T, I>::mutate(relayer, |count| *count = count.saturating_add(1));
+        let deposit = Self::relayer_deposit(relayer);
+        let to_slash = parameters.outlier_slash.min(deposit);
+        let mut slashed = 0;
+        if to_slash > 0 {
+            // slashed imbalance is dropped, so the slashed amount is burned
+            let (_, not_slashed) = T::Currency::slash_reserved(relayer, to_slash);
+            slashed = to_slash - not_slashed;
+            RelayerDeposits::<T, I>::insert(relayer, deposit - slashed);
+        }
+        Self::deposit_event(Event::OutlierDetected(
+            symbol.clone(),
+            relayer.clone(),
+            value,
+            median,
+            slashed,
+        ));
     }
 
     pub fn raw_rate_into_balance(raw_rate: u64) -> Result<Balance, DispatchError> {
@@ -49,6 +49,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system,
+        Balances: pallet_balances,
         Band: band,
         OracleProxy: oracle_proxy,
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
@@ -58,7 +59,9 @@ frame_support::construct_runtime!(
 frame_support::parameter_types! {
     pub const GetRateStalePeriod: Moment = 60*5*1000; // 5 minutes
     pub const GetRateStaleBlockPeriod: u64 = 600;
+    pub const GetBandOracleVariant: common::Oracle = common::Oracle::BandChainFeed;
     pub const MinimumPeriod: u64 = 5;
+    pub const ExistentialDeposit: common::Balance = 1;
 }
 
 impl system::Config for Runtime {
@@ -79,7 +82,7 @@ impl system::Config for Runtime {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<common::Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -88,6 +91,18 @@ impl system::Config for Runtime {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Runtime {
+    type Balance = common::Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+}
+
 impl pallet_timestamp::Config for Runtime {
     type Moment = Moment;
     type OnTimestampSet = ();
@@ -104,7 +119,10 @@ impl Config for Runtime {
     type GetBandRateStalePeriod = GetRateStalePeriod;
     type OnSymbolDisabledHook = ();
     type GetBandRateStaleBlockPeriod = GetRateStaleBlockPeriod;
+    type OracleVariant = GetBandOracleVariant;
+    type Currency = Balances;
     type MaxRelaySymbols = frame_support::traits::ConstU32<100>;
+    type MaxRelayers = frame_support::traits::ConstU32<100>;
 }
 
 impl oracle_proxy::Config for Runtime {
@@ -112,6 +130,8 @@ impl oracle_proxy::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type BandChainOracle = crate::Pallet<Runtime>;
+    type AdditionalFeeds = ();
+    type Time = Timestamp;
 }
 
 // Build genesis storage according to the mock runtime.
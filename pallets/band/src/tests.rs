@@ -31,13 +31,16 @@
 use codec::alloc::collections::HashSet;
 use common::{fixed, DataFeed, Rate};
 use common::{prelude::FixedWrapper, Balance, Fixed};
-use frame_support::traits::Hooks;
-use frame_support::{assert_noop, error::BadOrigin};
+use frame_support::traits::{Currency, Get, Hooks, ReservableCurrency};
+use frame_support::{assert_noop, assert_ok, error::BadOrigin};
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::TryCollect;
 use sp_std::collections::btree_set::BTreeSet;
 
-use crate::{mock::*, BandRate, Error, FeeCalculationParameters, SymbolCheckBlock};
+use crate::{
+    mock::*, BandRate, Error, FeeCalculationParameters, OutliersCount, RoundParameters,
+    SymbolCheckBlock,
+};
 
 pub fn band_rate_into_balance(rate: u64) -> Balance {
     let fixed = Fixed::from_bits(rate as i128 * super::RATE_MULTIPLIER);
@@ -644,3 +647,328 @@ fn should_calculate_dynamic_fee() {
         assert_eq!(rate_d.dynamic_fee, fixed!(1));
     })
 }
+
+fn round_parameters(quorum: u32) -> RoundParameters<BlockNumberFor<Runtime>> {
+    RoundParameters {
+        quorum,
+        tolerance: fixed!(0.1),
+        round_period: 5,
+        relayer_deposit: 0,
+        outlier_slash: 0,
+    }
+}
+
+fn relay_usd(relayer: u64, rate: u64) {
+    Band::relay(
+        RuntimeOrigin::signed(relayer),
+        vec![("USD".to_owned(), rate)].try_into().unwrap(),
+        100,
+        relayer,
+    )
+    .expect("Failed to relay rates");
+}
+
+#[test]
+fn set_round_parameters_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let parameters = round_parameters(3);
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2, 3]).expect("Failed to add relayers");
+
+        assert_noop!(
+            Band::set_round_parameters(RuntimeOrigin::signed(1), parameters),
+            BadOrigin
+        );
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            parameters
+        ));
+        assert_eq!(Band::round_parameters(), parameters);
+        System::assert_last_event(
+            crate::Event::<Runtime>::RoundParametersUpdated(parameters).into(),
+        );
+
+        for invalid in [
+            RoundParameters {
+                quorum: 0,
+                ..parameters
+            },
+            RoundParameters {
+                quorum: 4,
+                ..parameters
+            },
+            RoundParameters {
+                tolerance: fixed!(-0.1),
+                ..parameters
+            },
+            RoundParameters {
+                round_period: 0,
+                ..parameters
+            },
+            RoundParameters {
+                outlier_slash: 1,
+                ..parameters
+            },
+        ] {
+            assert_noop!(
+                Band::set_round_parameters(RuntimeOrigin::root(), invalid),
+                Error::<Runtime>::InvalidRoundParameters
+            );
+        }
+    });
+}
+
+#[test]
+fn round_should_be_finalized_at_median_on_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2, 3]).expect("Failed to add relayers");
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            round_parameters(3)
+        ));
+        let usd = "USD".to_owned();
+
+        relay_usd(1, 12);
+        relay_usd(2, 10);
+        // repeated submission of the same relayer replaces the previous one
+        relay_usd(2, 11);
+        assert_eq!(Band::rates(&usd), None);
+        assert_eq!(Band::rounds(&usd).unwrap().submissions.len(), 2);
+
+        relay_usd(3, 10);
+        assert_eq!(Band::rounds(&usd), None);
+        assert_eq!(
+            Band::rates(&usd),
+            Some(BandRate {
+                value: band_rate_into_balance(11),
+                last_updated: 100,
+                request_id: 2,
+                dynamic_fee: fixed!(0),
+                last_updated_block: 1,
+            })
+        );
+        System::assert_has_event(
+            crate::Event::<Runtime>::RoundFinalized(usd.clone(), band_rate_into_balance(11), 3)
+                .into(),
+        );
+        System::assert_last_event(
+            crate::Event::<Runtime>::SymbolsRelayed(vec![(usd, band_rate_into_balance(11))]).into(),
+        );
+        for relayer in [1, 2, 3] {
+            assert_eq!(OutliersCount::<Runtime>::get(relayer), 0);
+        }
+    });
+}
+
+#[test]
+fn round_with_even_submissions_should_be_finalized_at_mean_of_middle_rates() {
+    new_test_ext().execute_with(|| {
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2]).expect("Failed to add relayers");
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            round_parameters(2)
+        ));
+
+        relay_usd(1, 10);
+        relay_usd(2, 11);
+        assert_eq!(
+            Band::rates(&"USD".to_owned()).map(|rate| rate.value),
+            Some(band_rate_into_balance(10) / 2 + band_rate_into_balance(11) / 2)
+        );
+    });
+}
+
+#[test]
+fn outdated_round_should_be_discarded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2]).expect("Failed to add relayers");
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            round_parameters(2)
+        ));
+        let usd = "USD".to_owned();
+
+        relay_usd(1, 10);
+        System::set_block_number(6);
+        relay_usd(2, 11);
+        assert_eq!(Band::rates(&usd), None);
+        let round = Band::rounds(&usd).unwrap();
+        assert_eq!(round.started, 6);
+        assert_eq!(
+            round.submissions.keys().copied().collect::<Vec<_>>(),
+            vec![2]
+        );
+
+        relay_usd(1, 11);
+        assert_eq!(
+            Band::rates(&usd).map(|rate| rate.value),
+            Some(band_rate_into_balance(11))
+        );
+    });
+}
+
+#[test]
+fn outliers_should_be_tracked_and_slashed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let relayers = vec![1, 2, 3];
+        for relayer in relayers.iter() {
+            Balances::make_free_balance_be(relayer, 1000);
+        }
+        Band::add_relayers(RuntimeOrigin::root(), relayers.clone())
+            .expect("Failed to add relayers");
+        let parameters = RoundParameters {
+            relayer_deposit: 100,
+            outlier_slash: 40,
+            ..round_parameters(3)
+        };
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            parameters
+        ));
+        for relayer in relayers.iter() {
+            assert_eq!(Balances::reserved_balance(relayer), 100);
+            assert_eq!(Band::relayer_deposit(relayer), 100);
+        }
+
+        relay_usd(1, 10);
+        relay_usd(2, 10);
+        relay_usd(3, 20);
+
+        let usd = "USD".to_owned();
+        assert_eq!(
+            Band::rates(&usd).map(|rate| rate.value),
+            Some(band_rate_into_balance(10))
+        );
+        System::assert_has_event(
+            crate::Event::<Runtime>::OutlierDetected(
+                usd,
+                3,
+                band_rate_into_balance(20),
+                band_rate_into_balance(10),
+                40,
+            )
+            .into(),
+        );
+        assert_eq!(OutliersCount::<Runtime>::get(3), 1);
+        assert_eq!(OutliersCount::<Runtime>::get(1), 0);
+        assert_eq!(Balances::reserved_balance(3), 60);
+        assert_eq!(Balances::total_balance(&3), 960);
+        assert_eq!(Band::relayer_deposit(3), 60);
+
+        assert_noop!(
+            Band::remove_relayers(RuntimeOrigin::root(), vec![3]),
+            Error::<Runtime>::NotEnoughRelayers
+        );
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            RoundParameters {
+                quorum: 2,
+                ..parameters
+            }
+        ));
+        // the deposit is not changed, so the slashed deposit is not topped up
+        assert_eq!(Band::relayer_deposit(3), 60);
+        Band::remove_relayers(RuntimeOrigin::root(), vec![3]).expect("Failed to remove relayers");
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(Balances::free_balance(3), 960);
+        assert_eq!(Band::relayer_deposit(3), 0);
+    });
+}
+
+#[test]
+fn add_relayers_should_fail_without_deposit_balance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            RoundParameters {
+                relayer_deposit: 100,
+                ..round_parameters(1)
+            }
+        ));
+        Balances::make_free_balance_be(&1, 50);
+        assert!(Band::add_relayers(RuntimeOrigin::root(), vec![1]).is_err());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Band::relayer_deposit(1), 0);
+    });
+}
+
+#[test]
+fn set_round_parameters_should_update_deposits_of_relayers() {
+    new_test_ext().execute_with(|| {
+        for relayer in [1, 2] {
+            Balances::make_free_balance_be(&relayer, 1000);
+        }
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2]).expect("Failed to add relayers");
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            RoundParameters {
+                relayer_deposit: 100,
+                ..round_parameters(2)
+            }
+        ));
+        for relayer in [1, 2] {
+            assert_eq!(Balances::reserved_balance(relayer), 100);
+            assert_eq!(Band::relayer_deposit(relayer), 100);
+        }
+
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            RoundParameters {
+                relayer_deposit: 30,
+                ..round_parameters(2)
+            }
+        ));
+        for relayer in [1, 2] {
+            assert_eq!(Balances::reserved_balance(relayer), 30);
+            assert_eq!(Balances::free_balance(relayer), 970);
+            assert_eq!(Band::relayer_deposit(relayer), 30);
+        }
+
+        assert_ok!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            round_parameters(2)
+        ));
+        for relayer in [1, 2] {
+            assert_eq!(Balances::reserved_balance(relayer), 0);
+            assert_eq!(Band::relayer_deposit(relayer), 0);
+        }
+    });
+}
+
+#[test]
+fn set_round_parameters_should_fail_without_deposit_balance_of_relayers() {
+    new_test_ext().execute_with(|| {
+        Balances::make_free_balance_be(&1, 1000);
+        Balances::make_free_balance_be(&2, 50);
+        Band::add_relayers(RuntimeOrigin::root(), vec![1, 2]).expect("Failed to add relayers");
+        assert!(Band::set_round_parameters(
+            RuntimeOrigin::root(),
+            RoundParameters {
+                relayer_deposit: 100,
+                ..round_parameters(1)
+            }
+        )
+        .is_err());
+        assert_eq!(Band::round_parameters().relayer_deposit, 0);
+    });
+}
+
+#[test]
+fn add_relayers_should_be_bounded() {
+    new_test_ext().execute_with(|| {
+        let max_relayers = <Runtime as crate::Config>::MaxRelayers::get() as u64;
+        assert_noop!(
+            Band::add_relayers(RuntimeOrigin::root(), (0..=max_relayers).collect()),
+            Error::<Runtime>::TooManyRelayers
+        );
+        Band::add_relayers(RuntimeOrigin::root(), (0..max_relayers).collect())
+            .expect("Failed to add relayers");
+        assert_noop!(
+            Band::add_relayers(RuntimeOrigin::root(), vec![max_relayers]),
+            Error::<Runtime>::TooManyRelayers
+        );
+    });
+}
@@ -60,11 +60,12 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for band.
 pub trait WeightInfo {
-	fn relay() -> Weight;
-	fn force_relay() -> Weight;
-	fn add_relayers() -> Weight;
-	fn remove_relayers() -> Weight;
+	fn relay(n: u32, q: u32, ) -> Weight;
+	fn force_relay(n: u32, q: u32, ) -> Weight;
+	fn add_relayers(n: u32, ) -> Weight;
+	fn remove_relayers(n: u32, ) -> Weight;
 	fn set_dynamic_fee_parameters() -> Weight;
+	fn set_round_parameters(n: u32, ) -> Weight;
 }
 
 /// Weights for band using the Substrate node and recommended hardware.
@@ -72,59 +73,125 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: Band TrustedRelayers (r:1 w:0)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Band SymbolRates (r:1 w:1)
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Rounds (r:100 w:100)
+	/// Proof Skipped: Band Rounds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band OutliersCount (r:100 w:100)
+	/// Proof Skipped: Band OutliersCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band SymbolRates (r:100 w:100)
 	/// Proof Skipped: Band SymbolRates (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Band SymbolCheckBlock (r:0 w:1)
+	/// Storage: Band SymbolCheckBlock (r:0 w:100)
 	/// Proof Skipped: Band SymbolCheckBlock (max_values: None, max_size: None, mode: Measured)
-	/// Storage: OracleProxy SymbolProviders (r:0 w:1)
+	/// Storage: OracleProxy SymbolProviders (r:0 w:100)
 	/// Proof Skipped: OracleProxy SymbolProviders (max_values: None, max_size: None, mode: Measured)
-	fn relay() -> Weight {
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn relay(n: u32, q: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `3350`
-		// Minimum execution time: 26_033_000 picoseconds.
-		Weight::from_parts(26_446_000, 3350)
+		//  Measured:  `1374 + n * (2561 ±0) + q * (285 ±0)`
+		//  Estimated: `11190 + n * (12751 ±0) + q * (9811 ±0)`
+		// Minimum execution time: 55_893_000 picoseconds.
+		Weight::from_parts(30_512_000, 11190)
+			// Standard Error: 1_093_402
+			.saturating_add(Weight::from_parts(18_934_611, 0).saturating_mul(n.into()))
+			// Standard Error: 1_093_402
+			.saturating_add(Weight::from_parts(9_873_225, 0).saturating_mul(q.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 12751).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 9811).saturating_mul(q.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:0)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Band SymbolRates (r:1 w:1)
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Rounds (r:100 w:100)
+	/// Proof Skipped: Band Rounds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band OutliersCount (r:100 w:100)
+	/// Proof Skipped: Band OutliersCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band SymbolRates (r:100 w:100)
 	/// Proof Skipped: Band SymbolRates (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Band SymbolCheckBlock (r:0 w:1)
+	/// Storage: Band SymbolCheckBlock (r:0 w:100)
 	/// Proof Skipped: Band SymbolCheckBlock (max_values: None, max_size: None, mode: Measured)
-	/// Storage: OracleProxy SymbolProviders (r:0 w:1)
+	/// Storage: OracleProxy SymbolProviders (r:0 w:100)
 	/// Proof Skipped: OracleProxy SymbolProviders (max_values: None, max_size: None, mode: Measured)
-	fn force_relay() -> Weight {
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn force_relay(n: u32, q: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `3350`
-		// Minimum execution time: 25_950_000 picoseconds.
-		Weight::from_parts(26_298_000, 3350)
+		//  Measured:  `1374 + n * (2561 ±0) + q * (285 ±0)`
+		//  Estimated: `11190 + n * (12751 ±0) + q * (9811 ±0)`
+		// Minimum execution time: 55_120_000 picoseconds.
+		Weight::from_parts(30_147_000, 11190)
+			// Standard Error: 1_081_765
+			.saturating_add(Weight::from_parts(18_716_290, 0).saturating_mul(n.into()))
+			// Standard Error: 1_081_765
+			.saturating_add(Weight::from_parts(9_851_384, 0).saturating_mul(q.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 12751).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 9811).saturating_mul(q.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:1)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	fn add_relayers() -> Weight {
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band RelayerDeposits (r:0 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 100]`.
+	fn add_relayers(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `4`
-		//  Estimated: `499`
-		// Minimum execution time: 14_615_000 picoseconds.
-		Weight::from_parts(14_948_000, 499)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+		//  Measured:  `4 + n * (103 ±0)`
+		//  Estimated: `998 + n * (2603 ±0)`
+		// Minimum execution time: 38_117_000 picoseconds.
+		Weight::from_parts(16_407_542, 998)
+			// Standard Error: 28_714
+			.saturating_add(Weight::from_parts(22_856_117, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:1)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	fn remove_relayers() -> Weight {
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn remove_relayers(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `590`
-		// Minimum execution time: 16_783_000 picoseconds.
-		Weight::from_parts(17_058_000, 590)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+		//  Measured:  `246 + n * (260 ±0)`
+		//  Estimated: `1482 + n * (5342 ±0)`
+		// Minimum execution time: 41_302_000 picoseconds.
+		Weight::from_parts(18_264_051, 1482)
+			// Standard Error: 31_455
+			.saturating_add(Weight::from_parts(23_540_872, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5342).saturating_mul(n.into()))
 	}
 	/// Storage: Band DynamicFeeParameters (r:0 w:1)
 	/// Proof Skipped: Band DynamicFeeParameters (max_values: Some(1), max_size: None, mode: Measured)
@@ -136,65 +203,154 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(5_313_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Band TrustedRelayers (r:1 w:0)
+	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Parameters (r:1 w:1)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn set_round_parameters(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270 + n * (260 ±0)`
+		//  Estimated: `1530 + n * (5342 ±0)`
+		// Minimum execution time: 40_518_000 picoseconds.
+		Weight::from_parts(15_902_318, 1530)
+			// Standard Error: 29_871
+			.saturating_add(Weight::from_parts(24_612_409, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5342).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	/// Storage: Band TrustedRelayers (r:1 w:0)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Band SymbolRates (r:1 w:1)
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Rounds (r:100 w:100)
+	/// Proof Skipped: Band Rounds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band OutliersCount (r:100 w:100)
+	/// Proof Skipped: Band OutliersCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band SymbolRates (r:100 w:100)
 	/// Proof Skipped: Band SymbolRates (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Band SymbolCheckBlock (r:0 w:1)
+	/// Storage: Band SymbolCheckBlock (r:0 w:100)
 	/// Proof Skipped: Band SymbolCheckBlock (max_values: None, max_size: None, mode: Measured)
-	/// Storage: OracleProxy SymbolProviders (r:0 w:1)
+	/// Storage: OracleProxy SymbolProviders (r:0 w:100)
 	/// Proof Skipped: OracleProxy SymbolProviders (max_values: None, max_size: None, mode: Measured)
-	fn relay() -> Weight {
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn relay(n: u32, q: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `3350`
-		// Minimum execution time: 26_033_000 picoseconds.
-		Weight::from_parts(26_446_000, 3350)
+		//  Measured:  `1374 + n * (2561 ±0) + q * (285 ±0)`
+		//  Estimated: `11190 + n * (12751 ±0) + q * (9811 ±0)`
+		// Minimum execution time: 55_893_000 picoseconds.
+		Weight::from_parts(30_512_000, 11190)
+			// Standard Error: 1_093_402
+			.saturating_add(Weight::from_parts(18_934_611, 0).saturating_mul(n.into()))
+			// Standard Error: 1_093_402
+			.saturating_add(Weight::from_parts(9_873_225, 0).saturating_mul(q.into()))
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 12751).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 9811).saturating_mul(q.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:0)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: Band SymbolRates (r:1 w:1)
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Rounds (r:100 w:100)
+	/// Proof Skipped: Band Rounds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band OutliersCount (r:100 w:100)
+	/// Proof Skipped: Band OutliersCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band SymbolRates (r:100 w:100)
 	/// Proof Skipped: Band SymbolRates (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Band SymbolCheckBlock (r:0 w:1)
+	/// Storage: Band SymbolCheckBlock (r:0 w:100)
 	/// Proof Skipped: Band SymbolCheckBlock (max_values: None, max_size: None, mode: Measured)
-	/// Storage: OracleProxy SymbolProviders (r:0 w:1)
+	/// Storage: OracleProxy SymbolProviders (r:0 w:100)
 	/// Proof Skipped: OracleProxy SymbolProviders (max_values: None, max_size: None, mode: Measured)
-	fn force_relay() -> Weight {
+	/// The range of component `n` is `[1, 100]`.
+	/// The range of component `q` is `[1, 100]`.
+	fn force_relay(n: u32, q: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `3350`
-		// Minimum execution time: 25_950_000 picoseconds.
-		Weight::from_parts(26_298_000, 3350)
+		//  Measured:  `1374 + n * (2561 ±0) + q * (285 ±0)`
+		//  Estimated: `11190 + n * (12751 ±0) + q * (9811 ±0)`
+		// Minimum execution time: 55_120_000 picoseconds.
+		Weight::from_parts(30_147_000, 11190)
+			// Standard Error: 1_081_765
+			.saturating_add(Weight::from_parts(18_716_290, 0).saturating_mul(n.into()))
+			// Standard Error: 1_081_765
+			.saturating_add(Weight::from_parts(9_851_384, 0).saturating_mul(q.into()))
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 12751).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 9811).saturating_mul(q.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:1)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	fn add_relayers() -> Weight {
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Band RelayerDeposits (r:0 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 100]`.
+	fn add_relayers(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `4`
-		//  Estimated: `499`
-		// Minimum execution time: 14_615_000 picoseconds.
-		Weight::from_parts(14_948_000, 499)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+		//  Measured:  `4 + n * (103 ±0)`
+		//  Estimated: `998 + n * (2603 ±0)`
+		// Minimum execution time: 38_117_000 picoseconds.
+		Weight::from_parts(16_407_542, 998)
+			// Standard Error: 28_714
+			.saturating_add(Weight::from_parts(22_856_117, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
 	}
 	/// Storage: Band TrustedRelayers (r:1 w:1)
 	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
-	fn remove_relayers() -> Weight {
+	/// Storage: Band Parameters (r:1 w:0)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn remove_relayers(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `95`
-		//  Estimated: `590`
-		// Minimum execution time: 16_783_000 picoseconds.
-		Weight::from_parts(17_058_000, 590)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+		//  Measured:  `246 + n * (260 ±0)`
+		//  Estimated: `1482 + n * (5342 ±0)`
+		// Minimum execution time: 41_302_000 picoseconds.
+		Weight::from_parts(18_264_051, 1482)
+			// Standard Error: 31_455
+			.saturating_add(Weight::from_parts(23_540_872, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5342).saturating_mul(n.into()))
 	}
 	/// Storage: Band DynamicFeeParameters (r:0 w:1)
 	/// Proof Skipped: Band DynamicFeeParameters (max_values: Some(1), max_size: None, mode: Measured)
@@ -206,4 +362,27 @@ impl WeightInfo for () {
 		Weight::from_parts(5_313_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Band TrustedRelayers (r:1 w:0)
+	/// Proof Skipped: Band TrustedRelayers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band Parameters (r:1 w:1)
+	/// Proof Skipped: Band Parameters (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Band RelayerDeposits (r:100 w:100)
+	/// Proof Skipped: Band RelayerDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn set_round_parameters(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270 + n * (260 ±0)`
+		//  Estimated: `1530 + n * (5342 ±0)`
+		// Minimum execution time: 40_518_000 picoseconds.
+		Weight::from_parts(15_902_318, 1530)
+			// Standard Error: 29_871
+			.saturating_add(Weight::from_parts(24_612_409, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5342).saturating_mul(n.into()))
+	}
 }
@@ -155,6 +155,7 @@ impl demeter_farming_platform::Config for Runtime {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.0007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -166,7 +167,13 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = ();
     type EnabledSourcesManager = ();
+    type LockedLiquiditySourcesManager = ();
     type GetFee = GetXykFee;
+    type GetMinPoolFee = ();
+    type GetMaxPoolFee = ();
+    type TreasuryTechAccountId = ();
+    type MaxPriceObservations = ();
+    type PriceObservationPeriod = ();
     type OnPoolCreated = PswapDistribution;
     type OnPoolReservesChanged = ();
     type WeightInfo = ();
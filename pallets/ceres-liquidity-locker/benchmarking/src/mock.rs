@@ -189,6 +189,7 @@ impl dex_api::Config for Runtime {
     type XSTPool = ();
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type OrderBook = ();
+    type StableSwapPool = ();
     type WeightInfo = ();
 }
 
@@ -211,6 +212,7 @@ impl demeter_farming_platform::Config for Runtime {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.0007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -222,7 +224,13 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
     type EnabledSourcesManager = trading_pair::Pallet<Runtime>;
+    type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type GetFee = GetXykFee;
+    type GetMinPoolFee = ();
+    type GetMaxPoolFee = ();
+    type TreasuryTechAccountId = ();
+    type MaxPriceObservations = ();
+    type PriceObservationPeriod = ();
     type OnPoolCreated = PswapDistribution;
     type OnPoolReservesChanged = ();
     type WeightInfo = ();
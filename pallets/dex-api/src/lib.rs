@@ -81,6 +81,7 @@ impl<T: Config>
             MulticollateralBondingCurvePool => can_exchange!(MulticollateralBondingCurvePool),
             XSTPool => can_exchange!(XSTPool),
             OrderBook => can_exchange!(OrderBook),
+            StableSwapPool => can_exchange!(StableSwapPool),
             MockPool => can_exchange!(MockLiquiditySource),
             MockPool2 => can_exchange!(MockLiquiditySource2),
             MockPool3 => can_exchange!(MockLiquiditySource3),
@@ -113,6 +114,7 @@ impl<T: Config>
             MulticollateralBondingCurvePool => quote!(MulticollateralBondingCurvePool),
             XSTPool => quote!(XSTPool),
             OrderBook => quote!(OrderBook),
+            StableSwapPool => quote!(StableSwapPool),
             MockPool => quote!(MockLiquiditySource),
             MockPool2 => quote!(MockLiquiditySource2),
             MockPool3 => quote!(MockLiquiditySource3),
@@ -147,6 +149,7 @@ impl<T: Config>
             MulticollateralBondingCurvePool => exchange!(MulticollateralBondingCurvePool),
             XSTPool => exchange!(XSTPool),
             OrderBook => exchange!(OrderBook),
+            StableSwapPool => exchange!(StableSwapPool),
             MockPool => exchange!(MockLiquiditySource),
             MockPool2 => exchange!(MockLiquiditySource2),
             MockPool3 => exchange!(MockLiquiditySource3),
@@ -179,6 +182,7 @@ impl<T: Config>
             MulticollateralBondingCurvePool => check_rewards!(MulticollateralBondingCurvePool),
             XSTPool => check_rewards!(XSTPool),
             OrderBook => check_rewards!(OrderBook),
+            StableSwapPool => check_rewards!(StableSwapPool),
             MockPool => check_rewards!(MockLiquiditySource),
             MockPool2 => check_rewards!(MockLiquiditySource2),
             MockPool3 => check_rewards!(MockLiquiditySource3),
@@ -213,6 +217,7 @@ impl<T: Config>
             }
             XSTPool => quote_without_impact!(XSTPool),
             OrderBook => quote_without_impact!(OrderBook),
+            StableSwapPool => quote_without_impact!(StableSwapPool),
             MockPool => quote_without_impact!(MockLiquiditySource),
             MockPool2 => quote_without_impact!(MockLiquiditySource2),
             MockPool3 => quote_without_impact!(MockLiquiditySource3),
@@ -226,6 +231,7 @@ impl<T: Config>
             .max(T::XYKPool::quote_weight())
             .max(T::MulticollateralBondingCurvePool::quote_weight())
             .max(T::OrderBook::quote_weight())
+            .max(T::StableSwapPool::quote_weight())
     }
 
     fn exchange_weight() -> Weight {
@@ -235,6 +241,7 @@ impl<T: Config>
                 LiquiditySourceType::MulticollateralBondingCurvePool,
                 LiquiditySourceType::XSTPool,
                 LiquiditySourceType::OrderBook,
+                LiquiditySourceType::StableSwapPool,
             ]
             .into_iter(),
         )
@@ -245,6 +252,7 @@ impl<T: Config>
             .max(T::XYKPool::check_rewards_weight())
             .max(T::MulticollateralBondingCurvePool::check_rewards_weight())
             .max(T::OrderBook::check_rewards_weight())
+            .max(T::StableSwapPool::check_rewards_weight())
     }
 }
 
@@ -305,6 +313,7 @@ impl<T: Config>
                 }
                 LiquiditySourceType::XSTPool => T::XSTPool::exchange_weight(),
                 LiquiditySourceType::OrderBook => T::OrderBook::exchange_weight(),
+                LiquiditySourceType::StableSwapPool => T::StableSwapPool::exchange_weight(),
                 LiquiditySourceType::BondingCurvePool
                 | LiquiditySourceType::MockPool
                 | LiquiditySourceType::MockPool2
@@ -384,6 +393,13 @@ pub mod pallet {
             Balance,
             DispatchError,
         >;
+        type StableSwapPool: LiquiditySource<
+            Self::DEXId,
+            Self::AccountId,
+            Self::AssetId,
+            Balance,
+            DispatchError,
+        >;
 
         type WeightInfo: WeightInfo;
     }
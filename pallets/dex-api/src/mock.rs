@@ -259,6 +259,7 @@ impl Config for Runtime {
     type XYKPool = pool_xyk::Pallet<Runtime>;
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type OrderBook = WeightedEmptyLiquiditySource;
+    type StableSwapPool = WeightedEmptyLiquiditySource;
 
     type WeightInfo = ();
 }
@@ -382,6 +383,7 @@ impl demeter_farming_platform::Config for Runtime {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.0007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -393,7 +395,13 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = ();
     type EnabledSourcesManager = ();
+    type LockedLiquiditySourcesManager = ();
     type GetFee = GetXykFee;
+    type GetMinPoolFee = ();
+    type GetMaxPoolFee = ();
+    type TreasuryTechAccountId = ();
+    type MaxPriceObservations = ();
+    type PriceObservationPeriod = ();
     type OnPoolCreated = PswapDistribution;
     type OnPoolReservesChanged = ();
     type WeightInfo = ();
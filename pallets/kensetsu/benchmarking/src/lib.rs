@@ -71,6 +71,28 @@ fn set_xor_as_collateral_type<T: Config>() {
                 liquidation_ratio: Perbill::from_percent(50),
                 max_liquidation_lot: balance!(100),
                 stability_fee_rate: FixedU128::from_perbill(Perbill::from_percent(10)),
+                price_source: kensetsu::CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
+            },
+            kusd_supply: balance!(0),
+            last_fee_update_time: Default::default(),
+            interest_coefficient: FixedU128::one(),
+        }),
+    );
+}
+
+/// Sets DAI as collateral type with default risk parameters
+fn set_dai_as_collateral_type<T: Config>() {
+    kensetsu::CollateralInfos::<T>::set::<AssetIdOf<T>>(
+        DAI.into(),
+        Some(kensetsu::CollateralInfo {
+            risk_parameters: kensetsu::CollateralRiskParameters {
+                hard_cap: Balance::MAX,
+                liquidation_ratio: Perbill::from_percent(80),
+                max_liquidation_lot: balance!(100),
+                stability_fee_rate: FixedU128::from_perbill(Perbill::from_percent(10)),
+                price_source: kensetsu::CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
             },
             kusd_supply: balance!(0),
             last_fee_update_time: Default::default(),
@@ -91,6 +113,36 @@ fn create_cdp_with_xor<T: Config>() -> CdpId {
     kensetsu::NextCDPId::<T>::get()
 }
 
+/// Creates basket CDP with XOR as the main collateral
+fn create_basket_cdp_with_xor<T: Config>() -> CdpId {
+    kensetsu::Pallet::<T>::create_basket_cdp(
+        RawOrigin::Signed(caller::<T>()).into(),
+        XOR.into(),
+        balance!(0),
+        balance!(0),
+    )
+    .expect("Shall create CDP");
+    kensetsu::NextCDPId::<T>::get()
+}
+
+/// Mints DAI and deposits as collateral to basket CDP
+fn deposit_dai_collateral<T: Config>(cdp_id: CdpId, amount: Balance) {
+    assets::Pallet::<T>::update_balance(
+        RawOrigin::Root.into(),
+        caller::<T>(),
+        DAI.into(),
+        amount.try_into().unwrap(),
+    )
+    .expect("Shall mint DAI");
+    kensetsu::Pallet::<T>::deposit_collateral_asset(
+        RawOrigin::Signed(caller::<T>()).into(),
+        cdp_id,
+        DAI.into(),
+        amount,
+    )
+    .expect("Shall deposit");
+}
+
 /// Mints XOR and deposited as collateral to CDP
 fn deposit_xor_collateral<T: Config>(cdp_id: CdpId, amount: Balance) {
     assets::Pallet::<T>::update_balance(
@@ -118,6 +170,8 @@ fn make_cdps_unsafe<T: Config>() {
                 liquidation_ratio: Perbill::from_percent(10),
                 max_liquidation_lot: balance!(100),
                 stability_fee_rate: FixedU128::from_perbill(Perbill::from_percent(10)),
+                price_source: kensetsu::CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
             },
             kusd_supply: balance!(0),
             last_fee_update_time: Default::default(),
@@ -347,6 +401,8 @@ benchmarks! {
                 liquidation_ratio: Perbill::from_percent(50),
                 max_liquidation_lot: balance!(100),
                 stability_fee_rate: Default::default(),
+                price_source: kensetsu::CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
             }
         ).unwrap();
     }
@@ -413,4 +469,174 @@ benchmarks! {
     remove_risk_manager {}: {
         kensetsu::Pallet::<T>::remove_risk_manager(RawOrigin::Root.into(), caller::<T>()).unwrap();
     }
+
+    bid {
+        kensetsu::Pallet::<T>::add_risk_manager(RawOrigin::Root.into(), risk_manager::<T>())
+            .expect("Must set risk manager");
+        initialize_liquidity_sources::<T>();
+        set_xor_as_collateral_type::<T>();
+        let cdp_id = create_cdp_with_xor::<T>();
+        let amount = balance!(100);
+        deposit_xor_collateral::<T>(cdp_id, amount);
+        let debt = balance!(10);
+        kensetsu::Pallet::<T>::update_hard_cap_total_supply(
+            RawOrigin::Signed(risk_manager::<T>()).into(),
+            Balance::MAX,
+        ).expect("Shall update hard cap");
+        kensetsu::Pallet::<T>::borrow(RawOrigin::Signed(caller::<T>()).into(), cdp_id, debt)
+            .expect("Shall borrow");
+        make_cdps_unsafe::<T>();
+        kensetsu::Pallet::<T>::liquidate(RawOrigin::Signed(caller::<T>()).into(), cdp_id)
+            .expect("Shall liquidate");
+        let auction_id = kensetsu::NextAuctionId::<T>::get();
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            caller::<T>(),
+            KUSD.into(),
+            balance!(1000).try_into().unwrap(),
+        )
+        .expect("Shall mint KUSD");
+    }: {
+        kensetsu::Pallet::<T>::bid(
+            RawOrigin::Signed(caller::<T>()).into(),
+            auction_id,
+            amount,
+            FixedU128::from(1000),
+        ).unwrap();
+    }
+
+    deposit_savings {
+        let amount = balance!(10);
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            caller::<T>(),
+            KUSD.into(),
+            amount.try_into().unwrap(),
+        )
+        .expect("Shall mint KUSD");
+    }: {
+        kensetsu::Pallet::<T>::deposit_savings(RawOrigin::Signed(caller::<T>()).into(), amount)
+            .unwrap();
+    }
+
+    withdraw_savings {
+        let amount = balance!(10);
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            caller::<T>(),
+            KUSD.into(),
+            amount.try_into().unwrap(),
+        )
+        .expect("Shall mint KUSD");
+        kensetsu::Pallet::<T>::deposit_savings(RawOrigin::Signed(caller::<T>()).into(), amount)
+            .expect("Shall deposit savings");
+    }: {
+        kensetsu::Pallet::<T>::withdraw_savings(RawOrigin::Signed(caller::<T>()).into(), amount)
+            .unwrap();
+    }
+
+    update_savings_rate {
+        kensetsu::Pallet::<T>::add_risk_manager(RawOrigin::Root.into(), risk_manager::<T>())
+            .expect("Must set risk manager");
+    }: {
+        kensetsu::Pallet::<T>::update_savings_rate(
+            RawOrigin::Signed(risk_manager::<T>()).into(),
+            <T as kensetsu::Config>::MaxSavingsRate::get(),
+        ).unwrap();
+    }
+
+    create_basket_cdp {
+        kensetsu::Pallet::<T>::add_risk_manager(RawOrigin::Root.into(), risk_manager::<T>())
+            .expect("Must set risk manager");
+        kensetsu::Pallet::<T>::update_hard_cap_total_supply(
+            RawOrigin::Signed(risk_manager::<T>()).into(),
+            Balance::MAX,
+        ).expect("Shall update hard cap");
+        initialize_liquidity_sources::<T>();
+        set_xor_as_collateral_type::<T>();
+        let collateral = balance!(10);
+        let debt = balance!(1);
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            caller::<T>(),
+            XOR.into(),
+            collateral.try_into().unwrap(),
+        )
+        .expect("Shall mint XOR");
+    }: {
+        kensetsu::Pallet::<T>::create_basket_cdp(
+            RawOrigin::Signed(caller::<T>()).into(),
+            XOR.into(),
+            collateral,
+            debt
+        ).unwrap();
+    }
+
+    deposit_collateral_asset {
+        set_xor_as_collateral_type::<T>();
+        set_dai_as_collateral_type::<T>();
+        let cdp_id = create_basket_cdp_with_xor::<T>();
+        let amount = balance!(10);
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            caller::<T>(),
+            DAI.into(),
+            amount.try_into().unwrap()
+        ).expect("Shall mint DAI");
+    }: {
+        kensetsu::Pallet::<T>::deposit_collateral_asset(
+            RawOrigin::Signed(caller::<T>()).into(),
+            cdp_id,
+            DAI.into(),
+            amount
+        ).unwrap();
+    }
+
+    withdraw_collateral_asset {
+        kensetsu::Pallet::<T>::add_risk_manager(RawOrigin::Root.into(), risk_manager::<T>())
+            .expect("Must set risk manager");
+        initialize_liquidity_sources::<T>();
+        set_xor_as_collateral_type::<T>();
+        set_dai_as_collateral_type::<T>();
+        let cdp_id = create_basket_cdp_with_xor::<T>();
+        deposit_xor_collateral::<T>(cdp_id, balance!(100));
+        let amount = balance!(10);
+        deposit_dai_collateral::<T>(cdp_id, amount);
+        kensetsu::Pallet::<T>::update_hard_cap_total_supply(
+            RawOrigin::Signed(risk_manager::<T>()).into(),
+            Balance::MAX,
+        ).expect("Shall update hard cap");
+        kensetsu::Pallet::<T>::borrow(RawOrigin::Signed(caller::<T>()).into(), cdp_id, balance!(1))
+            .expect("Shall borrow");
+    }: {
+        kensetsu::Pallet::<T>::withdraw_collateral_asset(
+            RawOrigin::Signed(caller::<T>()).into(),
+            cdp_id,
+            DAI.into(),
+            amount
+        ).unwrap();
+    }
+
+    withdraw_unsold_collateral {
+        kensetsu::Pallet::<T>::add_risk_manager(RawOrigin::Root.into(), risk_manager::<T>())
+            .expect("Must set risk manager");
+        let technical_account_id = technical::Pallet::<T>::tech_account_id_to_account_id(
+            &T::TreasuryTechAccount::get(),
+        ).expect("Shall resolve tech account id");
+        let amount = balance!(10);
+        assets::Pallet::<T>::update_balance(
+            RawOrigin::Root.into(),
+            technical_account_id,
+            XOR.into(),
+            amount.try_into().unwrap(),
+        )
+        .expect("Shall mint XOR");
+        kensetsu::UnsoldCollateral::<T>::insert::<AssetIdOf<T>, Balance>(XOR.into(), amount);
+    }: {
+        kensetsu::Pallet::<T>::withdraw_unsold_collateral(
+            RawOrigin::Signed(risk_manager::<T>()).into(),
+            XOR.into(),
+            amount
+        ).unwrap();
+    }
 }
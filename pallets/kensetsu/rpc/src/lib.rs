@@ -0,0 +1,140 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{Error as RpcError, RpcResult},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Block as BlockT;
+use sp_runtime::DispatchError;
+use std::sync::Arc;
+
+// Runtime API imports.
+use kensetsu_runtime_api::CdpInfo;
+pub use kensetsu_runtime_api::KensetsuAPI as KensetsuRuntimeApi;
+
+#[rpc(server, client)]
+pub trait KensetsuApi<BlockHash, AccountId, AssetId, CdpInfo> {
+    #[method(name = "kensetsu_getCdpInfo")]
+    fn get_cdp_info(
+        &self,
+        cdp_id: u128,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<Option<CdpInfo>, DispatchError>>;
+
+    #[method(name = "kensetsu_listOwnerCdps")]
+    fn list_owner_cdps(
+        &self,
+        owner: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<Vec<u128>, DispatchError>>;
+
+    #[method(name = "kensetsu_listUnsafeCdps")]
+    fn list_unsafe_cdps(
+        &self,
+        collateral_asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<Vec<u128>, DispatchError>>;
+}
+
+pub struct KensetsuClient<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> KensetsuClient<C, B> {
+    /// Construct default Kensetsu as intermediary impl for rpc.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, AssetId>
+    KensetsuApiServer<<Block as BlockT>::Hash, AccountId, AssetId, CdpInfo<AccountId, AssetId>>
+    for KensetsuClient<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: KensetsuRuntimeApi<Block, AccountId, AssetId>,
+    AccountId: Codec,
+    AssetId: Codec,
+    CdpInfo<AccountId, AssetId>: Codec,
+{
+    fn get_cdp_info(
+        &self,
+        cdp_id: u128,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<Option<CdpInfo<AccountId, AssetId>>, DispatchError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_cdp_info(&at, cdp_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn list_owner_cdps(
+        &self,
+        owner: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<Vec<u128>, DispatchError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.list_owner_cdps(&at, owner)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn list_unsafe_cdps(
+        &self,
+        collateral_asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<Vec<u128>, DispatchError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.list_unsafe_cdps(&at, collateral_asset_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+}
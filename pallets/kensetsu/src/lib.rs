@@ -37,7 +37,11 @@
 //! `liquidation_ratio` coefficient. The debt in KUSD is a subject of `stability_fee` interest rate.
 //! Collateral may be unlocked only when the debt and the interest are payed back. If the value of
 //! collateral has changed in a way that it does not secure the debt, the collateral is liquidated
-//! to cover the debt and the interest.
+//! to cover the debt and the interest. Liquidated collateral is sold on Dutch auction, where
+//! keepers buy collateral for KUSD at the price decreasing every block.
+//! KUSD holders may lock KUSD in savings and earn savings rate interest paid from the accrued
+//! stability fees. Savings are accounted in shares of the global savings index growing with the
+//! savings rate as long as the protocol profit covers the interest.
 
 pub use pallet::*;
 
@@ -59,6 +63,7 @@ mod tests;
 mod test_utils;
 
 mod compounding;
+pub mod migrations;
 pub mod weights;
 
 pub const TECH_ACCOUNT_PREFIX: &[u8] = b"kensetsu";
@@ -69,12 +74,30 @@ const VALIDATION_ERROR_ACCRUE: u8 = 1;
 const VALIDATION_ERROR_ACCRUE_NO_DEBT: u8 = 2;
 const VALIDATION_ERROR_CHECK_SAFE: u8 = 3;
 const VALIDATION_ERROR_CDP_SAFE: u8 = 4;
+const VALIDATION_ERROR_PRICE_SOURCES_DEVIATION: u8 = 5;
+
+/// Off-chain worker local storage keys
+const OFFCHAIN_CDP_CURSOR_KEY: &[u8] = b"kensetsu::cdp-cursor";
+const OFFCHAIN_ACCRUE_PREFIX: &[u8] = b"kensetsu::accrue::";
+const OFFCHAIN_LIQUIDATE_PREFIX: &[u8] = b"kensetsu::liquidate::";
+
+/// Source of collateral reference price used for CDP safety checks and auctions.
+#[derive(Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CollateralPriceSource<Symbol> {
+    /// Average price from price-tools, collateral/DAI pair
+    PriceTools,
+
+    /// Oracle price of the symbol in USD
+    Oracle(Symbol),
+
+    /// Median of price-tools and oracle prices, i.e. their average. The prices must not deviate
+    /// by more than `max_price_deviation`, so that none of them can move the median arbitrarily.
+    Median(Symbol),
+}
 
 /// Risk management parameters for the specific collateral type.
-#[derive(
-    Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord, Copy,
-)]
-pub struct CollateralRiskParameters {
+#[derive(Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CollateralRiskParameters<Symbol> {
     /// Hard cap of total KUSD issued for the collateral.
     pub hard_cap: Balance,
 
@@ -86,13 +109,20 @@ pub struct CollateralRiskParameters {
 
     /// Protocol Interest rate per second
     pub stability_fee_rate: FixedU128,
+
+    /// Collateral reference price source
+    pub price_source: CollateralPriceSource<Symbol>,
+
+    /// Max deviation between price sources, liquidations are paused if the deviation is
+    /// exceeded. Applies to the median price source only.
+    pub max_price_deviation: Perbill,
 }
 
 /// Collateral parameters, includes risk info and additional data for interest rate calculation
 #[derive(Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord)]
-pub struct CollateralInfo<Moment> {
+pub struct CollateralInfo<Moment, Symbol> {
     /// Collateral Risk parameters set by risk management
-    pub risk_parameters: CollateralRiskParameters,
+    pub risk_parameters: CollateralRiskParameters<Symbol>,
 
     /// Amount of KUSD issued for the collateral
     pub kusd_supply: Balance,
@@ -104,7 +134,20 @@ pub struct CollateralInfo<Moment> {
     pub interest_coefficient: FixedU128,
 }
 
-/// CDP - Collateralized Debt Position. It is a single collateral/debt record.
+/// CDP type, defines which collateral assets CDP may hold.
+#[derive(
+    Debug, Clone, Copy, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum CdpType {
+    /// CDP holds only the main collateral asset
+    Single,
+
+    /// CDP holds a basket of collateral assets, the main collateral asset defines stability fee
+    /// and hard cap, other collateral assets are kept in `BasketCollaterals`
+    Basket,
+}
+
+/// CDP - Collateralized Debt Position. It is a collateral/debt record.
 #[derive(Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CollateralizedDebtPosition<AccountId, AssetId> {
     /// CDP owner
@@ -122,6 +165,80 @@ pub struct CollateralizedDebtPosition<AccountId, AssetId> {
     /// The coefficient is growing over time with interest rate.
     /// Actual interest is: (collateral.coefficient - cdp.coefficient) / cdp.coefficient
     pub interest_coefficient: FixedU128,
+
+    /// CDP type, single collateral or basket
+    pub cdp_type: CdpType,
+}
+
+/// CDP state calculated at the current timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CdpHealth {
+    /// Debt with interest accrued at the current timestamp
+    pub debt: Balance,
+
+    /// Collateral value in KUSD
+    pub collateral_value: Balance,
+
+    /// Collateral value to debt ratio, `None` if CDP has no debt
+    pub collateral_ratio: Option<FixedU128>,
+
+    /// Collateral price in KUSD at which CDP becomes unsafe, `None` if CDP has no debt or holds
+    /// a basket of collaterals
+    pub liquidation_price: Option<FixedU128>,
+
+    /// KUSD amount that can be borrowed against CDP collateral without exceeding hard caps
+    pub max_borrow: Balance,
+}
+
+/// Collateral auction opened on CDP liquidation.
+/// Collateral lot is sold with descending (Dutch) price, the price starts from reference price
+/// corrected with start price multiplier and decays every block.
+#[derive(Debug, Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CollateralAuction<AccountId, AssetId, BlockNumber> {
+    /// Liquidated CDP
+    pub cdp_id: CdpId,
+
+    /// Owner of liquidated CDP, receives collateral leftover
+    pub cdp_owner: AccountId,
+
+    /// Collateral lot left for sale
+    pub collateral_asset_id: AssetId,
+    pub collateral_amount: Balance,
+
+    /// Collateral sold during the auction
+    pub collateral_sold: Balance,
+
+    /// CDP debt in KUSD to be covered with auction
+    pub debt: Balance,
+
+    /// Liquidation penalty in KUSD to be raised in addition to debt
+    pub penalty: Balance,
+
+    /// KUSD raised with bids
+    pub kusd_raised: Balance,
+
+    /// Price of 1 collateral token in KUSD at the auction (re)start
+    pub start_price: FixedU128,
+
+    /// Block number of the auction (re)start
+    pub started_at: BlockNumber,
+
+    /// Number of auction restarts
+    pub restarts: u32,
+}
+
+impl<AccountId, AssetId, BlockNumber> CollateralAuction<AccountId, AssetId, BlockNumber> {
+    /// Returns KUSD amount left to raise: debt + penalty - raised
+    pub fn kusd_to_raise(&self) -> Balance {
+        self.debt
+            .saturating_add(self.penalty)
+            .saturating_sub(self.kusd_raised)
+    }
+
+    /// Returns true if there is nothing to sell or nothing to raise.
+    pub fn is_finished(&self) -> bool {
+        self.collateral_amount == 0 || self.kusd_to_raise() == 0
+    }
 }
 
 #[frame_support::pallet]
@@ -129,35 +246,98 @@ pub mod pallet {
     use super::*;
     use crate::compounding::compound;
     use crate::weights::WeightInfo;
-    use common::prelude::{QuoteAmount, SwapAmount, SwapOutcome};
     use common::{
         AccountIdOf, AssetInfoProvider, AssetName, AssetSymbol, BalancePrecision, ContentSource,
-        DEXId, Description, LiquidityProxyTrait, LiquiditySourceFilter, PriceToolsProvider,
-        PriceVariant, DAI,
+        DataFeed, Description, IsValid, LiquidityProxyTrait, PriceToolsProvider, PriceVariant,
+        Rate, DAI,
     };
     use frame_support::pallet_prelude::*;
     use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
     use frame_system::pallet_prelude::*;
     use pallet_timestamp as timestamp;
+    use sp_arithmetic::helpers_128bit::multiply_by_rational_with_rounding;
     use sp_arithmetic::traits::{CheckedMul, Saturating};
-    use sp_arithmetic::Percent;
+    use sp_arithmetic::{FixedPointNumber, Percent, Rounding};
     use sp_core::bounded::{BoundedBTreeSet, BoundedVec};
-    use sp_runtime::traits::{CheckedConversion, CheckedDiv, CheckedSub, One};
+    use sp_runtime::offchain::storage::StorageValueRef;
+    use sp_runtime::traits::{
+        CheckedAdd, CheckedConversion, CheckedDiv, CheckedSub, One, SaturatedConversion, Zero,
+    };
     use sp_std::collections::btree_set::BTreeSet;
     use sp_std::vec::Vec;
 
     /// CDP id type
     pub type CdpId = u128;
 
+    /// Collateral auction id type
+    pub type AuctionId = u128;
+
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Restarts expired collateral auctions, closes auctions expired `MaxAuctionRestarts`
+        /// times. Checks up to `MaxAuctionsPerBlock` auctions per block continuing from the last
+        /// checked auction.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            let max_auctions = T::MaxAuctionsPerBlock::get();
+            let auctions: Vec<_> = match <AuctionsCursor<T>>::get() {
+                Some(last_auction_id) => <CollateralAuctions<T>>::iter_from(
+                    <CollateralAuctions<T>>::hashed_key_for(last_auction_id),
+                )
+                .take(max_auctions as usize)
+                .collect(),
+                None => <CollateralAuctions<T>>::iter()
+                    .take(max_auctions as usize)
+                    .collect(),
+            };
+            match auctions.last() {
+                Some((last_auction_id, _)) if auctions.len() as u32 == max_auctions => {
+                    <AuctionsCursor<T>>::put(last_auction_id);
+                }
+                // all auctions are checked, start from the beginning in the next block
+                _ => <AuctionsCursor<T>>::kill(),
+            }
+            for (auction_id, auction) in auctions {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                if now.saturating_sub(auction.started_at) < T::AuctionDuration::get() {
+                    continue;
+                }
+                if auction.restarts >= T::MaxAuctionRestarts::get() {
+                    if let Err(err) =
+                        common::with_transaction(|| Self::close_auction(auction_id, auction))
+                    {
+                        warn!(
+                            "Failed to close collateral auction {:?}: {:?}",
+                            auction_id, err
+                        );
+                    }
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 4));
+                } else {
+                    if let Err(err) = Self::restart_auction(auction_id, now) {
+                        warn!(
+                            "Failed to restart collateral auction {:?}: {:?}",
+                            auction_id, err
+                        );
+                    }
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+                }
+            }
+            weight
+        }
+
         /// Main off-chain worker procedure.
         ///
-        /// Accrues fees and calls liquidations
+        /// Accrues fees and calls liquidations.
+        /// Checks up to `OffchainWorkerMaxCdpsPerBlock` CDPs per block continuing from the last
+        /// checked CDP, transactions for the same CDP are not resent while the previous one may
+        /// be in the pool.
         fn offchain_worker(block_number: T::BlockNumber) {
             debug!(
                 "Entering off-chain worker, block number is {:?}",
@@ -171,42 +351,59 @@ pub mod pallet {
                     collaterals_to_update.insert(collateral_asset_id);
                 }
             }
-            // TODO optimize CDP accrue (https://github.com/sora-xor/sora2-network/issues/878)
-            for (cdp_id, cdp) in <CDPDepository<T>>::iter() {
+            let mut cursor = StorageValueRef::persistent(OFFCHAIN_CDP_CURSOR_KEY);
+            let mut cdps = match cursor.get::<CdpId>() {
+                Ok(Some(last_cdp_id)) => {
+                    <CDPDepository<T>>::iter_from(<CDPDepository<T>>::hashed_key_for(last_cdp_id))
+                }
+                _ => <CDPDepository<T>>::iter(),
+            };
+            let mut checked = 0;
+            while checked < T::OffchainWorkerMaxCdpsPerBlock::get() {
+                let (cdp_id, cdp) = match cdps.next() {
+                    Some(next) => next,
+                    None => {
+                        // all CDPs are checked, start from the beginning in the next block
+                        cursor.clear();
+                        return;
+                    }
+                };
+                checked += 1;
+                cursor.set(&cdp_id);
+
                 // Debt recalculation with interest
                 if collaterals_to_update.contains(&cdp.collateral_asset_id) {
                     debug!("Accrue for CDP {:?}", cdp_id);
-                    let call = Call::<T>::accrue { cdp_id };
-                    if let Err(err) =
-                        SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
-                    {
-                        warn!(
-                            "Failed in offchain_worker send accrue(cdp_id: {:?}): {:?}",
-                            cdp_id, err
-                        );
-                    }
+                    Self::submit_unsigned_for_cdp(
+                        OFFCHAIN_ACCRUE_PREFIX,
+                        cdp_id,
+                        Call::<T>::accrue { cdp_id },
+                        block_number,
+                    );
                 }
 
                 // Liquidation
-                match Self::check_cdp_is_safe(
-                    cdp.debt,
-                    cdp.collateral_amount,
-                    cdp.collateral_asset_id,
-                ) {
+                let collaterals = Self::cdp_collaterals(cdp_id, &cdp);
+                match Self::get_accrued_debt(&cdp)
+                    .and_then(|debt| Self::check_cdp_is_safe(debt, &collaterals))
+                {
                     Ok(cdp_is_safe) => {
                         if !cdp_is_safe {
-                            debug!("Liquidation of CDP {:?}", cdp_id);
-                            let call = Call::<T>::liquidate { cdp_id };
                             if let Err(err) =
-                                SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
-                                    call.into(),
-                                )
+                                collaterals.iter().try_for_each(|(collateral_asset_id, _)| {
+                                    Self::ensure_price_sources_agree(collateral_asset_id)
+                                })
                             {
-                                warn!(
-                                    "Failed in offchain_worker send liquidate(cdp_id: {:?}): {:?}",
-                                    cdp_id, err
-                                );
+                                warn!("Liquidation of CDP {:?} is paused: {:?}", cdp_id, err);
+                                continue;
                             }
+                            debug!("Liquidation of CDP {:?}", cdp_id);
+                            Self::submit_unsigned_for_cdp(
+                                OFFCHAIN_LIQUIDATE_PREFIX,
+                                cdp_id,
+                                Call::<T>::liquidate { cdp_id },
+                                block_number,
+                            );
                         }
                     }
                     Err(err) => {
@@ -241,6 +438,8 @@ pub mod pallet {
         type TreasuryTechAccount: Get<Self::TechAccountId>;
         type KusdAssetId: Get<Self::AssetId>;
         type PriceTools: PriceToolsProvider<Self::AssetId>;
+        type Symbol: Parameter + Ord + MaxEncodedLen + IsValid;
+        type Oracle: DataFeed<Self::Symbol, Rate, u64>;
         type LiquidityProxy: LiquidityProxyTrait<Self::DEXId, Self::AccountId, Self::AssetId>;
 
         /// Maximum number of CDP that one user can create
@@ -255,6 +454,18 @@ pub mod pallet {
         #[pallet::constant]
         type AccrueInterestPeriod: Get<Self::Moment>;
 
+        /// Collateral auction start price is a reference price multiplied by this coefficient
+        #[pallet::constant]
+        type AuctionStartPriceMultiplier: Get<FixedU128>;
+
+        /// Collateral auction price decreases by this part every block
+        #[pallet::constant]
+        type AuctionPriceDecay: Get<Perbill>;
+
+        /// Number of blocks after which collateral auction is restarted with a new start price
+        #[pallet::constant]
+        type AuctionDuration: Get<Self::BlockNumber>;
+
         /// A configuration for base priority of unsigned transactions.
         #[pallet::constant]
         type UnsignedPriority: Get<TransactionPriority>;
@@ -263,6 +474,30 @@ pub mod pallet {
         #[pallet::constant]
         type UnsignedLongevity: Get<u64>;
 
+        /// Maximum number of CDPs checked by off-chain worker in one block
+        #[pallet::constant]
+        type OffchainWorkerMaxCdpsPerBlock: Get<u32>;
+
+        /// Maximum number of collateral assets in basket CDP in addition to the main one
+        #[pallet::constant]
+        type MaxBasketCollaterals: Get<u32>;
+
+        /// Oracle prices updated earlier than this number of seconds ago are not used
+        #[pallet::constant]
+        type MaxOraclePriceAge: Get<u64>;
+
+        /// Maximum number of collateral auctions checked for expiration per block
+        #[pallet::constant]
+        type MaxAuctionsPerBlock: Get<u32>;
+
+        /// Number of collateral auction restarts after which the expired auction is closed
+        #[pallet::constant]
+        type MaxAuctionRestarts: Get<u32>;
+
+        /// Maximum savings rate per second risk management can set
+        #[pallet::constant]
+        type MaxSavingsRate: Get<FixedU128>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -278,7 +513,7 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn collateral_infos)]
     pub type CollateralInfos<T: Config> =
-        StorageMap<_, Identity, AssetIdOf<T>, CollateralInfo<T::Moment>>;
+        StorageMap<_, Identity, AssetIdOf<T>, CollateralInfo<T::Moment, T::Symbol>>;
 
     /// Risk parameter
     /// Hard cap of KUSD may be minted by the system
@@ -302,12 +537,74 @@ pub mod pallet {
     pub type CDPDepository<T: Config> =
         StorageMap<_, Identity, CdpId, CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>>;
 
+    /// Collateral assets of basket CDPs in addition to the main collateral asset
+    #[pallet::storage]
+    #[pallet::getter(fn basket_collateral)]
+    pub type BasketCollaterals<T: Config> =
+        StorageDoubleMap<_, Identity, CdpId, Identity, AssetIdOf<T>, Balance, ValueQuery>;
+
     /// Index links owner to CDP ids, not needed by protocol, but used by front-end
     #[pallet::storage]
     #[pallet::getter(fn cdp_owner_index)]
     pub type CdpOwnerIndex<T: Config> =
         StorageMap<_, Identity, AccountIdOf<T>, BoundedVec<CdpId, T::MaxCdpsPerOwner>>;
 
+    /// Collateral auction counter used for auction id
+    #[pallet::storage]
+    pub type NextAuctionId<T> = StorageValue<_, AuctionId, ValueQuery>;
+
+    /// Active collateral auctions, where key is an unique auction identifier
+    #[pallet::storage]
+    #[pallet::getter(fn collateral_auction)]
+    pub type CollateralAuctions<T: Config> = StorageMap<
+        _,
+        Identity,
+        AuctionId,
+        CollateralAuction<AccountIdOf<T>, AssetIdOf<T>, T::BlockNumber>,
+    >;
+
+    /// The last collateral auction checked for expiration, checks continue from the next one
+    #[pallet::storage]
+    #[pallet::getter(fn auctions_cursor)]
+    pub type AuctionsCursor<T> = StorageValue<_, AuctionId>;
+
+    /// Collateral left unsold by closed auctions, kept on treasury account until withdrawn
+    #[pallet::storage]
+    #[pallet::getter(fn unsold_collateral)]
+    pub type UnsoldCollateral<T: Config> =
+        StorageMap<_, Identity, AssetIdOf<T>, Balance, ValueQuery>;
+
+    /// Savings rate per second set by risk management
+    #[pallet::storage]
+    #[pallet::getter(fn savings_rate)]
+    pub type SavingsRate<T> = StorageValue<_, FixedU128, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultSavingsIndex() -> FixedU128 {
+        FixedU128::one()
+    }
+
+    /// Savings accrued for all time, KUSD amount of 1 savings share
+    #[pallet::storage]
+    #[pallet::getter(fn savings_index)]
+    pub type SavingsIndex<T> = StorageValue<_, FixedU128, ValueQuery, DefaultSavingsIndex>;
+
+    /// The last timestamp when savings index was updated
+    #[pallet::storage]
+    #[pallet::getter(fn savings_last_update_time)]
+    pub type SavingsLastUpdateTime<T: Config> = StorageValue<_, T::Moment, ValueQuery>;
+
+    /// Total amount of savings shares
+    #[pallet::storage]
+    #[pallet::getter(fn total_savings_shares)]
+    pub type TotalSavingsShares<T> = StorageValue<_, Balance, ValueQuery>;
+
+    /// Savings shares of accounts
+    #[pallet::storage]
+    #[pallet::getter(fn savings_shares)]
+    pub type SavingsShares<T: Config> =
+        StorageMap<_, Identity, AccountIdOf<T>, Balance, ValueQuery>;
+
     /// Accounts of risk management team
     #[pallet::storage]
     #[pallet::getter(fn risk_managers)]
@@ -365,7 +662,35 @@ pub mod pallet {
         },
         CollateralRiskParametersUpdated {
             collateral_asset_id: AssetIdOf<T>,
-            risk_parameters: CollateralRiskParameters,
+            risk_parameters: CollateralRiskParameters<T::Symbol>,
+        },
+        AuctionStarted {
+            auction_id: AuctionId,
+            cdp_id: CdpId,
+            collateral_asset_id: AssetIdOf<T>,
+            // collateral lot for sale
+            collateral_amount: Balance,
+            // KUSD debt to cover
+            debt: Balance,
+            // KUSD price for 1 collateral token
+            start_price: FixedU128,
+        },
+        AuctionRestarted {
+            auction_id: AuctionId,
+            start_price: FixedU128,
+        },
+        AuctionClosed {
+            auction_id: AuctionId,
+            // unsold collateral kept by protocol
+            collateral_amount: Balance,
+        },
+        AuctionBid {
+            auction_id: AuctionId,
+            bidder: AccountIdOf<T>,
+            // collateral bought
+            collateral_amount: Balance,
+            // KUSD paid
+            kusd_amount: Balance,
         },
         KusdHardCapUpdated {
             hard_cap: Balance,
@@ -379,6 +704,23 @@ pub mod pallet {
         Donation {
             amount: Balance,
         },
+        SavingsDeposited {
+            account: AccountIdOf<T>,
+            // KUSD amount locked
+            amount: Balance,
+        },
+        SavingsWithdrawn {
+            account: AccountIdOf<T>,
+            // KUSD amount unlocked with interest
+            amount: Balance,
+        },
+        SavingsRateUpdated {
+            savings_rate: FixedU128,
+        },
+        UnsoldCollateralWithdrawn {
+            collateral_asset_id: AssetIdOf<T>,
+            amount: Balance,
+        },
     }
 
     #[pallet::error]
@@ -404,6 +746,31 @@ pub mod pallet {
         AccrueWrongTime,
         /// Liquidation lot set in risk parameters is zero, cannot liquidate
         ZeroLiquidationLot,
+        AuctionNotFound,
+        /// Current auction price is higher than the bidder limit
+        AuctionPriceTooHigh,
+        /// Bid for zero collateral amount
+        ZeroBid,
+        /// Oracle has no price for the collateral symbol
+        OraclePriceNotFound,
+        /// Oracle price is outdated
+        OraclePriceStale,
+        /// Oracle symbol of the price source is invalid
+        InvalidOracleSymbol,
+        /// Collateral price sources deviation exceeds the limit, liquidations are paused
+        PriceSourcesDeviation,
+        /// Savings rate exceeds `MaxSavingsRate`
+        SavingsRateTooHigh,
+        /// Savings deposit or withdrawal of zero amount
+        ZeroSavingsAmount,
+        /// Account savings are less than the amount to withdraw
+        SavingsNotEnough,
+        /// Protocol profit is less than the amount to withdraw, savings are not protocol profit
+        ProfitNotEnough,
+        /// Basket CDP has max number of collateral assets
+        TooManyBasketCollaterals,
+        /// Unsold collateral is less than the amount to withdraw
+        UnsoldCollateralNotEnough,
     }
 
     #[pallet::call]
@@ -425,33 +792,13 @@ pub mod pallet {
             borrow_amount: Balance,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let interest_coefficient = Self::collateral_infos(collateral_asset_id)
-                .ok_or(Error::<T>::CollateralInfoNotFound)?
-                .interest_coefficient;
-            let cdp_id = Self::increment_cdp_id()?;
-            Self::insert_cdp(
+            Self::create_cdp_internal(
                 &who,
-                cdp_id,
-                CollateralizedDebtPosition {
-                    owner: who.clone(),
-                    collateral_asset_id,
-                    collateral_amount: balance!(0),
-                    debt: balance!(0),
-                    interest_coefficient,
-                },
-            )?;
-            Self::deposit_event(Event::CDPCreated {
-                cdp_id,
-                owner: who.clone(),
                 collateral_asset_id,
-            });
-            if collateral_amount > 0 {
-                Self::deposit_internal(&who, cdp_id, collateral_amount)?;
-            }
-            if borrow_amount > 0 {
-                Self::borrow_internal(&who, cdp_id, borrow_amount)?;
-            }
-            Ok(())
+                collateral_amount,
+                borrow_amount,
+                CdpType::Single,
+            )
         }
 
         /// Closes a Collateralized Debt Position (CDP).
@@ -467,12 +814,14 @@ pub mod pallet {
             let cdp = Self::accrue_internal(cdp_id)?;
             ensure!(who == cdp.owner, Error::<T>::OperationNotPermitted);
             ensure!(cdp.debt == 0, Error::<T>::OutstandingDebt);
-            technical::Pallet::<T>::transfer_out(
-                &cdp.collateral_asset_id,
-                &T::TreasuryTechAccount::get(),
-                &who,
-                cdp.collateral_amount,
-            )?;
+            for (collateral_asset_id, collateral_amount) in Self::cdp_collaterals(cdp_id, &cdp) {
+                technical::Pallet::<T>::transfer_out(
+                    &collateral_asset_id,
+                    &T::TreasuryTechAccount::get(),
+                    &who,
+                    collateral_amount,
+                )?;
+            }
             Self::delete_cdp(cdp_id)
         }
 
@@ -491,7 +840,8 @@ pub mod pallet {
             collateral_amount: Balance,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            Self::deposit_internal(&who, cdp_id, collateral_amount)
+            let cdp = Self::cdp(cdp_id).ok_or(Error::<T>::CDPNotFound)?;
+            Self::deposit_internal(&who, cdp_id, cdp.collateral_asset_id, collateral_amount)
         }
 
         /// Withdraws collateral from a Collateralized Debt Position (CDP).
@@ -509,31 +859,8 @@ pub mod pallet {
             collateral_amount: Balance,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let cdp = Self::accrue_internal(cdp_id)?;
-            ensure!(who == cdp.owner, Error::<T>::OperationNotPermitted);
-            let new_collateral_amount = cdp
-                .collateral_amount
-                .checked_sub(collateral_amount)
-                .ok_or(Error::<T>::NotEnoughCollateral)?;
-            ensure!(
-                Self::check_cdp_is_safe(cdp.debt, new_collateral_amount, cdp.collateral_asset_id,)?,
-                Error::<T>::CDPUnsafe
-            );
-            technical::Pallet::<T>::transfer_out(
-                &cdp.collateral_asset_id,
-                &T::TreasuryTechAccount::get(),
-                &who,
-                collateral_amount,
-            )?;
-            Self::update_cdp_collateral(cdp_id, new_collateral_amount)?;
-            Self::deposit_event(Event::CollateralWithdrawn {
-                cdp_id,
-                owner: who,
-                collateral_asset_id: cdp.collateral_asset_id,
-                amount: collateral_amount,
-            });
-
-            Ok(())
+            let cdp = Self::cdp(cdp_id).ok_or(Error::<T>::CDPNotFound)?;
+            Self::withdraw_internal(&who, cdp_id, cdp.collateral_asset_id, collateral_amount)
         }
 
         /// Borrows funds against a Collateralized Debt Position (CDP).
@@ -587,6 +914,9 @@ pub mod pallet {
         }
 
         /// Liquidates a Collateralized Debt Position (CDP) if it becomes unsafe.
+        /// Collateral lot is moved from CDP to a new collateral auction together with the
+        /// corresponding part of debt. Basket CDP is liquidated starting from the riskiest
+        /// collateral, i.e. the collateral with the lowest liquidation ratio.
         ///
         /// ## Parameters
         ///
@@ -596,64 +926,85 @@ pub mod pallet {
         #[pallet::weight(<T as Config>::WeightInfo::liquidate())]
         pub fn liquidate(_origin: OriginFor<T>, cdp_id: CdpId) -> DispatchResult {
             let cdp = Self::accrue_internal(cdp_id)?;
+            let collaterals = Self::cdp_collaterals(cdp_id, &cdp);
+            for (collateral_asset_id, _) in collaterals.iter() {
+                Self::ensure_price_sources_agree(collateral_asset_id)?;
+            }
             ensure!(
-                !Self::check_cdp_is_safe(cdp.debt, cdp.collateral_amount, cdp.collateral_asset_id)?,
+                !Self::check_cdp_is_safe(cdp.debt, &collaterals)?,
                 Error::<T>::CDPSafe
             );
-            let technical_account_id = technical::Pallet::<T>::tech_account_id_to_account_id(
-                &T::TreasuryTechAccount::get(),
-            )?;
-            let (collateral_liquidated, proceeds, penalty) =
-                Self::swap(&cdp, &technical_account_id)?;
-            Self::update_cdp_collateral(
-                cdp_id,
-                cdp.collateral_amount
-                    .checked_sub(collateral_liquidated)
-                    .ok_or(Error::<T>::ArithmeticError)?,
-            )?;
-            // KUSD supply change for collateral.
-            let kusd_supply_change: Balance;
-            if cdp.debt >= proceeds {
-                Self::burn_treasury(proceeds)?;
-                let shortage = cdp
-                    .debt
-                    .checked_sub(proceeds)
-                    .ok_or(Error::<T>::CDPNotFound)?;
-                if cdp.collateral_amount <= collateral_liquidated {
-                    // no collateral, total default
-                    // CDP debt is not covered with liquidation, now it is a protocol bad debt
-                    Self::cover_with_protocol(shortage)?;
-                    // close empty CDP, debt == 0, collateral == 0
-                    Self::delete_cdp(cdp_id)?;
-                    kusd_supply_change = cdp.debt;
+            // basket CDP sells the riskiest collateral first
+            let (collateral_asset_id, collateral_amount) = Self::riskiest_collateral(&collaterals)?
+                .unwrap_or((cdp.collateral_asset_id, balance!(0)));
+            let risk_parameters = Self::collateral_infos(collateral_asset_id)
+                .ok_or(Error::<T>::CollateralInfoNotFound)?
+                .risk_parameters;
+            let collateral_to_liquidate =
+                collateral_amount.min(risk_parameters.max_liquidation_lot);
+            ensure!(collateral_to_liquidate > 0, Error::<T>::ZeroLiquidationLot);
+            let is_last_collateral = collaterals.iter().all(|(asset_id, amount)| {
+                *asset_id == collateral_asset_id || *amount == balance!(0)
+            });
+            // debt is moved to auction in proportion to the collateral lot value
+            let debt_to_liquidate =
+                if is_last_collateral && collateral_to_liquidate == collateral_amount {
+                    cdp.debt
                 } else {
-                    // partly covered
-                    Self::update_cdp_debt(cdp_id, shortage)?;
-                    kusd_supply_change = proceeds;
-                }
+                    let lot_share = if is_last_collateral {
+                        FixedU128::checked_from_rational(collateral_to_liquidate, collateral_amount)
+                    } else {
+                        Self::collateral_value(&collateral_asset_id, collateral_to_liquidate)?
+                            .checked_div(&Self::total_collateral_value(&collaterals)?)
+                    }
+                    .ok_or(Error::<T>::ArithmeticError)?;
+                    FixedU128::from_inner(cdp.debt)
+                        .checked_mul(&lot_share)
+                        .ok_or(Error::<T>::ArithmeticError)?
+                        .into_inner()
+                        .min(cdp.debt)
+                };
+            let penalty = Self::liquidation_penalty() * debt_to_liquidate;
+            let new_collateral_amount = collateral_amount
+                .checked_sub(collateral_to_liquidate)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            let new_debt = cdp
+                .debt
+                .checked_sub(debt_to_liquidate)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            if is_last_collateral && new_collateral_amount == 0 && new_debt == 0 {
+                // all the collateral is sold on auction, CDP is empty
+                Self::delete_cdp(cdp_id)?;
             } else {
-                Self::burn_treasury(cdp.debt)?;
-                // CDP debt is covered
-                Self::update_cdp_debt(cdp_id, 0)?;
-                kusd_supply_change = cdp.debt;
-                // There is more KUSD than to cover debt and penalty, leftover goes to cdp.owner
-                let leftover = proceeds
-                    .checked_sub(cdp.debt)
-                    .ok_or(Error::<T>::CDPNotFound)?;
-                assets::Pallet::<T>::transfer_from(
-                    &T::KusdAssetId::get(),
-                    &technical_account_id,
-                    &cdp.owner,
-                    leftover,
-                )?;
-            };
-            Self::decrease_collateral_kusd_supply(&cdp.collateral_asset_id, kusd_supply_change)?;
-            Self::deposit_event(Event::Liquidated {
+                Self::update_cdp_collateral(cdp_id, &collateral_asset_id, new_collateral_amount)?;
+                Self::update_cdp_debt(cdp_id, new_debt)?;
+            }
+            Self::decrease_collateral_kusd_supply(&cdp.collateral_asset_id, debt_to_liquidate)?;
+            let start_price = Self::auction_start_price(&collateral_asset_id)?;
+            let auction_id = Self::increment_auction_id()?;
+            <CollateralAuctions<T>>::insert(
+                auction_id,
+                CollateralAuction {
+                    cdp_id,
+                    cdp_owner: cdp.owner,
+                    collateral_asset_id,
+                    collateral_amount: collateral_to_liquidate,
+                    collateral_sold: balance!(0),
+                    debt: debt_to_liquidate,
+                    penalty,
+                    kusd_raised: balance!(0),
+                    start_price,
+                    started_at: frame_system::Pallet::<T>::block_number(),
+                    restarts: 0,
+                },
+            );
+            Self::deposit_event(Event::AuctionStarted {
+                auction_id,
                 cdp_id,
-                collateral_asset_id: cdp.collateral_asset_id,
-                collateral_amount: collateral_liquidated,
-                proceeds,
-                penalty,
+                collateral_asset_id,
+                collateral_amount: collateral_to_liquidate,
+                debt: debt_to_liquidate,
+                start_price,
             });
 
             Ok(())
@@ -685,7 +1036,7 @@ pub mod pallet {
         pub fn update_collateral_risk_parameters(
             origin: OriginFor<T>,
             collateral_asset_id: AssetIdOf<T>,
-            new_risk_parameters: CollateralRiskParameters,
+            new_risk_parameters: CollateralRiskParameters<T::Symbol>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             Self::ensure_risk_manager(&who)?;
@@ -693,7 +1044,12 @@ pub mod pallet {
                 T::AssetInfoProvider::asset_exists(&collateral_asset_id),
                 Error::<T>::WrongAssetId
             );
-            Self::upsert_collateral_info(&collateral_asset_id, new_risk_parameters)?;
+            if let CollateralPriceSource::Oracle(symbol) | CollateralPriceSource::Median(symbol) =
+                &new_risk_parameters.price_source
+            {
+                ensure!(symbol.is_valid(), Error::<T>::InvalidOracleSymbol);
+            }
+            Self::upsert_collateral_info(&collateral_asset_id, new_risk_parameters.clone())?;
             Self::deposit_event(Event::CollateralRiskParametersUpdated {
                 collateral_asset_id,
                 risk_parameters: new_risk_parameters,
@@ -761,6 +1117,11 @@ pub mod pallet {
         pub fn withdraw_profit(origin: OriginFor<T>, kusd_amount: Balance) -> DispatchResult {
             let who = ensure_signed(origin)?;
             Self::ensure_protocol_owner(&who)?;
+            Self::update_savings_index()?;
+            ensure!(
+                kusd_amount <= Self::protocol_profit()?,
+                Error::<T>::ProfitNotEnough
+            );
             technical::Pallet::<T>::transfer_out(
                 &T::KusdAssetId::get(),
                 &T::TreasuryTechAccount::get(),
@@ -834,42 +1195,354 @@ pub mod pallet {
 
             Ok(())
         }
-    }
-
-    /// Validate unsigned call to this pallet.
-    #[pallet::validate_unsigned]
-    impl<T: Config> ValidateUnsigned for Pallet<T> {
-        type Call = Call<T>;
 
-        /// It is allowed to call only accrue() and liquidate() and only if
-        /// it fulfills conditions.
-        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            match call {
-                // TODO spamming with accrue calls, add some filter to not call too often
-                // https://github.com/sora-xor/sora2-network/issues/878
-                Call::accrue { cdp_id } => {
-                    if Self::is_accruable(cdp_id)
-                        .map_err(|_| InvalidTransaction::Custom(VALIDATION_ERROR_ACCRUE))?
-                    {
-                        ValidTransaction::with_tag_prefix("Kensetsu::accrue")
-                            .priority(T::UnsignedPriority::get())
-                            .longevity(T::UnsignedLongevity::get())
-                            .and_provides([&cdp_id])
-                            .propagate(true)
-                            .build()
-                    } else {
-                        InvalidTransaction::Custom(VALIDATION_ERROR_ACCRUE_NO_DEBT).into()
-                    }
-                }
-                Call::liquidate { cdp_id } => {
-                    let cdp = Self::cdp(cdp_id)
-                        .ok_or(InvalidTransaction::Custom(VALIDATION_ERROR_CHECK_SAFE))?;
-                    if !Self::check_cdp_is_safe(
-                        cdp.debt,
-                        cdp.collateral_amount,
-                        cdp.collateral_asset_id,
-                    )
-                    .map_err(|_| InvalidTransaction::Custom(VALIDATION_ERROR_CHECK_SAFE))?
+        /// Buys collateral on auction at the current auction price.
+        /// If the amount of KUSD to pay exceeds what is left to raise, the collateral amount is
+        /// decreased correspondingly.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `auction_id`: The ID of the collateral auction.
+        /// - `collateral_amount`: The max amount of collateral to buy.
+        /// - `max_price`: The max KUSD price for 1 collateral token the bidder is ready to pay.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::bid())]
+        pub fn bid(
+            origin: OriginFor<T>,
+            auction_id: AuctionId,
+            collateral_amount: Balance,
+            max_price: FixedU128,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(collateral_amount > 0, Error::<T>::ZeroBid);
+            let mut auction =
+                Self::collateral_auction(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+            let price = Self::auction_price(&auction, frame_system::Pallet::<T>::block_number());
+            ensure!(price <= max_price, Error::<T>::AuctionPriceTooHigh);
+            let mut collateral_to_buy = collateral_amount.min(auction.collateral_amount);
+            let mut kusd_to_pay = FixedU128::from_inner(collateral_to_buy)
+                .checked_mul(&price)
+                .ok_or(Error::<T>::ArithmeticError)?
+                .into_inner();
+            let kusd_to_raise = auction.kusd_to_raise();
+            if kusd_to_pay > kusd_to_raise {
+                kusd_to_pay = kusd_to_raise;
+                collateral_to_buy = FixedU128::from_inner(kusd_to_pay)
+                    .checked_div(&price)
+                    .ok_or(Error::<T>::ArithmeticError)?
+                    .into_inner()
+                    .min(collateral_to_buy);
+            }
+            ensure!(collateral_to_buy > 0, Error::<T>::ZeroBid);
+            technical::Pallet::<T>::transfer_in(
+                &T::KusdAssetId::get(),
+                &who,
+                &T::TreasuryTechAccount::get(),
+                kusd_to_pay,
+            )?;
+            technical::Pallet::<T>::transfer_out(
+                &auction.collateral_asset_id,
+                &T::TreasuryTechAccount::get(),
+                &who,
+                collateral_to_buy,
+            )?;
+            // KUSD raised covers debt first and is burned, the rest is a penalty that stays as
+            // protocol profit
+            let debt_covered_before = auction.kusd_raised.min(auction.debt);
+            auction.kusd_raised = auction
+                .kusd_raised
+                .checked_add(kusd_to_pay)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            let debt_covered_after = auction.kusd_raised.min(auction.debt);
+            Self::burn_treasury(
+                debt_covered_after
+                    .checked_sub(debt_covered_before)
+                    .ok_or(Error::<T>::ArithmeticError)?,
+            )?;
+            auction.collateral_amount = auction
+                .collateral_amount
+                .checked_sub(collateral_to_buy)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            auction.collateral_sold = auction
+                .collateral_sold
+                .checked_add(collateral_to_buy)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            Self::deposit_event(Event::AuctionBid {
+                auction_id,
+                bidder: who,
+                collateral_amount: collateral_to_buy,
+                kusd_amount: kusd_to_pay,
+            });
+            if auction.is_finished() {
+                <CollateralAuctions<T>>::remove(auction_id);
+                Self::settle_auction(auction)?;
+            } else {
+                <CollateralAuctions<T>>::insert(auction_id, auction);
+            }
+
+            Ok(())
+        }
+
+        /// Locks KUSD in savings, the savings earn interest with savings rate.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `kusd_amount`: The amount of stablecoin (KUSD) to lock.
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit_savings())]
+        pub fn deposit_savings(origin: OriginFor<T>, kusd_amount: Balance) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(kusd_amount > 0, Error::<T>::ZeroSavingsAmount);
+            let savings_index = Self::update_savings_index()?;
+            let shares = Self::kusd_to_savings_shares(kusd_amount, savings_index, Rounding::Down)?;
+            ensure!(shares > 0, Error::<T>::ZeroSavingsAmount);
+            technical::Pallet::<T>::transfer_in(
+                &T::KusdAssetId::get(),
+                &who,
+                &T::TreasuryTechAccount::get(),
+                kusd_amount,
+            )?;
+            <SavingsShares<T>>::try_mutate(&who, |account_shares| {
+                *account_shares = account_shares
+                    .checked_add(shares)
+                    .ok_or(Error::<T>::ArithmeticError)?;
+                DispatchResult::Ok(())
+            })?;
+            <TotalSavingsShares<T>>::try_mutate(|total_shares| {
+                *total_shares = total_shares
+                    .checked_add(shares)
+                    .ok_or(Error::<T>::ArithmeticError)?;
+                DispatchResult::Ok(())
+            })?;
+            Self::deposit_event(Event::SavingsDeposited {
+                account: who,
+                amount: kusd_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Unlocks KUSD with earned interest from savings.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `kusd_amount`: The amount of stablecoin (KUSD) to unlock.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_savings())]
+        pub fn withdraw_savings(origin: OriginFor<T>, kusd_amount: Balance) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(kusd_amount > 0, Error::<T>::ZeroSavingsAmount);
+            let savings_index = Self::update_savings_index()?;
+            let shares = Self::kusd_to_savings_shares(kusd_amount, savings_index, Rounding::Up)?;
+            <SavingsShares<T>>::try_mutate_exists(&who, |account_shares| {
+                let remaining_shares = account_shares
+                    .unwrap_or_default()
+                    .checked_sub(shares)
+                    .ok_or(Error::<T>::SavingsNotEnough)?;
+                *account_shares = if remaining_shares > 0 {
+                    Some(remaining_shares)
+                } else {
+                    None
+                };
+                DispatchResult::Ok(())
+            })?;
+            <TotalSavingsShares<T>>::try_mutate(|total_shares| {
+                *total_shares = total_shares
+                    .checked_sub(shares)
+                    .ok_or(Error::<T>::ArithmeticError)?;
+                DispatchResult::Ok(())
+            })?;
+            technical::Pallet::<T>::transfer_out(
+                &T::KusdAssetId::get(),
+                &T::TreasuryTechAccount::get(),
+                &who,
+                kusd_amount,
+            )?;
+            Self::deposit_event(Event::SavingsWithdrawn {
+                account: who,
+                amount: kusd_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Updates savings rate, interest accrued with the previous rate is saved in the savings
+        /// index. The rate can not exceed `MaxSavingsRate`.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `new_savings_rate`: The new savings rate per second.
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_savings_rate())]
+        pub fn update_savings_rate(
+            origin: OriginFor<T>,
+            new_savings_rate: FixedU128,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_risk_manager(&who)?;
+            ensure!(
+                new_savings_rate <= T::MaxSavingsRate::get(),
+                Error::<T>::SavingsRateTooHigh
+            );
+            Self::update_savings_index()?;
+            <SavingsRate<T>>::set(new_savings_rate);
+            Self::deposit_event(Event::SavingsRateUpdated {
+                savings_rate: new_savings_rate,
+            });
+
+            Ok(())
+        }
+
+        /// Creates a basket Collateralized Debt Position (CDP) that may hold several collateral
+        /// assets. The main collateral asset defines stability fee and hard cap for the debt,
+        /// other collateral assets are deposited with `deposit_collateral_asset`.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `collateral_asset_id`: The identifier of the main collateral asset.
+        /// - `collateral_amount`: The amount of the main collateral to be deposited.
+        /// - `borrow_amount`: The amount the user wants to borrow.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_basket_cdp())]
+        pub fn create_basket_cdp(
+            origin: OriginFor<T>,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+            borrow_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::create_cdp_internal(
+                &who,
+                collateral_asset_id,
+                collateral_amount,
+                borrow_amount,
+                CdpType::Basket,
+            )
+        }
+
+        /// Deposits collateral asset into a Collateralized Debt Position (CDP).
+        /// Single collateral CDP accepts only its collateral asset.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `cdp_id`: The ID of the CDP to deposit collateral into.
+        /// - `collateral_asset_id`: The identifier of the collateral asset.
+        /// - `collateral_amount`: The amount of collateral to deposit.
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit_collateral_asset())]
+        pub fn deposit_collateral_asset(
+            origin: OriginFor<T>,
+            cdp_id: CdpId,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::deposit_internal(&who, cdp_id, collateral_asset_id, collateral_amount)
+        }
+
+        /// Withdraws collateral asset from a Collateralized Debt Position (CDP).
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `cdp_id`: The ID of the CDP to withdraw collateral from.
+        /// - `collateral_asset_id`: The identifier of the collateral asset.
+        /// - `collateral_amount`: The amount of collateral to withdraw.
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_collateral_asset())]
+        pub fn withdraw_collateral_asset(
+            origin: OriginFor<T>,
+            cdp_id: CdpId,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::withdraw_internal(&who, cdp_id, collateral_asset_id, collateral_amount)
+        }
+
+        /// Withdraws collateral left unsold by closed auctions.
+        ///
+        /// ## Parameters
+        ///
+        /// - `origin`: The origin of the transaction.
+        /// - `collateral_asset_id`: The identifier of the collateral asset.
+        /// - `collateral_amount`: The amount of collateral to withdraw.
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_unsold_collateral())]
+        pub fn withdraw_unsold_collateral(
+            origin: OriginFor<T>,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_protocol_owner(&who)?;
+            <UnsoldCollateral<T>>::try_mutate_exists(&collateral_asset_id, |unsold| {
+                let remaining = unsold
+                    .unwrap_or_default()
+                    .checked_sub(collateral_amount)
+                    .ok_or(Error::<T>::UnsoldCollateralNotEnough)?;
+                *unsold = Some(remaining).filter(|remaining| *remaining > 0);
+                DispatchResult::Ok(())
+            })?;
+            technical::Pallet::<T>::transfer_out(
+                &collateral_asset_id,
+                &T::TreasuryTechAccount::get(),
+                &who,
+                collateral_amount,
+            )?;
+            Self::deposit_event(Event::UnsoldCollateralWithdrawn {
+                collateral_asset_id,
+                amount: collateral_amount,
+            });
+
+            Ok(())
+        }
+    }
+
+    /// Validate unsigned call to this pallet.
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// It is allowed to call only accrue() and liquidate() and only if
+        /// it fulfills conditions.
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                // TODO spamming with accrue calls, add some filter to not call too often
+                // https://github.com/sora-xor/sora2-network/issues/878
+                Call::accrue { cdp_id } => {
+                    if Self::is_accruable(cdp_id)
+                        .map_err(|_| InvalidTransaction::Custom(VALIDATION_ERROR_ACCRUE))?
+                    {
+                        ValidTransaction::with_tag_prefix("Kensetsu::accrue")
+                            .priority(T::UnsignedPriority::get())
+                            .longevity(T::UnsignedLongevity::get())
+                            .and_provides([&cdp_id])
+                            .propagate(true)
+                            .build()
+                    } else {
+                        InvalidTransaction::Custom(VALIDATION_ERROR_ACCRUE_NO_DEBT).into()
+                    }
+                }
+                Call::liquidate { cdp_id } => {
+                    let cdp = Self::cdp(cdp_id)
+                        .ok_or(InvalidTransaction::Custom(VALIDATION_ERROR_CHECK_SAFE))?;
+                    let collaterals = Self::cdp_collaterals(*cdp_id, &cdp);
+                    for (collateral_asset_id, _) in collaterals.iter() {
+                        Self::ensure_price_sources_agree(collateral_asset_id).map_err(|_| {
+                            InvalidTransaction::Custom(VALIDATION_ERROR_PRICE_SOURCES_DEVIATION)
+                        })?;
+                    }
+                    let debt = Self::get_accrued_debt(&cdp)
+                        .map_err(|_| InvalidTransaction::Custom(VALIDATION_ERROR_CHECK_SAFE))?;
+                    if !Self::check_cdp_is_safe(debt, &collaterals)
+                        .map_err(|_| InvalidTransaction::Custom(VALIDATION_ERROR_CHECK_SAFE))?
                     {
                         ValidTransaction::with_tag_prefix("Kensetsu::liquidate")
                             .priority(T::UnsignedPriority::get())
@@ -912,6 +1585,36 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Submits unsigned transaction for CDP from off-chain worker.
+        /// The transaction is not resent if it was sent less than `UnsignedLongevity` blocks ago,
+        /// submission block is saved in off-chain local storage.
+        fn submit_unsigned_for_cdp(
+            key_prefix: &[u8],
+            cdp_id: CdpId,
+            call: Call<T>,
+            block_number: T::BlockNumber,
+        ) {
+            let key = [key_prefix, &cdp_id.encode()].concat();
+            let submitted_at = StorageValueRef::persistent(&key);
+            if let Ok(Some(submitted_block)) = submitted_at.get::<T::BlockNumber>() {
+                let blocks_passed: u64 = block_number
+                    .saturating_sub(submitted_block)
+                    .saturated_into();
+                if blocks_passed < T::UnsignedLongevity::get() {
+                    debug!(
+                        "Skip CDP {:?}, transaction was submitted in block {:?}",
+                        cdp_id, submitted_block
+                    );
+                    return;
+                }
+            }
+            match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.clone().into())
+            {
+                Ok(()) => submitted_at.set(&block_number),
+                Err(err) => warn!("Failed in offchain_worker send {:?}: {:?}", call, err),
+            }
+        }
+
         /// Checks whether a Collateralized Debt Position (CDP) is currently considered safe based on its debt and collateral.
         /// The function evaluates the safety of a CDP based on predefined liquidation ratios and collateral values,
         /// providing an indication of its current safety status.
@@ -919,34 +1622,89 @@ pub mod pallet {
         /// ## Parameters
         ///
         /// - `debt`: The current debt amount in the CDP.
-        /// - `collateral`: The current collateral amount in the CDP.
-        /// - `collateral_asset_id`: The asset ID associated with the collateral in the CDP.
+        /// - `collaterals`: The current collateral asset ids and amounts in the CDP.
         pub(crate) fn check_cdp_is_safe(
             debt: Balance,
-            collateral: Balance,
-            collateral_asset_id: AssetIdOf<T>,
+            collaterals: &[(AssetIdOf<T>, Balance)],
         ) -> Result<bool, DispatchError> {
-            let liquidation_ratio = Self::collateral_infos(collateral_asset_id)
-                .ok_or(Error::<T>::CollateralInfoNotFound)?
-                .risk_parameters
-                .liquidation_ratio;
-            // DAI is assumed as $1
-            let collateral_reference_price =
-                FixedU128::from_inner(T::PriceTools::get_average_price(
-                    &collateral_asset_id,
-                    &DAI.into(),
-                    PriceVariant::Sell,
-                )?);
-            let collateral_volume = collateral_reference_price
-                .checked_mul(&FixedU128::from_inner(collateral))
-                .ok_or(Error::<T>::ArithmeticError)?;
-            let max_safe_debt = FixedU128::from_perbill(liquidation_ratio)
-                .checked_mul(&collateral_volume)
-                .ok_or(Error::<T>::ArithmeticError)?;
+            let max_safe_debt = Self::max_safe_debt(collaterals)?;
             let debt = FixedU128::from_inner(debt);
             Ok(debt <= max_safe_debt)
         }
 
+        /// Returns max debt secured with collaterals, that is collateral value weighted with
+        /// liquidation ratios of collateral assets:
+        /// `max_safe_debt = sum(liquidation_ratio_i * price_i * amount_i)`
+        fn max_safe_debt(
+            collaterals: &[(AssetIdOf<T>, Balance)],
+        ) -> Result<FixedU128, DispatchError> {
+            let mut max_safe_debt = FixedU128::zero();
+            for (collateral_asset_id, collateral_amount) in collaterals {
+                let liquidation_ratio = Self::collateral_infos(collateral_asset_id)
+                    .ok_or(Error::<T>::CollateralInfoNotFound)?
+                    .risk_parameters
+                    .liquidation_ratio;
+                let collateral_volume =
+                    Self::collateral_value(collateral_asset_id, *collateral_amount)?;
+                max_safe_debt = FixedU128::from_perbill(liquidation_ratio)
+                    .checked_mul(&collateral_volume)
+                    .and_then(|safe_debt| max_safe_debt.checked_add(&safe_debt))
+                    .ok_or(Error::<T>::ArithmeticError)?;
+            }
+            Ok(max_safe_debt)
+        }
+
+        /// Returns collateral value in KUSD.
+        fn collateral_value(
+            collateral_asset_id: &AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> Result<FixedU128, DispatchError> {
+            Ok(Self::collateral_reference_price(collateral_asset_id)?
+                .checked_mul(&FixedU128::from_inner(collateral_amount))
+                .ok_or(Error::<T>::ArithmeticError)?)
+        }
+
+        /// Returns total value of collaterals in KUSD.
+        fn total_collateral_value(
+            collaterals: &[(AssetIdOf<T>, Balance)],
+        ) -> Result<FixedU128, DispatchError> {
+            let mut total_value = FixedU128::zero();
+            for (collateral_asset_id, collateral_amount) in collaterals {
+                total_value = total_value
+                    .checked_add(&Self::collateral_value(
+                        collateral_asset_id,
+                        *collateral_amount,
+                    )?)
+                    .ok_or(Error::<T>::ArithmeticError)?;
+            }
+            Ok(total_value)
+        }
+
+        /// Returns the riskiest collateral with non-zero amount, that is the collateral with the
+        /// lowest liquidation ratio. The first one is returned if ratios are equal.
+        fn riskiest_collateral(
+            collaterals: &[(AssetIdOf<T>, Balance)],
+        ) -> Result<Option<(AssetIdOf<T>, Balance)>, DispatchError> {
+            let mut riskiest: Option<(AssetIdOf<T>, Balance, Perbill)> = None;
+            for (collateral_asset_id, collateral_amount) in collaterals {
+                if *collateral_amount == 0 {
+                    continue;
+                }
+                let liquidation_ratio = Self::collateral_infos(collateral_asset_id)
+                    .ok_or(Error::<T>::CollateralInfoNotFound)?
+                    .risk_parameters
+                    .liquidation_ratio;
+                if riskiest.map_or(true, |(_, _, lowest_ratio)| {
+                    liquidation_ratio < lowest_ratio
+                }) {
+                    riskiest = Some((*collateral_asset_id, *collateral_amount, liquidation_ratio));
+                }
+            }
+            Ok(riskiest.map(|(collateral_asset_id, collateral_amount, _)| {
+                (collateral_asset_id, collateral_amount)
+            }))
+        }
+
         /// Ensures that new emission will not exceed collateral hard cap
         fn ensure_collateral_cap(
             collateral_asset_id: AssetIdOf<T>,
@@ -979,6 +1737,53 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Creates CDP of the type and deposits collateral and borrows if amounts are not zero.
+        ///
+        /// ## Parameters
+        ///
+        /// - `who`: The CDP owner.
+        /// - `collateral_asset_id`: The identifier of the main collateral asset.
+        /// - `collateral_amount`: The amount of collateral to be deposited.
+        /// - `borrow_amount`: The amount to be borrowed.
+        /// - `cdp_type`: Single collateral or basket CDP.
+        fn create_cdp_internal(
+            who: &AccountIdOf<T>,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+            borrow_amount: Balance,
+            cdp_type: CdpType,
+        ) -> DispatchResult {
+            let interest_coefficient = Self::collateral_infos(collateral_asset_id)
+                .ok_or(Error::<T>::CollateralInfoNotFound)?
+                .interest_coefficient;
+            let cdp_id = Self::increment_cdp_id()?;
+            Self::insert_cdp(
+                who,
+                cdp_id,
+                CollateralizedDebtPosition {
+                    owner: who.clone(),
+                    collateral_asset_id,
+                    collateral_amount: balance!(0),
+                    debt: balance!(0),
+                    interest_coefficient,
+                    cdp_type,
+                },
+            )?;
+            Self::deposit_event(Event::CDPCreated {
+                cdp_id,
+                owner: who.clone(),
+                collateral_asset_id,
+            });
+            if collateral_amount > 0 {
+                Self::deposit_internal(who, cdp_id, collateral_asset_id, collateral_amount)?;
+            }
+            if borrow_amount > 0 {
+                Self::borrow_internal(who, cdp_id, borrow_amount)?;
+            }
+
+            Ok(())
+        }
+
         /// Deposits collateral to CDP.
         /// Handles internal deposit of collateral into a Collateralized Debt Position (CDP).
         ///
@@ -986,29 +1791,82 @@ pub mod pallet {
         ///
         /// - `who`: The account making the collateral deposit.
         /// - `cdp_id`: The ID of the CDP where the collateral is being deposited.
+        /// - `collateral_asset_id`: The identifier of the collateral asset.
         /// - `collateral_amount`: The amount of collateral being deposited.
         fn deposit_internal(
             who: &AccountIdOf<T>,
             cdp_id: CdpId,
+            collateral_asset_id: AssetIdOf<T>,
             collateral_amount: Balance,
         ) -> DispatchResult {
             let cdp = Self::cdp(cdp_id).ok_or(Error::<T>::CDPNotFound)?;
+            let new_collateral_amount =
+                Self::cdp_collateral_amount(cdp_id, &cdp, &collateral_asset_id)?
+                    .checked_add(collateral_amount)
+                    .ok_or(Error::<T>::ArithmeticError)?;
             technical::Pallet::<T>::transfer_in(
-                &cdp.collateral_asset_id,
+                &collateral_asset_id,
                 who,
                 &T::TreasuryTechAccount::get(),
                 collateral_amount,
             )?;
-            Self::update_cdp_collateral(
+            Self::update_cdp_collateral(cdp_id, &collateral_asset_id, new_collateral_amount)?;
+            Self::deposit_event(Event::CollateralDeposit {
                 cdp_id,
-                cdp.collateral_amount
-                    .checked_add(collateral_amount)
-                    .ok_or(Error::<T>::ArithmeticError)?,
+                owner: who.clone(),
+                collateral_asset_id,
+                amount: collateral_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Handles internal withdrawal of collateral from a Collateralized Debt Position (CDP).
+        /// CDP must stay safe after the withdrawal.
+        ///
+        /// ## Parameters
+        ///
+        /// - `who`: The CDP owner.
+        /// - `cdp_id`: The ID of the CDP to withdraw collateral from.
+        /// - `collateral_asset_id`: The identifier of the collateral asset.
+        /// - `collateral_amount`: The amount of collateral to withdraw.
+        fn withdraw_internal(
+            who: &AccountIdOf<T>,
+            cdp_id: CdpId,
+            collateral_asset_id: AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> DispatchResult {
+            let cdp = Self::accrue_internal(cdp_id)?;
+            ensure!(*who == cdp.owner, Error::<T>::OperationNotPermitted);
+            let new_collateral_amount =
+                Self::cdp_collateral_amount(cdp_id, &cdp, &collateral_asset_id)?
+                    .checked_sub(collateral_amount)
+                    .ok_or(Error::<T>::NotEnoughCollateral)?;
+            let new_collaterals: Vec<(AssetIdOf<T>, Balance)> = Self::cdp_collaterals(cdp_id, &cdp)
+                .into_iter()
+                .map(|(asset_id, amount)| {
+                    if asset_id == collateral_asset_id {
+                        (asset_id, new_collateral_amount)
+                    } else {
+                        (asset_id, amount)
+                    }
+                })
+                .collect();
+            ensure!(
+                Self::check_cdp_is_safe(cdp.debt, &new_collaterals)?,
+                Error::<T>::CDPUnsafe
+            );
+            technical::Pallet::<T>::transfer_out(
+                &collateral_asset_id,
+                &T::TreasuryTechAccount::get(),
+                who,
+                collateral_amount,
             )?;
-            Self::deposit_event(Event::CollateralDeposit {
+            Self::update_cdp_collateral(cdp_id, &collateral_asset_id, new_collateral_amount)?;
+            Self::deposit_event(Event::CollateralWithdrawn {
                 cdp_id,
                 owner: who.clone(),
-                collateral_asset_id: cdp.collateral_asset_id,
+                collateral_asset_id,
                 amount: collateral_amount,
             });
 
@@ -1034,7 +1892,7 @@ pub mod pallet {
                 .checked_add(will_to_borrow_amount)
                 .ok_or(Error::<T>::ArithmeticError)?;
             ensure!(
-                Self::check_cdp_is_safe(new_debt, cdp.collateral_amount, cdp.collateral_asset_id)?,
+                Self::check_cdp_is_safe(new_debt, &Self::cdp_collaterals(cdp_id, &cdp))?,
                 Error::<T>::CDPUnsafe
             );
             Self::ensure_collateral_cap(cdp.collateral_asset_id, will_to_borrow_amount)?;
@@ -1101,43 +1959,71 @@ pub mod pallet {
             Ok(cdp.debt > 0)
         }
 
+        /// Calculates collateral interest coefficient at the timestamp, doesn't change storage.
+        fn calculate_collateral_interest_coefficient(
+            collateral_info: &CollateralInfo<T::Moment, T::Symbol>,
+            now: T::Moment,
+        ) -> Result<FixedU128, DispatchError> {
+            ensure!(
+                now >= collateral_info.last_fee_update_time,
+                Error::<T>::AccrueWrongTime
+            );
+            // do not update if time is the same
+            if now == collateral_info.last_fee_update_time {
+                return Ok(collateral_info.interest_coefficient);
+            }
+            let time_passed = now
+                .checked_sub(&collateral_info.last_fee_update_time)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            let new_coefficient = compound(
+                collateral_info.interest_coefficient.into_inner(),
+                collateral_info.risk_parameters.stability_fee_rate,
+                time_passed
+                    .checked_into::<u64>()
+                    .ok_or(Error::<T>::ArithmeticError)?,
+            )
+            .map_err(|_| Error::<T>::ArithmeticError)?;
+            Ok(FixedU128::from_inner(new_coefficient))
+        }
+
         /// Recalculates collateral interest coefficient with the current timestamp
         fn update_collateral_interest_coefficient(
             collateral_asset_id: &AssetIdOf<T>,
-        ) -> Result<CollateralInfo<T::Moment>, DispatchError> {
+        ) -> Result<CollateralInfo<T::Moment, T::Symbol>, DispatchError> {
             let collateral_info =
                 <CollateralInfos<T>>::try_mutate(collateral_asset_id, |collateral_info| {
                     let collateral_info = collateral_info
                         .as_mut()
                         .ok_or(Error::<T>::CollateralInfoNotFound)?;
                     let now = Timestamp::<T>::get();
-                    ensure!(
-                        now >= collateral_info.last_fee_update_time,
-                        Error::<T>::AccrueWrongTime
-                    );
-                    // do not update if time is the same
-                    if now > collateral_info.last_fee_update_time {
-                        let time_passed = now
-                            .checked_sub(&collateral_info.last_fee_update_time)
-                            .ok_or(Error::<T>::ArithmeticError)?;
-                        let new_coefficient = compound(
-                            collateral_info.interest_coefficient.into_inner(),
-                            collateral_info.risk_parameters.stability_fee_rate,
-                            time_passed
-                                .checked_into::<u64>()
-                                .ok_or(Error::<T>::ArithmeticError)?,
-                        )
-                        .map_err(|_| Error::<T>::ArithmeticError)?;
-                        collateral_info.last_fee_update_time = now;
-                        collateral_info.interest_coefficient =
-                            FixedU128::from_inner(new_coefficient);
-                    }
-                    Ok::<CollateralInfo<T::Moment>, DispatchError>(collateral_info.clone())
+                    collateral_info.interest_coefficient =
+                        Self::calculate_collateral_interest_coefficient(collateral_info, now)?;
+                    collateral_info.last_fee_update_time = now;
+                    Ok::<CollateralInfo<T::Moment, T::Symbol>, DispatchError>(
+                        collateral_info.clone(),
+                    )
                 })?;
 
             Ok(collateral_info)
         }
 
+        /// Calculates stability fee accrued for CDP debt with the new collateral interest
+        /// coefficient.
+        fn calculate_stability_fee(
+            cdp: &CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>,
+            new_coefficient: FixedU128,
+        ) -> Result<Balance, DispatchError> {
+            let interest_percent = (new_coefficient
+                .checked_sub(&cdp.interest_coefficient)
+                .ok_or(Error::<T>::ArithmeticError)?)
+            .checked_div(&cdp.interest_coefficient)
+            .ok_or(Error::<T>::ArithmeticError)?;
+            Ok(FixedU128::from_inner(cdp.debt)
+                .checked_mul(&interest_percent)
+                .ok_or(Error::<T>::ArithmeticError)?
+                .into_inner())
+        }
+
         /// Accrues interest on a Collateralized Debt Position (CDP) and updates relevant parameters.
         ///
         /// ## Parameters
@@ -1151,15 +2037,7 @@ pub mod pallet {
             let collateral_info =
                 Self::update_collateral_interest_coefficient(&cdp.collateral_asset_id)?;
             let new_coefficient = collateral_info.interest_coefficient;
-            let interest_percent = (new_coefficient
-                .checked_sub(&cdp.interest_coefficient)
-                .ok_or(Error::<T>::ArithmeticError)?)
-            .checked_div(&cdp.interest_coefficient)
-            .ok_or(Error::<T>::ArithmeticError)?;
-            let mut stability_fee = FixedU128::from_inner(cdp.debt)
-                .checked_mul(&interest_percent)
-                .ok_or(Error::<T>::ArithmeticError)?
-                .into_inner();
+            let mut stability_fee = Self::calculate_stability_fee(&cdp, new_coefficient)?;
             let new_debt = cdp
                 .debt
                 .checked_add(stability_fee)
@@ -1253,62 +2131,239 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Swaps collateral for KUSD
-        /// ## Returns
-        /// - sold - collateral sold (in swap amount)
-        /// - proceeds - KUSD got from swap (out amount) minus liquidation penalty
-        /// - penalty - liquidation penalty
-        fn swap(
-            cdp: &CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>,
-            technical_account_id: &AccountIdOf<T>,
-        ) -> Result<(Balance, Balance, Balance), DispatchError> {
-            let risk_parameters = Self::collateral_infos(cdp.collateral_asset_id)
+        /// Returns collateral reference price in KUSD from the price source set in collateral
+        /// risk parameters, DAI and USD are assumed as $1.
+        fn collateral_reference_price(
+            collateral_asset_id: &AssetIdOf<T>,
+        ) -> Result<FixedU128, DispatchError> {
+            let risk_parameters = Self::collateral_infos(collateral_asset_id)
                 .ok_or(Error::<T>::CollateralInfoNotFound)?
                 .risk_parameters;
-            let mut desired_kusd_amount = cdp
+            match risk_parameters.price_source {
+                CollateralPriceSource::PriceTools => Self::price_tools_price(collateral_asset_id),
+                CollateralPriceSource::Oracle(symbol) => Self::oracle_price(&symbol),
+                CollateralPriceSource::Median(symbol) => {
+                    // median of 2 values is their average
+                    let (min_price, max_price) = Self::agreed_prices(
+                        collateral_asset_id,
+                        &symbol,
+                        risk_parameters.max_price_deviation,
+                    )?;
+                    Ok(min_price
+                        .checked_add(&max_price)
+                        .ok_or(Error::<T>::ArithmeticError)?
+                        .checked_div(&FixedU128::from(2))
+                        .ok_or(Error::<T>::ArithmeticError)?)
+                }
+            }
+        }
+
+        /// Returns collateral average price in DAI from price-tools.
+        fn price_tools_price(
+            collateral_asset_id: &AssetIdOf<T>,
+        ) -> Result<FixedU128, DispatchError> {
+            Ok(FixedU128::from_inner(T::PriceTools::get_average_price(
+                collateral_asset_id,
+                &DAI.into(),
+                PriceVariant::Sell,
+            )?))
+        }
+
+        /// Returns oracle price of the symbol in USD, fails if the price is older than
+        /// `MaxOraclePriceAge`.
+        fn oracle_price(symbol: &T::Symbol) -> Result<FixedU128, DispatchError> {
+            let rate = T::Oracle::quote(symbol)?.ok_or(Error::<T>::OraclePriceNotFound)?;
+            // oracle rates are updated with timestamps in seconds
+            let now = Timestamp::<T>::get().saturated_into::<u64>() / 1000;
+            ensure!(
+                now.saturating_sub(rate.last_updated) <= T::MaxOraclePriceAge::get(),
+                Error::<T>::OraclePriceStale
+            );
+            Ok(FixedU128::from_inner(rate.value))
+        }
+
+        /// Ensures that collateral price sources agree, i.e. deviation between price-tools and
+        /// oracle prices doesn't exceed `max_price_deviation`. Liquidations are paused otherwise
+        /// since one of the prices may be manipulated.
+        pub(crate) fn ensure_price_sources_agree(
+            collateral_asset_id: &AssetIdOf<T>,
+        ) -> DispatchResult {
+            let risk_parameters = Self::collateral_infos(collateral_asset_id)
+                .ok_or(Error::<T>::CollateralInfoNotFound)?
+                .risk_parameters;
+            if let CollateralPriceSource::Median(symbol) = risk_parameters.price_source {
+                Self::agreed_prices(
+                    collateral_asset_id,
+                    &symbol,
+                    risk_parameters.max_price_deviation,
+                )?;
+            }
+            Ok(())
+        }
+
+        /// Returns price-tools and oracle prices ordered as (min, max) if the max price exceeds
+        /// the min one by no more than `max_price_deviation`.
+        fn agreed_prices(
+            collateral_asset_id: &AssetIdOf<T>,
+            symbol: &T::Symbol,
+            max_price_deviation: Perbill,
+        ) -> Result<(FixedU128, FixedU128), DispatchError> {
+            let price_tools_price = Self::price_tools_price(collateral_asset_id)?;
+            let oracle_price = Self::oracle_price(symbol)?;
+            let (min_price, max_price) = if price_tools_price < oracle_price {
+                (price_tools_price, oracle_price)
+            } else {
+                (oracle_price, price_tools_price)
+            };
+            let max_allowed_price = min_price
+                .checked_mul(
+                    &FixedU128::one()
+                        .checked_add(&FixedU128::from_perbill(max_price_deviation))
+                        .ok_or(Error::<T>::ArithmeticError)?,
+                )
+                .ok_or(Error::<T>::ArithmeticError)?;
+            ensure!(
+                max_price <= max_allowed_price,
+                Error::<T>::PriceSourcesDeviation
+            );
+            Ok((min_price, max_price))
+        }
+
+        /// Returns collateral auction start price.
+        fn auction_start_price(
+            collateral_asset_id: &AssetIdOf<T>,
+        ) -> Result<FixedU128, DispatchError> {
+            Ok(Self::collateral_reference_price(collateral_asset_id)?
+                .checked_mul(&T::AuctionStartPriceMultiplier::get())
+                .ok_or(Error::<T>::ArithmeticError)?)
+        }
+
+        /// Returns collateral auction price at the block, the price decays every block since
+        /// the auction (re)start:
+        /// `price = start_price * (1 - decay) ^ blocks_passed`
+        pub fn auction_price(
+            auction: &CollateralAuction<AccountIdOf<T>, AssetIdOf<T>, T::BlockNumber>,
+            block_number: T::BlockNumber,
+        ) -> FixedU128 {
+            let blocks_passed: u32 = block_number
+                .saturating_sub(auction.started_at)
+                .saturated_into();
+            let decay =
+                FixedU128::from_perbill(Perbill::one().saturating_sub(T::AuctionPriceDecay::get()))
+                    .saturating_pow(blocks_passed as usize);
+            auction.start_price.saturating_mul(decay)
+        }
+
+        /// Restarts collateral auction with a new start price.
+        fn restart_auction(auction_id: AuctionId, now: T::BlockNumber) -> DispatchResult {
+            let start_price = <CollateralAuctions<T>>::try_mutate(auction_id, |auction| {
+                let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+                auction.start_price = Self::auction_start_price(&auction.collateral_asset_id)?;
+                auction.started_at = now;
+                auction.restarts = auction.restarts.saturating_add(1);
+                Ok::<FixedU128, DispatchError>(auction.start_price)
+            })?;
+            Self::deposit_event(Event::AuctionRestarted {
+                auction_id,
+                start_price,
+            });
+
+            Ok(())
+        }
+
+        /// Settles finished collateral auction.
+        /// Collateral leftover is returned to CDP if it still exists or to the CDP owner.
+        /// Debt not covered with the auction is covered with protocol profit or becomes bad debt.
+        fn settle_auction(
+            auction: CollateralAuction<AccountIdOf<T>, AssetIdOf<T>, T::BlockNumber>,
+        ) -> DispatchResult {
+            if auction.collateral_amount > 0 {
+                let returned_to_cdp = match Self::cdp(auction.cdp_id) {
+                    Some(cdp) => {
+                        let new_collateral_amount = Self::cdp_collateral_amount(
+                            auction.cdp_id,
+                            &cdp,
+                            &auction.collateral_asset_id,
+                        )?
+                        .checked_add(auction.collateral_amount)
+                        .ok_or(Error::<T>::ArithmeticError)?;
+                        // basket CDP may have no room for the collateral asset anymore
+                        Self::update_cdp_collateral(
+                            auction.cdp_id,
+                            &auction.collateral_asset_id,
+                            new_collateral_amount,
+                        )
+                        .is_ok()
+                    }
+                    None => false,
+                };
+                if !returned_to_cdp {
+                    technical::Pallet::<T>::transfer_out(
+                        &auction.collateral_asset_id,
+                        &T::TreasuryTechAccount::get(),
+                        &auction.cdp_owner,
+                        auction.collateral_amount,
+                    )?;
+                }
+            }
+            Self::cover_auction_shortage(&auction)
+        }
+
+        /// Closes collateral auction which has not raised enough KUSD in `MaxAuctionRestarts`
+        /// restarts. Unsold collateral is kept by protocol and may be withdrawn with
+        /// `withdraw_unsold_collateral`, debt not covered with the auction is covered with
+        /// protocol profit or becomes bad debt.
+        fn close_auction(
+            auction_id: AuctionId,
+            auction: CollateralAuction<AccountIdOf<T>, AssetIdOf<T>, T::BlockNumber>,
+        ) -> DispatchResult {
+            <CollateralAuctions<T>>::remove(auction_id);
+            <UnsoldCollateral<T>>::try_mutate(&auction.collateral_asset_id, |unsold| {
+                *unsold = unsold
+                    .checked_add(auction.collateral_amount)
+                    .ok_or(Error::<T>::ArithmeticError)?;
+                DispatchResult::Ok(())
+            })?;
+            Self::cover_auction_shortage(&auction)?;
+            Self::deposit_event(Event::AuctionClosed {
+                auction_id,
+                collateral_amount: auction.collateral_amount,
+            });
+
+            Ok(())
+        }
+
+        /// Covers auction debt shortage with protocol and reports liquidation results.
+        fn cover_auction_shortage(
+            auction: &CollateralAuction<AccountIdOf<T>, AssetIdOf<T>, T::BlockNumber>,
+        ) -> DispatchResult {
+            let proceeds = auction.kusd_raised.min(auction.debt);
+            let shortage = auction
                 .debt
-                .checked_add(Self::liquidation_penalty() * cdp.debt)
+                .checked_sub(proceeds)
                 .ok_or(Error::<T>::ArithmeticError)?;
-            let collateral_to_liquidate = cdp
-                .collateral_amount
-                .min(risk_parameters.max_liquidation_lot);
-            ensure!(collateral_to_liquidate > 0, Error::<T>::ZeroLiquidationLot);
-            // With quote before exchange we are sure that it will not result in infinite amount in for exchange and
-            // there is enough liquidity for swap.
-            let SwapOutcome { amount, .. } = T::LiquidityProxy::quote(
-                DEXId::Polkaswap.into(),
-                &cdp.collateral_asset_id,
-                &T::KusdAssetId::get(),
-                QuoteAmount::WithDesiredInput {
-                    desired_amount_in: collateral_to_liquidate,
-                },
-                LiquiditySourceFilter::empty(DEXId::Polkaswap.into()),
-                true,
-            )?;
-            desired_kusd_amount = desired_kusd_amount.min(amount);
-            let swap_outcome = T::LiquidityProxy::exchange(
-                DEXId::Polkaswap.into(),
-                technical_account_id,
-                technical_account_id,
-                &cdp.collateral_asset_id,
-                &T::KusdAssetId::get(),
-                SwapAmount::with_desired_output(desired_kusd_amount, collateral_to_liquidate),
-                LiquiditySourceFilter::empty(DEXId::Polkaswap.into()),
-            )?;
-            // penalty is a protocol profit which stays on treasury tech account
-            let penalty = Self::liquidation_penalty() * swap_outcome.amount.min(cdp.debt);
-            let proceeds = swap_outcome.amount - penalty;
-            Ok((collateral_to_liquidate, proceeds, penalty))
+            if shortage > 0 {
+                Self::cover_with_protocol(shortage)?;
+            }
+            Self::deposit_event(Event::Liquidated {
+                cdp_id: auction.cdp_id,
+                collateral_asset_id: auction.collateral_asset_id.clone(),
+                collateral_amount: auction.collateral_sold,
+                proceeds,
+                penalty: auction
+                    .kusd_raised
+                    .checked_sub(proceeds)
+                    .ok_or(Error::<T>::ArithmeticError)?,
+            });
+
+            Ok(())
         }
 
         /// Cover CDP debt with protocol balance
         /// If protocol balance is less than amount to cover, it is a bad debt
         fn cover_with_protocol(amount: Balance) -> DispatchResult {
-            let treasury_account_id = technical::Pallet::<T>::tech_account_id_to_account_id(
-                &T::TreasuryTechAccount::get(),
-            )?;
-            let protocol_positive_balance =
-                T::AssetInfoProvider::free_balance(&T::KusdAssetId::get(), &treasury_account_id)?;
+            Self::update_savings_index()?;
+            let protocol_positive_balance = Self::protocol_profit()?;
             let to_burn = if amount <= protocol_positive_balance {
                 amount
             } else {
@@ -1329,6 +2384,112 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Recalculates savings index with the current timestamp.
+        fn update_savings_index() -> Result<FixedU128, DispatchError> {
+            let savings_index = Self::accrued_savings_index()?;
+            <SavingsIndex<T>>::set(savings_index);
+            <SavingsLastUpdateTime<T>>::set(Timestamp::<T>::get());
+
+            Ok(savings_index)
+        }
+
+        /// Returns savings index with interest accrued at the current timestamp, doesn't change
+        /// storage. Savings interest is paid from protocol profit, so the accrual is capped by the
+        /// profit, KUSD is never minted for savings interest.
+        fn accrued_savings_index() -> Result<FixedU128, DispatchError> {
+            let now = Timestamp::<T>::get();
+            let last_update_time = Self::savings_last_update_time();
+            ensure!(now >= last_update_time, Error::<T>::AccrueWrongTime);
+            let old_index = Self::savings_index();
+            // do not update if time is the same
+            if now == last_update_time {
+                return Ok(old_index);
+            }
+            let time_passed = now
+                .checked_sub(&last_update_time)
+                .ok_or(Error::<T>::ArithmeticError)?;
+            let new_index = FixedU128::from_inner(
+                compound(
+                    old_index.into_inner(),
+                    Self::savings_rate(),
+                    time_passed
+                        .checked_into::<u64>()
+                        .ok_or(Error::<T>::ArithmeticError)?,
+                )
+                .map_err(|_| Error::<T>::ArithmeticError)?,
+            );
+            let total_shares = Self::total_savings_shares();
+            if total_shares == 0 || new_index <= old_index {
+                return Ok(new_index);
+            }
+            // index increment that spends all the protocol profit on interest
+            let max_increment = FixedU128::from_inner(
+                multiply_by_rational_with_rounding(
+                    Self::protocol_profit()?,
+                    FixedU128::DIV,
+                    total_shares,
+                    Rounding::Down,
+                )
+                .ok_or(Error::<T>::ArithmeticError)?,
+            );
+            let max_index = old_index
+                .checked_add(&max_increment)
+                .ok_or(Error::<T>::ArithmeticError)?;
+
+            Ok(new_index.min(max_index))
+        }
+
+        /// Returns KUSD amount of savings shares with the savings index, rounded down.
+        fn savings_shares_to_kusd(
+            shares: Balance,
+            savings_index: FixedU128,
+        ) -> Result<Balance, DispatchError> {
+            Ok(multiply_by_rational_with_rounding(
+                shares,
+                savings_index.into_inner(),
+                FixedU128::DIV,
+                Rounding::Down,
+            )
+            .ok_or(Error::<T>::ArithmeticError)?)
+        }
+
+        /// Returns savings shares amount for KUSD amount with the savings index.
+        fn kusd_to_savings_shares(
+            kusd_amount: Balance,
+            savings_index: FixedU128,
+            rounding: Rounding,
+        ) -> Result<Balance, DispatchError> {
+            Ok(multiply_by_rational_with_rounding(
+                kusd_amount,
+                FixedU128::DIV,
+                savings_index.into_inner(),
+                rounding,
+            )
+            .ok_or(Error::<T>::ArithmeticError)?)
+        }
+
+        /// Returns protocol profit, KUSD on treasury account that is not locked in savings.
+        /// Savings index must be updated before the call.
+        fn protocol_profit() -> Result<Balance, DispatchError> {
+            let treasury_account_id = technical::Pallet::<T>::tech_account_id_to_account_id(
+                &T::TreasuryTechAccount::get(),
+            )?;
+            let treasury_balance =
+                T::AssetInfoProvider::free_balance(&T::KusdAssetId::get(), &treasury_account_id)?;
+            let savings =
+                Self::savings_shares_to_kusd(Self::total_savings_shares(), Self::savings_index())?;
+            Ok(treasury_balance.saturating_sub(savings))
+        }
+
+        /// Returns account savings in KUSD with interest accrued at the current timestamp,
+        /// doesn't change storage.
+        pub fn get_account_savings(account_id: &AccountIdOf<T>) -> Result<Balance, DispatchError> {
+            Self::savings_shares_to_kusd(
+                Self::savings_shares(account_id),
+                Self::accrued_savings_index()?,
+            )
+        }
+
         /// Increments CDP Id counter, changes storage state.
         fn increment_cdp_id() -> Result<CdpId, DispatchError> {
             NextCDPId::<T>::try_mutate(|cdp_id| {
@@ -1339,6 +2500,16 @@ pub mod pallet {
             })
         }
 
+        /// Increments collateral auction id counter, changes storage state.
+        fn increment_auction_id() -> Result<AuctionId, DispatchError> {
+            NextAuctionId::<T>::try_mutate(|auction_id| {
+                *auction_id = auction_id
+                    .checked_add(1)
+                    .ok_or(crate::pallet::Error::<T>::ArithmeticError)?;
+                Ok(*auction_id)
+            })
+        }
+
         /// Inserts a new CDP
         /// Updates CDP storage and updates index owner -> CDP
         fn insert_cdp(
@@ -1351,11 +2522,67 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::CDPLimitPerUser.into())
         }
 
-        /// Updates CDP collateral balance
-        fn update_cdp_collateral(cdp_id: CdpId, collateral_amount: Balance) -> DispatchResult {
+        /// Returns CDP collateral asset ids and amounts, the main collateral goes first.
+        pub fn cdp_collaterals(
+            cdp_id: CdpId,
+            cdp: &CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>,
+        ) -> Vec<(AssetIdOf<T>, Balance)> {
+            let mut collaterals = sp_std::vec![(cdp.collateral_asset_id, cdp.collateral_amount)];
+            if cdp.cdp_type == CdpType::Basket {
+                collaterals.extend(<BasketCollaterals<T>>::iter_prefix(cdp_id));
+            }
+            collaterals
+        }
+
+        /// Returns CDP collateral amount of the collateral asset.
+        fn cdp_collateral_amount(
+            cdp_id: CdpId,
+            cdp: &CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>,
+            collateral_asset_id: &AssetIdOf<T>,
+        ) -> Result<Balance, DispatchError> {
+            if cdp.collateral_asset_id == *collateral_asset_id {
+                return Ok(cdp.collateral_amount);
+            }
+            ensure!(
+                cdp.cdp_type == CdpType::Basket,
+                Error::<T>::WrongCollateralAssetId
+            );
+            Ok(Self::basket_collateral(cdp_id, collateral_asset_id))
+        }
+
+        /// Updates CDP collateral balance of the collateral asset.
+        /// Basket CDP keeps collateral assets other than the main one in `BasketCollaterals`.
+        fn update_cdp_collateral(
+            cdp_id: CdpId,
+            collateral_asset_id: &AssetIdOf<T>,
+            collateral_amount: Balance,
+        ) -> DispatchResult {
             CDPDepository::<T>::try_mutate(cdp_id, |cdp| {
                 let cdp = cdp.as_mut().ok_or(Error::<T>::CDPNotFound)?;
-                cdp.collateral_amount = collateral_amount;
+                if cdp.collateral_asset_id == *collateral_asset_id {
+                    cdp.collateral_amount = collateral_amount;
+                    return Ok(());
+                }
+                ensure!(
+                    cdp.cdp_type == CdpType::Basket,
+                    Error::<T>::WrongCollateralAssetId
+                );
+                if collateral_amount == 0 {
+                    <BasketCollaterals<T>>::remove(cdp_id, collateral_asset_id);
+                    return Ok(());
+                }
+                if !<BasketCollaterals<T>>::contains_key(cdp_id, collateral_asset_id) {
+                    ensure!(
+                        <CollateralInfos<T>>::contains_key(collateral_asset_id),
+                        Error::<T>::CollateralInfoNotFound
+                    );
+                    ensure!(
+                        (<BasketCollaterals<T>>::iter_prefix(cdp_id).count() as u32)
+                            < T::MaxBasketCollaterals::get(),
+                        Error::<T>::TooManyBasketCollaterals
+                    );
+                }
+                <BasketCollaterals<T>>::insert(cdp_id, collateral_asset_id, collateral_amount);
                 Ok(())
             })
         }
@@ -1372,6 +2599,9 @@ pub mod pallet {
         /// Removes CDP entry from the storage
         fn delete_cdp(cdp_id: CdpId) -> DispatchResult {
             let cdp = <CDPDepository<T>>::take(cdp_id).ok_or(Error::<T>::CDPNotFound)?;
+            if cdp.cdp_type == CdpType::Basket {
+                let _ = <BasketCollaterals<T>>::clear_prefix(cdp_id, u32::MAX, None);
+            }
             if let Some(mut cdp_ids) = <CdpOwnerIndex<T>>::take(&cdp.owner) {
                 cdp_ids.retain(|&x| x != cdp_id);
                 if !cdp_ids.is_empty() {
@@ -1425,7 +2655,7 @@ pub mod pallet {
         /// Else if `CollateralRiskParameters` does not exist, inserts a new value.
         fn upsert_collateral_info(
             collateral_asset_id: &AssetIdOf<T>,
-            new_risk_parameters: CollateralRiskParameters,
+            new_risk_parameters: CollateralRiskParameters<T::Symbol>,
         ) -> DispatchResult {
             <CollateralInfos<T>>::try_mutate(collateral_asset_id, |option_collateral_info| {
                 match option_collateral_info {
@@ -1448,6 +2678,100 @@ pub mod pallet {
             })
         }
 
+        /// Returns CDP debt with interest accrued at the current timestamp, doesn't change storage.
+        pub fn get_accrued_debt(
+            cdp: &CollateralizedDebtPosition<AccountIdOf<T>, AssetIdOf<T>>,
+        ) -> Result<Balance, DispatchError> {
+            let collateral_info = Self::collateral_infos(cdp.collateral_asset_id)
+                .ok_or(Error::<T>::CollateralInfoNotFound)?;
+            let new_coefficient = Self::calculate_collateral_interest_coefficient(
+                &collateral_info,
+                Timestamp::<T>::get(),
+            )?;
+            let stability_fee = Self::calculate_stability_fee(cdp, new_coefficient)?;
+            Ok(cdp
+                .debt
+                .checked_add(stability_fee)
+                .ok_or(Error::<T>::ArithmeticError)?)
+        }
+
+        /// Returns CDP health at the current timestamp: accrued debt, collateral ratio,
+        /// liquidation price and KUSD amount that can be borrowed.
+        pub fn get_cdp_health(cdp_id: CdpId) -> Result<Option<CdpHealth>, DispatchError> {
+            let cdp = match Self::cdp(cdp_id) {
+                Some(cdp) => cdp,
+                None => return Ok(None),
+            };
+            let collateral_info = Self::collateral_infos(cdp.collateral_asset_id)
+                .ok_or(Error::<T>::CollateralInfoNotFound)?;
+            let debt = Self::get_accrued_debt(&cdp)?;
+            let collaterals = Self::cdp_collaterals(cdp_id, &cdp);
+            let collateral_value = Self::total_collateral_value(&collaterals)?;
+            let max_safe_debt = Self::max_safe_debt(&collaterals)?;
+            let (collateral_ratio, liquidation_price) = if debt == 0 {
+                (None, None)
+            } else {
+                let liquidation_price = if cdp.cdp_type == CdpType::Single {
+                    let max_safe_debt_per_collateral =
+                        FixedU128::from_perbill(collateral_info.risk_parameters.liquidation_ratio)
+                            .checked_mul(&FixedU128::from_inner(cdp.collateral_amount))
+                            .ok_or(Error::<T>::ArithmeticError)?;
+                    FixedU128::from_inner(debt).checked_div(&max_safe_debt_per_collateral)
+                } else {
+                    // basket liquidation price depends on prices of all collateral assets
+                    None
+                };
+                (
+                    collateral_value.checked_div(&FixedU128::from_inner(debt)),
+                    liquidation_price,
+                )
+            };
+            let kusd_issued = T::AssetInfoProvider::total_issuance(&T::KusdAssetId::get())?;
+            let max_borrow = max_safe_debt
+                .into_inner()
+                .saturating_sub(debt)
+                .min(
+                    collateral_info
+                        .risk_parameters
+                        .hard_cap
+                        .saturating_sub(collateral_info.kusd_supply),
+                )
+                .min(Self::max_supply().saturating_sub(kusd_issued));
+            Ok(Some(CdpHealth {
+                debt,
+                collateral_value: collateral_value.into_inner(),
+                collateral_ratio,
+                liquidation_price,
+                max_borrow,
+            }))
+        }
+
+        /// Returns ids of unsafe CDPs holding the collateral asset, debt is checked with interest
+        /// accrued at the current timestamp. CDPs which safety can't be checked, e.g. because a
+        /// price of one of the basket collaterals is not available, are skipped.
+        pub fn get_unsafe_cdp_ids(collateral_asset_id: &AssetIdOf<T>) -> Vec<CdpId> {
+            let mut unsafe_cdp_ids = Vec::new();
+            for (cdp_id, cdp) in <CDPDepository<T>>::iter() {
+                let collaterals = Self::cdp_collaterals(cdp_id, &cdp);
+                if !collaterals
+                    .iter()
+                    .any(|(asset_id, _)| asset_id == collateral_asset_id)
+                {
+                    continue;
+                }
+                match Self::get_accrued_debt(&cdp)
+                    .and_then(|debt| Self::check_cdp_is_safe(debt, &collaterals))
+                {
+                    Ok(true) => {}
+                    Ok(false) => unsafe_cdp_ids.push(cdp_id),
+                    Err(err) => {
+                        warn!("Failed to check CDP {:?} safety: {:?}", cdp_id, err);
+                    }
+                }
+            }
+            unsafe_cdp_ids
+        }
+
         /// Returns CDP ids where the account id is owner
         pub fn get_account_cdp_ids(
             account_id: &AccountIdOf<T>,
@@ -0,0 +1,286 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+pub mod v1 {
+    use crate::{
+        CollateralInfo, CollateralInfos, CollateralPriceSource, CollateralRiskParameters, Config,
+        Pallet,
+    };
+    use codec::{Decode, Encode};
+    use common::Balance;
+    use frame_support::log::info;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sp_arithmetic::{FixedU128, Perbill};
+
+    #[cfg(feature = "try-runtime")]
+    use sp_std::prelude::Vec;
+
+    /// Risk parameters before the collateral price source was introduced.
+    #[derive(Debug, Clone, Encode, Decode)]
+    pub struct CollateralRiskParametersV0 {
+        pub hard_cap: Balance,
+        pub liquidation_ratio: Perbill,
+        pub max_liquidation_lot: Balance,
+        pub stability_fee_rate: FixedU128,
+    }
+
+    /// Collateral info before the collateral price source was introduced.
+    #[derive(Debug, Clone, Encode, Decode)]
+    pub struct CollateralInfoV0<Moment> {
+        pub risk_parameters: CollateralRiskParametersV0,
+        pub kusd_supply: Balance,
+        pub last_fee_update_time: Moment,
+        pub interest_coefficient: FixedU128,
+    }
+
+    pub struct MigrateToV1<T>(core::marker::PhantomData<T>);
+
+    /// Migration which sets price-tools as the price source of existing collaterals, it was the
+    /// only source before.
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() >= StorageVersion::new(1) {
+                info!("Migration to version 1 has already been applied");
+                return T::DbWeight::get().reads(1);
+            }
+            info!("Migrating Kensetsu to v1");
+
+            let mut weight = T::DbWeight::get().reads(1);
+            CollateralInfos::<T>::translate::<CollateralInfoV0<T::Moment>, _>(
+                |_collateral_asset_id, collateral_info| {
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+                    let risk_parameters = collateral_info.risk_parameters;
+                    Some(CollateralInfo {
+                        risk_parameters: CollateralRiskParameters {
+                            hard_cap: risk_parameters.hard_cap,
+                            liquidation_ratio: risk_parameters.liquidation_ratio,
+                            max_liquidation_lot: risk_parameters.max_liquidation_lot,
+                            stability_fee_rate: risk_parameters.stability_fee_rate,
+                            price_source: CollateralPriceSource::PriceTools,
+                            max_price_deviation: Perbill::zero(),
+                        },
+                        kusd_supply: collateral_info.kusd_supply,
+                        last_fee_update_time: collateral_info.last_fee_update_time,
+                        interest_coefficient: collateral_info.interest_coefficient,
+                    })
+                },
+            );
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            weight.saturating_add(T::DbWeight::get().writes(1))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            frame_support::ensure!(
+                StorageVersion::get::<Pallet<T>>() == StorageVersion::new(0),
+                "must upgrade linearly"
+            );
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                StorageVersion::get::<Pallet<T>>() == StorageVersion::new(1),
+                "should be upgraded to version 1"
+            );
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::mock::{new_test_ext, TestRuntime};
+        use common::{balance, XOR};
+        use frame_support::traits::{GetStorageVersion as _, OnRuntimeUpgrade, StorageVersion};
+        use sp_runtime::traits::One;
+
+        #[test]
+        fn test() {
+            new_test_ext().execute_with(|| {
+                StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+                frame_support::storage::unhashed::put(
+                    &CollateralInfos::<TestRuntime>::hashed_key_for(XOR),
+                    &CollateralInfoV0 {
+                        risk_parameters: CollateralRiskParametersV0 {
+                            hard_cap: balance!(1000),
+                            liquidation_ratio: Perbill::from_percent(50),
+                            max_liquidation_lot: balance!(100),
+                            stability_fee_rate: FixedU128::one(),
+                        },
+                        kusd_supply: balance!(10),
+                        last_fee_update_time: 123u64,
+                        interest_coefficient: FixedU128::one(),
+                    },
+                );
+
+                MigrateToV1::<TestRuntime>::on_runtime_upgrade();
+
+                assert_eq!(
+                    CollateralInfos::<TestRuntime>::get(XOR),
+                    Some(CollateralInfo {
+                        risk_parameters: CollateralRiskParameters {
+                            hard_cap: balance!(1000),
+                            liquidation_ratio: Perbill::from_percent(50),
+                            max_liquidation_lot: balance!(100),
+                            stability_fee_rate: FixedU128::one(),
+                            price_source: CollateralPriceSource::PriceTools,
+                            max_price_deviation: Perbill::zero(),
+                        },
+                        kusd_supply: balance!(10),
+                        last_fee_update_time: 123,
+                        interest_coefficient: FixedU128::one(),
+                    })
+                );
+                assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+            });
+        }
+    }
+}
+
+pub mod v2 {
+    use crate::{CDPDepository, CdpType, CollateralizedDebtPosition, Config, Pallet};
+    use assets::AssetIdOf;
+    use codec::{Decode, Encode};
+    use common::{AccountIdOf, Balance};
+    use frame_support::log::info;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sp_arithmetic::FixedU128;
+
+    #[cfg(feature = "try-runtime")]
+    use sp_std::prelude::Vec;
+
+    /// CDP before basket CDPs were introduced, all CDPs are single collateral.
+    #[derive(Debug, Clone, Encode, Decode)]
+    pub struct CollateralizedDebtPositionV1<AccountId, AssetId> {
+        pub owner: AccountId,
+        pub collateral_asset_id: AssetId,
+        pub collateral_amount: Balance,
+        pub debt: Balance,
+        pub interest_coefficient: FixedU128,
+    }
+
+    pub struct MigrateToV2<T>(core::marker::PhantomData<T>);
+
+    /// Migration which sets type of existing CDPs to single collateral.
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() >= StorageVersion::new(2) {
+                info!("Migration to version 2 has already been applied");
+                return T::DbWeight::get().reads(1);
+            }
+            info!("Migrating Kensetsu to v2");
+
+            let mut weight = T::DbWeight::get().reads(1);
+            CDPDepository::<T>::translate::<
+                CollateralizedDebtPositionV1<AccountIdOf<T>, AssetIdOf<T>>,
+                _,
+            >(|_cdp_id, cdp| {
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+                Some(CollateralizedDebtPosition {
+                    owner: cdp.owner,
+                    collateral_asset_id: cdp.collateral_asset_id,
+                    collateral_amount: cdp.collateral_amount,
+                    debt: cdp.debt,
+                    interest_coefficient: cdp.interest_coefficient,
+                    cdp_type: CdpType::Single,
+                })
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            weight.saturating_add(T::DbWeight::get().writes(1))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            frame_support::ensure!(
+                StorageVersion::get::<Pallet<T>>() == StorageVersion::new(1),
+                "must upgrade linearly"
+            );
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                StorageVersion::get::<Pallet<T>>() == StorageVersion::new(2),
+                "should be upgraded to version 2"
+            );
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::mock::{new_test_ext, TestRuntime};
+        use crate::test_utils::alice_account_id;
+        use common::{balance, XOR};
+        use frame_support::traits::{GetStorageVersion as _, OnRuntimeUpgrade, StorageVersion};
+        use sp_runtime::traits::One;
+
+        #[test]
+        fn test() {
+            new_test_ext().execute_with(|| {
+                StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+                let cdp_id = 1;
+                frame_support::storage::unhashed::put(
+                    &CDPDepository::<TestRuntime>::hashed_key_for(cdp_id),
+                    &CollateralizedDebtPositionV1 {
+                        owner: alice_account_id(),
+                        collateral_asset_id: XOR,
+                        collateral_amount: balance!(100),
+                        debt: balance!(10),
+                        interest_coefficient: FixedU128::one(),
+                    },
+                );
+
+                MigrateToV2::<TestRuntime>::on_runtime_upgrade();
+
+                assert_eq!(
+                    CDPDepository::<TestRuntime>::get(cdp_id),
+                    Some(CollateralizedDebtPosition {
+                        owner: alice_account_id(),
+                        collateral_asset_id: XOR,
+                        collateral_amount: balance!(100),
+                        debt: balance!(10),
+                        interest_coefficient: FixedU128::one(),
+                        cdp_type: CdpType::Single,
+                    })
+                );
+                assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 2);
+            });
+        }
+    }
+}
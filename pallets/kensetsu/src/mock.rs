@@ -36,9 +36,10 @@ use common::{
     balance, mock_assets_config, mock_common_config, mock_currencies_config,
     mock_frame_system_config, mock_pallet_balances_config, mock_pallet_timestamp_config,
     mock_permissions_config, mock_technical_config, mock_tokens_config, Amount, AssetId32,
-    AssetInfoProvider, AssetName, AssetSymbol, DEXId, FromGenericPair, LiquidityProxyTrait,
-    LiquiditySourceFilter, LiquiditySourceType, PredefinedAssetId, PriceToolsProvider,
-    PriceVariant, DAI, DEFAULT_BALANCE_PRECISION, KUSD, XOR, XST,
+    AssetInfoProvider, AssetName, AssetSymbol, DEXId, DataFeed, FromGenericPair,
+    LiquidityProxyTrait, LiquiditySourceFilter, LiquiditySourceType, PredefinedAssetId,
+    PriceToolsProvider, PriceVariant, Rate, SymbolName, DAI, DEFAULT_BALANCE_PRECISION, KUSD, XOR,
+    XST,
 };
 use currencies::BasicCurrencyAdapter;
 use frame_support::dispatch::DispatchResult;
@@ -46,35 +47,56 @@ use frame_support::traits::{ConstU16, ConstU64, Everything, GenesisBuild};
 use frame_support::{ensure, parameter_types};
 use frame_system::offchain::SendTransactionTypes;
 use hex_literal::hex;
+use parking_lot::RwLock;
 use permissions::Scope;
 use sp_core::crypto::AccountId32;
+use sp_core::offchain::testing::{PoolState, TestOffchainExt, TestTransactionPoolExt};
+use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
 use sp_core::{ConstU32, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
     traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
 };
-use sp_runtime::{DispatchError, MultiSignature};
+use sp_runtime::{DispatchError, FixedU128, MultiSignature, Perbill};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 type AssetId = AssetId32<PredefinedAssetId>;
 type Balance = u128;
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type BlockNumber = u64;
 type Moment = u64;
 type Signature = MultiSignature;
 type TechAccountId = common::TechAccountId<AccountId, TechAssetId, DEXId>;
 type TechAssetId = common::TechAssetId<PredefinedAssetId>;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 
+thread_local! {
+    static PRICE_TOOLS_PRICES: RefCell<BTreeMap<AssetId, Balance>> = RefCell::new(BTreeMap::new());
+    static ORACLE_RATES: RefCell<BTreeMap<SymbolName, (Balance, u64)>> = RefCell::new(BTreeMap::new());
+}
+
 pub struct PriceToolsMock;
 
+impl PriceToolsMock {
+    /// Sets `asset_id` average price
+    pub fn set_price(asset_id: AssetId, price: Balance) {
+        PRICE_TOOLS_PRICES.with(|prices| prices.borrow_mut().insert(asset_id, price));
+    }
+}
+
 impl PriceToolsProvider<AssetId> for PriceToolsMock {
-    /// Returns `asset_id` price is $1
+    /// Returns `asset_id` price set with `set_price`, $1 by default
     fn get_average_price(
-        _input_asset_id: &AssetId,
+        input_asset_id: &AssetId,
         _output_asset_id: &AssetId,
         _price_variant: PriceVariant,
     ) -> Result<Balance, DispatchError> {
-        Ok(balance!(1))
+        Ok(PRICE_TOOLS_PRICES
+            .with(|prices| prices.borrow().get(input_asset_id).copied())
+            .unwrap_or(balance!(1)))
     }
 
     /// Method not used
@@ -83,22 +105,55 @@ impl PriceToolsProvider<AssetId> for PriceToolsMock {
     }
 }
 
+pub struct MockOracle;
+
+impl MockOracle {
+    /// Sets oracle rate for `symbol` updated at the current timestamp
+    pub fn set_rate(symbol: SymbolName, value: Balance) {
+        let now = pallet_timestamp::Pallet::<TestRuntime>::get() / 1000;
+        Self::set_rate_updated_at(symbol, value, now);
+    }
+
+    /// Sets oracle rate for `symbol` updated at `last_updated` timestamp in seconds
+    pub fn set_rate_updated_at(symbol: SymbolName, value: Balance, last_updated: u64) {
+        ORACLE_RATES.with(|rates| rates.borrow_mut().insert(symbol, (value, last_updated)));
+    }
+}
+
+impl DataFeed<SymbolName, Rate, u64> for MockOracle {
+    fn quote(symbol: &SymbolName) -> Result<Option<Rate>, DispatchError> {
+        Ok(Self::quote_unchecked(symbol))
+    }
+
+    fn list_enabled_symbols() -> Result<Vec<(SymbolName, u64)>, DispatchError> {
+        Ok(ORACLE_RATES.with(|rates| {
+            rates
+                .borrow()
+                .keys()
+                .map(|symbol| (symbol.clone(), 0))
+                .collect()
+        }))
+    }
+
+    fn quote_unchecked(symbol: &SymbolName) -> Option<Rate> {
+        ORACLE_RATES.with(|rates| {
+            rates
+                .borrow()
+                .get(symbol)
+                .map(|(value, last_updated)| Rate {
+                    value: *value,
+                    last_updated: *last_updated,
+                    dynamic_fee: Default::default(),
+                    sources: Vec::new(),
+                })
+        })
+    }
+}
+
 pub struct MockLiquidityProxy;
 
 impl MockLiquidityProxy {
     const EXCHANGE_TECH_ACCOUNT: AccountId = AccountId32::new([33u8; 32]);
-
-    /// Sets output amount in KUSD, mints this amount to LiquidityProxy account
-    /// This amount will be used in the next exchange
-    pub fn set_output_amount_for_the_next_exchange(output_amount_kusd: Balance) {
-        assets::Pallet::<TestRuntime>::update_balance(
-            RuntimeOrigin::root(),
-            Self::EXCHANGE_TECH_ACCOUNT,
-            KUSD,
-            output_amount_kusd.try_into().unwrap(),
-        )
-        .expect("must succeed");
-    }
 }
 
 impl LiquidityProxyTrait<DEXId, AccountId, AssetId> for MockLiquidityProxy {
@@ -223,6 +278,13 @@ parameter_types! {
     // 1 day
     pub const AccrueInterestPeriod: Moment = 86_400_000;
 
+    pub AuctionStartPriceMultiplier: FixedU128 = FixedU128::from_rational(12, 10);
+    pub const AuctionPriceDecay: Perbill = Perbill::from_percent(10);
+    pub const AuctionDuration: BlockNumber = 10;
+
+    pub const OffchainWorkerMaxCdpsPerBlock: u32 = 2;
+
+    pub MaxSavingsRate: FixedU128 = FixedU128::from_rational(2, 10);
 }
 
 mock_assets_config!(TestRuntime);
@@ -241,12 +303,23 @@ impl kensetsu::Config for TestRuntime {
     type TreasuryTechAccount = KensetsuTreasuryTechAccountId;
     type KusdAssetId = KusdAssetId;
     type PriceTools = PriceToolsMock;
+    type Symbol = SymbolName;
+    type Oracle = MockOracle;
     type LiquidityProxy = MockLiquidityProxy;
     type MaxCdpsPerOwner = ConstU32<100>;
     type MaxRiskManagementTeamSize = ConstU32<100>;
     type AccrueInterestPeriod = AccrueInterestPeriod;
+    type AuctionStartPriceMultiplier = AuctionStartPriceMultiplier;
+    type AuctionPriceDecay = AuctionPriceDecay;
+    type AuctionDuration = AuctionDuration;
     type UnsignedPriority = ConstU64<100>;
     type UnsignedLongevity = ConstU64<100>;
+    type OffchainWorkerMaxCdpsPerBlock = OffchainWorkerMaxCdpsPerBlock;
+    type MaxBasketCollaterals = ConstU32<1>;
+    type MaxOraclePriceAge = ConstU64<300>;
+    type MaxAuctionsPerBlock = ConstU32<2>;
+    type MaxAuctionRestarts = ConstU32<2>;
+    type MaxSavingsRate = MaxSavingsRate;
     type WeightInfo = ();
 }
 
@@ -353,3 +426,14 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     });
     ext
 }
+
+// Builds testing externalities with off-chain worker and transaction pool extensions
+pub fn new_test_ext_with_offchain() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+    let mut ext = new_test_ext();
+    let (offchain, _offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    (ext, pool_state)
+}
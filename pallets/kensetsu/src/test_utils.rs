@@ -31,13 +31,14 @@
 use super::*;
 use crate::mock::{RuntimeOrigin, TestRuntime};
 
-use common::{AssetInfoProvider, Balance, XOR};
+use common::{AssetInfoProvider, Balance, SymbolName, DAI, KUSD, XOR};
 use frame_support::assert_ok;
 use frame_system::pallet_prelude::OriginFor;
 use hex_literal::hex;
 use sp_arithmetic::Perbill;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{One, Zero};
 use sp_runtime::AccountId32;
+use sp_std::str::FromStr;
 
 type AccountId = AccountId32;
 type KensetsuPallet = Pallet<TestRuntime>;
@@ -125,6 +126,8 @@ pub fn set_xor_as_collateral_type(
                 max_liquidation_lot: balance!(1000),
                 liquidation_ratio,
                 stability_fee_rate,
+                price_source: CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
             },
             kusd_supply: balance!(0),
             last_fee_update_time: 0,
@@ -134,6 +137,48 @@ pub fn set_xor_as_collateral_type(
     KusdHardCap::<TestRuntime>::set(hard_cap);
 }
 
+/// Sets DAI asset id as collateral with default parameters
+/// As if Risk Manager called `update_collateral_risk_parameters(DAI, some_info)`
+pub fn set_dai_as_collateral_type(liquidation_ratio: Perbill) {
+    CollateralInfos::<TestRuntime>::set(
+        DAI,
+        Some(CollateralInfo {
+            risk_parameters: CollateralRiskParameters {
+                hard_cap: Balance::MAX,
+                max_liquidation_lot: balance!(1000),
+                liquidation_ratio,
+                stability_fee_rate: FixedU128::zero(),
+                price_source: CollateralPriceSource::PriceTools,
+                max_price_deviation: Perbill::from_percent(10),
+            },
+            kusd_supply: balance!(0),
+            last_fee_update_time: 0,
+            interest_coefficient: FixedU128::one(),
+        }),
+    );
+}
+
+/// Returns oracle symbol for XOR
+pub fn xor_symbol() -> SymbolName {
+    SymbolName::from_str("XOR").expect("Must be valid symbol")
+}
+
+/// Sets XOR collateral price source
+/// As if Risk Manager changed `price_source` and `max_price_deviation` in risk parameters
+pub fn set_xor_price_source(
+    price_source: CollateralPriceSource<SymbolName>,
+    max_price_deviation: Perbill,
+) {
+    CollateralInfos::<TestRuntime>::mutate(XOR, |collateral_info| {
+        let risk_parameters = &mut collateral_info
+            .as_mut()
+            .expect("XOR must be collateral")
+            .risk_parameters;
+        risk_parameters.price_source = price_source;
+        risk_parameters.max_price_deviation = max_price_deviation;
+    });
+}
+
 /// Creates CDP with XOR as collateral asset id
 pub fn create_cdp_for_xor(
     owner: OriginFor<TestRuntime>,
@@ -145,6 +190,39 @@ pub fn create_cdp_for_xor(
     NextCDPId::<TestRuntime>::get()
 }
 
+/// Creates basket CDP with XOR as the main collateral asset id
+pub fn create_basket_cdp_for_xor(
+    owner: OriginFor<TestRuntime>,
+    collateral: Balance,
+    debt: Balance,
+) -> CdpId {
+    set_balance(alice_account_id(), collateral);
+    assert_ok!(KensetsuPallet::create_basket_cdp(
+        owner, XOR, collateral, debt
+    ));
+    NextCDPId::<TestRuntime>::get()
+}
+
+/// Deposits DAI to basket CDP
+pub fn deposit_dai_to_cdp(
+    owner: OriginFor<TestRuntime>,
+    cdp_id: CdpId,
+    collateral_amount: Balance,
+) {
+    assert_ok!(assets::Pallet::<TestRuntime>::update_balance(
+        RuntimeOrigin::root(),
+        alice_account_id(),
+        DAI,
+        collateral_amount.try_into().unwrap()
+    ));
+    assert_ok!(KensetsuPallet::deposit_collateral_asset(
+        owner,
+        cdp_id,
+        DAI,
+        collateral_amount
+    ));
+}
+
 /// Deposits to CDP
 pub fn deposit_xor_to_cdp(
     owner: OriginFor<TestRuntime>,
@@ -169,6 +247,16 @@ pub fn set_balance(account: AccountId, balance: Balance) {
     ));
 }
 
+/// Updates account KUSD balance
+pub fn set_kusd_balance(account: AccountId, balance: Balance) {
+    assert_ok!(assets::Pallet::<TestRuntime>::update_balance(
+        RuntimeOrigin::root(),
+        account,
+        KUSD,
+        balance.try_into().unwrap()
+    ));
+}
+
 /// Returns total supply for asset.
 pub fn get_total_supply(asset_id: &AssetId) -> Balance {
     <TestRuntime as pallet::Config>::AssetInfoProvider::total_issuance(asset_id)
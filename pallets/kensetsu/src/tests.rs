@@ -30,21 +30,30 @@
 
 use super::*;
 
-use crate::mock::{new_test_ext, MockLiquidityProxy, RuntimeOrigin, TestRuntime};
+use crate::mock::{
+    new_test_ext, new_test_ext_with_offchain, MockExtrinsic, MockOracle, PriceToolsMock,
+    RuntimeCall, RuntimeOrigin, TestRuntime,
+};
 use crate::test_utils::{
-    alice, alice_account_id, assert_bad_debt, assert_balance, bob, create_cdp_for_xor,
-    deposit_xor_to_cdp, get_total_supply, protocol_owner, protocol_owner_account_id, risk_manager,
-    risk_manager_account_id, set_bad_debt, set_balance, set_up_risk_manager,
-    set_xor_as_collateral_type, tech_account_id,
+    alice, alice_account_id, assert_bad_debt, assert_balance, bob, bob_account_id,
+    create_basket_cdp_for_xor, create_cdp_for_xor, deposit_dai_to_cdp, deposit_xor_to_cdp,
+    get_total_supply, protocol_owner, protocol_owner_account_id, risk_manager,
+    risk_manager_account_id, set_bad_debt, set_balance, set_dai_as_collateral_type,
+    set_kusd_balance, set_up_risk_manager, set_xor_as_collateral_type, set_xor_price_source,
+    tech_account_id, xor_symbol,
 };
 
-use common::{balance, AssetId32, Balance, KUSD, XOR};
+use codec::Decode;
+use common::{balance, AssetId32, Balance, SymbolName, DAI, KUSD, XOR};
+use frame_support::traits::Hooks;
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
 use sp_arithmetic::{ArithmeticError, Percent};
 use sp_core::bounded::BoundedVec;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{One, ValidateUnsigned};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
 use sp_runtime::DispatchError::BadOrigin;
+use sp_std::str::FromStr;
 
 type KensetsuError = Error<TestRuntime>;
 type KensetsuPallet = Pallet<TestRuntime>;
@@ -1026,8 +1035,9 @@ fn test_liquidate_cdp_safe() {
 }
 
 /// Given: CDP with collateral 10000 XOR and it is unsafe
-/// @When: Liquidation triggered that sell 1000 XOR and doesn't change debt
-/// Success, debt increased and KUSD is minted to tech treasury account.
+/// @When: Liquidation triggered that puts 1000 XOR on auction
+/// Success, debt increased and KUSD is minted to tech treasury account, part of debt is moved to
+/// auction.
 #[test]
 fn test_liquidate_accrue() {
     new_test_ext().execute_with(|| {
@@ -1040,7 +1050,6 @@ fn test_liquidate_accrue() {
         let debt = balance!(1000);
         let cdp_id = create_cdp_for_xor(alice(), balance!(10000), debt);
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(balance!(0));
         let initial_total_kusd_supply = get_total_supply(&KUSD);
         assert_eq!(initial_total_kusd_supply, debt);
 
@@ -1049,10 +1058,13 @@ fn test_liquidate_accrue() {
         // interest is 1000*10%*1 = 100,
         // where 1000 - initial balance, 10% - per second rate, 1 - seconds passed
         let interest = balance!(100);
+        // 1000 XOR of 10000 XOR is liquidated, so 10% of debt is moved to auction
+        let auction_debt = balance!(110);
         let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
-        assert_eq!(collateral_info.kusd_supply, debt + interest);
+        assert_eq!(collateral_info.kusd_supply, debt + interest - auction_debt);
         let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
-        assert_eq!(cdp.debt, debt + interest);
+        assert_eq!(cdp.debt, debt + interest - auction_debt);
+        assert_eq!(cdp.collateral_amount, balance!(9000));
         assert_balance(&alice_account_id(), &KUSD, debt);
         let total_kusd_supply = get_total_supply(&KUSD);
         assert_eq!(total_kusd_supply, initial_total_kusd_supply + interest);
@@ -1060,14 +1072,12 @@ fn test_liquidate_accrue() {
     });
 }
 
-/// CDP has debt
-/// Liquidation sells only part of collateral.
-/// Liquidation results with output KUSD amount > cdp.debt + liquidation penalty
-/// CDP debt is repaid, corresponding amount of collateral is sold
-/// Liquidation penalty is a protocol profit
-/// Leftover from liquidation goes to CDP owner
+/// Given: unsafe CDP
+/// @When: Liquidation triggered
+/// @Then: collateral lot and debt are moved to a new collateral auction, auction start price is
+/// reference price with a premium.
 #[test]
-fn test_liquidate_kusd_amount_covers_cdp_debt_and_penalty() {
+fn test_liquidate_starts_auction() {
     new_test_ext().execute_with(|| {
         set_up_risk_manager();
         KensetsuPallet::update_liquidation_penalty(risk_manager(), Percent::from_percent(10))
@@ -1077,60 +1087,47 @@ fn test_liquidate_kusd_amount_covers_cdp_debt_and_penalty() {
             Perbill::from_percent(5),
             FixedU128::from_float(0.1),
         );
-        let collateral = balance!(2000);
-        let debt = balance!(100);
-        let collateral_liquidated = balance!(1000);
-        let liquidation_income = balance!(200);
-        let cdp_id = create_cdp_for_xor(alice(), collateral, debt);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(liquidation_income);
+        let cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
         // CDP debt now is 110 KUSD, it is unsafe
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
-        // 100 KUSD debt + 200 KUSD liquidity provider
-        let initial_kusd_supply = get_total_supply(&KUSD);
 
-        // 1000 XOR sold for 200 KUSD
         assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
 
-        let penalty = balance!(11); // (debt + interest) * liquidation penalty
+        // max liquidation lot is 1000 XOR, half of collateral and half of debt are liquidated
+        let auction_id = 1;
         System::assert_has_event(
-            Event::Liquidated {
+            Event::AuctionStarted {
+                auction_id,
                 cdp_id,
                 collateral_asset_id: XOR,
-                collateral_amount: collateral_liquidated,
-                proceeds: liquidation_income - penalty,
-                penalty,
+                collateral_amount: balance!(1000),
+                debt: balance!(55),
+                start_price: FixedU128::from_float(1.2),
             }
             .into(),
         );
-        let interest = balance!(10);
-        assert_balance(&tech_account_id(), &KUSD, interest + penalty);
-        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
-        assert_eq!(collateral_info.kusd_supply, balance!(0));
+        let auction = KensetsuPallet::collateral_auction(auction_id).expect("Must exist");
+        assert_eq!(auction.cdp_id, cdp_id);
+        assert_eq!(auction.cdp_owner, alice_account_id());
+        assert_eq!(auction.penalty, balance!(5.5));
+        assert_eq!(auction.kusd_raised, balance!(0));
+        assert_eq!(auction.started_at, 1);
         let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
-        // initial collateral 2000 XOR, 1000 XOR sold during liquidation
         assert_eq!(cdp.collateral_amount, balance!(1000));
-        assert_eq!(cdp.debt, balance!(0));
-        // alice balance is:
-        // debt (from borrow) + liquidation leftover
-        // where liquidation leftover is (liquidation_income - debt - interest - penalty)
-        assert_balance(
-            &alice_account_id(),
-            &KUSD,
-            liquidation_income - interest - penalty,
-        );
-        let kusd_supply = get_total_supply(&KUSD);
-        // 100 KUSD which is debt amount is burned
-        assert_eq!(initial_kusd_supply - debt, kusd_supply);
+        assert_eq!(cdp.debt, balance!(55));
+        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
+        assert_eq!(collateral_info.kusd_supply, balance!(55));
+        // collateral on auction is kept on treasury account
+        assert_balance(&tech_account_id(), &XOR, balance!(2000));
     });
 }
 
-/// CDP has debt
-/// Liquidation results with output KUSD amount = cdp.debt + liquidation penalty
-/// CDP debt is repaid, corresponding amount of collateral is sold
-/// Liquidation penalty is a protocol profit
+/// Given: unsafe CDP, all the collateral is liquidated.
+/// @When: bid covers debt and liquidation penalty
+/// @Then: debt is burned, penalty is a protocol profit, CDP is closed and collateral leftover
+/// goes to CDP owner.
 #[test]
-fn test_liquidate_kusd_amount_eq_cdp_debt_and_penalty() {
+fn test_bid_covers_debt_and_penalty() {
     new_test_ext().execute_with(|| {
         set_up_risk_manager();
         KensetsuPallet::update_liquidation_penalty(risk_manager(), Percent::from_percent(10))
@@ -1138,60 +1135,69 @@ fn test_liquidate_kusd_amount_eq_cdp_debt_and_penalty() {
         set_xor_as_collateral_type(
             Balance::MAX,
             Perbill::from_percent(5),
-            FixedU128::from_float(0.1),
+            FixedU128::from_float(0.2),
         );
-        let collateral = balance!(2000);
-        let debt = balance!(100);
-        // debt + interest + penalty = 100 + 10 + 11
-        let liquidation_income = balance!(121);
-        let collateral_liquidated = balance!(1000);
-        let cdp_id = create_cdp_for_xor(alice(), collateral, debt);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(liquidation_income);
-        // CDP debt now is 110 KUSD, it is unsafe
+        let cdp_id = create_cdp_for_xor(alice(), balance!(1000), balance!(50));
+        // CDP debt now is 60 KUSD, it is unsafe
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
-        // 100 KUSD debt + 200 KUSD liquidity provider
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        System::assert_has_event(
+            Event::CDPClosed {
+                cdp_id,
+                owner: alice_account_id(),
+                collateral_asset_id: XOR,
+            }
+            .into(),
+        );
+        set_kusd_balance(bob_account_id(), balance!(100));
         let initial_kusd_supply = get_total_supply(&KUSD);
 
-        // 100 XOR sold for 200 KUSD
-        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        // debt + penalty = 60 + 6 = 66 KUSD, price 1.2, 55 XOR is enough
+        assert_ok!(KensetsuPallet::bid(
+            bob(),
+            1,
+            balance!(1000),
+            FixedU128::from_float(1.2)
+        ));
 
-        // (debt + interest) * liquidation penalty = 11 KUSD
-        let penalty = balance!(11);
+        System::assert_has_event(
+            Event::AuctionBid {
+                auction_id: 1,
+                bidder: bob_account_id(),
+                collateral_amount: balance!(55),
+                kusd_amount: balance!(66),
+            }
+            .into(),
+        );
         System::assert_has_event(
             Event::Liquidated {
                 cdp_id,
                 collateral_asset_id: XOR,
-                collateral_amount: collateral_liquidated,
-                proceeds: liquidation_income - penalty,
-                penalty,
+                collateral_amount: balance!(55),
+                proceeds: balance!(60),
+                penalty: balance!(6),
             }
             .into(),
         );
-        let interest = balance!(10);
-        assert_balance(&tech_account_id(), &KUSD, interest + penalty);
-        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
-        assert_eq!(collateral_info.kusd_supply, balance!(0));
-        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
-        // initial collateral 2000 XOR, 1000 XOR sold during liquidation
-        assert_eq!(cdp.collateral_amount, collateral_liquidated);
-        assert_eq!(cdp.debt, balance!(0));
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        let kusd_supply = get_total_supply(&KUSD);
-        // 100 KUSD which is debt amount is burned
-        assert_eq!(initial_kusd_supply - debt, kusd_supply);
+        assert_eq!(KensetsuPallet::collateral_auction(1), None);
+        assert_balance(&bob_account_id(), &KUSD, balance!(34));
+        assert_balance(&bob_account_id(), &XOR, balance!(55));
+        // collateral leftover
+        assert_balance(&alice_account_id(), &XOR, balance!(945));
+        // interest 10 KUSD + penalty 6 KUSD
+        assert_balance(&tech_account_id(), &KUSD, balance!(16));
+        assert_balance(&tech_account_id(), &XOR, balance!(0));
+        assert_bad_debt(balance!(0));
+        // debt is burned
+        assert_eq!(get_total_supply(&KUSD), initial_kusd_supply - balance!(60));
     });
 }
 
-/// CDP has debt and unsafe
-/// Liquidation results with revenue KUSD amount where
-/// revenue KUSD amount > cdp.debt
-/// revenue KUSD amount < cdp.debt + liquidation penalty
-/// CDP debt is repaid, corresponding amount of collateral is sold
-/// Liquidation penalty is a protocol profit
-/// CDP has outstanding debt
+/// Given: collateral auction
+/// @When: bids are placed in different blocks
+/// @Then: auction price decays every block, auction is not finished.
 #[test]
-fn test_liquidate_kusd_amount_covers_cdp_debt_and_partly_penalty() {
+fn test_bid_price_decays() {
     new_test_ext().execute_with(|| {
         set_up_risk_manager();
         KensetsuPallet::update_liquidation_penalty(risk_manager(), Percent::from_percent(10))
@@ -1199,58 +1205,55 @@ fn test_liquidate_kusd_amount_covers_cdp_debt_and_partly_penalty() {
         set_xor_as_collateral_type(
             Balance::MAX,
             Perbill::from_percent(5),
-            FixedU128::from_float(0.1),
+            FixedU128::from_float(0.2),
         );
-        let collateral = balance!(2000);
-        let debt = balance!(100);
-        let collateral_liquidated = balance!(1000);
-        let liquidation_income = balance!(120);
-        let cdp_id = create_cdp_for_xor(alice(), collateral, debt);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(liquidation_income);
-        // CDP debt now is 110 KUSD, it is unsafe
+        let cdp_id = create_cdp_for_xor(alice(), balance!(1000), balance!(50));
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
-        // 100 KUSD debt + 120 KUSD minted for liquidity provider
-        let initial_kusd_supply = get_total_supply(&KUSD);
-
-        // 1000 XOR sold for 120 KUSD
         assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        set_kusd_balance(bob_account_id(), balance!(100));
 
-        // min(liquidation_income, cdp.debt) * liquidation penalty = 11 KUSD
-        let penalty = balance!(11);
-        System::assert_has_event(
-            Event::Liquidated {
-                cdp_id,
-                collateral_asset_id: XOR,
-                collateral_amount: collateral_liquidated,
-                proceeds: liquidation_income - penalty,
-                penalty,
+        assert_ok!(KensetsuPallet::bid(
+            bob(),
+            1,
+            balance!(10),
+            FixedU128::from_float(1.2)
+        ));
+        // price = 1.2 * (1 - 10%) ^ 2 = 0.972
+        System::set_block_number(3);
+        assert_noop!(
+            KensetsuPallet::bid(bob(), 1, balance!(10), FixedU128::from_float(0.9)),
+            KensetsuError::AuctionPriceTooHigh
+        );
+        assert_ok!(KensetsuPallet::bid(
+            bob(),
+            1,
+            balance!(10),
+            FixedU128::from_float(1.0)
+        ));
+
+        System::assert_last_event(
+            Event::AuctionBid {
+                auction_id: 1,
+                bidder: bob_account_id(),
+                collateral_amount: balance!(10),
+                kusd_amount: balance!(9.72),
             }
             .into(),
         );
-        let interest = balance!(10);
-        assert_balance(&tech_account_id(), &KUSD, interest + penalty);
-        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
-        assert_eq!(collateral_info.kusd_supply, balance!(1));
-        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
-        // initial collateral 2000 XOR, 1000 XOR sold during liquidation
-        assert_eq!(cdp.collateral_amount, balance!(1000));
-        // debt = (debt + interest) 110 KUSD + (penalty) 11 KUSD - (liquidation_income) 120 KUSD
-        // = 1 KUSD
-        assert_eq!(cdp.debt, balance!(1));
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        let kusd_supply = get_total_supply(&KUSD);
-        // were minted as interest 10 KUSD
-        // were burned in liquidation (debt + interest) - cdp.debt = 108 KUSD
-        assert_eq!(initial_kusd_supply - debt + cdp.debt, kusd_supply);
+        let auction = KensetsuPallet::collateral_auction(1).expect("Must exist");
+        assert_eq!(auction.collateral_amount, balance!(980));
+        assert_eq!(auction.collateral_sold, balance!(20));
+        assert_eq!(auction.kusd_raised, balance!(21.72));
+        assert_balance(&bob_account_id(), &KUSD, balance!(78.28));
+        assert_balance(&bob_account_id(), &XOR, balance!(20));
     });
 }
 
-// Given: Unsafe CDP
-// Liquidation of all the collateral, debt is covered.
-// CDP is closed, no bad debt, liquidation penalty is a profit.
+/// Given: collateral auction
+/// @When: all the collateral is sold, but the debt is not covered
+/// @Then: shortage is covered by protocol, bad debt increased
 #[test]
-fn test_liquidate_kusd_amount_does_not_cover_cdp_debt() {
+fn test_bid_shortage_bad_debt() {
     new_test_ext().execute_with(|| {
         set_up_risk_manager();
         KensetsuPallet::update_liquidation_penalty(risk_manager(), Percent::from_percent(10))
@@ -1263,123 +1266,271 @@ fn test_liquidate_kusd_amount_does_not_cover_cdp_debt() {
         let collateral = balance!(100);
         let debt = balance!(100);
         let cdp_id = create_cdp_for_xor(alice(), collateral, debt);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        // liquidation amount is the same, 100 XOR
-        let liquidation_income = balance!(100);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(liquidation_income);
         // CDP debt now is 110 KUSD, it is unsafe
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
-        // 100 KUSD debt + 100 KUSD liquidity provider
-        let initial_kusd_supply = get_total_supply(&KUSD);
-
-        // 100 XOR sold for 100 KUSD
+        assert_ok!(KensetsuPallet::accrue(RuntimeOrigin::none(), cdp_id));
+        // withdraw 10 KUSD from interest, so the protocol can not cover bad debt
+        assert_ok!(KensetsuPallet::withdraw_profit(
+            protocol_owner(),
+            balance!(10)
+        ));
         assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        set_kusd_balance(bob_account_id(), balance!(100));
+        // price = 1.2 * (1 - 10%) ^ 2 = 0.972
+        System::set_block_number(3);
+
+        assert_ok!(KensetsuPallet::bid(
+            bob(),
+            1,
+            collateral,
+            FixedU128::from_float(1.0)
+        ));
 
-        // debt * liquidation penalty = 10 KUSD
-        let penalty = balance!(10);
         System::assert_has_event(
             Event::Liquidated {
                 cdp_id,
                 collateral_asset_id: XOR,
                 collateral_amount: collateral,
-                proceeds: liquidation_income - penalty,
-                penalty,
-            }
-            .into(),
-        );
-        System::assert_has_event(
-            Event::CDPClosed {
-                cdp_id,
-                owner: alice_account_id(),
-                collateral_asset_id: XOR,
+                proceeds: balance!(97.2),
+                penalty: balance!(0),
             }
             .into(),
         );
-        // tech account was 10 interest + 10 penalty = 20
-        // debt is 100 + 10 interest
-        // liquidation revenue is 100 - 10 penalty = 90
-        // bad debt = debt - liquidation = 110 - 90 = 20 - covered with protocol profit
+        assert_eq!(KensetsuPallet::collateral_auction(1), None);
+        assert_eq!(KensetsuPallet::cdp(cdp_id), None);
+        // debt 110 KUSD - proceeds 97.2 KUSD
+        assert_bad_debt(balance!(12.8));
         assert_balance(&tech_account_id(), &KUSD, balance!(0));
-        assert_bad_debt(balance!(0));
         let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
         assert_eq!(collateral_info.kusd_supply, balance!(0));
-        assert_eq!(KensetsuPallet::cdp(cdp_id), None);
-        assert_eq!(KensetsuPallet::cdp_owner_index(alice_account_id()), None);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        let kusd_supply = get_total_supply(&KUSD);
-        // 100 KUSD which is debt amount is burned
-        assert_eq!(initial_kusd_supply - debt, kusd_supply);
     });
 }
 
-/// CDP is unsafe
-/// Liquidation results with revenue < debt
-/// Protocol bad debt increased
-/// CDP closed
+/// Bid for collateral auction that doesn't exist or for zero collateral must fail.
+#[test]
+fn test_bid_wrong_params() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KensetsuPallet::bid(bob(), 1, balance!(0), FixedU128::from_float(1.0)),
+            KensetsuError::ZeroBid
+        );
+        assert_noop!(
+            KensetsuPallet::bid(bob(), 1, balance!(10), FixedU128::from_float(1.0)),
+            KensetsuError::AuctionNotFound
+        );
+    });
+}
+
+/// Given: collateral auction
+/// @When: auction duration has passed
+/// @Then: auction is restarted with a new start price
+#[test]
+fn test_auction_restart() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(5),
+            FixedU128::from_float(0.2),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(1000), balance!(50));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+
+        // auction duration is 10 blocks
+        System::set_block_number(10);
+        KensetsuPallet::on_initialize(10);
+        let auction = KensetsuPallet::collateral_auction(1).expect("Must exist");
+        assert_eq!(auction.started_at, 1);
+        assert_eq!(auction.restarts, 0);
+
+        System::set_block_number(11);
+        KensetsuPallet::on_initialize(11);
+
+        System::assert_last_event(
+            Event::AuctionRestarted {
+                auction_id: 1,
+                start_price: FixedU128::from_float(1.2),
+            }
+            .into(),
+        );
+        let auction = KensetsuPallet::collateral_auction(1).expect("Must exist");
+        assert_eq!(auction.started_at, 11);
+        assert_eq!(auction.restarts, 1);
+        assert_eq!(
+            KensetsuPallet::auction_price(&auction, 11),
+            FixedU128::from_float(1.2)
+        );
+    });
+}
+
+/// Given: collateral auction without bids, protocol has no profit
+/// @When: auction expires after `MaxAuctionRestarts` restarts
+/// @Then: auction is closed, collateral is kept by protocol, debt becomes bad debt.
 #[test]
-fn test_liquidate_kusd_bad_debt() {
+fn test_auction_closed_after_max_restarts() {
     new_test_ext().execute_with(|| {
         set_up_risk_manager();
-        KensetsuPallet::update_liquidation_penalty(risk_manager(), Percent::from_percent(10))
-            .expect("Must set liquidation penalty");
         set_xor_as_collateral_type(
             Balance::MAX,
             Perbill::from_percent(100),
             FixedU128::from_float(0.1),
         );
         let collateral = balance!(100);
-        let debt = balance!(100);
-        let cdp_id = create_cdp_for_xor(alice(), collateral, debt);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        // liquidation amount < debt
-        let liquidation_income = balance!(100);
-        MockLiquidityProxy::set_output_amount_for_the_next_exchange(liquidation_income);
+        let cdp_id = create_cdp_for_xor(alice(), collateral, balance!(100));
         // CDP debt now is 110 KUSD, it is unsafe
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
         assert_ok!(KensetsuPallet::accrue(RuntimeOrigin::none(), cdp_id));
-        // withdraw 10 KUSD from interest, so the protocol can not cover bad debt
-        let interest = balance!(10);
-        assert_ok!(KensetsuPallet::withdraw_profit(protocol_owner(), interest));
-        // 110 KUSD debt + 10 KUSD liquidity provider
-        let initial_kusd_supply = get_total_supply(&KUSD);
-
-        // 100 XOR sold for 10 KUSD
+        assert_ok!(KensetsuPallet::withdraw_profit(
+            protocol_owner(),
+            balance!(10)
+        ));
         assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
 
-        // debt * liquidation penalty = 10 KUSD
-        let penalty = balance!(10);
+        // auction duration is 10 blocks, max restarts is 2
+        for block_number in [11, 21] {
+            System::set_block_number(block_number);
+            KensetsuPallet::on_initialize(block_number);
+        }
+        let auction = KensetsuPallet::collateral_auction(1).expect("Must exist");
+        assert_eq!(auction.restarts, 2);
+
+        System::set_block_number(31);
+        KensetsuPallet::on_initialize(31);
+
         System::assert_has_event(
-            Event::Liquidated {
-                cdp_id,
-                collateral_asset_id: XOR,
+            Event::AuctionClosed {
+                auction_id: 1,
                 collateral_amount: collateral,
-                proceeds: liquidation_income - penalty,
-                penalty,
             }
             .into(),
         );
         System::assert_has_event(
-            Event::CDPClosed {
+            Event::Liquidated {
                 cdp_id,
-                owner: alice_account_id(),
                 collateral_asset_id: XOR,
+                collateral_amount: balance!(0),
+                proceeds: balance!(0),
+                penalty: balance!(0),
             }
             .into(),
         );
-        // tech account has 10 KUSD penalty
-        // debt is 100 + 10 interest
-        // liquidation revenue is 100 - 10 penalty = 90
-        // bad debt = debt - liquidation = 110 - 90 = 20 - covered with protocol profit
+        assert_eq!(KensetsuPallet::collateral_auction(1), None);
+        assert_bad_debt(balance!(110));
+        assert_balance(&tech_account_id(), &XOR, collateral);
         assert_balance(&tech_account_id(), &KUSD, balance!(0));
-        assert_bad_debt(balance!(10));
-        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
-        assert_eq!(collateral_info.kusd_supply, balance!(0));
-        assert_eq!(KensetsuPallet::cdp(cdp_id), None);
-        assert_eq!(KensetsuPallet::cdp_owner_index(alice_account_id()), None);
-        assert_balance(&alice_account_id(), &KUSD, debt);
-        let kusd_supply = get_total_supply(&KUSD);
-        // 100 KUSD which is debt amount is burned
-        assert_eq!(initial_kusd_supply - debt, kusd_supply);
+        assert_eq!(KensetsuPallet::unsold_collateral(XOR), collateral);
+    });
+}
+
+/// Given: collateral auction closed without bids
+/// @When: protocol owner withdraws unsold collateral
+/// @Then: collateral is moved from treasury to protocol owner, only unsold amount may be
+/// withdrawn.
+#[test]
+fn test_withdraw_unsold_collateral() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(100),
+            FixedU128::from_float(0.1),
+        );
+        let collateral = balance!(100);
+        let cdp_id = create_cdp_for_xor(alice(), collateral, balance!(100));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        assert_ok!(KensetsuPallet::accrue(RuntimeOrigin::none(), cdp_id));
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        for block_number in [11, 21, 31] {
+            System::set_block_number(block_number);
+            KensetsuPallet::on_initialize(block_number);
+        }
+        assert_eq!(KensetsuPallet::collateral_auction(1), None);
+        assert_eq!(KensetsuPallet::unsold_collateral(XOR), collateral);
+        let owner_balance =
+            assets::Pallet::<TestRuntime>::free_balance(&XOR, &protocol_owner_account_id())
+                .unwrap();
+
+        assert_noop!(
+            KensetsuPallet::withdraw_unsold_collateral(alice(), XOR, balance!(40)),
+            KensetsuError::OperationNotPermitted
+        );
+        assert_noop!(
+            KensetsuPallet::withdraw_unsold_collateral(
+                protocol_owner(),
+                XOR,
+                collateral + balance!(1)
+            ),
+            KensetsuError::UnsoldCollateralNotEnough
+        );
+
+        assert_ok!(KensetsuPallet::withdraw_unsold_collateral(
+            protocol_owner(),
+            XOR,
+            balance!(40)
+        ));
+        System::assert_last_event(
+            Event::UnsoldCollateralWithdrawn {
+                collateral_asset_id: XOR,
+                amount: balance!(40),
+            }
+            .into(),
+        );
+        assert_eq!(KensetsuPallet::unsold_collateral(XOR), balance!(60));
+        assert_balance(&tech_account_id(), &XOR, balance!(60));
+        assert_balance(
+            &protocol_owner_account_id(),
+            &XOR,
+            owner_balance + balance!(40),
+        );
+
+        assert_ok!(KensetsuPallet::withdraw_unsold_collateral(
+            protocol_owner(),
+            XOR,
+            balance!(60)
+        ));
+        assert!(!crate::UnsoldCollateral::<TestRuntime>::contains_key(XOR));
+        assert_balance(&tech_account_id(), &XOR, balance!(0));
+    });
+}
+
+/// Given: 3 expired collateral auctions, 2 auctions are checked per block
+/// @When: on_initialize is called in 2 consecutive blocks
+/// @Then: 2 auctions are restarted in the first block and the last one in the next block.
+#[test]
+fn test_auction_restarts_are_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(100),
+            FixedU128::from_float(0.1),
+        );
+        let cdp_ids: Vec<CdpId> = (0..3)
+            .map(|_| create_cdp_for_xor(alice(), balance!(100), balance!(100)))
+            .collect();
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        for cdp_id in cdp_ids {
+            assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        }
+        let restarted_at = |block_number| {
+            (1..=3)
+                .filter(|auction_id| {
+                    KensetsuPallet::collateral_auction(*auction_id)
+                        .expect("Must exist")
+                        .started_at
+                        == block_number
+                })
+                .count()
+        };
+
+        System::set_block_number(11);
+        KensetsuPallet::on_initialize(11);
+        assert_eq!(restarted_at(11), 2);
+        assert!(KensetsuPallet::auctions_cursor().is_some());
+
+        System::set_block_number(12);
+        KensetsuPallet::on_initialize(12);
+        assert_eq!(restarted_at(12), 1);
+        assert_eq!(KensetsuPallet::auctions_cursor(), None);
     });
 }
 
@@ -1396,6 +1547,8 @@ fn test_liquidate_zero_lot() {
             liquidation_ratio: Perbill::from_percent(100),
             max_liquidation_lot: balance!(0),
             stability_fee_rate: FixedU128::from_float(0.1),
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
         assert_ok!(KensetsuPallet::update_collateral_risk_parameters(
             risk_manager(),
@@ -1673,13 +1826,15 @@ fn test_update_collateral_risk_parameters_only_signed_origin() {
             liquidation_ratio: Perbill::from_percent(50),
             max_liquidation_lot: balance!(100),
             stability_fee_rate: FixedU128::from_float(0.1),
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
 
         assert_noop!(
             KensetsuPallet::update_collateral_risk_parameters(
                 RuntimeOrigin::none(),
                 XOR,
-                parameters
+                parameters.clone()
             ),
             BadOrigin
         );
@@ -1703,6 +1858,8 @@ fn test_update_collateral_risk_parameters_only_risk_manager() {
             liquidation_ratio: Perbill::from_percent(50),
             max_liquidation_lot: balance!(100),
             stability_fee_rate: FixedU128::from_float(0.1),
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
 
         assert_noop!(
@@ -1722,6 +1879,8 @@ fn test_update_collateral_risk_parameters_wrong_asset_id() {
             liquidation_ratio: Perbill::from_percent(50),
             max_liquidation_lot: balance!(100),
             stability_fee_rate: FixedU128::from_float(0.1),
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
         let wrong_asset_id = AssetId32::from_bytes(hex!(
             "0000000000000000000000000000000000000000000000000000000007654321"
@@ -1755,12 +1914,14 @@ fn test_update_collateral_risk_parameters_no_rate_change() {
             liquidation_ratio: Perbill::from_percent(10),
             max_liquidation_lot: balance!(100),
             stability_fee_rate,
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
         assert_ok!(KensetsuPallet::update_collateral_risk_parameters(
             risk_manager(),
             asset_id,
-            old_parameters
+            old_parameters.clone()
         ));
         let old_info = CollateralInfos::<TestRuntime>::get(asset_id).expect("Must succeed");
         assert_eq!(old_info.risk_parameters, old_parameters);
@@ -1772,18 +1933,20 @@ fn test_update_collateral_risk_parameters_no_rate_change() {
             liquidation_ratio: Perbill::from_percent(10),
             max_liquidation_lot: balance!(200),
             stability_fee_rate: FixedU128::from_float(0.2),
+            price_source: CollateralPriceSource::PriceTools,
+            max_price_deviation: Perbill::from_percent(10),
         };
         pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(2);
         assert_ok!(KensetsuPallet::update_collateral_risk_parameters(
             risk_manager(),
             asset_id,
-            new_parameters
+            new_parameters.clone()
         ));
 
         System::assert_has_event(
             Event::CollateralRiskParametersUpdated {
                 collateral_asset_id: XOR,
-                risk_parameters: new_parameters,
+                risk_parameters: new_parameters.clone(),
             }
             .into(),
         );
@@ -2077,7 +2240,25 @@ fn test_withdraw_profit_not_enough() {
 
         assert_noop!(
             KensetsuPallet::withdraw_profit(protocol_owner(), profit),
-            tokens::Error::<TestRuntime>::BalanceTooLow
+            Error::<TestRuntime>::ProfitNotEnough
+        );
+    });
+}
+
+/// Given: KUSD locked in savings, no protocol profit.
+/// @When: protocol owner withdraws profit
+/// @Then: error is returned, savings are not a protocol profit.
+#[test]
+fn test_withdraw_profit_savings_not_withdrawn() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+        assert_balance(&tech_account_id(), &KUSD, balance!(100));
+
+        assert_noop!(
+            KensetsuPallet::withdraw_profit(protocol_owner(), balance!(10)),
+            Error::<TestRuntime>::ProfitNotEnough
         );
     });
 }
@@ -2243,3 +2424,997 @@ fn test_remove_risk_manager_twice() {
         assert!(!risk_managers.unwrap().contains(&risk_manager_account_id()));
     });
 }
+
+/// Given: CDP with debt, stability fee is 25% per millisecond.
+/// @When: CDP health is requested after 1 ms
+/// @Then: debt with accrued interest is returned, storage is not changed.
+#[test]
+fn test_get_cdp_health_accrued_debt() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.25),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        let health = KensetsuPallet::get_cdp_health(cdp_id)
+            .expect("Must succeed")
+            .expect("Must exist");
+
+        assert_eq!(
+            health,
+            CdpHealth {
+                debt: balance!(125),
+                collateral_value: balance!(2000),
+                collateral_ratio: Some(FixedU128::from(16)),
+                liquidation_price: Some(FixedU128::from_float(0.125)),
+                max_borrow: balance!(875),
+            }
+        );
+        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
+        assert_eq!(cdp.debt, balance!(100));
+    });
+}
+
+/// Given: CDP with debt, collateral and protocol hard caps are set.
+/// @When: CDP health is requested
+/// @Then: max borrow amount is limited with hard caps.
+#[test]
+fn test_get_cdp_health_max_borrow_hard_cap() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            balance!(500),
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+
+        let health = KensetsuPallet::get_cdp_health(cdp_id)
+            .expect("Must succeed")
+            .expect("Must exist");
+
+        assert_eq!(health.max_borrow, balance!(400));
+    });
+}
+
+/// Given: CDP without debt.
+/// @When: CDP health is requested
+/// @Then: collateral ratio and liquidation price are not defined.
+#[test]
+fn test_get_cdp_health_no_debt() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.1),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(0));
+
+        let health = KensetsuPallet::get_cdp_health(cdp_id)
+            .expect("Must succeed")
+            .expect("Must exist");
+
+        assert_eq!(health.debt, balance!(0));
+        assert_eq!(health.collateral_ratio, None);
+        assert_eq!(health.liquidation_price, None);
+        assert_eq!(health.max_borrow, balance!(50));
+    });
+}
+
+/// Given: CDP doesn't exist.
+/// @When: CDP health is requested
+/// @Then: None is returned.
+#[test]
+fn test_get_cdp_health_not_found() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(KensetsuPallet::get_cdp_health(1), Ok(None));
+    });
+}
+
+/// Given: two CDPs, one becomes unsafe because of accrued interest.
+/// @When: unsafe CDPs are requested
+/// @Then: only unsafe CDP id is returned.
+#[test]
+fn test_get_unsafe_cdp_ids() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(5),
+            FixedU128::from_float(0.1),
+        );
+        let unsafe_cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        create_cdp_for_xor(alice(), balance!(2000), balance!(50));
+        assert_eq!(KensetsuPallet::get_unsafe_cdp_ids(&XOR), vec![]);
+
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        assert_eq!(
+            KensetsuPallet::get_unsafe_cdp_ids(&XOR),
+            vec![unsafe_cdp_id]
+        );
+    });
+}
+
+/// Given: unsafe CDP and basket CDP with DAI which price is not available.
+/// @When: unsafe CDPs are requested
+/// @Then: the basket CDP is skipped, unsafe CDP id is returned.
+#[test]
+fn test_get_unsafe_cdp_ids_skips_cdp_without_price() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(5),
+            FixedU128::from_float(0.1),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let unsafe_cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        let basket_cdp_id = create_basket_cdp_for_xor(alice(), balance!(2000), balance!(0));
+        deposit_dai_to_cdp(alice(), basket_cdp_id, balance!(100));
+        // there is no oracle rate for DAI
+        CollateralInfos::<TestRuntime>::mutate(DAI, |collateral_info| {
+            collateral_info
+                .as_mut()
+                .expect("DAI must be collateral")
+                .risk_parameters
+                .price_source = CollateralPriceSource::Oracle(SymbolName::from_str("DAI").unwrap());
+        });
+
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        assert_eq!(
+            KensetsuPallet::get_unsafe_cdp_ids(&XOR),
+            vec![unsafe_cdp_id]
+        );
+    });
+}
+
+/// Only risk manager can update savings rate.
+#[test]
+fn test_update_savings_rate_only_risk_manager() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KensetsuPallet::update_savings_rate(alice(), FixedU128::from_float(0.1)),
+            Error::<TestRuntime>::OperationNotPermitted
+        );
+    });
+}
+
+/// Risk manager updates savings rate, event is emitted.
+#[test]
+fn test_update_savings_rate_sunny_day() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        let savings_rate = FixedU128::from_float(0.1);
+
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            savings_rate
+        ));
+
+        System::assert_has_event(Event::SavingsRateUpdated { savings_rate }.into());
+        assert_eq!(KensetsuPallet::savings_rate(), savings_rate);
+    });
+}
+
+/// Savings rate greater than `MaxSavingsRate` must fail.
+#[test]
+fn test_update_savings_rate_too_high() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+
+        assert_noop!(
+            KensetsuPallet::update_savings_rate(risk_manager(), FixedU128::from_float(0.3)),
+            Error::<TestRuntime>::SavingsRateTooHigh
+        );
+    });
+}
+
+/// Deposit of zero amount to savings must fail.
+#[test]
+fn test_deposit_savings_zero_amount() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KensetsuPallet::deposit_savings(alice(), balance!(0)),
+            Error::<TestRuntime>::ZeroSavingsAmount
+        );
+    });
+}
+
+/// Given: account has KUSD.
+/// @When: account deposits KUSD to savings
+/// @Then: KUSD is transferred to treasury, account gets savings shares.
+#[test]
+fn test_deposit_savings_sunny_day() {
+    new_test_ext().execute_with(|| {
+        let amount = balance!(100);
+        set_kusd_balance(alice_account_id(), amount);
+
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), amount));
+
+        System::assert_has_event(
+            Event::SavingsDeposited {
+                account: alice_account_id(),
+                amount,
+            }
+            .into(),
+        );
+        assert_eq!(KensetsuPallet::savings_shares(alice_account_id()), amount);
+        assert_eq!(KensetsuPallet::total_savings_shares(), amount);
+        assert_eq!(
+            KensetsuPallet::get_account_savings(&alice_account_id()),
+            Ok(amount)
+        );
+        assert_balance(&alice_account_id(), &KUSD, balance!(0));
+        assert_balance(&tech_account_id(), &KUSD, amount);
+    });
+}
+
+/// Withdrawal of more than account savings must fail.
+#[test]
+fn test_withdraw_savings_not_enough() {
+    new_test_ext().execute_with(|| {
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+
+        assert_noop!(
+            KensetsuPallet::withdraw_savings(alice(), balance!(101)),
+            Error::<TestRuntime>::SavingsNotEnough
+        );
+        assert_noop!(
+            KensetsuPallet::withdraw_savings(bob(), balance!(1)),
+            Error::<TestRuntime>::SavingsNotEnough
+        );
+    });
+}
+
+/// Given: savings rate is 10% per second, protocol has 100 KUSD profit, 100 KUSD in savings.
+/// @When: savings are withdrawn in 1 second
+/// @Then: interest is paid from protocol profit.
+#[test]
+fn test_withdraw_savings_interest_from_profit() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            FixedU128::from_float(0.1)
+        ));
+        set_kusd_balance(tech_account_id(), balance!(100));
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        assert_eq!(
+            KensetsuPallet::get_account_savings(&alice_account_id()),
+            Ok(balance!(110))
+        );
+
+        assert_ok!(KensetsuPallet::withdraw_savings(alice(), balance!(110)));
+
+        System::assert_has_event(
+            Event::SavingsWithdrawn {
+                account: alice_account_id(),
+                amount: balance!(110),
+            }
+            .into(),
+        );
+        assert_eq!(KensetsuPallet::savings_index(), FixedU128::from_float(1.1));
+        assert_eq!(
+            KensetsuPallet::savings_shares(alice_account_id()),
+            balance!(0)
+        );
+        assert_eq!(KensetsuPallet::total_savings_shares(), balance!(0));
+        assert_balance(&alice_account_id(), &KUSD, balance!(110));
+        assert_balance(&tech_account_id(), &KUSD, balance!(90));
+        assert_bad_debt(balance!(0));
+    });
+}
+
+/// Given: savings rate is 10% per second, protocol has 5 KUSD profit, 100 KUSD in savings.
+/// @When: savings are withdrawn in 1 second
+/// @Then: interest is capped by the protocol profit, nothing is minted and no bad debt appears.
+#[test]
+fn test_withdraw_savings_interest_capped_by_profit() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            FixedU128::from_float(0.1)
+        ));
+        set_kusd_balance(tech_account_id(), balance!(5));
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+        let total_supply = get_total_supply(&KUSD);
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        assert_eq!(
+            KensetsuPallet::get_account_savings(&alice_account_id()),
+            Ok(balance!(105))
+        );
+
+        assert_noop!(
+            KensetsuPallet::withdraw_savings(alice(), balance!(110)),
+            Error::<TestRuntime>::SavingsNotEnough
+        );
+        assert_ok!(KensetsuPallet::withdraw_savings(alice(), balance!(105)));
+
+        assert_eq!(KensetsuPallet::savings_index(), FixedU128::from_float(1.05));
+        assert_balance(&alice_account_id(), &KUSD, balance!(105));
+        assert_balance(&tech_account_id(), &KUSD, balance!(0));
+        assert_eq!(get_total_supply(&KUSD), total_supply);
+        assert_bad_debt(balance!(0));
+    });
+}
+
+/// Given: savings rate is 10% per second, protocol has no profit, 100 KUSD in savings.
+/// @When: savings are withdrawn in 1 second
+/// @Then: no interest is accrued.
+#[test]
+fn test_withdraw_savings_no_profit_no_interest() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            FixedU128::from_float(0.1)
+        ));
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        assert_ok!(KensetsuPallet::withdraw_savings(alice(), balance!(100)));
+
+        assert_eq!(KensetsuPallet::savings_index(), FixedU128::one());
+        assert_balance(&alice_account_id(), &KUSD, balance!(100));
+        assert_balance(&tech_account_id(), &KUSD, balance!(0));
+        assert_bad_debt(balance!(0));
+    });
+}
+
+/// Given: savings rate is 10% per second, 100 KUSD in savings.
+/// @When: savings rate is changed to 0 in 1 second
+/// @Then: interest accrued with the previous rate is kept, no interest after.
+#[test]
+fn test_update_savings_rate_keeps_accrued_interest() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            FixedU128::from_float(0.1)
+        ));
+        set_kusd_balance(tech_account_id(), balance!(100));
+        set_kusd_balance(alice_account_id(), balance!(100));
+        assert_ok!(KensetsuPallet::deposit_savings(alice(), balance!(100)));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        assert_ok!(KensetsuPallet::update_savings_rate(
+            risk_manager(),
+            FixedU128::from_float(0.0)
+        ));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(10);
+
+        assert_eq!(
+            KensetsuPallet::get_account_savings(&alice_account_id()),
+            Ok(balance!(110))
+        );
+    });
+}
+
+/// Returns CDP ids of liquidations submitted by off-chain worker and clears the pool.
+fn take_submitted_liquidations(
+    pool_state: &std::sync::Arc<parking_lot::RwLock<sp_core::offchain::testing::PoolState>>,
+) -> Vec<CdpId> {
+    let transactions = std::mem::take(&mut pool_state.write().transactions);
+    transactions
+        .into_iter()
+        .filter_map(|tx| {
+            match MockExtrinsic::decode(&mut &tx[..])
+                .expect("Must decode transaction")
+                .call
+            {
+                RuntimeCall::Kensetsu(Call::liquidate { cdp_id }) => Some(cdp_id),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Given: safe CDP.
+/// @When: off-chain worker runs
+/// @Then: no liquidation is submitted.
+#[test]
+fn test_offchain_worker_safe_cdp() {
+    let (mut ext, pool_state) = new_test_ext_with_offchain();
+    ext.execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        create_cdp_for_xor(alice(), balance!(100), balance!(10));
+
+        KensetsuPallet::offchain_worker(1);
+
+        assert!(take_submitted_liquidations(&pool_state).is_empty());
+    });
+}
+
+/// Given: 3 CDPs unsafe because of accrued interest, off-chain worker checks 2 CDPs per block.
+/// @When: off-chain worker runs in 3 blocks
+/// @Then: 2 CDPs are liquidated in the first block, 1 in the second, submitted liquidations are
+/// not resent in the third block.
+#[test]
+fn test_offchain_worker_liquidates_unsafe_cdps() {
+    let (mut ext, pool_state) = new_test_ext_with_offchain();
+    ext.execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(5),
+            FixedU128::from_float(0.1),
+        );
+        let cdp_id_1 = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        let cdp_id_2 = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        let cdp_id_3 = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        // CDP debt now is 110 KUSD, it is unsafe
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+
+        KensetsuPallet::offchain_worker(1);
+        assert_eq!(
+            take_submitted_liquidations(&pool_state),
+            vec![cdp_id_1, cdp_id_2]
+        );
+
+        KensetsuPallet::offchain_worker(2);
+        assert_eq!(take_submitted_liquidations(&pool_state), vec![cdp_id_3]);
+
+        KensetsuPallet::offchain_worker(3);
+        assert!(take_submitted_liquidations(&pool_state).is_empty());
+    });
+}
+
+/// Given: unsafe CDP, liquidation was submitted by off-chain worker.
+/// @When: off-chain worker runs when the submitted transaction is outdated
+/// @Then: liquidation is submitted again.
+#[test]
+fn test_offchain_worker_resubmits_outdated_liquidation() {
+    let (mut ext, pool_state) = new_test_ext_with_offchain();
+    ext.execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(5),
+            FixedU128::from_float(0.1),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(2000), balance!(100));
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1);
+        KensetsuPallet::offchain_worker(1);
+        assert_eq!(take_submitted_liquidations(&pool_state), vec![cdp_id]);
+
+        // unsigned transaction longevity is 100 blocks
+        KensetsuPallet::offchain_worker(101);
+
+        assert_eq!(take_submitted_liquidations(&pool_state), vec![cdp_id]);
+    });
+}
+
+/// Given: CDP is safe with the collateral price $1, price source is price-tools.
+/// @When: price-tools price is manipulated
+/// @Then: CDP becomes unsafe and is liquidated.
+#[test]
+fn test_price_source_price_tools_manipulated() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+
+        PriceToolsMock::set_price(XOR, balance!(0.5));
+
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+    });
+}
+
+/// Given: CDP is safe with the collateral price $1, price source is oracle.
+/// @When: price-tools price is manipulated
+/// @Then: CDP is still safe and can not be liquidated.
+#[test]
+fn test_price_source_oracle_not_manipulated() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        MockOracle::set_rate(xor_symbol(), balance!(1));
+        set_xor_price_source(
+            CollateralPriceSource::Oracle(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+
+        PriceToolsMock::set_price(XOR, balance!(0.5));
+
+        assert_noop!(
+            KensetsuPallet::liquidate(alice(), cdp_id),
+            Error::<TestRuntime>::CDPSafe
+        );
+    });
+}
+
+/// Given: price source is oracle, oracle has no price for the symbol.
+/// @When: CDP is liquidated
+/// @Then: error is returned.
+#[test]
+fn test_price_source_oracle_no_price() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+        set_xor_price_source(
+            CollateralPriceSource::Oracle(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+
+        assert_noop!(
+            KensetsuPallet::liquidate(alice(), cdp_id),
+            Error::<TestRuntime>::OraclePriceNotFound
+        );
+    });
+}
+
+/// Given: price source is oracle, oracle price was updated 400 seconds ago.
+/// @When: CDP is liquidated
+/// @Then: error is returned since the price is older than 300 seconds.
+#[test]
+fn test_price_source_oracle_stale_price() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+        set_xor_price_source(
+            CollateralPriceSource::Oracle(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+        pallet_timestamp::Pallet::<TestRuntime>::set_timestamp(1_000_000);
+        MockOracle::set_rate_updated_at(xor_symbol(), balance!(0.5), 600);
+
+        assert_noop!(
+            KensetsuPallet::liquidate(alice(), cdp_id),
+            Error::<TestRuntime>::OraclePriceStale
+        );
+
+        MockOracle::set_rate_updated_at(xor_symbol(), balance!(0.5), 700);
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+    });
+}
+
+/// Given: CDP with the collateral price $1, price source is median of price-tools and oracle,
+/// max deviation is 10%.
+/// @When: price-tools price is manipulated and CDP owner borrows
+/// @Then: the manipulated median price is not used, borrow fails.
+#[test]
+fn test_price_source_median_deviation_blocks_borrow() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        MockOracle::set_rate(xor_symbol(), balance!(1));
+        set_xor_price_source(
+            CollateralPriceSource::Median(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(0));
+
+        PriceToolsMock::set_price(XOR, balance!(10));
+
+        assert_noop!(
+            KensetsuPallet::borrow(alice(), cdp_id, balance!(60)),
+            Error::<TestRuntime>::PriceSourcesDeviation
+        );
+    });
+}
+
+/// Given: CDP is safe with the collateral price $1, price source is median of price-tools and
+/// oracle, max deviation is 10%.
+/// @When: price-tools price is manipulated
+/// @Then: liquidation is paused, unsigned liquidation transaction is invalid.
+#[test]
+fn test_price_source_median_deviation_pauses_liquidation() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        MockOracle::set_rate(xor_symbol(), balance!(1));
+        set_xor_price_source(
+            CollateralPriceSource::Median(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+
+        PriceToolsMock::set_price(XOR, balance!(0.5));
+
+        assert_noop!(
+            KensetsuPallet::liquidate(alice(), cdp_id),
+            Error::<TestRuntime>::PriceSourcesDeviation
+        );
+        assert_eq!(
+            KensetsuPallet::validate_unsigned(
+                TransactionSource::External,
+                &Call::liquidate { cdp_id }
+            ),
+            InvalidTransaction::Custom(VALIDATION_ERROR_PRICE_SOURCES_DEVIATION).into()
+        );
+    });
+}
+
+/// Given: CDP is safe with the collateral price $1, price source is median of price-tools and
+/// oracle, max deviation is 10%.
+/// @When: both prices fall within the allowed deviation
+/// @Then: median price is used and CDP is liquidated.
+#[test]
+fn test_price_source_median_within_deviation() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        MockOracle::set_rate(xor_symbol(), balance!(1));
+        set_xor_price_source(
+            CollateralPriceSource::Median(xor_symbol()),
+            Perbill::from_percent(10),
+        );
+        let cdp_id = create_cdp_for_xor(alice(), balance!(100), balance!(40));
+
+        // median price is 0.77, max safe debt is 38.5 KUSD
+        PriceToolsMock::set_price(XOR, balance!(0.78));
+        MockOracle::set_rate(xor_symbol(), balance!(0.76));
+
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+        System::assert_has_event(
+            Event::AuctionStarted {
+                auction_id: 1,
+                cdp_id,
+                collateral_asset_id: XOR,
+                collateral_amount: balance!(100),
+                debt: balance!(40),
+                start_price: FixedU128::from_inner(balance!(0.924)),
+            }
+            .into(),
+        );
+    });
+}
+
+/// Given: collateral with price-tools price source.
+/// @When: risk manager changes price source to oracle
+/// @Then: risk parameters updated event is emitted with the new price source.
+#[test]
+fn test_update_collateral_price_source() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let mut risk_parameters = KensetsuPallet::collateral_infos(XOR)
+            .expect("Must exist")
+            .risk_parameters;
+        risk_parameters.price_source = CollateralPriceSource::Oracle(xor_symbol());
+        risk_parameters.max_price_deviation = Perbill::from_percent(5);
+
+        assert_ok!(KensetsuPallet::update_collateral_risk_parameters(
+            risk_manager(),
+            XOR,
+            risk_parameters.clone()
+        ));
+
+        System::assert_has_event(
+            Event::CollateralRiskParametersUpdated {
+                collateral_asset_id: XOR,
+                risk_parameters: risk_parameters.clone(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            KensetsuPallet::collateral_infos(XOR)
+                .expect("Must exist")
+                .risk_parameters,
+            risk_parameters
+        );
+    });
+}
+
+/// Given: collateral with price-tools price source.
+/// @When: risk manager sets oracle price source with invalid symbol
+/// @Then: error is returned.
+#[test]
+fn test_update_collateral_price_source_invalid_symbol() {
+    new_test_ext().execute_with(|| {
+        set_up_risk_manager();
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let mut risk_parameters = KensetsuPallet::collateral_infos(XOR)
+            .expect("Must exist")
+            .risk_parameters;
+        risk_parameters.price_source =
+            CollateralPriceSource::Median(SymbolName(b"TOO_LONG_SYMBOL".to_vec()));
+
+        assert_noop!(
+            KensetsuPallet::update_collateral_risk_parameters(risk_manager(), XOR, risk_parameters),
+            Error::<TestRuntime>::InvalidOracleSymbol
+        );
+    });
+}
+
+/// Given: basket CDP with XOR as the main collateral.
+/// @When: DAI is deposited to the CDP.
+/// @Then: DAI is kept in basket collaterals and on treasury account.
+#[test]
+fn test_deposit_collateral_asset_basket() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(10), balance!(0));
+
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(20));
+
+        System::assert_last_event(
+            Event::CollateralDeposit {
+                cdp_id,
+                owner: alice_account_id(),
+                collateral_asset_id: DAI,
+                amount: balance!(20),
+            }
+            .into(),
+        );
+        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
+        assert_eq!(cdp.cdp_type, CdpType::Basket);
+        assert_eq!(cdp.collateral_amount, balance!(10));
+        assert_eq!(KensetsuPallet::basket_collateral(cdp_id, DAI), balance!(20));
+        assert_eq!(
+            KensetsuPallet::cdp_collaterals(cdp_id, &cdp),
+            vec![(XOR, balance!(10)), (DAI, balance!(20))]
+        );
+        assert_balance(&tech_account_id(), &DAI, balance!(20));
+    });
+}
+
+/// Given: single collateral CDP with XOR.
+/// @When: DAI is deposited to the CDP.
+/// @Then: WrongCollateralAssetId error is returned.
+#[test]
+fn test_deposit_collateral_asset_single_cdp() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_cdp_for_xor(alice(), balance!(10), balance!(0));
+
+        assert_noop!(
+            KensetsuPallet::deposit_collateral_asset(alice(), cdp_id, DAI, balance!(1)),
+            KensetsuError::WrongCollateralAssetId
+        );
+    });
+}
+
+/// Given: basket CDP, DAI is not a collateral type.
+/// @When: DAI is deposited to the CDP.
+/// @Then: CollateralInfoNotFound error is returned.
+#[test]
+fn test_deposit_collateral_asset_not_collateral_type() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(10), balance!(0));
+        assert_ok!(assets::Pallet::<TestRuntime>::update_balance(
+            RuntimeOrigin::root(),
+            alice_account_id(),
+            DAI,
+            balance!(1).try_into().unwrap()
+        ));
+
+        assert_noop!(
+            KensetsuPallet::deposit_collateral_asset(alice(), cdp_id, DAI, balance!(1)),
+            KensetsuError::CollateralInfoNotFound
+        );
+    });
+}
+
+/// Given: basket CDP with max number of collateral assets, mock limit is 1 in addition to the
+/// main one.
+/// @When: another collateral asset is deposited.
+/// @Then: TooManyBasketCollaterals error is returned.
+#[test]
+fn test_deposit_collateral_asset_too_many_collaterals() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        CollateralInfos::<TestRuntime>::set(KUSD, KensetsuPallet::collateral_infos(DAI));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(10), balance!(0));
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(20));
+        set_kusd_balance(alice_account_id(), balance!(1));
+
+        assert_noop!(
+            KensetsuPallet::deposit_collateral_asset(alice(), cdp_id, KUSD, balance!(1)),
+            KensetsuError::TooManyBasketCollaterals
+        );
+    });
+}
+
+/// Given: basket CDP with 100 XOR with liquidation ratio 50% and 100 DAI with liquidation
+/// ratio 80%, prices are $1.
+/// @When: user borrows.
+/// @Then: max safe debt is the collateral value weighted with liquidation ratios, 130 KUSD.
+#[test]
+fn test_borrow_basket_weighted_liquidation_ratio() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(100), balance!(0));
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(100));
+
+        assert_noop!(
+            KensetsuPallet::borrow(alice(), cdp_id, balance!(130) + 1),
+            KensetsuError::CDPUnsafe
+        );
+        assert_ok!(KensetsuPallet::borrow(alice(), cdp_id, balance!(130)));
+
+        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
+        assert_eq!(cdp.debt, balance!(130));
+        let health = KensetsuPallet::get_cdp_health(cdp_id)
+            .expect("Must succeed")
+            .expect("Must exist");
+        assert_eq!(health.collateral_value, balance!(200));
+        assert_eq!(health.liquidation_price, None);
+        assert_eq!(health.max_borrow, balance!(0));
+    });
+}
+
+/// Given: basket CDP with XOR and DAI collaterals and debt.
+/// @When: user withdraws DAI making CDP unsafe, then withdraws DAI keeping CDP safe.
+/// @Then: the first withdrawal fails, the second succeeds.
+#[test]
+fn test_withdraw_collateral_asset_basket() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(100), balance!(0));
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(100));
+        assert_ok!(KensetsuPallet::borrow(alice(), cdp_id, balance!(90)));
+
+        assert_noop!(
+            KensetsuPallet::withdraw_collateral_asset(alice(), cdp_id, DAI, balance!(100)),
+            KensetsuError::CDPUnsafe
+        );
+        assert_noop!(
+            KensetsuPallet::withdraw_collateral_asset(alice(), cdp_id, DAI, balance!(101)),
+            KensetsuError::NotEnoughCollateral
+        );
+        assert_ok!(KensetsuPallet::withdraw_collateral_asset(
+            alice(),
+            cdp_id,
+            DAI,
+            balance!(50)
+        ));
+
+        System::assert_last_event(
+            Event::CollateralWithdrawn {
+                cdp_id,
+                owner: alice_account_id(),
+                collateral_asset_id: DAI,
+                amount: balance!(50),
+            }
+            .into(),
+        );
+        assert_eq!(KensetsuPallet::basket_collateral(cdp_id, DAI), balance!(50));
+        assert_balance(&alice_account_id(), &DAI, balance!(50));
+    });
+}
+
+/// Given: basket CDP with XOR and DAI.
+/// @When: CDP is closed.
+/// @Then: all collaterals are returned to the owner and basket storage is cleared.
+#[test]
+fn test_close_basket_cdp() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(10), balance!(0));
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(20));
+
+        assert_ok!(KensetsuPallet::close_cdp(alice(), cdp_id));
+
+        assert_balance(&alice_account_id(), &XOR, balance!(10));
+        assert_balance(&alice_account_id(), &DAI, balance!(20));
+        assert_eq!(KensetsuPallet::cdp(cdp_id), None);
+        assert_eq!(KensetsuPallet::basket_collateral(cdp_id, DAI), balance!(0));
+    });
+}
+
+/// Given: basket CDP with 200 XOR with liquidation ratio 50% and 100 DAI with liquidation
+/// ratio 80%, debt is 150 KUSD. XOR price drops to $0.5 and CDP becomes unsafe.
+/// @When: CDP is liquidated.
+/// @Then: XOR is the riskiest collateral and is sold first, debt is moved to auction in
+/// proportion to XOR value share, XOR value is a half of collateral value.
+#[test]
+fn test_liquidate_basket_riskiest_collateral_first() {
+    new_test_ext().execute_with(|| {
+        set_xor_as_collateral_type(
+            Balance::MAX,
+            Perbill::from_percent(50),
+            FixedU128::from_float(0.0),
+        );
+        set_dai_as_collateral_type(Perbill::from_percent(80));
+        let cdp_id = create_basket_cdp_for_xor(alice(), balance!(200), balance!(0));
+        deposit_dai_to_cdp(alice(), cdp_id, balance!(100));
+        assert_ok!(KensetsuPallet::borrow(alice(), cdp_id, balance!(150)));
+        PriceToolsMock::set_price(XOR, balance!(0.5));
+
+        assert_ok!(KensetsuPallet::liquidate(alice(), cdp_id));
+
+        System::assert_has_event(
+            Event::AuctionStarted {
+                auction_id: 1,
+                cdp_id,
+                collateral_asset_id: XOR,
+                collateral_amount: balance!(200),
+                debt: balance!(75),
+                start_price: FixedU128::from_inner(balance!(0.6)),
+            }
+            .into(),
+        );
+        let cdp = KensetsuPallet::cdp(cdp_id).expect("Must exist");
+        assert_eq!(cdp.collateral_amount, balance!(0));
+        assert_eq!(cdp.debt, balance!(75));
+        assert_eq!(
+            KensetsuPallet::basket_collateral(cdp_id, DAI),
+            balance!(100)
+        );
+        let collateral_info = KensetsuPallet::collateral_infos(XOR).expect("must exists");
+        assert_eq!(collateral_info.kusd_supply, balance!(75));
+        // the rest is safe
+        assert_noop!(
+            KensetsuPallet::liquidate(alice(), cdp_id),
+            KensetsuError::CDPSafe
+        );
+    });
+}
@@ -80,6 +80,14 @@ pub trait WeightInfo {
 	fn donate() -> Weight;
 	fn add_risk_manager() -> Weight;
 	fn remove_risk_manager() -> Weight;
+	fn bid() -> Weight;
+	fn deposit_savings() -> Weight;
+	fn withdraw_savings() -> Weight;
+	fn update_savings_rate() -> Weight;
+	fn create_basket_cdp() -> Weight;
+	fn deposit_collateral_asset() -> Weight;
+	fn withdraw_collateral_asset() -> Weight;
+	fn withdraw_unsold_collateral() -> Weight;
 }
 
 /// Weights for kensetsu using the Substrate node and recommended hardware.
@@ -456,6 +464,176 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Kensetsu CollateralAuctions (r:1 w:1)
+	/// Proof Skipped: Kensetsu CollateralAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:1 w:1)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `12000`
+		//  Estimated: `12000`
+		// Minimum execution time: 120_000_000 picoseconds.
+		Weight::from_parts(120_000_000, 12000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:0)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn deposit_savings() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6212`
+		//  Estimated: `6212`
+		// Minimum execution time: 58_000_000 picoseconds.
+		Weight::from_parts(58_000_000, 6212)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:0)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_savings() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6212`
+		//  Estimated: `6212`
+		// Minimum execution time: 60_000_000 picoseconds.
+		Weight::from_parts(60_000_000, 6212)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Kensetsu RiskManagers (r:1 w:0)
+	/// Proof Skipped: Kensetsu RiskManagers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:0)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:1 w:0)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn update_savings_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3598`
+		//  Estimated: `3598`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 3598)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Kensetsu CollateralInfos (r:1 w:1)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu NextCDPId (r:1 w:1)
+	/// Proof Skipped: Kensetsu NextCDPId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu CDPDepository (r:2 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CdpOwnerIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu CdpOwnerIndex (max_values: None, max_size: None, mode: Measured)
+	fn create_basket_cdp() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `118027`
+		//  Estimated: `118027`
+		// Minimum execution time: 165000000 picoseconds.
+		Weight::from_parts(165000000, 118027)
+			.saturating_add(T::DbWeight::get().reads(25_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Kensetsu CDPDepository (r:1 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu BasketCollaterals (r:2 w:1)
+	/// Proof Skipped: Kensetsu BasketCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CollateralInfos (r:1 w:0)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn deposit_collateral_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `18512`
+		//  Estimated: `18512`
+		// Minimum execution time: 52000000 picoseconds.
+		Weight::from_parts(52000000, 18512)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Kensetsu CDPDepository (r:1 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CollateralInfos (r:2 w:1)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu BasketCollaterals (r:2 w:1)
+	/// Proof Skipped: Kensetsu BasketCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PriceInfos (r:2 w:0)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn withdraw_collateral_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `98214`
+		//  Estimated: `98214`
+		// Minimum execution time: 131000000 picoseconds.
+		Weight::from_parts(131000000, 98214)
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Kensetsu RiskManagers (r:1 w:0)
+	/// Proof Skipped: Kensetsu RiskManagers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu UnsoldCollateral (r:1 w:1)
+	/// Proof Skipped: Kensetsu UnsoldCollateral (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_unsold_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1906`
+		//  Estimated: `19391`
+		// Minimum execution time: 46_000_000 picoseconds.
+		Weight::from_parts(47_000_000, 19391)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -831,4 +1009,174 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Kensetsu CollateralAuctions (r:1 w:1)
+	/// Proof Skipped: Kensetsu CollateralAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:1 w:1)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn bid() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `12000`
+		//  Estimated: `12000`
+		// Minimum execution time: 120_000_000 picoseconds.
+		Weight::from_parts(120_000_000, 12000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:0)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn deposit_savings() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6212`
+		//  Estimated: `6212`
+		// Minimum execution time: 58_000_000 picoseconds.
+		Weight::from_parts(58_000_000, 6212)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:0)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsShares (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsShares (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_savings() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6212`
+		//  Estimated: `6212`
+		// Minimum execution time: 60_000_000 picoseconds.
+		Weight::from_parts(60_000_000, 6212)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Kensetsu RiskManagers (r:1 w:0)
+	/// Proof Skipped: Kensetsu RiskManagers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Kensetsu SavingsLastUpdateTime (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsLastUpdateTime (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu SavingsRate (r:1 w:1)
+	/// Proof Skipped: Kensetsu SavingsRate (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu TotalSavingsShares (r:1 w:0)
+	/// Proof Skipped: Kensetsu TotalSavingsShares (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:1 w:0)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn update_savings_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3598`
+		//  Estimated: `3598`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 3598)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Kensetsu CollateralInfos (r:1 w:1)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu NextCDPId (r:1 w:1)
+	/// Proof Skipped: Kensetsu NextCDPId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu CDPDepository (r:2 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CdpOwnerIndex (r:1 w:1)
+	/// Proof Skipped: Kensetsu CdpOwnerIndex (max_values: None, max_size: None, mode: Measured)
+	fn create_basket_cdp() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `118027`
+		//  Estimated: `118027`
+		// Minimum execution time: 165000000 picoseconds.
+		Weight::from_parts(165000000, 118027)
+			.saturating_add(RocksDbWeight::get().reads(25_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Kensetsu CDPDepository (r:1 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu BasketCollaterals (r:2 w:1)
+	/// Proof Skipped: Kensetsu BasketCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CollateralInfos (r:1 w:0)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn deposit_collateral_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `18512`
+		//  Estimated: `18512`
+		// Minimum execution time: 52000000 picoseconds.
+		Weight::from_parts(52000000, 18512)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Kensetsu CDPDepository (r:1 w:1)
+	/// Proof Skipped: Kensetsu CDPDepository (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu CollateralInfos (r:2 w:1)
+	/// Proof Skipped: Kensetsu CollateralInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Kensetsu BasketCollaterals (r:2 w:1)
+	/// Proof Skipped: Kensetsu BasketCollaterals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PriceInfos (r:2 w:0)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	fn withdraw_collateral_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `98214`
+		//  Estimated: `98214`
+		// Minimum execution time: 131000000 picoseconds.
+		Weight::from_parts(131000000, 98214)
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Kensetsu RiskManagers (r:1 w:0)
+	/// Proof Skipped: Kensetsu RiskManagers (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Kensetsu UnsoldCollateral (r:1 w:1)
+	/// Proof Skipped: Kensetsu UnsoldCollateral (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_unsold_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1906`
+		//  Estimated: `19391`
+		// Minimum execution time: 46_000_000 picoseconds.
+		Weight::from_parts(47_000_000, 19391)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
@@ -34,10 +34,12 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use common::{
-    balance, DEXId, DexInfoProvider, FilterMode, LiquidityRegistry, LiquiditySourceFilter,
-    LiquiditySourceType, VAL, XOR, XSTUSD,
+    balance, AssetName, AssetSymbol, DEXId, DexInfoProvider, FilterMode, LiquidityRegistry,
+    LiquiditySourceFilter, LiquiditySourceType, TradingPairSourceManager,
+    DEFAULT_BALANCE_PRECISION, VAL, XOR, XSTUSD,
 };
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Get;
 use frame_system::{EventRecord, RawOrigin};
 use liquidity_proxy::ExchangePath;
 use sp_std::prelude::*;
@@ -128,6 +130,50 @@ benchmarks! {
     verify {
     }
 
+    new_multi_hop {
+        let n in 2 .. 16;
+        let caller: T::AccountId = account("caller", 0, 0);
+        frame_system::Pallet::<T>::inc_providers(&caller);
+        let dex_info = <T as pool_xyk::Config>::DexInfoProvider::get_dex_info(&DEX.into())?;
+        // every asset is paired with the next four ones, so that the neighbours of the maximal
+        // number of assets are read from both sides
+        let mut assets = Vec::new();
+        for i in 0..n as usize {
+            let asset_id = assets::Pallet::<T>::register_from(
+                &caller,
+                AssetSymbol(b"TEST".to_vec()),
+                AssetName(b"Test".to_vec()),
+                DEFAULT_BALANCE_PRECISION,
+                balance!(0),
+                true,
+                None,
+                None,
+            )?;
+            for &neighbour in assets.iter().skip(i.saturating_sub(4)) {
+                T::TradingPairSourceManager::register_non_base_pair(
+                    DEX.into(),
+                    neighbour,
+                    asset_id,
+                )?;
+            }
+            assets.push(asset_id);
+        }
+        let from_asset = assets[0];
+        let to_asset = assets[assets.len() - 1];
+    }: {
+        let (paths, _) = ExchangePath::<T>::new_multi_hop(
+            &DEX.into(),
+            &dex_info,
+            from_asset,
+            to_asset,
+            <T as liquidity_proxy::Config>::MaxRouteHops::get(),
+            <T as liquidity_proxy::Config>::MaxRoutePaths::get(),
+        );
+        assert!(!paths.is_empty());
+    }
+    verify {
+    }
+
     is_forbidden_filter {
         let from_asset: T::AssetId = XOR.into();
         let to_asset: T::AssetId = VAL.into();
@@ -181,6 +227,7 @@ mod tests {
             assert_ok!(Pallet::<Runtime>::test_benchmark_disable_liquidity_source());
             assert_ok!(Pallet::<Runtime>::test_benchmark_check_indivisible_assets());
             assert_ok!(Pallet::<Runtime>::test_benchmark_new_trivial());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_new_multi_hop());
             assert_ok!(Pallet::<Runtime>::test_benchmark_is_forbidden_filter());
             assert_ok!(Pallet::<Runtime>::test_benchmark_list_liquidity_sources());
         });
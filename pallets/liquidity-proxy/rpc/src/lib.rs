@@ -184,6 +184,22 @@ where
                 .map(Into::into)
             }
         } else if version == Some(2) {
+            #[allow(deprecated)]
+            {
+                api.quote_before_version_3(
+                    &at,
+                    dex_id,
+                    input_asset_id,
+                    output_asset_id,
+                    amount,
+                    swap_variant,
+                    selected_source_types,
+                    filter_mode,
+                )
+                .map_err(|e| RpcError::Call(CallError::Failed(e.into())))?
+                .map(Into::into)
+            }
+        } else if version == Some(3) {
             api.quote(
                 &at,
                 dex_id,
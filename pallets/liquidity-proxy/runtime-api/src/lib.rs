@@ -79,6 +79,23 @@ pub struct SwapOutcomeInfo<Balance, AssetId: MaybeDisplay + MaybeFromStr> {
     pub fee: Balance,
     pub rewards: Vec<RewardsInfo<Balance, AssetId>>,
     pub route: Vec<AssetId>,
+    /// Amounts of every exchange in `route`
+    pub hops: Vec<SwapHopInfo<Balance, AssetId>>,
+}
+
+impl<Balance: Default, AssetId: MaybeDisplay + MaybeFromStr>
+    From<SwapOutcomeInfoV2<Balance, AssetId>> for SwapOutcomeInfo<Balance, AssetId>
+{
+    fn from(value: SwapOutcomeInfoV2<Balance, AssetId>) -> Self {
+        Self {
+            amount: value.amount,
+            amount_without_impact: value.amount_without_impact,
+            fee: value.fee,
+            rewards: value.rewards,
+            route: value.route,
+            hops: Default::default(),
+        }
+    }
 }
 
 impl<Balance: Default, AssetId: MaybeDisplay + MaybeFromStr>
@@ -91,10 +108,101 @@ impl<Balance: Default, AssetId: MaybeDisplay + MaybeFromStr>
             fee: value.fee,
             rewards: value.rewards,
             route: Default::default(),
+            hops: Default::default(),
         }
     }
 }
 
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct SwapOutcomeInfoV2<Balance, AssetId: MaybeDisplay + MaybeFromStr> {
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub amount_without_impact: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub fee: Balance,
+    pub rewards: Vec<RewardsInfo<Balance, AssetId>>,
+    pub route: Vec<AssetId>,
+}
+
+/// Direct exchange in the route of a swap.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct SwapHopInfo<Balance, AssetId> {
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "AssetId: std::fmt::Display",
+                deserialize = "AssetId: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub input_asset_id: AssetId,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "AssetId: std::fmt::Display",
+                deserialize = "AssetId: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub output_asset_id: AssetId,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub input_amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub output_amount: Balance,
+}
+
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 pub struct SwapOutcomeInfoV1<Balance, AssetId: MaybeDisplay + MaybeFromStr> {
@@ -152,7 +260,7 @@ pub struct RewardsInfo<Balance, AssetId> {
 }
 
 sp_api::decl_runtime_apis! {
-    #[api_version(2)]
+    #[api_version(3)]
     pub trait LiquidityProxyAPI<DEXId, AssetId, Balance, SwapVariant, LiquiditySourceType, FilterMode> where
         DEXId: Codec,
         AssetId: Codec + MaybeFromStr + MaybeDisplay,
@@ -171,6 +279,17 @@ sp_api::decl_runtime_apis! {
             filter_mode: FilterMode,
         ) -> Option<SwapOutcomeInfo<Balance, AssetId>>;
 
+        #[changed_in(3)]
+        fn quote(
+            dex_id: DEXId,
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            amount: BalanceWrapper,
+            swap_variant: SwapVariant,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> Option<SwapOutcomeInfoV2<Balance, AssetId>>;
+
         #[changed_in(2)]
         fn quote(
             dex_id: DEXId,
@@ -51,9 +51,9 @@ use common::{
 };
 use fallible_iterator::FallibleIterator as _;
 use frame_support::dispatch::PostDispatchInfo;
-use frame_support::traits::Get;
+use frame_support::traits::{ConstU32, Get, Time};
 use frame_support::weights::Weight;
-use frame_support::{ensure, fail, RuntimeDebug};
+use frame_support::{ensure, fail, BoundedVec, RuntimeDebug};
 use frame_system::ensure_signed;
 use itertools::Itertools as _;
 pub use pallet::*;
@@ -65,6 +65,12 @@ use sp_std::{cmp::Ord, cmp::Ordering, vec};
 
 type LiquiditySourceIdOf<T> = LiquiditySourceId<<T as common::Config>::DEXId, LiquiditySourceType>;
 type Rewards<AssetId> = Vec<(Balance, AssetId, RewardReason)>;
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+pub type SwapOptionsOf<T> = SwapOptions<
+    <T as assets::Config>::AssetId,
+    <T as frame_system::Config>::BlockNumber,
+    MomentOf<T>,
+>;
 
 pub mod weights;
 pub use weights::WeightInfo;
@@ -83,6 +89,12 @@ pub const TECH_ACCOUNT_MAIN: &[u8] = b"main";
 
 const REJECTION_WEIGHT: Weight = Weight::from_parts(u64::MAX, u64::MAX);
 
+/// Number of equal parts the amount is divided into by [`Pallet::marginal_split()`].
+pub const SPLIT_PARTS: Balance = 10;
+
+/// Maximum number of the minimum output amounts in [`SwapOptions`].
+pub const MAX_HOP_AMOUNTS_OUT: u32 = 8;
+
 /// Possible exchange paths for two assets.
 pub struct ExchangePath<T: Config>(Vec<T::AssetId>);
 
@@ -123,12 +135,26 @@ impl<T: Config> ExchangePath<T> {
         dex_info: &DEXInfo<T::AssetId>,
         input_asset_id: T::AssetId,
         output_asset_id: T::AssetId,
+    ) -> Option<Vec<Self>> {
+        let synthetic_assets = T::PrimaryMarketXST::enabled_target_assets();
+        Self::new_trivial_with_synthetics(
+            dex_info,
+            &synthetic_assets,
+            input_asset_id,
+            output_asset_id,
+        )
+    }
+
+    fn new_trivial_with_synthetics(
+        dex_info: &DEXInfo<T::AssetId>,
+        synthetic_assets: &BTreeSet<T::AssetId>,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
     ) -> Option<Vec<Self>> {
         use AssetType::*;
 
-        let synthetic_assets = T::PrimaryMarketXST::enabled_target_assets();
-        let input_type = AssetType::determine::<T>(dex_info, &synthetic_assets, input_asset_id);
-        let output_type = AssetType::determine::<T>(dex_info, &synthetic_assets, output_asset_id);
+        let input_type = AssetType::determine::<T>(dex_info, synthetic_assets, input_asset_id);
+        let output_type = AssetType::determine::<T>(dex_info, synthetic_assets, output_asset_id);
 
         match (input_type, output_type) {
             forward_or_backward!(Base, Basic) | forward_or_backward!(Base, SyntheticBase) => {
@@ -196,6 +222,107 @@ impl<T: Config> ExchangePath<T> {
             (Base, Base) | (SyntheticBase, SyntheticBase) => None,
         }
     }
+
+    /// Builds paths from `input_asset_id` to `output_asset_id`, which start and/or end with
+    /// exchanges over trading pairs without the base assets of the DEX (see
+    /// `TradingPairSourceManager::list_asset_neighbours`), at most `max_hops` of such exchanges
+    /// in total. The assets in between are exchanged via trivial paths (see `new_trivial`).
+    /// The trivial paths of the input and output assets themselves aren't included.
+    ///
+    /// Neighbours of at most `max_paths` assets are read both from the input and the output
+    /// asset. Shorter paths come first, at most `max_paths` paths are returned together with
+    /// the number of the read neighbour lists.
+    pub fn new_multi_hop(
+        dex_id: &T::DEXId,
+        dex_info: &DEXInfo<T::AssetId>,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+        max_hops: u32,
+        max_paths: u32,
+    ) -> (Vec<Self>, u32) {
+        let mut paths = Vec::<Self>::new();
+        if max_hops == 0 || max_paths == 0 || input_asset_id == output_asset_id {
+            return (paths, 0);
+        }
+
+        let (from_input, input_reads) =
+            Self::reachable_assets(dex_id, input_asset_id, max_hops, max_paths);
+        let (from_output, output_reads) =
+            Self::reachable_assets(dex_id, output_asset_id, max_hops, max_paths);
+        let synthetic_assets = T::PrimaryMarketXST::enabled_target_assets();
+
+        for prefix in from_input.iter() {
+            for suffix in from_output.iter() {
+                let hops = prefix.len() + suffix.len() - 2;
+                if hops == 0 || hops > max_hops as usize {
+                    continue;
+                }
+                // `suffix` starts with the output asset
+                let (from, to) = (prefix[prefix.len() - 1], suffix[suffix.len() - 1]);
+                let middles = if from == to {
+                    vec![vec![from]]
+                } else {
+                    Self::new_trivial_with_synthetics(dex_info, &synthetic_assets, from, to)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|ExchangePath(middle)| middle)
+                        .collect()
+                };
+                for middle in middles {
+                    let mut path = prefix[..prefix.len() - 1].to_vec();
+                    path.extend(middle);
+                    path.extend(suffix[..suffix.len() - 1].iter().rev());
+                    let unique_assets = path.iter().collect::<BTreeSet<_>>().len();
+                    if unique_assets == path.len()
+                        && !paths.iter().any(|ExchangePath(found)| found == &path)
+                    {
+                        paths.push(Self(path));
+                    }
+                }
+            }
+        }
+
+        paths.sort_by_key(|ExchangePath(path)| path.len());
+        paths.truncate(max_paths as usize);
+        (paths, input_reads.saturating_add(output_reads))
+    }
+
+    /// Breadth-first search over trading pairs without the base assets of the DEX. Returns
+    /// paths from `asset_id` to at most `max_assets` assets (including `asset_id` itself)
+    /// with at most `max_hops` exchanges, together with the number of the read neighbour lists.
+    fn reachable_assets(
+        dex_id: &T::DEXId,
+        asset_id: T::AssetId,
+        max_hops: u32,
+        max_assets: u32,
+    ) -> (Vec<Vec<T::AssetId>>, u32) {
+        let mut reached = vec![vec![asset_id]];
+        let mut reads = 0;
+        let mut next = 0;
+        while next < reached.len() && reads < max_assets {
+            let path = reached[next].clone();
+            next += 1;
+            if path.len() > max_hops as usize {
+                continue;
+            }
+            reads += 1;
+            let current = path[path.len() - 1];
+            for neighbour in T::TradingPairSourceManager::list_asset_neighbours(dex_id, &current) {
+                if reached.len() >= max_assets as usize {
+                    break;
+                }
+                if reached
+                    .iter()
+                    .all(|found| found[found.len() - 1] != neighbour)
+                {
+                    let mut extended = path.clone();
+                    extended.push(neighbour);
+                    reached.push(extended);
+                }
+            }
+        }
+        (reached, reads)
+    }
 }
 
 /// Output of the aggregated LiquidityProxy::quote() price.
@@ -232,6 +359,8 @@ pub struct QuoteInfo<AssetId, LiquiditySource> {
     pub rewards: Rewards<AssetId>,
     pub liquidity_sources: Vec<LiquiditySource>,
     pub path: Vec<AssetId>,
+    /// Amounts of the assets in `path`, i.e. input and output amounts of every exchange in it
+    pub path_amounts: Vec<Balance>,
 }
 
 fn merge_two_vectors_unique<T: PartialEq>(vec_1: &mut Vec<T>, vec_2: Vec<T>) {
@@ -286,6 +415,19 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Fails if the deadline of a swap has already passed.
+    pub fn ensure_deadline(
+        deadline: &Option<SwapDeadline<T::BlockNumber, MomentOf<T>>>,
+    ) -> Result<(), DispatchError> {
+        let expired = match deadline {
+            Some(SwapDeadline::Block(block)) => frame_system::Pallet::<T>::block_number() > *block,
+            Some(SwapDeadline::Timestamp(moment)) => T::Time::now() > *moment,
+            None => false,
+        };
+        ensure!(!expired, Error::<T>::SwapDeadlineExpired);
+        Ok(())
+    }
+
     pub fn inner_swap(
         sender: T::AccountId,
         receiver: T::AccountId,
@@ -296,6 +438,31 @@ impl<T: Config> Pallet<T> {
         selected_source_types: Vec<LiquiditySourceType>,
         filter_mode: FilterMode,
     ) -> Result<Weight, DispatchError> {
+        Self::inner_swap_with_options(
+            sender,
+            receiver,
+            dex_id,
+            input_asset_id,
+            output_asset_id,
+            swap_amount,
+            selected_source_types,
+            filter_mode,
+            SwapOptions::default(),
+        )
+    }
+
+    pub fn inner_swap_with_options(
+        sender: T::AccountId,
+        receiver: T::AccountId,
+        dex_id: T::DEXId,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        options: SwapOptionsOf<T>,
+    ) -> Result<Weight, DispatchError> {
+        Self::ensure_deadline(&options.deadline)?;
         Self::check_indivisible_assets(&input_asset_id, &output_asset_id)?;
         let mut total_weight = <T as Config>::WeightInfo::check_indivisible_assets();
 
@@ -310,7 +477,7 @@ impl<T: Config> Pallet<T> {
         total_weight =
             total_weight.saturating_add(<T as Config>::WeightInfo::is_forbidden_filter());
 
-        let (outcome, sources, weight) = Self::inner_exchange(
+        let (outcome, sources, weight) = Self::inner_exchange_with_hop_minimums(
             dex_id,
             &sender,
             &receiver,
@@ -318,6 +485,7 @@ impl<T: Config> Pallet<T> {
             &output_asset_id,
             swap_amount,
             LiquiditySourceFilter::with_mode(dex_id, filter_mode, selected_source_types),
+            &options.min_hop_amounts_out,
         )?;
         total_weight = total_weight.saturating_add(weight);
 
@@ -343,7 +511,46 @@ impl<T: Config> Pallet<T> {
         Ok(total_weight)
     }
 
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
+    /// Collects candidate paths between two assets: trivial paths (via Base Asset) come first,
+    /// followed by the paths which use trading pairs without the base assets with at most
+    /// `MaxRouteHops` such exchanges (see `ExchangePath::new_multi_hop`). At most `MaxRoutePaths`
+    /// paths are collected.
+    ///
+    /// Returns `None` if there are no paths, together with the weight of the search.
+    pub fn exchange_paths(
+        dex_id: &T::DEXId,
+        dex_info: &DEXInfo<T::AssetId>,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+    ) -> (Option<Vec<ExchangePath<T>>>, Weight) {
+        let mut paths = ExchangePath::<T>::new_trivial(dex_info, input_asset_id, output_asset_id)
+            .unwrap_or_default();
+        let (multi_hop_paths, reads) = ExchangePath::<T>::new_multi_hop(
+            dex_id,
+            dex_info,
+            input_asset_id,
+            output_asset_id,
+            T::MaxRouteHops::get(),
+            T::MaxRoutePaths::get(),
+        );
+        let weight = <T as Config>::WeightInfo::new_trivial()
+            .saturating_add(<T as Config>::WeightInfo::new_multi_hop(reads));
+
+        for ExchangePath(atomic_path) in multi_hop_paths {
+            if !paths
+                .iter()
+                .any(|ExchangePath(trivial_path)| trivial_path == &atomic_path)
+            {
+                paths.push(ExchangePath(atomic_path));
+            }
+        }
+
+        paths.truncate(T::MaxRoutePaths::get() as usize);
+
+        ((!paths.is_empty()).then_some(paths), weight)
+    }
+
+    /// Applies routing over candidate paths (see `exchange_paths`), resulting in a poly-swap which may contain several individual swaps.
     /// Those individual swaps are subject to liquidity aggregation algorithm.
     ///
     /// This a wrapper for `exchange_single`.
@@ -355,6 +562,30 @@ impl<T: Config> Pallet<T> {
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<(SwapOutcome<Balance>, Vec<LiquiditySourceIdOf<T>>, Weight), DispatchError> {
+        Self::inner_exchange_with_hop_minimums(
+            dex_id,
+            sender,
+            receiver,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            filter,
+            &[],
+        )
+    }
+
+    /// Same as `inner_exchange`, but fails if an exchange in the route outputs less than
+    /// the minimum amount specified for its output asset in `min_hop_amounts_out`.
+    pub fn inner_exchange_with_hop_minimums(
+        dex_id: T::DEXId,
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        min_hop_amounts_out: &[(T::AssetId, Balance)],
     ) -> Result<(SwapOutcome<Balance>, Vec<LiquiditySourceIdOf<T>>, Weight), DispatchError> {
         ensure!(
             input_asset_id != output_asset_id,
@@ -363,12 +594,19 @@ impl<T: Config> Pallet<T> {
 
         common::with_transaction(|| {
             let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
-            let maybe_paths =
-                ExchangePath::<T>::new_trivial(&dex_info, *input_asset_id, *output_asset_id);
-            let total_weight = <T as Config>::WeightInfo::new_trivial();
+            let (maybe_paths, total_weight) =
+                Self::exchange_paths(&dex_id, &dex_info, *input_asset_id, *output_asset_id);
             maybe_paths
                 .map_or(Err(Error::<T>::UnavailableExchangePath.into()), |paths| {
-                    Self::exchange_sequence(&dex_info, sender, receiver, paths, amount, &filter)
+                    Self::exchange_sequence(
+                        &dex_info,
+                        sender,
+                        receiver,
+                        paths,
+                        amount,
+                        &filter,
+                        min_hop_amounts_out,
+                    )
                 })
                 .map(|(outcome, sources, weight)| {
                     (outcome, sources, total_weight.saturating_add(weight))
@@ -385,6 +623,7 @@ impl<T: Config> Pallet<T> {
         asset_paths: Vec<ExchangePath<T>>,
         amount: SwapAmount<Balance>,
         filter: &LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        min_hop_amounts_out: &[(T::AssetId, Balance)],
     ) -> Result<(SwapOutcome<Balance>, Vec<LiquiditySourceIdOf<T>>, Weight), DispatchError> {
         match amount {
             SwapAmount::WithDesiredInput {
@@ -408,6 +647,7 @@ impl<T: Config> Pallet<T> {
                     &best_path,
                     desired_amount_in,
                     filter,
+                    min_hop_amounts_out,
                 )
                 .and_then(|(swap, sources, weight)| {
                     ensure!(
@@ -446,6 +686,7 @@ impl<T: Config> Pallet<T> {
                     &best_path,
                     input_amount,
                     filter,
+                    min_hop_amounts_out,
                 )
                 .and_then(|(mut swap, sources, weight)| {
                     swap.amount = input_amount;
@@ -458,6 +699,7 @@ impl<T: Config> Pallet<T> {
     /// Exchange sequence of assets using input amount.
     ///
     /// Performs [`Self::exchange_single()`] for each pair of assets and aggregates the results.
+    /// Output of every exchange is checked against `min_hop_amounts_out`.
     fn exchange_sequence_with_input_amount(
         dex_info: &DEXInfo<T::AssetId>,
         sender: &T::AccountId,
@@ -465,6 +707,7 @@ impl<T: Config> Pallet<T> {
         assets: &[T::AssetId],
         input_amount: Balance,
         filter: &LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        min_hop_amounts_out: &[(T::AssetId, Balance)],
     ) -> Result<(SwapOutcome<Balance>, Vec<LiquiditySourceIdOf<T>>, Weight), DispatchError> {
         use itertools::EitherOrBoth::*;
 
@@ -509,6 +752,15 @@ impl<T: Config> Pallet<T> {
                             swap_amount,
                             filter.clone(),
                         )?;
+                        if let Some((_, min_amount_out)) = min_hop_amounts_out
+                            .iter()
+                            .find(|(asset_id, _)| asset_id == to)
+                        {
+                            ensure!(
+                                swap_outcome.amount >= *min_amount_out,
+                                Error::<T>::HopSlippageNotTolerated
+                            );
+                        }
 
                         current_amount = swap_outcome.amount;
                         Ok((swap_outcome, sources, weight))
@@ -637,7 +889,7 @@ impl<T: Config> Pallet<T> {
         })
     }
 
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
+    /// Applies routing over candidate paths (see `exchange_paths`), resulting in a poly-swap which may contain several individual swaps.
     /// Those individual swaps are subject to liquidity aggregation algorithm.
     ///
     /// This a wrapper for `quote_single`.
@@ -655,12 +907,16 @@ impl<T: Config> Pallet<T> {
             Error::<T>::UnavailableExchangePath
         );
         let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
-        let maybe_path =
-            ExchangePath::<T>::new_trivial(&dex_info, *input_asset_id, *output_asset_id);
-        maybe_path.map_or_else(
-            || Err(Error::<T>::UnavailableExchangePath.into()),
-            |paths| Self::quote_sequence(&dex_info, paths, amount, &filter, skip_info, deduce_fee),
-        )
+        let (maybe_path, paths_weight) =
+            Self::exchange_paths(&dex_id, &dex_info, *input_asset_id, *output_asset_id);
+        maybe_path
+            .map_or_else(
+                || Err(Error::<T>::UnavailableExchangePath.into()),
+                |paths| {
+                    Self::quote_sequence(&dex_info, paths, amount, &filter, skip_info, deduce_fee)
+                },
+            )
+            .map(|(quote_info, weight)| (quote_info, paths_weight.saturating_add(weight)))
     }
 
     /// Quote sequence of assets, where each pair is a direct exchange.
@@ -695,7 +951,7 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    /// Selects the best path between two swap paths
+    /// Selects the best path among candidate swap paths, each path is quoted via `quote_single`
     /// `ord` parameter influences the preprocessing before
     /// calling `quote_pairs_with_flexible_amount`. The Ordering:Greater variant
     /// is related to `QuoteAmount::WithDesiredInput` and other ordering variants are related to
@@ -737,14 +993,19 @@ impl<T: Config> Pallet<T> {
                     deduce_fee,
                 ),
             };
-            quote.map(|x| {
+            quote.map(|mut x| {
                 weight = weight.saturating_add(x.4);
+                if ord != Ordering::Greater {
+                    // amounts were collected from the end of the path
+                    x.5.reverse();
+                }
                 QuoteInfo {
                     outcome: x.0,
                     amount_without_impact: x.1,
                     rewards: x.2,
                     liquidity_sources: x.3,
                     path: atomic_path,
+                    path_amounts: x.5,
                 }
             })
         });
@@ -773,6 +1034,7 @@ impl<T: Config> Pallet<T> {
     /// Quote given pairs of assets using `amount_ctr` to construct [`QuoteAmount`] for each pair.
     ///
     /// Performs [`Self::quote_single()`] for each pair and aggregates the results.
+    /// The amounts of every quoted pair are returned in the order of `asset_pairs`, starting with `amount`.
     fn quote_pairs_with_flexible_amount<'asset, F: Fn(Balance) -> QuoteAmount<Balance>>(
         dex_info: &DEXInfo<T::AssetId>,
         asset_pairs: impl Iterator<Item = (&'asset T::AssetId, &'asset T::AssetId)>,
@@ -788,6 +1050,7 @@ impl<T: Config> Pallet<T> {
             Rewards<T::AssetId>,
             Vec<LiquiditySourceIdOf<T>>,
             Weight,
+            Vec<Balance>,
         ),
         DispatchError,
     > {
@@ -819,6 +1082,7 @@ impl<T: Config> Pallet<T> {
                 Rewards::new(),
                 Vec::new(),
                 Weight::zero(),
+                vec![amount],
             ),
             |(
                 mut outcome,
@@ -826,6 +1090,7 @@ impl<T: Config> Pallet<T> {
                 mut rewards,
                 mut liquidity_sources,
                 mut weight,
+                mut amounts,
             ),
              (
                 quote,
@@ -854,12 +1119,14 @@ impl<T: Config> Pallet<T> {
                 rewards.append(&mut quote_rewards);
                 weight = weight.saturating_add(quote_weight);
                 merge_two_vectors_unique(&mut liquidity_sources, quote_liquidity_sources);
+                amounts.push(quote.amount);
                 Ok((
                     outcome,
                     outcome_without_impact,
                     rewards,
                     liquidity_sources,
                     weight,
+                    amounts,
                 ))
             },
         )
@@ -954,11 +1221,6 @@ impl<T: Config> Pallet<T> {
             T::LiquidityRegistry::list_liquidity_sources(input_asset_id, output_asset_id, filter)?;
         let locked = T::LockedLiquiditySourcesManager::get();
         sources.retain(|x| !locked.contains(&x.liquidity_source_index));
-        // The temp solution is to exclude OrderBook source if there are multiple sources.
-        // Will be redesigned in #447
-        if sources.len() > 1 {
-            sources.retain(|x| x.liquidity_source_index != LiquiditySourceType::OrderBook);
-        }
         Ok(sources)
     }
 
@@ -1068,7 +1330,18 @@ impl<T: Config> Pallet<T> {
             }
         }
 
-        fail!(Error::<T>::UnavailableExchangePath);
+        // Any other combination of sources, including order book and XST, is split
+        // according to the marginal prices of the sources
+        let outcome = Self::marginal_split(
+            &sources,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            skip_info,
+            deduce_fee,
+        )?;
+        total_weight = total_weight.saturating_add(outcome.2);
+        Ok((outcome.0, outcome.1, sources, total_weight))
     }
 
     /// Check if given two arbitrary tokens can be used to perform an exchange via any available sources.
@@ -1078,7 +1351,8 @@ impl<T: Config> Pallet<T> {
         output_asset_id: T::AssetId,
     ) -> Result<bool, DispatchError> {
         let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
-        let maybe_path = ExchangePath::<T>::new_trivial(&dex_info, input_asset_id, output_asset_id);
+        let (maybe_path, _) =
+            Self::exchange_paths(&dex_id, &dex_info, input_asset_id, output_asset_id);
         maybe_path.map_or(Ok(false), |paths| {
             let paths_flag = paths
                 .into_iter()
@@ -1100,13 +1374,7 @@ impl<T: Config> Pallet<T> {
         path.iter()
             .tuple_windows()
             .filter_map(|(from, to)| {
-                let pair = Self::weak_sort_pair(&dex_info, *from, *to);
-                T::TradingPairSourceManager::list_enabled_sources_for_trading_pair(
-                    dex_id,
-                    &pair.base_asset_id,
-                    &pair.target_asset_id,
-                )
-                .ok()
+                Self::list_enabled_sources_for_assets(dex_id, dex_info, *from, *to).ok()
             })
             .all(|sources| !sources.is_empty())
     }
@@ -1119,12 +1387,7 @@ impl<T: Config> Pallet<T> {
     ) -> Result<BTreeSet<LiquiditySourceType>, DispatchError> {
         let sources_set = fallible_iterator::convert(path.to_vec().iter().tuple_windows().map(
             |(from, to)| -> Result<_, DispatchError> {
-                let pair = Self::weak_sort_pair(&dex_info, *from, *to);
-                let sources = T::TradingPairSourceManager::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &pair.base_asset_id,
-                    &pair.target_asset_id,
-                )?;
+                let sources = Self::list_enabled_sources_for_assets(dex_id, dex_info, *from, *to)?;
                 ensure!(!sources.is_empty(), Error::<T>::UnavailableExchangePath);
                 Ok(sources)
             },
@@ -1146,7 +1409,9 @@ impl<T: Config> Pallet<T> {
     /// The current code map:
     ///
     /// inner_exchange()
-    ///     new_trivial()
+    ///     exchange_paths()
+    ///         new_trivial()
+    ///         new_multi_hop()
     ///     exchange_sequence()
     ///         select_best_path()
     ///             quote_pairs_with_flexible_amount() - call M times, where M is a count of paths
@@ -1195,23 +1460,39 @@ impl<T: Config> Pallet<T> {
             return REJECTION_WEIGHT;
         };
 
-        // Get trivial path or return weight that will be rejected
-        let Some(trivial_paths) = ExchangePath::<T>::new_trivial(&dex_info, *input, *output) else {
+        // Get candidate paths or return weight that will be rejected
+        let (maybe_paths, paths_weight) = Self::exchange_paths(dex_id, &dex_info, *input, *output);
+        let Some(paths) = maybe_paths else {
             return REJECTION_WEIGHT;
         };
 
-        let quote_weight = T::LiquidityRegistry::quote_weight();
-        let check_rewards_weight = T::LiquidityRegistry::check_rewards_weight();
+        // no sources -> no exchanges -> no weight
+        let paths_sources: Vec<Vec<Vec<LiquiditySourceIdOf<T>>>> = paths
+            .iter()
+            .map(|path| {
+                path.0
+                    .iter()
+                    .tuple_windows()
+                    .map(|(input_asset_id, output_asset_id)| {
+                        Self::list_quote_liquidity_sources(input_asset_id, output_asset_id, &filter)
+                            .unwrap_or(Vec::new())
+                    })
+                    .collect()
+            })
+            .collect();
+        let max_sources_count = paths_sources
+            .iter()
+            .flatten()
+            .map(|sources| sources.len())
+            .max()
+            .unwrap_or(0);
 
-        let quote_single_weight = <T as Config>::WeightInfo::list_liquidity_sources()
-            .saturating_add(quote_weight.saturating_mul(4))
-            .saturating_add(check_rewards_weight.saturating_mul(2));
+        let quote_single_weight = Self::quote_single_weight(max_sources_count);
 
-        let mut weight = <T as Config>::WeightInfo::new_trivial();
+        let mut weight = paths_weight;
 
         // in quote_pairs_with_flexible_amount()
-        weight =
-            weight.saturating_add(quote_single_weight.saturating_mul(trivial_paths.len() as u64));
+        weight = weight.saturating_add(quote_single_weight.saturating_mul(paths.len() as u64));
 
         // in calculate_input_amount()
         weight = weight.saturating_add(match swap_variant {
@@ -1221,25 +1502,13 @@ impl<T: Config> Pallet<T> {
 
         let mut weights = Vec::new();
 
-        for path in trivial_paths {
+        for (path, path_sources) in paths.into_iter().zip(paths_sources.into_iter()) {
             if path.0.len() > 0 {
-                let path_weights =
-                    path.0
-                        .iter()
-                        .tuple_windows()
-                        .map(|(input_asset_id, output_asset_id)| {
-                            let exchange_sources = Self::list_quote_liquidity_sources(
-                                input_asset_id,
-                                output_asset_id,
-                                &filter,
-                            )
-                            .unwrap_or(Vec::new()); // no sources -> no exchanges -> no weight
-                            let single_exchange_weight =
-                                T::LiquidityRegistry::exchange_weight_filtered(
-                                    exchange_sources.iter().map(|s| s.liquidity_source_index),
-                                );
-                            single_exchange_weight
-                        });
+                let path_weights = path_sources.into_iter().map(|exchange_sources| {
+                    T::LiquidityRegistry::exchange_weight_filtered(
+                        exchange_sources.iter().map(|s| s.liquidity_source_index),
+                    )
+                });
                 let total_exchange_weight = path_weights
                     .fold(Weight::zero(), |acc, next_exchange_weight| {
                         acc.saturating_add(next_exchange_weight)
@@ -1256,6 +1525,31 @@ impl<T: Config> Pallet<T> {
         weights.iter().fold(weights[0], |max, &x| max.max(x))
     }
 
+    /// Calculates the max potential weight of `quote_single` for a pair of assets with
+    /// `sources_count` liquidity sources.
+    fn quote_single_weight(sources_count: usize) -> Weight {
+        let quote_weight = T::LiquidityRegistry::quote_weight();
+        let check_rewards_weight = T::LiquidityRegistry::check_rewards_weight();
+
+        // a single source or `smart_split()`
+        let mut weight = quote_weight
+            .saturating_mul(4)
+            .saturating_add(check_rewards_weight.saturating_mul(2));
+        if sources_count > 1 {
+            // `marginal_split()`: two quotes for each part, the quote without impact,
+            // one failed quote and the quote of the whole amount for each source
+            let sources_count = sources_count as u64;
+            let parts = SPLIT_PARTS as u64;
+            weight = weight.max(
+                quote_weight
+                    .saturating_mul(parts.saturating_mul(2))
+                    .saturating_add(quote_weight.saturating_mul(sources_count.saturating_mul(3)))
+                    .saturating_add(check_rewards_weight.saturating_mul(sources_count)),
+            );
+        }
+        <T as Config>::WeightInfo::list_liquidity_sources().saturating_add(weight)
+    }
+
     /// Calculates the max potential weight of swap
     ///
     /// This function should cover the current code map and all possible calls of some functions that can take a weight.
@@ -1363,7 +1657,8 @@ impl<T: Config> Pallet<T> {
         output_asset_id: T::AssetId,
     ) -> Result<Vec<LiquiditySourceType>, DispatchError> {
         let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
-        let maybe_path = ExchangePath::<T>::new_trivial(&dex_info, input_asset_id, output_asset_id);
+        let (maybe_path, _) =
+            Self::exchange_paths(&dex_id, &dex_info, input_asset_id, output_asset_id);
         maybe_path.map_or_else(
             || Err(Error::<T>::UnavailableExchangePath.into()),
             |paths| {
@@ -1409,6 +1704,35 @@ impl<T: Config> Pallet<T> {
         Ok(initial_result)
     }
 
+    /// Lists enabled sources of the trading pair of two assets. Trading pairs without the base
+    /// assets can be registered in any order of the assets, so both orders are checked for them.
+    fn list_enabled_sources_for_assets(
+        dex_id: &T::DEXId,
+        dex_info: &DEXInfo<T::AssetId>,
+        asset_a: T::AssetId,
+        asset_b: T::AssetId,
+    ) -> Result<BTreeSet<LiquiditySourceType>, DispatchError> {
+        let pair = Self::weak_sort_pair(dex_info, asset_a, asset_b);
+        T::TradingPairSourceManager::list_enabled_sources_for_trading_pair(
+            dex_id,
+            &pair.base_asset_id,
+            &pair.target_asset_id,
+        )
+        .or_else(|error| {
+            let is_dex_base_asset = |asset_id: &T::AssetId| {
+                *asset_id == dex_info.base_asset_id || *asset_id == dex_info.synthetic_base_asset_id
+            };
+            if is_dex_base_asset(&asset_a) || is_dex_base_asset(&asset_b) {
+                return Err(error);
+            }
+            T::TradingPairSourceManager::list_enabled_sources_for_trading_pair(
+                dex_id,
+                &pair.target_asset_id,
+                &pair.base_asset_id,
+            )
+        })
+    }
+
     // Not full sort, just ensure that if there is base asset then it's sorted, otherwise order is unchanged.
     fn weak_sort_pair(
         dex_info: &DEXInfo<T::AssetId>,
@@ -1663,6 +1987,216 @@ impl<T: Config> Pallet<T> {
         ))
     }
 
+    /// Splits a trade between any number of liquidity sources, e.g. order book price levels,
+    /// pools and primary markets, according to their marginal prices.
+    ///
+    /// - `sources` - IDs of the liquidity sources to split the trade between,
+    /// - `input_asset_id` - ID of the asset to sell,
+    /// - `output_asset_id` - ID of the asset to buy,
+    /// - `amount` - the amount with "direction" (sell or buy),
+    /// - `skip_info` - flag that indicates that additional info should not be shown, that is needed when actual exchange is performed.
+    ///
+    fn marginal_split(
+        sources: &[LiquiditySourceIdOf<T>],
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: QuoteAmount<Balance>,
+        skip_info: bool,
+        deduce_fee: bool,
+    ) -> Result<
+        (
+            AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>,
+            Rewards<T::AssetId>,
+            Weight,
+        ),
+        DispatchError,
+    > {
+        // The amount is divided into `SPLIT_PARTS` equal parts (the last one also takes the
+        // remainder) and each part goes to the source with the best price for it.
+        // The price of the first part in a source is its marginal price reported by
+        // `quote_without_impact`. Once a source has got a share of the trade, the price
+        // of its next part is the difference between the quotes of its current share and
+        // the share increased by one part. A source which fails to quote is not used anymore.
+        //
+        // The greedy allocation is optimal for sources with non-increasing marginal prices,
+        // but the price of the first part in a source is approximated, so the result is
+        // compared with the whole amount exchanged in each source alone. It guarantees that
+        // the split is never worse than the best single source.
+
+        let is_better: fn(a: Balance, b: Balance) -> bool = match amount {
+            QuoteAmount::WithDesiredInput { .. } => |a, b| a > b,
+            _ => |a, b| a < b,
+        };
+
+        let total_amount = amount.amount();
+        let parts = SPLIT_PARTS.min(total_amount).max(1);
+        let part = total_amount / parts;
+
+        // `quote_without_impact` doesn't report its weight, so it is taken as the weight of quote
+        let quote_weight = T::LiquidityRegistry::quote_weight();
+        let mut total_weight = quote_weight.saturating_mul(sources.len() as u64);
+        let mut allocated = vec![Balance::zero(); sources.len()];
+        let mut outcomes = vec![SwapOutcome::new(Balance::zero(), Balance::zero()); sources.len()];
+        let mut part_prices: Vec<Option<Balance>> = sources
+            .iter()
+            .map(|src| {
+                T::LiquidityRegistry::quote_without_impact(
+                    src,
+                    input_asset_id,
+                    output_asset_id,
+                    amount.copy_direction(part),
+                    deduce_fee,
+                )
+                .ok()
+                .map(|outcome| outcome.amount)
+            })
+            .collect();
+
+        let mut split = || -> Result<(), DispatchError> {
+            let mut remaining = total_amount;
+            while remaining > 0 {
+                let step = if remaining < part.saturating_mul(2) {
+                    remaining
+                } else {
+                    part
+                };
+                loop {
+                    let index = part_prices
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, price)| price.map(|price| (index, price)))
+                        .fold(
+                            None,
+                            |best: Option<(usize, Balance)>, (index, price)| match best {
+                                Some((_, best_price)) if !is_better(price, best_price) => best,
+                                _ => Some((index, price)),
+                            },
+                        )
+                        .map(|(index, _)| index)
+                        .ok_or(Error::<T>::AggregationError)?;
+
+                    let share = allocated[index].saturating_add(step);
+                    let Ok((outcome, weight)) = T::LiquidityRegistry::quote(
+                        &sources[index],
+                        input_asset_id,
+                        output_asset_id,
+                        amount.copy_direction(share),
+                        deduce_fee,
+                    ) else {
+                        total_weight = total_weight.saturating_add(quote_weight);
+                        part_prices[index] = None;
+                        continue;
+                    };
+                    total_weight = total_weight.saturating_add(weight);
+
+                    part_prices[index] = match T::LiquidityRegistry::quote(
+                        &sources[index],
+                        input_asset_id,
+                        output_asset_id,
+                        amount.copy_direction(share.saturating_add(part)),
+                        deduce_fee,
+                    ) {
+                        Ok((next_outcome, weight)) => {
+                            total_weight = total_weight.saturating_add(weight);
+                            Some(next_outcome.amount.saturating_sub(outcome.amount))
+                        }
+                        Err(_) => {
+                            total_weight = total_weight.saturating_add(quote_weight);
+                            None
+                        }
+                    };
+                    allocated[index] = share;
+                    outcomes[index] = outcome;
+                    break;
+                }
+                remaining = remaining.saturating_sub(step);
+            }
+            Ok(())
+        };
+
+        let mut distr = Vec::new();
+        let mut best = Balance::zero();
+        let mut total_fee = Balance::zero();
+        let mut maybe_error = split().err();
+
+        if maybe_error.is_none() {
+            for ((src, share), outcome) in sources
+                .iter()
+                .zip(allocated.into_iter())
+                .zip(outcomes.into_iter())
+            {
+                if share > Balance::zero() {
+                    best = best
+                        .checked_add(outcome.amount)
+                        .ok_or(Error::<T>::CalculationError)?;
+                    total_fee = total_fee
+                        .checked_add(outcome.fee)
+                        .ok_or(Error::<T>::CalculationError)?;
+                    distr.push((src.clone(), amount.copy_direction(share), outcome));
+                }
+            }
+        }
+
+        // The whole amount in a single source
+        for src in sources {
+            match T::LiquidityRegistry::quote(
+                src,
+                input_asset_id,
+                output_asset_id,
+                amount.clone(),
+                deduce_fee,
+            ) {
+                Ok((outcome, weight)) => {
+                    total_weight = total_weight.saturating_add(weight);
+                    if distr.is_empty() || is_better(outcome.amount, best) {
+                        best = outcome.amount;
+                        total_fee = outcome.fee;
+                        distr = vec![(src.clone(), amount.clone(), outcome)];
+                    }
+                }
+                Err(e) => {
+                    total_weight = total_weight.saturating_add(quote_weight);
+                    maybe_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if distr.is_empty() {
+            return Err(maybe_error.unwrap_or(Error::<T>::UnavailableExchangePath.into()));
+        }
+
+        let mut rewards = Vec::new();
+        if !skip_info {
+            for (src, share, outcome) in distr.iter() {
+                let (input_amount, output_amount) =
+                    share.clone().place_input_and_output(outcome.clone());
+                let (mut reward, reward_weight) = T::LiquidityRegistry::check_rewards(
+                    src,
+                    input_asset_id,
+                    output_asset_id,
+                    input_amount,
+                    output_amount,
+                )
+                .unwrap_or((Vec::new(), Weight::zero()));
+                total_weight = total_weight.saturating_add(reward_weight);
+                rewards.append(&mut reward);
+            }
+        }
+
+        Ok((
+            AggregatedSwapOutcome::new(
+                distr
+                    .into_iter()
+                    .map(|(src, share, _)| (src, share))
+                    .collect(),
+                best,
+                total_fee,
+            ),
+            rewards,
+            total_weight,
+        ))
+    }
+
     /// Determines the share of a swap that should be exchanged in the primary market
     /// (i.e., the multi-collateral bonding curve pool) based on the current reserves of
     /// the base asset and the collateral asset in the secondary market (e.g., an XYK pool)
@@ -1872,6 +2406,7 @@ impl<T: Config> Pallet<T> {
         dex_id: T::DEXId,
         filter_mode: &FilterMode,
         out_amount: Balance,
+        min_hop_amounts_out: &[(T::AssetId, Balance)],
     ) -> Result<(Balance, Balance, Weight), DispatchError> {
         Self::check_indivisible_assets(input_asset_id, output_asset_id)?;
         let mut total_weight = <T as Config>::WeightInfo::check_indivisible_assets();
@@ -1900,7 +2435,7 @@ impl<T: Config> Pallet<T> {
             },
             sources,
             weights,
-        ) = Self::inner_exchange(
+        ) = Self::inner_exchange_with_hop_minimums(
             dex_id,
             &sender,
             &sender,
@@ -1911,6 +2446,7 @@ impl<T: Config> Pallet<T> {
                 max_amount_in: max_input_amount,
             },
             filter.clone(),
+            min_hop_amounts_out,
         )?;
         total_weight = total_weight.saturating_add(weights);
 
@@ -2003,7 +2539,9 @@ impl<T: Config> Pallet<T> {
         mut max_input_amount: Balance,
         selected_source_types: &Vec<LiquiditySourceType>,
         filter_mode: &FilterMode,
+        options: &SwapOptionsOf<T>,
     ) -> Result<(Balance, Balance, Weight), DispatchError> {
+        Self::ensure_deadline(&options.deadline)?;
         let mut unique_asset_ids: BTreeSet<T::AssetId> = BTreeSet::new();
 
         let mut executed_batch_input_amount = balance!(0);
@@ -2070,6 +2608,7 @@ impl<T: Config> Pallet<T> {
                             dex_id,
                             &filter_mode,
                             desired_exchange_amount,
+                            &options.min_hop_amounts_out,
                         )?
                     } else {
                         (0, 0, Weight::zero())
@@ -2194,6 +2733,34 @@ impl<AssetId, DEXId, AccountId> SwapBatchInfo<AssetId, DEXId, AccountId> {
     }
 }
 
+/// The last moment when a swap can be executed.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum SwapDeadline<BlockNumber, Moment> {
+    /// The last block number
+    Block(BlockNumber),
+    /// The last timestamp, in milliseconds
+    Timestamp(Moment),
+}
+
+/// Additional restrictions of a swap execution.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SwapOptions<AssetId, BlockNumber, Moment> {
+    /// The swap fails if it's executed after the deadline
+    pub deadline: Option<SwapDeadline<BlockNumber, Moment>>,
+    /// Minimum output amounts of the exchanges in the route, by their output asset.
+    /// Exchanges to other assets are not restricted
+    pub min_hop_amounts_out: BoundedVec<(AssetId, Balance), ConstU32<MAX_HOP_AMOUNTS_OUT>>,
+}
+
+impl<AssetId, BlockNumber, Moment> Default for SwapOptions<AssetId, BlockNumber, Moment> {
+    fn default() -> Self {
+        Self {
+            deadline: None,
+            min_hop_amounts_out: BoundedVec::default(),
+        }
+    }
+}
+
 pub struct LiquidityProxyBuyBackHandler<T, GetDEXId>(PhantomData<(T, GetDEXId)>);
 
 impl<T: Config, GetDEXId: Get<T::DEXId>> BuyBackHandler<T::AccountId, T::AssetId>
@@ -2297,6 +2864,14 @@ pub mod pallet {
         type ADARCommissionRatioUpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         type MaxAdditionalDataLength: Get<u32>;
         type DexInfoProvider: DexInfoProvider<Self::DEXId, DEXInfo<Self::AssetId>>;
+        /// Maximum number of exchanges over trading pairs without the base assets in a path.
+        #[pallet::constant]
+        type MaxRouteHops: Get<u32>;
+        /// Maximum number of candidate paths quoted for a swap.
+        #[pallet::constant]
+        type MaxRoutePaths: Get<u32>;
+        /// Time used to check swap deadlines.
+        type Time: Time;
         /// Weight information for the extrinsics in this Pallet.
         type WeightInfo: WeightInfo;
     }
@@ -2425,6 +3000,7 @@ pub mod pallet {
                     max_input_amount,
                     &selected_source_types,
                     &filter_mode,
+                    &SwapOptions::default(),
                 )?;
 
             Self::deposit_event(Event::<T>::BatchSwapExecuted(
@@ -2575,6 +3151,101 @@ pub mod pallet {
                 pays_fee: Pays::Yes,
             })
         }
+
+        /// Perform swap of tokens (input/output defined via SwapAmount direction) with additional
+        /// restrictions on its execution.
+        ///
+        /// - `origin`: the account on whose behalf the transaction is being executed,
+        /// - `receiver`: the account that receives the output,
+        /// - `dex_id`: DEX ID for which liquidity sources aggregation is being done,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `output_asset_id`: ID of the asset being bought,
+        /// - `swap_amount`: the exact amount to be sold (either in input_asset_id or output_asset_id units with corresponding slippage tolerance absolute bound),
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering,
+        /// - `options`: the deadline (block number or timestamp) after which the swap fails and
+        ///              minimum output amounts of the exchanges in the route.
+        #[pallet::call_index(7)]
+        #[pallet::weight(Pallet::<T>::swap_weight(dex_id, input_asset_id, output_asset_id, (*swap_amount).into(), selected_source_types, filter_mode))]
+        pub fn swap_transfer_with_options(
+            origin: OriginFor<T>,
+            receiver: T::AccountId,
+            dex_id: T::DEXId,
+            input_asset_id: T::AssetId,
+            output_asset_id: T::AssetId,
+            swap_amount: SwapAmount<Balance>,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+            options: SwapOptionsOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let weight = Self::inner_swap_with_options(
+                who,
+                receiver,
+                dex_id,
+                input_asset_id,
+                output_asset_id,
+                swap_amount,
+                selected_source_types,
+                filter_mode,
+                options,
+            )?;
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight),
+                pays_fee: Pays::Yes,
+            })
+        }
+
+        /// Same as `swap_transfer_batch`, but with additional restrictions on execution of
+        /// the swaps.
+        ///
+        /// - `origin`: the account on whose behalf the transaction is being executed,
+        /// - `swap_batches`: the vector containing the SwapBatchInfo structs,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `max_input_amount`: the maximum amount to be sold in input_asset_id,
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is
+        ///                            determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering,
+        /// - `options`: the deadline (block number or timestamp) after which the batch fails and
+        ///              minimum output amounts of the exchanges in the routes.
+        #[transactional]
+        #[pallet::call_index(8)]
+        #[pallet::weight(Pallet::<T>::swap_transfer_batch_weight(swap_batches, input_asset_id, selected_source_types, filter_mode))]
+        pub fn swap_transfer_batch_with_options(
+            origin: OriginFor<T>,
+            swap_batches: Vec<SwapBatchInfo<T::AssetId, T::DEXId, T::AccountId>>,
+            input_asset_id: T::AssetId,
+            max_input_amount: Balance,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+            options: SwapOptionsOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let (adar_commission, executed_input_amount, mut weight) =
+                Self::inner_swap_batch_transfer(
+                    &who,
+                    &input_asset_id,
+                    swap_batches,
+                    max_input_amount,
+                    &selected_source_types,
+                    &filter_mode,
+                    &options,
+                )?;
+
+            Self::deposit_event(Event::<T>::BatchSwapExecuted(
+                adar_commission,
+                executed_input_amount,
+            ));
+
+            weight = weight.saturating_add(<T as assets::Config>::WeightInfo::transfer());
+
+            Ok(PostDispatchInfo {
+                actual_weight: Some(weight),
+                pays_fee: Pays::Yes,
+            })
+        }
     }
 
     #[pallet::event]
@@ -2653,6 +3324,10 @@ pub mod pallet {
         InsufficientBalance,
         // Sender and receiver should not be the same
         TheSameSenderAndReceiver,
+        /// Swap deadline has already passed
+        SwapDeadlineExpired,
+        /// Output amount of an exchange in the route is less than the specified minimum
+        HopSlippageNotTolerated,
     }
 
     #[pallet::type_value]
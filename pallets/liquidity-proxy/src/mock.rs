@@ -53,6 +53,7 @@ use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
 use sp_runtime::{AccountId32, DispatchError, Perbill, Percent};
 use sp_std::str::FromStr;
+use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap};
 
 pub type AccountId = AccountId32;
@@ -206,6 +207,9 @@ impl Config for Runtime {
     type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type TradingPairSourceManager = trading_pair::Pallet<Runtime>;
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
+    type MaxRouteHops = ConstU32<4>;
+    type MaxRoutePaths = ConstU32<8>;
+    type Time = Timestamp;
 }
 
 impl tokens::Config for Runtime {
@@ -332,7 +336,8 @@ impl dex_api::Config for Runtime {
     type MulticollateralBondingCurvePool = MockMCBCPool;
     type XSTPool = MockXSTPool;
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
-    type OrderBook = (); // todo (m.tagirov) ALT
+    type OrderBook = MockOrderBook;
+    type StableSwapPool = ();
     type WeightInfo = ();
 }
 
@@ -372,6 +377,7 @@ impl demeter_farming_platform::Config for Runtime {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -383,9 +389,15 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
     type EnabledSourcesManager = trading_pair::Pallet<Runtime>;
+    type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type OnPoolCreated = pswap_distribution::Pallet<Runtime>;
     type OnPoolReservesChanged = ();
     type GetFee = GetXykFee;
+    type GetMinPoolFee = ();
+    type GetMaxPoolFee = ();
+    type TreasuryTechAccountId = ();
+    type MaxPriceObservations = ();
+    type PriceObservationPeriod = ();
     type WeightInfo = ();
     type XSTMarketInfo = ();
     type GetTradingPairRestrictedFlag = GetTradingPairRestrictedFlag;
@@ -900,6 +912,11 @@ impl ExtBuilder {
         }
     }
 
+    pub fn with_order_book(mut self) -> Self {
+        self.source_types.push(LiquiditySourceType::OrderBook);
+        self
+    }
+
     pub fn with_xyk_pool(mut self) -> Self {
         self.xyk_reserves = vec![
             (DEX_A_ID, USDT, (balance!(1000), balance!(1000))),
@@ -1177,3 +1194,138 @@ impl GetMarketInfo<AssetId> for MockXSTPool {
         [XSTUSD].iter().cloned().collect()
     }
 }
+
+thread_local! {
+    static ORDER_BOOK_ASKS: RefCell<HashMap<(DEXId, AssetId), Vec<(Balance, Balance)>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Order book selling assets for XOR, the asset can be bought only if its asks are set.
+pub struct MockOrderBook;
+
+impl MockOrderBook {
+    /// Sets asks of the asset as (price in XOR, amount of the asset), the best price goes first.
+    pub fn set_asks(dex_id: DEXId, asset_id: AssetId, asks: Vec<(Balance, Balance)>) {
+        ORDER_BOOK_ASKS.with(|order_book| order_book.borrow_mut().insert((dex_id, asset_id), asks));
+    }
+
+    fn asks(dex_id: &DEXId, asset_id: &AssetId) -> Vec<(Balance, Balance)> {
+        ORDER_BOOK_ASKS.with(|order_book| {
+            order_book
+                .borrow()
+                .get(&(*dex_id, *asset_id))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+}
+
+fn mul(a: Balance, b: Balance) -> Balance {
+    (FixedWrapper::from(a) * FixedWrapper::from(b))
+        .try_into_balance()
+        .unwrap()
+}
+
+fn div(a: Balance, b: Balance) -> Balance {
+    (FixedWrapper::from(a) / FixedWrapper::from(b))
+        .try_into_balance()
+        .unwrap()
+}
+
+impl LiquiditySource<DEXId, AccountId, AssetId, Balance, DispatchError> for MockOrderBook {
+    fn can_exchange(dex_id: &DEXId, input_asset_id: &AssetId, output_asset_id: &AssetId) -> bool {
+        *input_asset_id == XOR && !Self::asks(dex_id, output_asset_id).is_empty()
+    }
+
+    fn quote(
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        amount: QuoteAmount<Balance>,
+        _deduce_fee: bool,
+    ) -> Result<(SwapOutcome<Balance>, Weight), DispatchError> {
+        if !Self::can_exchange(dex_id, input_asset_id, output_asset_id) {
+            panic!("Can't exchange");
+        }
+        let mut amount_left = amount.amount();
+        let mut result = 0;
+        for (price, volume) in Self::asks(dex_id, output_asset_id) {
+            // amounts of XOR and the asset
+            let (level_in, level_out) = (mul(price, volume), volume);
+            let (level_amount, level_result) = match amount {
+                QuoteAmount::WithDesiredInput { .. } => (level_in, level_out),
+                QuoteAmount::WithDesiredOutput { .. } => (level_out, level_in),
+            };
+            if amount_left <= level_amount {
+                result += match amount {
+                    QuoteAmount::WithDesiredInput { .. } => div(amount_left, price),
+                    QuoteAmount::WithDesiredOutput { .. } => mul(amount_left, price),
+                };
+                amount_left = 0;
+                break;
+            }
+            amount_left -= level_amount;
+            result += level_result;
+        }
+        ensure!(
+            amount_left == 0,
+            crate::Error::<Runtime>::InsufficientLiquidity
+        );
+        Ok((SwapOutcome::new(result, 0), Self::quote_weight()))
+    }
+
+    fn exchange(
+        _sender: &AccountId,
+        _receiver: &AccountId,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+        _desired_amount: SwapAmount<Balance>,
+    ) -> Result<(SwapOutcome<Balance>, Weight), DispatchError> {
+        unimplemented!()
+    }
+
+    fn check_rewards(
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+        _input_amount: Balance,
+        _output_amount: Balance,
+    ) -> Result<(Vec<(Balance, AssetId, RewardReason)>, Weight), DispatchError> {
+        Ok((Vec::new(), Weight::zero()))
+    }
+
+    fn quote_without_impact(
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        amount: QuoteAmount<Balance>,
+        _deduce_fee: bool,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        if !Self::can_exchange(dex_id, input_asset_id, output_asset_id) {
+            panic!("Can't exchange");
+        }
+        let (best_price, _) = Self::asks(dex_id, output_asset_id)[0];
+        let result = match amount {
+            QuoteAmount::WithDesiredInput { desired_amount_in } => {
+                div(desired_amount_in, best_price)
+            }
+            QuoteAmount::WithDesiredOutput { desired_amount_out } => {
+                mul(desired_amount_out, best_price)
+            }
+        };
+        Ok(SwapOutcome::new(result, 0))
+    }
+
+    fn quote_weight() -> Weight {
+        Weight::zero()
+    }
+
+    fn exchange_weight() -> Weight {
+        Weight::from_all(1)
+    }
+
+    fn check_rewards_weight() -> Weight {
+        Weight::zero()
+    }
+}
@@ -31,16 +31,19 @@
 use crate::mock::*;
 use crate::test_utils::calculate_swap_batch_input_amount_with_adar_commission;
 use crate::weights::WeightInfo;
-use crate::{test_utils, BatchReceiverInfo, Error, QuoteInfo, SwapBatchInfo};
+use crate::{
+    test_utils, AggregatedSwapOutcome, BatchReceiverInfo, Error, ExchangePath, QuoteInfo,
+    SwapBatchInfo, SwapDeadline, SwapOptions,
+};
 use common::prelude::fixnum::ops::CheckedSub;
 use common::prelude::{
     AssetName, AssetSymbol, Balance, FixedWrapper, QuoteAmount, SwapAmount, SwapVariant,
 };
 use common::{
-    assert_approx_eq, balance, fixed, fixed_wrapper, AssetInfoProvider, BuyBackHandler, FilterMode,
-    Fixed, LiquidityProxyTrait, LiquiditySource, LiquiditySourceFilter, LiquiditySourceId,
-    LiquiditySourceType, ReferencePriceProvider, RewardReason, TradingPairSourceManager, DAI, DOT,
-    ETH, KSM, PSWAP, USDT, VAL, XOR, XST, XSTUSD,
+    assert_approx_eq, balance, fixed, fixed_wrapper, AssetInfoProvider, BuyBackHandler,
+    DexInfoProvider, FilterMode, Fixed, LiquidityProxyTrait, LiquiditySource,
+    LiquiditySourceFilter, LiquiditySourceId, LiquiditySourceType, ReferencePriceProvider,
+    RewardReason, TradingPairSourceManager, DAI, DOT, ETH, KSM, PSWAP, USDT, VAL, XOR, XST, XSTUSD,
 };
 use core::convert::TryInto;
 use frame_support::weights::Weight;
@@ -725,7 +728,7 @@ fn test_quote_should_fail_with_aggregation_error() {
             false,
             true,
         );
-        assert_noop!(result, Error::<Runtime>::UnavailableExchangePath);
+        assert_noop!(result, Error::<Runtime>::AggregationError);
     });
 }
 
@@ -813,6 +816,7 @@ fn test_swap_weight_considers_available_sources() {
                     .saturating_mul(2),
             );
         let exchange_base_weight = <Runtime as crate::Config>::WeightInfo::new_trivial()
+            .saturating_add(<Runtime as crate::Config>::WeightInfo::new_multi_hop(2)) // neighbours of the input and output assets
             .saturating_add(quote_single_weight); // once within a path
         let multicollateral_weight =
             <Runtime as dex_api::Config>::MulticollateralBondingCurvePool::exchange_weight();
@@ -923,6 +927,7 @@ fn test_swap_weight_filters_sources() {
                     .saturating_mul(2),
             );
         let exchange_base_weight = <Runtime as crate::Config>::WeightInfo::new_trivial()
+            .saturating_add(<Runtime as crate::Config>::WeightInfo::new_multi_hop(2)) // neighbours of the input and output assets
             .saturating_add(quote_single_weight); // once within a path
         let multicollateral_weight =
             <Runtime as dex_api::Config>::MulticollateralBondingCurvePool::exchange_weight();
@@ -4214,3 +4219,609 @@ fn test_xorless_transfer_fails_on_transfer() {
         );
     });
 }
+
+fn register_assets_for_routing(assets: &[(AssetId, &[u8])]) {
+    for (asset_id, symbol) in assets {
+        assets::Pallet::<Runtime>::register_asset_id(
+            alice(),
+            *asset_id,
+            AssetSymbol(symbol.to_vec()),
+            AssetName(symbol.to_vec()),
+            0,
+            Balance::from(0u32),
+            true,
+            None,
+            None,
+        )
+        .expect("failed to register asset");
+    }
+}
+
+#[test]
+fn test_new_multi_hop_finds_bounded_paths() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        register_assets_for_routing(&[(DAI, b"DAI"), (DOT, b"DOT")]);
+        TradingPair::register_non_base_pair(0, USDT, DAI).expect("failed to register pair");
+        TradingPair::register_non_base_pair(0, DAI, DOT).expect("failed to register pair");
+        let dex_info = DexManager::get_dex_info(&0).unwrap();
+        let paths = |max_hops, max_paths| {
+            let (paths, reads) = ExchangePath::<Runtime>::new_multi_hop(
+                &0, &dex_info, USDT, DOT, max_hops, max_paths,
+            );
+            let paths: Vec<_> = paths.into_iter().map(|ExchangePath(path)| path).collect();
+            (paths, reads)
+        };
+
+        assert_eq!(paths(0, 8), (vec![], 0));
+        assert!(
+            ExchangePath::<Runtime>::new_multi_hop(&0, &dex_info, DOT, DOT, 4, 8)
+                .0
+                .is_empty()
+        );
+
+        // a single exchange over the pairs without the base asset at the beginning or the end
+        assert_eq!(
+            paths(1, 8),
+            (
+                vec![vec![USDT, XOR, DAI, DOT], vec![USDT, DAI, XOR, DOT]],
+                2
+            )
+        );
+
+        // shorter paths come first, paths which visit an asset twice are skipped
+        assert_eq!(
+            paths(4, 8),
+            (
+                vec![
+                    vec![USDT, DAI, DOT],
+                    vec![USDT, XOR, DAI, DOT],
+                    vec![USDT, DAI, XOR, DOT]
+                ],
+                6
+            )
+        );
+
+        // neighbours of at most `max_paths` assets are read from each side
+        assert_eq!(
+            paths(4, 2),
+            (vec![vec![USDT, DAI, DOT], vec![USDT, XOR, DAI, DOT]], 4)
+        );
+
+        // the assets without the pairs without the base asset are exchanged via trivial paths
+        let (paths, reads) = ExchangePath::<Runtime>::new_multi_hop(&0, &dex_info, VAL, DOT, 4, 8);
+        let paths: Vec<_> = paths.into_iter().map(|ExchangePath(path)| path).collect();
+        assert_eq!(
+            paths,
+            vec![vec![VAL, XOR, DAI, DOT], vec![VAL, XOR, USDT, DAI, DOT]]
+        );
+        assert_eq!(reads, 4);
+    });
+}
+
+#[test]
+fn test_exchange_paths_include_multi_hop_routes() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        use LiquiditySourceType::*;
+        register_assets_for_routing(&[(DAI, b"DAI"), (DOT, b"DOT")]);
+        let dex_info = DexManager::get_dex_info(&0).unwrap();
+
+        let (paths, weight) = LiquidityProxy::exchange_paths(&0, &dex_info, DOT, DAI);
+        let paths: Vec<_> = paths
+            .unwrap()
+            .into_iter()
+            .map(|ExchangePath(path)| path)
+            .collect();
+        assert_eq!(paths, vec![vec![DOT, XOR, DAI]]);
+        assert_eq!(
+            weight,
+            <Runtime as crate::Config>::WeightInfo::new_trivial()
+                .saturating_add(<Runtime as crate::Config>::WeightInfo::new_multi_hop(2))
+        );
+
+        TradingPair::register_non_base_pair(0, DAI, DOT).expect("failed to register pair");
+        let (paths, weight) = LiquidityProxy::exchange_paths(&0, &dex_info, DOT, DAI);
+        let paths: Vec<_> = paths
+            .unwrap()
+            .into_iter()
+            .map(|ExchangePath(path)| path)
+            .collect();
+        assert_eq!(paths, vec![vec![DOT, XOR, DAI], vec![DOT, DAI]]);
+        assert_eq!(
+            weight,
+            <Runtime as crate::Config>::WeightInfo::new_trivial()
+                .saturating_add(<Runtime as crate::Config>::WeightInfo::new_multi_hop(4))
+        );
+        // the pair has no enabled sources yet
+        assert!(LiquidityProxy::list_enabled_sources_for_path(0, DOT, DAI).is_err());
+
+        // the pair is found in both directions although it is registered as DAI/DOT
+        TradingPair::enable_source_for_trading_pair(&0, &DAI, &DOT, MockPool)
+            .expect("failed to enable source");
+        for (input_asset_id, output_asset_id) in [(DOT, DAI), (DAI, DOT)] {
+            assert_eq!(
+                LiquidityProxy::list_enabled_sources_for_path(0, input_asset_id, output_asset_id),
+                Ok(vec![MockPool])
+            );
+        }
+    });
+}
+
+#[test]
+fn test_quote_returns_path_amounts() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockMCBCPool::init(get_mcbc_reserves_normal()).unwrap();
+        let filter = LiquiditySourceFilter::with_allowed(
+            DEX_D_ID,
+            [
+                LiquiditySourceType::MulticollateralBondingCurvePool,
+                LiquiditySourceType::MockPool,
+            ]
+            .to_vec(),
+        );
+        let amount_val_in = balance!(45700);
+
+        let (
+            QuoteInfo {
+                outcome,
+                path,
+                path_amounts,
+                ..
+            },
+            _,
+        ) = LiquidityProxy::inner_quote(
+            DEX_D_ID,
+            &VAL,
+            &KSM,
+            QuoteAmount::with_desired_input(amount_val_in),
+            filter.clone(),
+            false,
+            true,
+        )
+        .expect("Failed to get a quote");
+        assert_eq!(path, vec![VAL, GetBaseAssetId::get(), KSM]);
+        assert_eq!(path_amounts.len(), path.len());
+        assert_eq!(path_amounts[0], amount_val_in);
+        assert_eq!(path_amounts[2], outcome.amount);
+
+        let (intermediate, _, _, _) = LiquidityProxy::quote_single(
+            &GetBaseAssetId::get(),
+            &VAL,
+            &GetBaseAssetId::get(),
+            QuoteAmount::with_desired_input(amount_val_in),
+            filter.clone(),
+            false,
+            true,
+        )
+        .expect("Failed to get a quote");
+        assert_eq!(path_amounts[1], intermediate.amount);
+
+        let amount_ksm_out = outcome.amount;
+        let (
+            QuoteInfo {
+                outcome,
+                path,
+                path_amounts,
+                ..
+            },
+            _,
+        ) = LiquidityProxy::inner_quote(
+            DEX_D_ID,
+            &VAL,
+            &KSM,
+            QuoteAmount::with_desired_output(amount_ksm_out),
+            filter,
+            false,
+            true,
+        )
+        .expect("Failed to get a quote");
+        assert_eq!(path, vec![VAL, GetBaseAssetId::get(), KSM]);
+        assert_eq!(path_amounts.len(), path.len());
+        assert_eq!(path_amounts[0], outcome.amount);
+        assert_eq!(path_amounts[2], amount_ksm_out);
+    });
+}
+
+#[test]
+fn test_swap_with_expired_deadline_should_fail() {
+    let mut ext = ExtBuilder::default().with_xyk_pool().build();
+    ext.execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(10);
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(1_000);
+
+        for deadline in [SwapDeadline::Block(9), SwapDeadline::Timestamp(999)] {
+            assert_noop!(
+                LiquidityProxy::swap_transfer_with_options(
+                    RuntimeOrigin::signed(alice()),
+                    bob(),
+                    DEX_A_ID,
+                    USDT,
+                    XOR,
+                    SwapAmount::with_desired_input(balance!(10), balance!(1)),
+                    [LiquiditySourceType::XYKPool].to_vec(),
+                    FilterMode::AllowSelected,
+                    SwapOptions {
+                        deadline: Some(deadline),
+                        min_hop_amounts_out: Default::default(),
+                    },
+                ),
+                Error::<Runtime>::SwapDeadlineExpired
+            );
+        }
+
+        for deadline in [SwapDeadline::Block(10), SwapDeadline::Timestamp(1_000)] {
+            assert_ok!(LiquidityProxy::swap_transfer_with_options(
+                RuntimeOrigin::signed(alice()),
+                bob(),
+                DEX_A_ID,
+                USDT,
+                XOR,
+                SwapAmount::with_desired_input(balance!(10), balance!(1)),
+                [LiquiditySourceType::XYKPool].to_vec(),
+                FilterMode::AllowSelected,
+                SwapOptions {
+                    deadline: Some(deadline),
+                    min_hop_amounts_out: Default::default(),
+                },
+            ));
+        }
+    });
+}
+
+#[test]
+fn test_swap_with_hop_minimums_should_pass() {
+    let mut ext = ExtBuilder::default().with_xyk_pool().build();
+    ext.execute_with(|| {
+        let amount_in = balance!(10);
+        let filter =
+            LiquiditySourceFilter::with_allowed(DEX_A_ID, [LiquiditySourceType::XYKPool].to_vec());
+        let (
+            QuoteInfo {
+                path, path_amounts, ..
+            },
+            _,
+        ) = LiquidityProxy::inner_quote(
+            DEX_A_ID,
+            &USDT,
+            &KSM,
+            QuoteAmount::with_desired_input(amount_in),
+            filter,
+            true,
+            true,
+        )
+        .expect("Failed to get a quote");
+        assert_eq!(path, vec![USDT, XOR, KSM]);
+
+        let swap = |min_xor_out: Balance| {
+            LiquidityProxy::swap_transfer_with_options(
+                RuntimeOrigin::signed(alice()),
+                bob(),
+                DEX_A_ID,
+                USDT,
+                KSM,
+                SwapAmount::with_desired_input(amount_in, balance!(0)),
+                [LiquiditySourceType::XYKPool].to_vec(),
+                FilterMode::AllowSelected,
+                SwapOptions {
+                    deadline: None,
+                    // the entry for the asset which is not in the route is ignored
+                    min_hop_amounts_out: vec![(XOR, min_xor_out), (DOT, balance!(1000))]
+                        .try_into()
+                        .unwrap(),
+                },
+            )
+        };
+
+        assert_noop!(
+            swap(path_amounts[1] + 1),
+            Error::<Runtime>::HopSlippageNotTolerated
+        );
+        assert_ok!(swap(path_amounts[1]));
+        assert_eq!(Assets::free_balance(&KSM, &bob()).unwrap(), path_amounts[2]);
+    });
+}
+
+#[test]
+fn test_batch_swap_with_expired_deadline_should_fail() {
+    let mut ext = ExtBuilder::default().with_xyk_pool().build();
+    ext.execute_with(|| {
+        let swap_batches = Vec::from([SwapBatchInfo {
+            outcome_asset_id: KSM,
+            dex_id: DEX_A_ID,
+            receivers: vec![BatchReceiverInfo::new(bob(), balance!(10))],
+            outcome_asset_reuse: 0,
+        }]);
+        let sources = [LiquiditySourceType::XYKPool].to_vec();
+        let max_input_amount =
+            calculate_swap_batch_input_amount_with_adar_commission(&swap_batches, sources.clone())
+                + balance!(1);
+
+        frame_system::Pallet::<Runtime>::set_block_number(2);
+        assert_noop!(
+            LiquidityProxy::swap_transfer_batch_with_options(
+                RuntimeOrigin::signed(alice()),
+                swap_batches.clone(),
+                XOR,
+                max_input_amount,
+                sources.clone(),
+                FilterMode::AllowSelected,
+                SwapOptions {
+                    deadline: Some(SwapDeadline::Block(1)),
+                    min_hop_amounts_out: Default::default(),
+                },
+            ),
+            Error::<Runtime>::SwapDeadlineExpired
+        );
+
+        assert_ok!(LiquidityProxy::swap_transfer_batch_with_options(
+            RuntimeOrigin::signed(alice()),
+            swap_batches.clone(),
+            XOR,
+            max_input_amount,
+            sources.clone(),
+            FilterMode::AllowSelected,
+            SwapOptions {
+                deadline: Some(SwapDeadline::Block(2)),
+                min_hop_amounts_out: Default::default(),
+            },
+        ));
+        test_utils::check_swap_batch_executed_amount(swap_batches);
+    });
+}
+
+#[test]
+fn test_marginal_split_between_many_sources_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let sources = [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ];
+        let amount = QuoteAmount::with_desired_input(balance!(500));
+
+        let (quotes, _rewards, _, _) = LiquidityProxy::quote_single(
+            &GetBaseAssetId::get(),
+            &GetBaseAssetId::get(),
+            &DOT,
+            amount,
+            LiquiditySourceFilter::with_allowed(DEX_C_ID, sources.to_vec()),
+            false,
+            true,
+        )
+        .expect("Failed to get a quote");
+
+        // The trade is split between several sources
+        assert!(quotes.distribution.len() > 1);
+        let distributed = quotes
+            .distribution
+            .iter()
+            .fold(balance!(0), |acc, (_, part)| acc + part.amount());
+        assert_eq!(distributed, amount.amount());
+
+        for source in sources {
+            let (single, _rewards, _, _) = LiquidityProxy::quote_single(
+                &GetBaseAssetId::get(),
+                &GetBaseAssetId::get(),
+                &DOT,
+                amount,
+                LiquiditySourceFilter::with_allowed(DEX_C_ID, vec![source]),
+                false,
+                true,
+            )
+            .expect("Failed to get a quote");
+            assert!(quotes.amount > single.amount);
+        }
+    });
+}
+
+#[test]
+fn test_marginal_split_is_never_worse_than_single_source() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let sources = [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ];
+
+        // Linear congruential generator, so the cases are reproducible
+        let mut seed: u128 = 42;
+        let mut random = |max: u128| -> Balance {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            balance!(1) * ((seed >> 64) % max + 1)
+        };
+
+        for _ in 0..50 {
+            let reserves: Vec<(Fixed, Fixed)> = (0..sources.len())
+                .map(|_| {
+                    (
+                        Fixed::from_bits(random(10000).try_into().unwrap()),
+                        Fixed::from_bits(random(10000).try_into().unwrap()),
+                    )
+                })
+                .collect();
+            mock_liquidity_source::Reserves::<Runtime, mock_liquidity_source::Instance1>::insert(
+                DEX_C_ID,
+                DOT,
+                reserves[0],
+            );
+            mock_liquidity_source::Reserves::<Runtime, mock_liquidity_source::Instance2>::insert(
+                DEX_C_ID,
+                DOT,
+                reserves[1],
+            );
+            mock_liquidity_source::Reserves::<Runtime, mock_liquidity_source::Instance3>::insert(
+                DEX_C_ID,
+                DOT,
+                reserves[2],
+            );
+            mock_liquidity_source::Reserves::<Runtime, mock_liquidity_source::Instance4>::insert(
+                DEX_C_ID,
+                DOT,
+                reserves[3],
+            );
+
+            let amounts = [
+                QuoteAmount::with_desired_input(random(2000)),
+                QuoteAmount::with_desired_output(random(100)),
+            ];
+            for (input_asset_id, output_asset_id) in [(XOR, DOT), (DOT, XOR)] {
+                for amount in amounts {
+                    let quote = |sources: Vec<LiquiditySourceType>| {
+                        LiquidityProxy::quote_single(
+                            &GetBaseAssetId::get(),
+                            &input_asset_id,
+                            &output_asset_id,
+                            amount,
+                            LiquiditySourceFilter::with_allowed(DEX_C_ID, sources),
+                            true,
+                            true,
+                        )
+                        .map(|(outcome, _, _, _)| outcome)
+                    };
+
+                    let singles: Vec<_> = sources
+                        .iter()
+                        .filter_map(|source| quote(vec![*source]).ok())
+                        .collect();
+                    if singles.is_empty() {
+                        continue;
+                    }
+
+                    let split = quote(sources.to_vec()).expect("Failed to get a quote");
+                    let distributed = split
+                        .distribution
+                        .iter()
+                        .fold(balance!(0), |acc, (_, part)| acc + part.amount());
+                    assert_eq!(distributed, amount.amount());
+
+                    for single in singles {
+                        match amount {
+                            QuoteAmount::WithDesiredInput { .. } => {
+                                assert!(split.amount >= single.amount)
+                            }
+                            QuoteAmount::WithDesiredOutput { .. } => {
+                                assert!(split.amount <= single.amount)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Checks that the marginal split of `amount` between `sources` distributes the whole amount,
+/// is not worse than any of the sources alone and its weight is covered by the quote weight.
+fn check_marginal_split(
+    dex_id: DEXId,
+    input_asset_id: AssetId,
+    output_asset_id: AssetId,
+    amount: QuoteAmount<Balance>,
+    sources: Vec<LiquiditySourceType>,
+) -> AggregatedSwapOutcome<LiquiditySourceId<DEXId, LiquiditySourceType>, Balance> {
+    let quote = |sources: Vec<LiquiditySourceType>| {
+        LiquidityProxy::quote_single(
+            &GetBaseAssetId::get(),
+            &input_asset_id,
+            &output_asset_id,
+            amount,
+            LiquiditySourceFilter::with_allowed(dex_id, sources),
+            true,
+            true,
+        )
+    };
+
+    let (split, _rewards, split_sources, weight) =
+        quote(sources.clone()).expect("Failed to get a quote");
+    assert_eq!(split_sources.len(), sources.len());
+    let distributed = split
+        .distribution
+        .iter()
+        .fold(balance!(0), |acc, (_, part)| acc + part.amount());
+    assert_eq!(distributed, amount.amount());
+
+    // `quote_without_impact` is called for each source
+    let quote_weight = <Runtime as crate::Config>::LiquidityRegistry::quote_weight();
+    assert!(weight.all_gte(quote_weight.saturating_mul(sources.len() as u64)));
+    assert!(LiquidityProxy::quote_single_weight(sources.len()).all_gte(weight));
+
+    for source in sources {
+        if let Ok((single, _, _, _)) = quote(vec![source]) {
+            match amount {
+                QuoteAmount::WithDesiredInput { .. } => assert!(split.amount >= single.amount),
+                QuoteAmount::WithDesiredOutput { .. } => assert!(split.amount <= single.amount),
+            }
+        }
+    }
+    split
+}
+
+#[test]
+fn test_marginal_split_with_order_book_should_pass() {
+    let mut ext = ExtBuilder::default().with_order_book().build();
+    ext.execute_with(|| {
+        // The order book has not enough liquidity for the whole amount, but has the best price
+        // for its first levels
+        MockOrderBook::set_asks(
+            DEX_C_ID,
+            DOT,
+            vec![
+                (balance!(0.7), balance!(50)),
+                (balance!(0.75), balance!(50)),
+                (balance!(0.9), balance!(100)),
+            ],
+        );
+        let sources = vec![
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::OrderBook,
+        ];
+
+        for amount in [
+            QuoteAmount::with_desired_input(balance!(200)),
+            QuoteAmount::with_desired_output(balance!(250)),
+        ] {
+            let split = check_marginal_split(DEX_C_ID, XOR, DOT, amount, sources.clone());
+            assert!(split.distribution.iter().any(|(source, _)| {
+                source.liquidity_source_index == LiquiditySourceType::OrderBook
+            }));
+            assert!(split.distribution.len() > 1);
+        }
+    });
+}
+
+#[test]
+fn test_marginal_split_with_mcbc_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockMCBCPool::init(get_mcbc_reserves_normal()).unwrap();
+        let sources = vec![
+            LiquiditySourceType::MulticollateralBondingCurvePool,
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+        ];
+
+        for (input_asset_id, output_asset_id) in [(XOR, DOT), (DOT, XOR)] {
+            for amount in [
+                QuoteAmount::with_desired_input(balance!(100)),
+                QuoteAmount::with_desired_output(balance!(10)),
+            ] {
+                check_marginal_split(
+                    DEX_C_ID,
+                    input_asset_id,
+                    output_asset_id,
+                    amount,
+                    sources.clone(),
+                );
+            }
+        }
+    });
+}
@@ -64,6 +64,7 @@ pub trait WeightInfo {
 	fn disable_liquidity_source() -> Weight;
 	fn check_indivisible_assets() -> Weight;
 	fn new_trivial() -> Weight;
+	fn new_multi_hop(n: u32, ) -> Weight;
 	fn is_forbidden_filter() -> Weight;
 	fn list_liquidity_sources() -> Weight;
 	fn set_adar_commission_ratio() -> Weight;
@@ -114,6 +115,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(15_615_000, 5295)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 	}
+	/// Storage: TradingPair AssetNeighbours (r:16 w:0)
+	/// Proof Skipped: TradingPair AssetNeighbours (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XSTPool EnabledSynthetics (r:2 w:0)
+	/// Proof Skipped: XSTPool EnabledSynthetics (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 16]`.
+	fn new_multi_hop(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412 + n * (297 ±0)`
+		//  Estimated: `5362 + n * (2772 ±0)`
+		// Minimum execution time: 13_208_000 picoseconds.
+		Weight::from_parts(14_317_490, 5362)
+			// Standard Error: 5_418
+			.saturating_add(Weight::from_parts(6_284_113, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2772).saturating_mul(n.into()))
+	}
 	/// Storage: MulticollateralBondingCurvePool EnabledTargets (r:1 w:0)
 	/// Proof Skipped: MulticollateralBondingCurvePool EnabledTargets (max_values: Some(1), max_size: None, mode: Measured)
 	fn is_forbidden_filter() -> Weight {
@@ -196,6 +214,23 @@ impl WeightInfo for () {
 		Weight::from_parts(15_615_000, 5295)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 	}
+	/// Storage: TradingPair AssetNeighbours (r:16 w:0)
+	/// Proof Skipped: TradingPair AssetNeighbours (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XSTPool EnabledSynthetics (r:2 w:0)
+	/// Proof Skipped: XSTPool EnabledSynthetics (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 16]`.
+	fn new_multi_hop(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412 + n * (297 ±0)`
+		//  Estimated: `5362 + n * (2772 ±0)`
+		// Minimum execution time: 13_208_000 picoseconds.
+		Weight::from_parts(14_317_490, 5362)
+			// Standard Error: 5_418
+			.saturating_add(Weight::from_parts(6_284_113, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2772).saturating_mul(n.into()))
+	}
 	/// Storage: MulticollateralBondingCurvePool EnabledTargets (r:1 w:0)
 	/// Proof Skipped: MulticollateralBondingCurvePool EnabledTargets (max_values: Some(1), max_size: None, mode: Measured)
 	fn is_forbidden_filter() -> Weight {
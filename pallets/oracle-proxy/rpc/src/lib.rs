@@ -34,7 +34,7 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::CallError,
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::Block as BlockT;
@@ -98,8 +98,26 @@ where
             // If the block hash is not supplied assume the best block.
             self.client.info().best_hash,
         ));
-        api.quote(&at, symbol)
-            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+
+        let version = api
+            .api_version::<dyn OracleProxyRuntimeApi<Block, Symbol, ResolveTime>>(&at)
+            .map_err(|e| RpcError::Custom(format!("Runtime API error: {}", e)))?;
+
+        if version == Some(1) {
+            #[allow(deprecated)]
+            {
+                api.quote_before_version_2(&at, symbol)
+                    .map(|result| result.map(|rate| rate.map(Into::into)))
+                    .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+            }
+        } else if version == Some(2) {
+            api.quote(&at, symbol)
+                .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+        } else {
+            Err(RpcError::Custom(
+                "Unsupported or invalid OracleProxyApi version".to_string(),
+            ))
+        }
     }
 
     fn list_enabled_symbols(
@@ -66,9 +66,49 @@ pub struct RateInfo {
         )
     )]
     pub last_updated: u64,
+    /// Oracles which contributed to the rate
+    pub sources: Vec<common::Oracle>,
+}
+
+impl From<RateInfoV1> for RateInfo {
+    fn from(value: RateInfoV1) -> Self {
+        Self {
+            value: value.value,
+            last_updated: value.last_updated,
+            sources: Default::default(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct RateInfoV1 {
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub value: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "u64: std::fmt::Display",
+                deserialize = "u64: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub last_updated: u64,
 }
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait OracleProxyAPI<Symbol, ResolveTime> where
         Symbol: Codec,
         ResolveTime: Codec
@@ -78,5 +118,10 @@ sp_api::decl_runtime_apis! {
         ) -> Result<Option<RateInfo>, DispatchError>;
 
         fn list_enabled_symbols() -> Result<Vec<(Symbol, ResolveTime)>, DispatchError>;
+
+        #[changed_in(2)]
+        fn quote(
+            symbol: Symbol,
+        ) -> Result<Option<RateInfoV1>, DispatchError>;
     }
 }
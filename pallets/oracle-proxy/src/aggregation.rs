@@ -0,0 +1,146 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use codec::{Decode, Encode};
+use common::{Balance, Oracle, Rate};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// Maximum number of oracles which could be aggregated for a single symbol
+pub const MAX_AGGREGATED_SOURCES: u32 = 8;
+
+/// Oracles aggregated for a single symbol along with their weights
+pub type AggregatedSources = BoundedVec<(Oracle, u32), ConstU32<MAX_AGGREGATED_SOURCES>>;
+
+/// Method of combining rates of several oracles
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Copy, Clone, PartialEq, Eq)]
+pub enum AggregationMethod {
+    /// Median of the fresh rates
+    Median,
+    /// Median of the fresh rates weighted by the weights of their sources
+    WeightedMedian,
+    /// First fresh rate in the order of sources priority
+    FirstFresh,
+}
+
+/// Describes how the rate of a symbol is obtained from several oracles
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct AggregationPolicy {
+    pub method: AggregationMethod,
+    /// Oracles quoted for the symbol in the order of priority along with their weights
+    pub sources: AggregatedSources,
+    /// Rates updated more than `max_staleness` seconds ago are ignored
+    pub max_staleness: u64,
+    /// Minimal number of fresh rates required to quote the symbol
+    pub min_sources: u32,
+}
+
+impl AggregationPolicy {
+    pub fn is_valid(&self) -> bool {
+        let sources_count = self.sources.len();
+        let unique_oracles: BTreeSet<_> = self.sources.iter().map(|(oracle, _)| oracle).collect();
+        let method_is_valid = match self.method {
+            AggregationMethod::Median => true,
+            AggregationMethod::WeightedMedian => self.sources.iter().all(|(_, weight)| *weight > 0),
+            AggregationMethod::FirstFresh => self.min_sources == 1,
+        };
+        sources_count > 0
+            && unique_oracles.len() == sources_count
+            && self.max_staleness > 0
+            && self.min_sources > 0
+            && self.min_sources as usize <= sources_count
+            && method_is_valid
+    }
+}
+
+/// Combines `rates` given in the order of sources priority along with the weights of their sources.
+///
+/// The resulting rate is as old as the oldest contributing rate and has the highest dynamic fee
+/// among them.
+pub fn aggregate(method: AggregationMethod, mut rates: Vec<(Oracle, u32, Rate)>) -> Option<Rate> {
+    if method == AggregationMethod::FirstFresh {
+        rates.truncate(1);
+    }
+    if rates.is_empty() {
+        return None;
+    }
+    let value = match method {
+        AggregationMethod::Median | AggregationMethod::FirstFresh => {
+            median(rates.iter().map(|(_, _, rate)| rate.value).collect())
+        }
+        AggregationMethod::WeightedMedian => weighted_median(
+            rates
+                .iter()
+                .map(|(_, weight, rate)| (rate.value, *weight))
+                .collect(),
+        ),
+    };
+    Some(Rate {
+        value,
+        last_updated: rates
+            .iter()
+            .map(|(_, _, rate)| rate.last_updated)
+            .min()
+            .unwrap_or_default(),
+        dynamic_fee: rates
+            .iter()
+            .map(|(_, _, rate)| rate.dynamic_fee)
+            .max()
+            .unwrap_or_default(),
+        sources: rates.into_iter().map(|(oracle, _, _)| oracle).collect(),
+    })
+}
+
+/// Median of non-empty `values`, mean of the two middle values is taken for even length
+fn median(mut values: Vec<Balance>) -> Balance {
+    values.sort_unstable();
+    let middle = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[middle]
+    } else {
+        let (lower, upper) = (values[middle - 1], values[middle]);
+        lower / 2 + upper / 2 + (lower % 2 + upper % 2) / 2
+    }
+}
+
+/// Lower weighted median of non-empty `values`
+fn weighted_median(mut values: Vec<(Balance, u32)>) -> Balance {
+    values.sort_unstable();
+    let total_weight: u64 = values.iter().map(|(_, weight)| u64::from(*weight)).sum();
+    let mut accumulated_weight = 0u64;
+    for (value, weight) in values.iter() {
+        accumulated_weight += u64::from(*weight);
+        if accumulated_weight * 2 >= total_weight {
+            return *value;
+        }
+    }
+    values.last().map(|(value, _)| *value).unwrap_or_default()
+}
@@ -34,10 +34,30 @@ use super::*;
 use crate::Oracle;
 use crate::Pallet as OracleProxy;
 use codec::alloc::collections::BTreeSet;
+use codec::{Decode, Encode};
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
 use sp_std::prelude::*;
 
+fn symbol<T: Config>(sym: &str) -> T::Symbol {
+    let bytes = sym.encode();
+    T::Symbol::decode(&mut &bytes[..]).expect("Failed to decode symbol")
+}
+
+fn policy() -> AggregationPolicy {
+    AggregationPolicy {
+        method: AggregationMethod::WeightedMedian,
+        sources: (0..aggregation::MAX_AGGREGATED_SOURCES as u8 - 1)
+            .map(|index| (Oracle::Feed(index), 1))
+            .chain([(Oracle::BandChainFeed, 1)])
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("Failed to bound policy sources"),
+        max_staleness: 600,
+        min_sources: 1,
+    }
+}
+
 benchmarks! {
     enable_oracle {
         let oracle = Oracle::BandChainFeed;
@@ -54,5 +74,20 @@ benchmarks! {
         assert_eq!(OracleProxy::<T>::enabled_oracles(), BTreeSet::new());
     }
 
+    set_aggregation_policy {
+        let symbol = symbol::<T>("USD");
+    }: _(RawOrigin::Root, symbol.clone(), policy())
+    verify {
+        assert_eq!(OracleProxy::<T>::aggregation_policy(symbol), Some(policy()));
+    }
+
+    remove_aggregation_policy {
+        let symbol = symbol::<T>("USD");
+        OracleProxy::<T>::set_aggregation_policy(RawOrigin::Root.into(), symbol.clone(), policy())?;
+    }: _(RawOrigin::Root, symbol.clone())
+    verify {
+        assert_eq!(OracleProxy::<T>::aggregation_policy(symbol), None);
+    }
+
     impl_benchmark_test_suite!(OracleProxy, crate::mock::new_test_ext(), crate::mock::Runtime);
 }
@@ -0,0 +1,89 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use common::{DataFeed, Rate};
+use frame_support::pallet_prelude::DispatchError;
+use sp_std::vec::Vec;
+
+/// Set of additional oracle feeds. `Oracle::Feed(index)` is resolved to the feed at `index`
+/// position of the tuple, unknown indices are treated as feeds without any data.
+pub trait DataFeeds<Symbol> {
+    fn quote(index: u8, symbol: &Symbol) -> Result<Option<Rate>, DispatchError>;
+
+    fn list_enabled_symbols(index: u8) -> Result<Vec<(Symbol, u64)>, DispatchError>;
+
+    fn quote_unchecked(index: u8, symbol: &Symbol) -> Option<Rate>;
+}
+
+impl<Symbol> DataFeeds<Symbol> for () {
+    fn quote(_index: u8, _symbol: &Symbol) -> Result<Option<Rate>, DispatchError> {
+        Ok(None)
+    }
+
+    fn list_enabled_symbols(_index: u8) -> Result<Vec<(Symbol, u64)>, DispatchError> {
+        Ok(Vec::new())
+    }
+
+    fn quote_unchecked(_index: u8, _symbol: &Symbol) -> Option<Rate> {
+        None
+    }
+}
+
+macro_rules! impl_data_feeds_for_tuple {
+    ($($feed:ident => $index:literal),+) => {
+        impl<Symbol, $($feed: DataFeed<Symbol, Rate, u64>),+> DataFeeds<Symbol> for ($($feed,)+) {
+            fn quote(index: u8, symbol: &Symbol) -> Result<Option<Rate>, DispatchError> {
+                match index {
+                    $($index => $feed::quote(symbol),)+
+                    _ => Ok(None),
+                }
+            }
+
+            fn list_enabled_symbols(index: u8) -> Result<Vec<(Symbol, u64)>, DispatchError> {
+                match index {
+                    $($index => $feed::list_enabled_symbols(),)+
+                    _ => Ok(Vec::new()),
+                }
+            }
+
+            fn quote_unchecked(index: u8, symbol: &Symbol) -> Option<Rate> {
+                match index {
+                    $($index => $feed::quote_unchecked(symbol),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_data_feeds_for_tuple!(A => 0);
+impl_data_feeds_for_tuple!(A => 0, B => 1);
+impl_data_feeds_for_tuple!(A => 0, B => 1, C => 2);
+impl_data_feeds_for_tuple!(A => 0, B => 1, C => 2, D => 3);
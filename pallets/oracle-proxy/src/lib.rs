@@ -35,9 +35,17 @@
 use common::{DataFeed, OnNewSymbolsRelayed, Oracle, Rate};
 use frame_support;
 use frame_support::pallet_prelude::*;
+use frame_support::traits::Time;
 use frame_system::pallet_prelude::*;
-use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
-
+use sp_runtime::SaturatedConversion;
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    vec,
+    vec::Vec,
+};
+
+pub use aggregation::{AggregationMethod, AggregationPolicy};
+pub use feeds::DataFeeds;
 pub use pallet::*;
 
 #[cfg(test)]
@@ -46,52 +54,147 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod aggregation;
 mod benchmarking;
+pub mod feeds;
 
 pub mod weights;
 pub use weights::WeightInfo;
 
+const MILLISECS_PER_SEC: u64 = 1000;
+
 impl<T: Config> DataFeed<T::Symbol, Rate, u64> for Pallet<T> {
     fn quote(symbol: &T::Symbol) -> Result<Option<Rate>, DispatchError> {
+        if let Some(policy) = Self::aggregation_policy(symbol) {
+            return Self::quote_aggregated(symbol, &policy);
+        }
+
         let enabled_oracles = Self::enabled_oracles();
 
         Self::enabled_symbols(symbol)
             .into_iter()
             .filter(|oracle| enabled_oracles.contains(&oracle))
-            .map(|oracle| match oracle {
-                Oracle::BandChainFeed => T::BandChainOracle::quote(symbol),
-            })
+            .map(|oracle| Self::quote_oracle(oracle, symbol))
             .next()
             .unwrap_or(Ok(None))
     }
 
     fn list_enabled_symbols() -> Result<Vec<(T::Symbol, u64)>, DispatchError> {
-        let symbols_rates = Self::enabled_oracles()
+        let mut symbols_rates = BTreeMap::new();
+        Self::enabled_oracles()
             .into_iter()
-            .flat_map(|oracle| match oracle {
-                Oracle::BandChainFeed => match T::BandChainOracle::list_enabled_symbols() {
-                    Ok(list) => list,
-                    _ => Vec::new(),
-                },
-            })
-            .collect();
-        Ok(symbols_rates)
+            .flat_map(|oracle| Self::list_oracle_symbols(oracle).unwrap_or_default())
+            .for_each(|(symbol, last_updated)| {
+                let symbol_last_updated = symbols_rates.entry(symbol).or_insert(last_updated);
+                *symbol_last_updated = last_updated.max(*symbol_last_updated);
+            });
+        Ok(symbols_rates.into_iter().collect())
     }
 
     fn quote_unchecked(symbol: &T::Symbol) -> Option<Rate> {
+        if let Some(policy) = Self::aggregation_policy(symbol) {
+            return Self::quote_aggregated_unchecked(symbol, &policy);
+        }
+
         let enabled_oracles = Self::enabled_oracles();
 
         Self::enabled_symbols(symbol)
             .into_iter()
             .filter(|oracle| enabled_oracles.contains(&oracle))
-            .map(|oracle| match oracle {
-                Oracle::BandChainFeed => T::BandChainOracle::quote_unchecked(symbol),
-            })
+            .map(|oracle| Self::quote_oracle_unchecked(oracle, symbol))
             .next()
             .unwrap_or(None)
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Get rate of `symbol` from `oracle`, the rate is marked as contributed by `oracle`
+    fn quote_oracle(oracle: Oracle, symbol: &T::Symbol) -> Result<Option<Rate>, DispatchError> {
+        let rate = match oracle {
+            Oracle::BandChainFeed => T::BandChainOracle::quote(symbol),
+            Oracle::Feed(index) => T::AdditionalFeeds::quote(index, symbol),
+        }?;
+        Ok(rate.map(|rate| Rate {
+            sources: vec![oracle],
+            ..rate
+        }))
+    }
+
+    fn quote_oracle_unchecked(oracle: Oracle, symbol: &T::Symbol) -> Option<Rate> {
+        let rate = match oracle {
+            Oracle::BandChainFeed => T::BandChainOracle::quote_unchecked(symbol),
+            Oracle::Feed(index) => T::AdditionalFeeds::quote_unchecked(index, symbol),
+        };
+        rate.map(|rate| Rate {
+            sources: vec![oracle],
+            ..rate
+        })
+    }
+
+    fn list_oracle_symbols(oracle: Oracle) -> Result<Vec<(T::Symbol, u64)>, DispatchError> {
+        match oracle {
+            Oracle::BandChainFeed => T::BandChainOracle::list_enabled_symbols(),
+            Oracle::Feed(index) => T::AdditionalFeeds::list_enabled_symbols(index),
+        }
+    }
+
+    /// Aggregate fresh rates of the enabled policy sources.
+    ///
+    /// Returns `None` if none of the sources has a rate for the symbol and
+    /// `NotEnoughFreshSources` if there are less than `min_sources` fresh rates.
+    fn quote_aggregated(
+        symbol: &T::Symbol,
+        policy: &AggregationPolicy,
+    ) -> Result<Option<Rate>, DispatchError> {
+        let enabled_oracles = Self::enabled_oracles();
+        let now = T::Time::now().saturated_into::<u64>() / MILLISECS_PER_SEC;
+        let mut fresh_rates = Vec::new();
+        let mut has_outdated_rates = false;
+
+        for (oracle, weight) in policy
+            .sources
+            .iter()
+            .filter(|(oracle, _)| enabled_oracles.contains(oracle))
+        {
+            match Self::quote_oracle(*oracle, symbol) {
+                Ok(None) => {}
+                Ok(Some(rate)) if now.saturating_sub(rate.last_updated) <= policy.max_staleness => {
+                    fresh_rates.push((*oracle, *weight, rate));
+                    if policy.method == AggregationMethod::FirstFresh {
+                        break;
+                    }
+                }
+                // failure of a single oracle must not prevent others from contributing
+                _ => has_outdated_rates = true,
+            }
+        }
+
+        if fresh_rates.len() < policy.min_sources as usize {
+            ensure!(
+                fresh_rates.is_empty() && !has_outdated_rates,
+                Error::<T>::NotEnoughFreshSources
+            );
+            return Ok(None);
+        }
+
+        Ok(aggregation::aggregate(policy.method, fresh_rates))
+    }
+
+    /// Aggregate all available rates of the enabled policy sources without staleness checks
+    fn quote_aggregated_unchecked(symbol: &T::Symbol, policy: &AggregationPolicy) -> Option<Rate> {
+        let enabled_oracles = Self::enabled_oracles();
+        let rates = policy
+            .sources
+            .iter()
+            .filter(|(oracle, _)| enabled_oracles.contains(oracle))
+            .filter_map(|(oracle, weight)| {
+                Self::quote_oracle_unchecked(*oracle, symbol).map(|rate| (*oracle, *weight, rate))
+            })
+            .collect();
+        aggregation::aggregate(policy.method, rates)
+    }
+}
+
 impl<T: Config> OnNewSymbolsRelayed<T::Symbol> for Pallet<T> {
     fn on_new_symbols_relayed(
         oracle_variant: Oracle,
@@ -117,6 +220,10 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
         type Symbol: Parameter + Ord;
         type BandChainOracle: DataFeed<Self::Symbol, Rate, u64>;
+        /// Oracles available as `Oracle::Feed` in addition to `BandChainOracle`
+        type AdditionalFeeds: DataFeeds<Self::Symbol>;
+        /// Time used for checking staleness of the aggregated rates
+        type Time: Time;
     }
 
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
@@ -135,6 +242,12 @@ pub mod pallet {
     #[pallet::getter(fn enabled_symbols)]
     pub type SymbolProviders<T: Config> = StorageMap<_, Blake2_128Concat, T::Symbol, Oracle>;
 
+    /// Symbols quoted from several oracles, other symbols are quoted from their provider
+    #[pallet::storage]
+    #[pallet::getter(fn aggregation_policy)]
+    pub type AggregationPolicies<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Symbol, AggregationPolicy>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -142,12 +255,22 @@ pub mod pallet {
         OracleEnabled(Oracle),
         /// Oracle was successfully disabled. [oracle]
         OracleDisabled(Oracle),
+        /// Aggregation policy of the symbol was set. [symbol, policy]
+        AggregationPolicySet(T::Symbol, AggregationPolicy),
+        /// Aggregation policy of the symbol was removed. [symbol]
+        AggregationPolicyRemoved(T::Symbol),
     }
 
     #[pallet::error]
     pub enum Error<T> {
         OracleAlreadyEnabled,
         OracleAlreadyDisabled,
+        /// Aggregation policy has no sources, duplicated sources or inconsistent limits
+        InvalidAggregationPolicy,
+        /// Symbol has no aggregation policy
+        AggregationPolicyDoesNotExist,
+        /// Less than the required number of oracles have fresh rates for the symbol
+        NotEnoughFreshSources,
     }
 
     #[pallet::call]
@@ -195,6 +318,55 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Sets the policy of aggregating rates of several oracles for a symbol
+        ///
+        /// Checks if the caller is root
+        ///
+        /// - `origin`: the sudo account
+        /// - `symbol`: symbol to be quoted using the policy
+        /// - `policy`: oracles to be quoted and the way of combining their rates
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_aggregation_policy())]
+        pub fn set_aggregation_policy(
+            origin: OriginFor<T>,
+            symbol: T::Symbol,
+            policy: AggregationPolicy,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(policy.is_valid(), Error::<T>::InvalidAggregationPolicy);
+
+            AggregationPolicies::<T>::insert(&symbol, &policy);
+
+            Self::deposit_event(Event::AggregationPolicySet(symbol, policy));
+
+            Ok(().into())
+        }
+
+        /// Removes the aggregation policy of a symbol, so it is quoted from its provider again
+        ///
+        /// Checks if the caller is root
+        ///
+        /// - `origin`: the sudo account
+        /// - `symbol`: symbol which policy should be removed
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_aggregation_policy())]
+        pub fn remove_aggregation_policy(
+            origin: OriginFor<T>,
+            symbol: T::Symbol,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                AggregationPolicies::<T>::contains_key(&symbol),
+                Error::<T>::AggregationPolicyDoesNotExist
+            );
+
+            AggregationPolicies::<T>::remove(&symbol);
+
+            Self::deposit_event(Event::AggregationPolicyRemoved(symbol));
+
+            Ok(().into())
+        }
     }
 
     #[pallet::genesis_config]
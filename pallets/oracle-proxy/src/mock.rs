@@ -30,6 +30,7 @@
 
 use crate::{self as oracle_proxy, Config};
 use band;
+use frame_support::instances::Instance1;
 use frame_support::traits::{ConstU16, ConstU64};
 use frame_system as system;
 use sp_core::H256;
@@ -50,7 +51,9 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system,
+        Balances: pallet_balances,
         Band: band,
+        BandSecondary: band::<Instance1>,
         OracleProxy: oracle_proxy,
         Timestamp: pallet_timestamp,
     }
@@ -74,7 +77,7 @@ impl system::Config for Runtime {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<common::Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -88,12 +91,29 @@ impl Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type BandChainOracle = band::Pallet<Runtime>;
+    type AdditionalFeeds = (band::Pallet<Runtime, Instance1>,);
+    type Time = Timestamp;
 }
 
 frame_support::parameter_types! {
     pub const GetBandRateStalePeriod: Moment = 5*60*1000; // 5 minutes
     pub const GetBandRateStaleBlockPeriod: u64 = 600; // 1 hour in blocks
+    pub const GetBandOracleVariant: common::Oracle = common::Oracle::BandChainFeed;
+    pub const GetSecondaryBandOracleVariant: common::Oracle = common::Oracle::Feed(0);
     pub const MinimumPeriod: u64 = 5;
+    pub const ExistentialDeposit: common::Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+    type Balance = common::Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -111,8 +131,26 @@ impl band::Config for Runtime {
     type Time = Timestamp;
     type GetBandRateStalePeriod = GetBandRateStalePeriod;
     type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetBandOracleVariant;
+    type Currency = Balances;
+    type OnSymbolDisabledHook = ();
+    type MaxRelaySymbols = frame_support::traits::ConstU32<100>;
+    type MaxRelayers = frame_support::traits::ConstU32<100>;
+}
+
+impl band::Config<Instance1> for Runtime {
+    type Symbol = <Runtime as Config>::Symbol;
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type OnNewSymbolsRelayedHook = oracle_proxy::Pallet<Runtime>;
+    type Time = Timestamp;
+    type GetBandRateStalePeriod = GetBandRateStalePeriod;
+    type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetSecondaryBandOracleVariant;
+    type Currency = Balances;
     type OnSymbolDisabledHook = ();
     type MaxRelaySymbols = frame_support::traits::ConstU32<100>;
+    type MaxRelayers = frame_support::traits::ConstU32<100>;
 }
 
 // Build genesis storage according to the mock runtime.
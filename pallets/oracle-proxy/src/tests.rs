@@ -29,11 +29,12 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use codec::alloc::collections::HashSet;
+use codec::{Decode, Encode};
 use common::{fixed, DataFeed};
-use frame_support::{assert_err, error::BadOrigin};
+use frame_support::{assert_err, assert_noop, assert_ok, error::BadOrigin};
 use sp_core::TryCollect;
 
-use crate::{mock::*, Oracle, Rate};
+use crate::{mock::*, AggregationMethod, AggregationPolicy, Error, Oracle, Rate};
 
 fn relay_symbols() {
     let symbols = vec!["USD".to_owned(), "RUB".to_owned(), "YEN".to_owned()];
@@ -114,6 +115,7 @@ fn quote_and_list_enabled_symbols_should_work() {
                         .expect("failed to convert rate into Balance"),
                     last_updated: resolve_time,
                     dynamic_fee: fixed!(0),
+                    sources: vec![Oracle::BandChainFeed],
                 };
                 assert_eq!(
                     <OracleProxy as DataFeed<String, Rate, u64>>::quote(symbol),
@@ -150,3 +152,309 @@ fn quote_and_list_enabled_symbols_should_not_work_with_disabled_oracle() {
         )
     });
 }
+
+fn relay_usd_rates(band_rate: u64, band_time: u64, secondary_rate: u64, secondary_time: u64) {
+    let relayer = 1;
+    Band::add_relayers(RuntimeOrigin::root(), vec![relayer]).expect("Failed to add relayers");
+    Band::relay(
+        RuntimeOrigin::signed(relayer),
+        vec![("USD".to_owned(), band_rate)].try_into().unwrap(),
+        band_time,
+        0,
+    )
+    .expect("Failed to relay rates");
+    BandSecondary::add_relayers(RuntimeOrigin::root(), vec![relayer])
+        .expect("Failed to add relayers");
+    BandSecondary::relay(
+        RuntimeOrigin::signed(relayer),
+        vec![("USD".to_owned(), secondary_rate)].try_into().unwrap(),
+        secondary_time,
+        0,
+    )
+    .expect("Failed to relay rates");
+
+    for oracle in [Oracle::BandChainFeed, Oracle::Feed(0)] {
+        OracleProxy::enable_oracle(RuntimeOrigin::root(), oracle).expect("Failed to enable oracle");
+    }
+}
+
+fn policy(method: AggregationMethod, band_weight: u32, secondary_weight: u32) -> AggregationPolicy {
+    AggregationPolicy {
+        method,
+        sources: vec![
+            (Oracle::BandChainFeed, band_weight),
+            (Oracle::Feed(0), secondary_weight),
+        ]
+        .try_into()
+        .unwrap(),
+        max_staleness: 300,
+        min_sources: 1,
+    }
+}
+
+fn quote_usd() -> Result<Option<Rate>, sp_runtime::DispatchError> {
+    <OracleProxy as DataFeed<String, Rate, u64>>::quote(&"USD".to_owned())
+}
+
+#[test]
+fn set_and_remove_aggregation_policy_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let symbol = "USD".to_owned();
+        let policy = policy(AggregationMethod::Median, 1, 1);
+
+        assert_noop!(
+            OracleProxy::set_aggregation_policy(
+                RuntimeOrigin::signed(1),
+                symbol.clone(),
+                policy.clone()
+            ),
+            BadOrigin
+        );
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone(),
+            policy.clone()
+        ));
+        assert_eq!(
+            OracleProxy::aggregation_policy(&symbol),
+            Some(policy.clone())
+        );
+        System::assert_last_event(
+            crate::Event::<Runtime>::AggregationPolicySet(symbol.clone(), policy).into(),
+        );
+
+        assert_noop!(
+            OracleProxy::remove_aggregation_policy(RuntimeOrigin::signed(1), symbol.clone()),
+            BadOrigin
+        );
+        assert_ok!(OracleProxy::remove_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone()
+        ));
+        assert_eq!(OracleProxy::aggregation_policy(&symbol), None);
+        System::assert_last_event(
+            crate::Event::<Runtime>::AggregationPolicyRemoved(symbol.clone()).into(),
+        );
+        assert_noop!(
+            OracleProxy::remove_aggregation_policy(RuntimeOrigin::root(), symbol),
+            Error::<Runtime>::AggregationPolicyDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn set_aggregation_policy_should_reject_invalid_policy() {
+    new_test_ext().execute_with(|| {
+        let valid = policy(AggregationMethod::Median, 1, 1);
+        let invalid_policies = [
+            AggregationPolicy {
+                sources: Default::default(),
+                ..valid.clone()
+            },
+            AggregationPolicy {
+                sources: vec![(Oracle::BandChainFeed, 1), (Oracle::BandChainFeed, 2)]
+                    .try_into()
+                    .unwrap(),
+                ..valid.clone()
+            },
+            AggregationPolicy {
+                min_sources: 0,
+                ..valid.clone()
+            },
+            AggregationPolicy {
+                min_sources: 3,
+                ..valid.clone()
+            },
+            AggregationPolicy {
+                max_staleness: 0,
+                ..valid.clone()
+            },
+            AggregationPolicy {
+                min_sources: 2,
+                ..policy(AggregationMethod::FirstFresh, 1, 1)
+            },
+            policy(AggregationMethod::WeightedMedian, 1, 0),
+        ];
+        for invalid in invalid_policies {
+            assert_noop!(
+                OracleProxy::set_aggregation_policy(
+                    RuntimeOrigin::root(),
+                    "USD".to_owned(),
+                    invalid
+                ),
+                Error::<Runtime>::InvalidAggregationPolicy
+            );
+        }
+
+        // policies with too many sources can't even be decoded
+        let too_many_sources = (
+            AggregationMethod::Median,
+            (0..9)
+                .map(|index| (Oracle::Feed(index), 1u32))
+                .collect::<Vec<_>>(),
+            300u64,
+            1u32,
+        );
+        assert!(AggregationPolicy::decode(&mut &too_many_sources.encode()[..]).is_err());
+    });
+}
+
+#[test]
+fn median_aggregation_should_work() {
+    new_test_ext().execute_with(|| {
+        relay_usd_rates(1, 0, 4, 0);
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            "USD".to_owned(),
+            AggregationPolicy {
+                min_sources: 2,
+                ..policy(AggregationMethod::Median, 1, 1)
+            }
+        ));
+
+        assert_eq!(
+            quote_usd(),
+            Ok(Some(Rate {
+                value: Band::raw_rate_into_balance(1).unwrap() / 2
+                    + Band::raw_rate_into_balance(4).unwrap() / 2,
+                last_updated: 0,
+                dynamic_fee: fixed!(0),
+                sources: vec![Oracle::BandChainFeed, Oracle::Feed(0)],
+            }))
+        );
+
+        OracleProxy::disable_oracle(RuntimeOrigin::root(), Oracle::Feed(0)).unwrap();
+        assert_eq!(
+            quote_usd(),
+            Err(Error::<Runtime>::NotEnoughFreshSources.into())
+        );
+        assert_eq!(
+            <OracleProxy as DataFeed<String, Rate, u64>>::quote_unchecked(&"USD".to_owned())
+                .map(|rate| (rate.value, rate.sources)),
+            Some((
+                Band::raw_rate_into_balance(1).unwrap(),
+                vec![Oracle::BandChainFeed]
+            ))
+        );
+    });
+}
+
+#[test]
+fn weighted_median_aggregation_should_work() {
+    new_test_ext().execute_with(|| {
+        relay_usd_rates(1, 0, 4, 0);
+        let symbol = "USD".to_owned();
+
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone(),
+            policy(AggregationMethod::WeightedMedian, 1, 3)
+        ));
+        assert_eq!(
+            quote_usd().unwrap().map(|rate| rate.value),
+            Some(Band::raw_rate_into_balance(4).unwrap())
+        );
+
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol,
+            policy(AggregationMethod::WeightedMedian, 3, 1)
+        ));
+        let rate = quote_usd().unwrap().unwrap();
+        assert_eq!(rate.value, Band::raw_rate_into_balance(1).unwrap());
+        assert_eq!(rate.sources, vec![Oracle::BandChainFeed, Oracle::Feed(0)]);
+    });
+}
+
+#[test]
+fn first_fresh_aggregation_should_skip_stale_rates() {
+    new_test_ext().execute_with(|| {
+        relay_usd_rates(1, 0, 4, 100);
+        Timestamp::set_timestamp(200_000);
+        let symbol = "USD".to_owned();
+
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone(),
+            policy(AggregationMethod::FirstFresh, 1, 1)
+        ));
+        assert_eq!(
+            quote_usd(),
+            Ok(Some(Rate {
+                value: Band::raw_rate_into_balance(1).unwrap(),
+                last_updated: 0,
+                dynamic_fee: fixed!(0),
+                sources: vec![Oracle::BandChainFeed],
+            }))
+        );
+
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone(),
+            AggregationPolicy {
+                max_staleness: 150,
+                ..policy(AggregationMethod::FirstFresh, 1, 1)
+            }
+        ));
+        assert_eq!(
+            quote_usd(),
+            Ok(Some(Rate {
+                value: Band::raw_rate_into_balance(4).unwrap(),
+                last_updated: 100,
+                dynamic_fee: fixed!(0),
+                sources: vec![Oracle::Feed(0)],
+            }))
+        );
+
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            symbol.clone(),
+            AggregationPolicy {
+                max_staleness: 50,
+                ..policy(AggregationMethod::FirstFresh, 1, 1)
+            }
+        ));
+        assert_eq!(
+            quote_usd(),
+            Err(Error::<Runtime>::NotEnoughFreshSources.into())
+        );
+        assert_eq!(
+            <OracleProxy as DataFeed<String, Rate, u64>>::quote_unchecked(&symbol)
+                .map(|rate| rate.sources),
+            Some(vec![Oracle::BandChainFeed])
+        );
+    });
+}
+
+#[test]
+fn aggregated_symbol_without_rates_should_not_be_quoted() {
+    new_test_ext().execute_with(|| {
+        relay_usd_rates(1, 0, 4, 0);
+        assert_ok!(OracleProxy::set_aggregation_policy(
+            RuntimeOrigin::root(),
+            "EUR".to_owned(),
+            policy(AggregationMethod::Median, 1, 1)
+        ));
+        assert_eq!(
+            <OracleProxy as DataFeed<String, Rate, u64>>::quote(&"EUR".to_owned()),
+            Ok(None)
+        );
+    });
+}
+
+#[test]
+fn list_enabled_symbols_should_merge_oracles() {
+    new_test_ext().execute_with(|| {
+        relay_usd_rates(1, 10, 4, 20);
+        assert_eq!(
+            <OracleProxy as DataFeed<String, Rate, u64>>::list_enabled_symbols(),
+            Ok(vec![("USD".to_owned(), 20)])
+        );
+        OracleProxy::disable_oracle(RuntimeOrigin::root(), Oracle::Feed(0)).unwrap();
+        assert_eq!(
+            <OracleProxy as DataFeed<String, Rate, u64>>::list_enabled_symbols(),
+            Ok(vec![("USD".to_owned(), 10)])
+        );
+    });
+}
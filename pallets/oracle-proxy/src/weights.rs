@@ -62,6 +62,8 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn enable_oracle() -> Weight;
 	fn disable_oracle() -> Weight;
+	fn set_aggregation_policy() -> Weight;
+	fn remove_aggregation_policy() -> Weight;
 }
 
 /// Weights for oracle_proxy using the Substrate node and recommended hardware.
@@ -89,6 +91,27 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: OracleProxy AggregationPolicies (r:0 w:1)
+	/// Proof Skipped: OracleProxy AggregationPolicies (max_values: None, max_size: None, mode: Measured)
+	fn set_aggregation_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 13_841_000 picoseconds.
+		Weight::from_parts(14_306_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OracleProxy AggregationPolicies (r:1 w:1)
+	/// Proof Skipped: OracleProxy AggregationPolicies (max_values: None, max_size: None, mode: Measured)
+	fn remove_aggregation_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3607`
+		// Minimum execution time: 17_209_000 picoseconds.
+		Weight::from_parts(17_683_000, 3607)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -115,4 +138,25 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: OracleProxy AggregationPolicies (r:0 w:1)
+	/// Proof Skipped: OracleProxy AggregationPolicies (max_values: None, max_size: None, mode: Measured)
+	fn set_aggregation_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 13_841_000 picoseconds.
+		Weight::from_parts(14_306_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OracleProxy AggregationPolicies (r:1 w:1)
+	/// Proof Skipped: OracleProxy AggregationPolicies (max_values: None, max_size: None, mode: Measured)
+	fn remove_aggregation_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `3607`
+		// Minimum execution time: 17_209_000 picoseconds.
+		Weight::from_parts(17_683_000, 3607)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -137,11 +137,16 @@ pub use benchmarks_inner::*;
 #[cfg(not(test))]
 mod benchmarks_inner {
     use common::prelude::SwapAmount;
-    use common::{balance, AssetInfoProvider, AssetName, AssetSymbol, LiquiditySource, VAL, XOR};
+    use common::{
+        balance, AssetInfoProvider, AssetName, AssetSymbol, Balance, LiquiditySource, PriceVariant,
+        VAL, XOR,
+    };
     use frame_benchmarking::benchmarks;
+    use frame_support::traits::Get;
     use frame_support::weights::WeightMeter;
+    use frame_support::BoundedVec;
     use frame_system::RawOrigin;
-    use sp_runtime::traits::UniqueSaturatedInto;
+    use sp_runtime::traits::{One, UniqueSaturatedFrom, UniqueSaturatedInto, Zero};
     use sp_std::vec::Vec;
 
     use super::*;
@@ -149,8 +154,11 @@ mod benchmarks_inner {
     use order_book_imported::test_utils::fill_tools::FillSettings;
     use order_book_imported::test_utils::{accounts, create_and_fill_order_book};
     use order_book_imported::{
-        CancelReason, Event, ExpirationScheduler, MarketRole, OrderBook, OrderBookId,
-        OrderBookStatus, OrderPrice, OrderVolume,
+        CancelReason, Candle, CandleInterval, Candles, ConditionalOrderKind, Event,
+        ExpirationScheduler, FeeTier, FeesToBurn, MarketRole, OrderBook, OrderBookId,
+        OrderBookStatus, OrderPrice, OrderVolume, TradingFeeDestination, TradingVolumes,
+        TriggerPriceSource, TriggerScheduler, WeightInfo, BASIS_POINTS, MILLISECS_PER_DAY,
+        TRADING_VOLUME_DAYS,
     };
     use periphery::presets::*;
 
@@ -159,7 +167,7 @@ mod benchmarks_inner {
 
     benchmarks! {
         where_clause {
-            where T: trading_pair::Config + core::fmt::Debug
+            where T: trading_pair::Config + pallet_timestamp::Config + core::fmt::Debug
         }
 
         create_orderbook {
@@ -474,6 +482,287 @@ mod benchmarks_inner {
             assert_eq!(balance, expected_balance);
         }
 
+        place_conditional_order {
+            let caller = accounts::alice::<T>();
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            create_and_fill_order_book::<T>(order_book_id);
+            order_book_imported::test_utils::fill_balance::<T>(caller.clone(), order_book_id);
+
+            let order_id = OrderBookPallet::<T>::order_books(order_book_id).unwrap().last_order_id + T::OrderId::one();
+            let balance_before =
+                <T as order_book_imported::Config>::AssetInfoProvider::free_balance(&order_book_id.quote, &caller).unwrap();
+        }: {
+            OrderBookPallet::<T>::place_conditional_order(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                PriceVariant::Buy,
+                ConditionalOrderKind::TakeProfit,
+                TriggerPriceSource::BestBidAsk,
+                balance!(10.5),
+                Some(balance!(10.4)),
+                balance!(100)
+            ).unwrap();
+        }
+        verify {
+            assert_last_event::<T>(
+                Event::<T>::ConditionalOrderPlaced {
+                    order_book_id,
+                    order_id,
+                    owner_id: caller.clone(),
+                    side: PriceVariant::Buy,
+                    kind: ConditionalOrderKind::TakeProfit,
+                    price_source: TriggerPriceSource::BestBidAsk,
+                    trigger_price: balance!(10.5).into(),
+                    limit_price: Some(balance!(10.4).into()),
+                    amount: balance!(100).into(),
+                }
+                .into(),
+            );
+
+            let balance =
+                <T as order_book_imported::Config>::AssetInfoProvider::free_balance(&order_book_id.quote, &caller).unwrap();
+            assert_eq!(balance, balance_before - balance!(1040));
+        }
+
+        cancel_conditional_order {
+            let caller = accounts::alice::<T>();
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            create_and_fill_order_book::<T>(order_book_id);
+            order_book_imported::test_utils::fill_balance::<T>(caller.clone(), order_book_id);
+
+            let order_id = OrderBookPallet::<T>::order_books(order_book_id).unwrap().last_order_id + T::OrderId::one();
+            OrderBookPallet::<T>::place_conditional_order(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                PriceVariant::Buy,
+                ConditionalOrderKind::TakeProfit,
+                TriggerPriceSource::BestBidAsk,
+                balance!(10.5),
+                Some(balance!(10.4)),
+                balance!(100)
+            ).unwrap();
+        }: {
+            OrderBookPallet::<T>::cancel_conditional_order(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                order_id
+            ).unwrap();
+        }
+        verify {
+            assert_last_event::<T>(
+                Event::<T>::ConditionalOrderCanceled {
+                    order_book_id,
+                    order_id,
+                    owner_id: caller,
+                }
+                .into(),
+            );
+            assert!(OrderBookPallet::<T>::conditional_orders(order_book_id, order_id).is_none());
+        }
+
+        service_triggers_base {
+            let mut weight = WeightMeter::max_limit();
+        }: {
+            OrderBookPallet::<T>::service_triggers(&mut weight);
+        }
+        verify {}
+
+        service_single_trigger {
+            let caller = accounts::alice::<T>();
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            create_and_fill_order_book::<T>(order_book_id);
+            order_book_imported::test_utils::fill_balance::<T>(caller.clone(), order_book_id);
+
+            let order_id = OrderBookPallet::<T>::order_books(order_book_id).unwrap().last_order_id + T::OrderId::one();
+            // the order is checked, but not triggered
+            OrderBookPallet::<T>::place_conditional_order(
+                RawOrigin::Signed(caller).into(),
+                order_book_id,
+                PriceVariant::Sell,
+                ConditionalOrderKind::StopLoss,
+                TriggerPriceSource::BestBidAsk,
+                balance!(5),
+                None,
+                balance!(100)
+            ).unwrap();
+            let order = OrderBookPallet::<T>::conditional_orders(order_book_id, order_id).unwrap();
+
+            let mut weight = WeightMeter::max_limit();
+        }: {
+            OrderBookPallet::<T>::service_single_trigger(&order_book_id, order, &mut weight);
+        }
+        verify {
+            assert!(OrderBookPallet::<T>::conditional_orders(order_book_id, order_id).is_some());
+        }
+
+        set_trading_fees {
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            create_and_fill_order_book::<T>(order_book_id);
+        }: {
+            OrderBookPallet::<T>::set_trading_fees(
+                RawOrigin::Root.into(),
+                order_book_id,
+                10,
+                20
+            ).unwrap();
+        }
+        verify {
+            assert_last_event::<T>(
+                Event::<T>::TradingFeesChanged {
+                    order_book_id,
+                    maker_fee_bps: 10,
+                    taker_fee_bps: 20,
+                }
+                .into(),
+            );
+        }
+
+        set_fee_tiers {
+            let tiers: Vec<_> = (0..<T as order_book_imported::Config>::MaxFeeTiers::get())
+                .map(|i| FeeTier {
+                    min_volume: balance!(1000) * (i as Balance + 1),
+                    discount_bps: (i as u16 + 1).min(BASIS_POINTS),
+                })
+                .collect();
+        }: {
+            OrderBookPallet::<T>::set_fee_tiers(RawOrigin::Root.into(), tiers.clone()).unwrap();
+        }
+        verify {
+            assert_last_event::<T>(Event::<T>::FeeTiersChanged { tiers }.into());
+        }
+
+        set_trading_fee_destination {
+            let destination = TradingFeeDestination::BuyBackAndBurn(VAL.into());
+        }: {
+            OrderBookPallet::<T>::set_trading_fee_destination(
+                RawOrigin::Root.into(),
+                destination.clone()
+            ).unwrap();
+        }
+        verify {
+            assert_last_event::<T>(
+                Event::<T>::TradingFeeDestinationChanged { destination }.into(),
+            );
+        }
+
+        service_fee_burning {
+            let collector = <T as order_book_imported::Config>::TradingFeeCollector::get();
+            let amount = balance!(100);
+            assets::Pallet::<T>::mint_unchecked(&XOR.into(), &collector, amount).unwrap();
+            FeesToBurn::<T>::insert((XOR.into(), XOR.into()), amount);
+
+            let mut weight = WeightMeter::max_limit();
+        }: {
+            OrderBookPallet::<T>::service_fees_burning(&mut weight);
+        }
+        verify {
+            assert_eq!(FeesToBurn::<T>::get((XOR.into(), XOR.into())), 0);
+        }
+
+        prune_single_candle {
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            // the candle is outdated
+            Candles::<T>::insert(
+                (order_book_id, CandleInterval::Minute),
+                0,
+                Candle::new(MomentOf::<T>::zero(), balance!(10).into(), balance!(100).into()),
+            );
+            pallet_timestamp::Now::<T>::put(
+                <T as pallet_timestamp::Config>::Moment::unique_saturated_from(
+                    CandleInterval::Minute.millis() * CandleInterval::Minute.retention() as u64
+                )
+            );
+
+            let mut weight = WeightMeter::from_limit(
+                <T as order_book_imported::Config>::WeightInfo::prune_single_candle()
+            );
+        }: {
+            OrderBookPallet::<T>::service_candles_pruning(&mut weight);
+        }
+        verify {
+            assert!(Candles::<T>::get((order_book_id, CandleInterval::Minute), 0).is_none());
+        }
+
+        prune_single_trading_volume {
+            let caller = accounts::alice::<T>();
+
+            // the volumes are outdated
+            TradingVolumes::<T>::insert(
+                &caller,
+                T::DEXId::from(DEX),
+                BoundedVec::truncate_from(sp_std::vec![(0, balance!(100))]),
+            );
+            pallet_timestamp::Now::<T>::put(
+                <T as pallet_timestamp::Config>::Moment::unique_saturated_from(
+                    (MILLISECS_PER_DAY * TRADING_VOLUME_DAYS as u128) as u64
+                )
+            );
+
+            let mut weight = WeightMeter::from_limit(
+                <T as order_book_imported::Config>::WeightInfo::prune_single_trading_volume()
+            );
+        }: {
+            OrderBookPallet::<T>::service_trading_volumes_pruning(&mut weight);
+        }
+        verify {
+            assert!(TradingVolumes::<T>::get(&caller, T::DEXId::from(DEX)).is_empty());
+        }
+
+        record_trade {
+            let order_book_id = OrderBookId::<AssetIdOf<T>, T::DEXId> {
+                dex_id: DEX.into(),
+                base: VAL.into(),
+                quote: XOR.into(),
+            };
+
+            // the trade history is full, so the oldest trade is overwritten
+            for _ in 0..<T as order_book_imported::Config>::MaxTradesHistory::get() {
+                OrderBookPallet::<T>::record_trade(
+                    &order_book_id,
+                    PriceVariant::Buy,
+                    balance!(10).into(),
+                    balance!(100).into(),
+                );
+            }
+        }: {
+            OrderBookPallet::<T>::record_trade(
+                &order_book_id,
+                PriceVariant::Sell,
+                balance!(11).into(),
+                balance!(100).into(),
+            );
+        }
+        verify {
+            assert_eq!(
+                OrderBookPallet::<T>::recent_trades(&order_book_id, 1)[0].price,
+                balance!(11).into()
+            );
+        }
 
         // now it works only as benchmarks, not as unit tests
         // TODO fix when new approach be developed
@@ -0,0 +1,272 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use codec::Codec;
+use common::prelude::BalanceUnit;
+use common::{BalanceWrapper, PriceVariant};
+use jsonrpsee::{
+    core::{Error as RpcError, RpcResult},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Block as BlockT;
+use sp_runtime::DispatchError;
+use std::sync::Arc;
+
+// Runtime API imports.
+pub use order_book_runtime_api::OrderBookAPI as OrderBookRuntimeApi;
+use order_book_runtime_api::{
+    BestPricesInfo, CandleInfo, CandleIntervalInfo, LimitOrderInfo, MarketOrderQuoteInfo,
+    OrderBookDepthInfo, TradeInfo,
+};
+
+#[rpc(server, client)]
+pub trait OrderBookApi<BlockHash, OrderBookId, AccountId, OrderId, Moment, BlockNumber> {
+    #[method(name = "orderBook_depth")]
+    fn order_book_depth(
+        &self,
+        order_book_id: OrderBookId,
+        depth: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<OrderBookDepthInfo>>;
+
+    #[method(name = "orderBook_bestPrices")]
+    fn best_prices(
+        &self,
+        order_book_id: OrderBookId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BestPricesInfo>>;
+
+    #[method(name = "orderBook_userLimitOrders")]
+    fn user_limit_orders(
+        &self,
+        account_id: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<LimitOrderInfo<OrderBookId, OrderId, Moment, BlockNumber>>>;
+
+    #[method(name = "orderBook_quoteMarketOrder")]
+    fn quote_market_order(
+        &self,
+        order_book_id: OrderBookId,
+        direction: PriceVariant,
+        amount: BalanceWrapper,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<MarketOrderQuoteInfo, DispatchError>>;
+
+    #[method(name = "orderBook_lastTradePrice")]
+    fn last_trade_price(
+        &self,
+        order_book_id: OrderBookId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BalanceUnit>>;
+
+    #[method(name = "orderBook_vwap")]
+    fn vwap(
+        &self,
+        order_book_id: OrderBookId,
+        period: Moment,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<BalanceUnit>>;
+
+    #[method(name = "orderBook_candles")]
+    fn candles(
+        &self,
+        order_book_id: OrderBookId,
+        interval: CandleIntervalInfo,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<CandleInfo<Moment>>>;
+
+    #[method(name = "orderBook_recentTrades")]
+    fn recent_trades(
+        &self,
+        order_book_id: OrderBookId,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<TradeInfo<Moment>>>;
+}
+
+pub struct OrderBookClient<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> OrderBookClient<C, B> {
+    /// Construct default OrderBook as intermediary impl for rpc.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, OrderBookId, AccountId, OrderId, Moment, BlockNumber>
+    OrderBookApiServer<
+        <Block as BlockT>::Hash,
+        OrderBookId,
+        AccountId,
+        OrderId,
+        Moment,
+        BlockNumber,
+    > for OrderBookClient<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: OrderBookRuntimeApi<Block, OrderBookId, AccountId, OrderId, Moment, BlockNumber>,
+    OrderBookId: Codec,
+    AccountId: Codec,
+    OrderId: Codec,
+    Moment: Codec,
+    BlockNumber: Codec,
+{
+    fn order_book_depth(
+        &self,
+        order_book_id: OrderBookId,
+        depth: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<OrderBookDepthInfo>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.order_book_depth(&at, order_book_id, depth)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn best_prices(
+        &self,
+        order_book_id: OrderBookId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BestPricesInfo>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.best_prices(&at, order_book_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn user_limit_orders(
+        &self,
+        account_id: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<LimitOrderInfo<OrderBookId, OrderId, Moment, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.user_limit_orders(&at, account_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn quote_market_order(
+        &self,
+        order_book_id: OrderBookId,
+        direction: PriceVariant,
+        amount: BalanceWrapper,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<MarketOrderQuoteInfo, DispatchError>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.quote_market_order(&at, order_book_id, direction, amount)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn last_trade_price(
+        &self,
+        order_book_id: OrderBookId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BalanceUnit>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.last_trade_price(&at, order_book_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn vwap(
+        &self,
+        order_book_id: OrderBookId,
+        period: Moment,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<BalanceUnit>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.vwap(&at, order_book_id, period)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn candles(
+        &self,
+        order_book_id: OrderBookId,
+        interval: CandleIntervalInfo,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<CandleInfo<Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.candles(&at, order_book_id, interval, limit)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn recent_trades(
+        &self,
+        order_book_id: OrderBookId,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<TradeInfo<Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.recent_trades(&at, order_book_id, limit)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+}
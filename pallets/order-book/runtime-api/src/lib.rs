@@ -0,0 +1,162 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use common::prelude::BalanceUnit;
+use common::{BalanceWrapper, PriceVariant};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+/// Aggregated volume of all limit orders placed at the price.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct PriceLevelInfo {
+    pub price: BalanceUnit,
+    pub volume: BalanceUnit,
+}
+
+/// Order book depth, both sides start from the best price.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct OrderBookDepthInfo {
+    pub bids: Vec<PriceLevelInfo>,
+    pub asks: Vec<PriceLevelInfo>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct BestPricesInfo {
+    pub best_bid: Option<PriceLevelInfo>,
+    pub best_ask: Option<PriceLevelInfo>,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct LimitOrderInfo<OrderBookId, OrderId, Moment, BlockNumber> {
+    pub order_book_id: OrderBookId,
+    pub id: OrderId,
+    pub side: PriceVariant,
+    pub price: BalanceUnit,
+    pub original_amount: BalanceUnit,
+    pub amount: BalanceUnit,
+    pub time: Moment,
+    pub lifespan: Moment,
+    pub expires_at: BlockNumber,
+}
+
+/// Result of the market order execution if it was executed at the current block.
+/// `input_amount` is paid by the taker, `output_amount` is received by the taker.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct MarketOrderQuoteInfo {
+    pub input_amount: BalanceUnit,
+    pub output_amount: BalanceUnit,
+    pub average_price: BalanceUnit,
+}
+
+/// A trade between the taker and one maker, `direction` is the direction of the taker.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct TradeInfo<Moment> {
+    pub price: BalanceUnit,
+    pub amount: BalanceUnit,
+    pub direction: PriceVariant,
+    pub time: Moment,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub enum CandleIntervalInfo {
+    Minute,
+    Hour,
+    Day,
+}
+
+/// OHLCV candle, `volume` is in the base asset, `quote_volume` is in the quote asset.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct CandleInfo<Moment> {
+    pub start: Moment,
+    pub open: BalanceUnit,
+    pub high: BalanceUnit,
+    pub low: BalanceUnit,
+    pub close: BalanceUnit,
+    pub volume: BalanceUnit,
+    pub quote_volume: BalanceUnit,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait OrderBookAPI<OrderBookId, AccountId, OrderId, Moment, BlockNumber> where
+        OrderBookId: Codec,
+        AccountId: Codec,
+        OrderId: Codec,
+        Moment: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns up to `depth` best price levels of each side of the order book.
+        fn order_book_depth(order_book_id: OrderBookId, depth: u32) -> Option<OrderBookDepthInfo>;
+
+        fn best_prices(order_book_id: OrderBookId) -> Option<BestPricesInfo>;
+
+        /// Returns open limit orders of the user from all order books.
+        fn user_limit_orders(
+            account_id: AccountId,
+        ) -> Vec<LimitOrderInfo<OrderBookId, OrderId, Moment, BlockNumber>>;
+
+        /// `amount` is the volume of the base asset to buy or to sell.
+        /// Returns the reason if the market order cannot be executed.
+        fn quote_market_order(
+            order_book_id: OrderBookId,
+            direction: PriceVariant,
+            amount: BalanceWrapper,
+        ) -> Result<MarketOrderQuoteInfo, DispatchError>;
+
+        fn last_trade_price(order_book_id: OrderBookId) -> Option<BalanceUnit>;
+
+        /// Returns the volume-weighted average price of the trades for the last `period` milliseconds.
+        fn vwap(order_book_id: OrderBookId, period: Moment) -> Option<BalanceUnit>;
+
+        /// Returns up to `limit` last candles in chronological order.
+        fn candles(
+            order_book_id: OrderBookId,
+            interval: CandleIntervalInfo,
+            limit: u32,
+        ) -> Vec<CandleInfo<Moment>>;
+
+        /// Returns up to `limit` recent trades, the latest trade goes first.
+        fn recent_trades(order_book_id: OrderBookId, limit: u32) -> Vec<TradeInfo<Moment>>;
+    }
+}
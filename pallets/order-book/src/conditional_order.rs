@@ -0,0 +1,134 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use crate::{
+    ConditionalOrderKind, Error, MomentOf, OrderAmount, OrderPrice, OrderVolume, TriggerPriceSource,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use common::PriceVariant;
+use core::fmt::Debug;
+use frame_support::ensure;
+use frame_support::sp_runtime::DispatchError;
+use sp_runtime::traits::{CheckedMul, Zero};
+
+/// Stop-loss or take-profit order.
+/// It is kept off-book until the trigger price is reached and then it is converted into
+/// a limit order (if `limit_price` is defined) or into a market order.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, scale_info::TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct ConditionalOrder<T>
+where
+    T: crate::Config,
+{
+    pub id: T::OrderId,
+    pub owner: T::AccountId,
+    pub side: PriceVariant,
+    pub kind: ConditionalOrderKind,
+    pub price_source: TriggerPriceSource,
+
+    /// Price in OrderBookId `quote` asset that triggers the order.
+    pub trigger_price: OrderPrice,
+
+    /// Price of the limit order that is placed when the order is triggered.
+    /// If `None`, the order is executed as a market order.
+    pub limit_price: Option<OrderPrice>,
+
+    /// Amount of OrderBookId `base` asset
+    pub amount: OrderVolume,
+
+    pub time: MomentOf<T>,
+}
+
+impl<T: crate::Config + Sized> ConditionalOrder<T> {
+    pub fn new(
+        id: T::OrderId,
+        owner: T::AccountId,
+        side: PriceVariant,
+        kind: ConditionalOrderKind,
+        price_source: TriggerPriceSource,
+        trigger_price: OrderPrice,
+        limit_price: Option<OrderPrice>,
+        amount: OrderVolume,
+        time: MomentOf<T>,
+    ) -> Self {
+        Self {
+            id,
+            owner,
+            side,
+            kind,
+            price_source,
+            trigger_price,
+            limit_price,
+            amount,
+            time,
+        }
+    }
+
+    pub fn ensure_valid(&self) -> Result<(), DispatchError> {
+        ensure!(!self.amount.is_zero(), Error::<T>::InvalidOrderAmount);
+        ensure!(
+            !self.trigger_price.is_zero(),
+            Error::<T>::InvalidTriggerPrice
+        );
+        if let Some(limit_price) = self.limit_price {
+            ensure!(!limit_price.is_zero(), Error::<T>::InvalidLimitOrderPrice);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the order should be converted into a regular order at `price`.
+    pub fn is_triggered(&self, price: &OrderPrice) -> bool {
+        match (self.kind, self.side) {
+            (ConditionalOrderKind::StopLoss, PriceVariant::Sell)
+            | (ConditionalOrderKind::TakeProfit, PriceVariant::Buy) => *price <= self.trigger_price,
+            (ConditionalOrderKind::StopLoss, PriceVariant::Buy)
+            | (ConditionalOrderKind::TakeProfit, PriceVariant::Sell) => {
+                *price >= self.trigger_price
+            }
+        }
+    }
+
+    /// Returns the amount of asset that is locked while the order is waiting for the trigger.
+    ///
+    /// Sell order locks `amount` of `base` asset.
+    /// Buy order locks `quote` asset for `amount` at the limit price, or at the trigger price if the order is executed as a market order.
+    /// In the last case the owner should have enough free balance if the market price is worse than the trigger price.
+    pub fn locked_amount(&self) -> Result<OrderAmount, DispatchError> {
+        let locked = match self.side {
+            PriceVariant::Buy => OrderAmount::Quote(
+                self.limit_price
+                    .unwrap_or(self.trigger_price)
+                    .checked_mul(&self.amount)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?,
+            ),
+            PriceVariant::Sell => OrderAmount::Base(self.amount),
+        };
+        Ok(locked)
+    }
+}
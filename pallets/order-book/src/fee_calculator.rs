@@ -44,6 +44,7 @@ impl<T: Config> FeeCalculator<T> {
     /// The idea is to provide the reduced fee for market maker.
     /// If extrinsic is successfull, user pays maximum half network fee.
     /// If extrinsic failed, user pays full network fee.
+    /// Post-only limit orders are always charged the market maker fee, IOC & FOK limit orders are charged full network fee.
     ///
     /// The actual fee contains two parts: constant and dynamic.
     /// Dynamic part depends on limit order lifetime. The longer the lifetime, the higher the dynamic fee.
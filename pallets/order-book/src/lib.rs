@@ -39,8 +39,8 @@ use common::prelude::{
 };
 use common::LiquiditySourceType;
 use common::{
-    AssetInfoProvider, AssetName, AssetSymbol, Balance, BalancePrecision, ContentSource,
-    Description, DexInfoProvider, LiquiditySource, PriceVariant, RewardReason,
+    AssetInfoProvider, AssetName, AssetSymbol, Balance, BalancePrecision, BuyBackHandler,
+    ContentSource, Description, DexInfoProvider, LiquiditySource, PriceVariant, RewardReason,
     SyntheticInfoProvider, ToOrderTechUnitFromDEXAndTradingPair, TradingPairSourceManager,
 };
 use core::fmt::Debug;
@@ -50,7 +50,10 @@ use frame_support::sp_runtime::DispatchError;
 use frame_support::traits::{Get, Time};
 use frame_support::weights::{Weight, WeightMeter};
 use frame_system::pallet_prelude::BlockNumberFor;
-use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedDiv, CheckedMul, MaybeDisplay, Zero};
+use sp_runtime::traits::{
+    AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, MaybeDisplay, Saturating,
+    UniqueSaturatedInto, Zero,
+};
 use sp_runtime::BoundedVec;
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::vec::Vec;
@@ -64,26 +67,32 @@ mod tests;
 pub mod test_utils;
 
 pub mod cache_data_layer;
+mod conditional_order;
 pub mod fee_calculator;
 mod limit_order;
 mod market_order;
 mod order_book;
 mod scheduler;
 pub mod storage_data_layer;
+mod trade_history;
 pub mod traits;
 pub mod types;
 
 pub use crate::order_book::OrderBook;
 use cache_data_layer::CacheDataLayer;
+pub use conditional_order::ConditionalOrder;
 pub use limit_order::LimitOrder;
 pub use market_order::MarketOrder;
 pub use traits::{
     AlignmentScheduler, CurrencyLocker, CurrencyUnlocker, DataLayer, Delegate, ExpirationScheduler,
+    TriggerScheduler,
 };
 pub use types::{
-    CancelReason, DealInfo, MarketChange, MarketRole, MarketSide, OrderAmount, OrderBookEvent,
-    OrderBookId, OrderBookStatus, OrderBookTechStatus, OrderPrice, OrderVolume, Payment,
-    PriceOrders, UserOrders,
+    CancelReason, Candle, CandleInterval, ConditionalOrderKind, DealFees, DealInfo, FeeTier,
+    MarketChange, MarketRole, MarketSide, OrderAmount, OrderBookEvent, OrderBookId,
+    OrderBookStatus, OrderBookTechStatus, OrderPrice, OrderVolume, Payment, PriceOrders,
+    TimeInForce, Trade, TradingFeeDestination, TradingFees, TriggerPriceSource, UserOrders,
+    BASIS_POINTS, MILLISECS_PER_DAY, TRADING_VOLUME_DAYS,
 };
 pub use weights::WeightInfo;
 
@@ -122,6 +131,8 @@ pub mod pallet {
         /// In particular, it defines the max number of limit orders that could be executed by one big market order in one block.
         /// During update of parameters, the limits must satisfy this ratio.
         const HARD_MIN_MAX_RATIO: usize;
+        /// The max maker or taker fee in basis points that could be set for an order book.
+        const MAX_TRADING_FEE_BPS: u16;
 
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -140,6 +151,7 @@ pub mod pallet {
         type Locker: CurrencyLocker<Self::AccountId, Self::AssetId, Self::DEXId, DispatchError>;
         type Unlocker: CurrencyUnlocker<Self::AccountId, Self::AssetId, Self::DEXId, DispatchError>;
         type Scheduler: AlignmentScheduler
+            + TriggerScheduler
             + ExpirationScheduler<
                 Self::BlockNumber,
                 OrderBookId<Self::AssetId, Self::DEXId>,
@@ -160,6 +172,8 @@ pub mod pallet {
         type MaxExpiringOrdersPerBlock: Get<u32>;
         type MaxExpirationWeightPerBlock: Get<Weight>;
         type MaxAlignmentWeightPerBlock: Get<Weight>;
+        type MaxConditionalOrdersPerUser: Get<u32>;
+        type MaxTriggersWeightPerBlock: Get<Weight>;
         type EnsureTradingPairExists: EnsureTradingPairExists<
             Self::DEXId,
             Self::AssetId,
@@ -183,6 +197,12 @@ pub mod pallet {
             Success = Either<Self::AccountId, ()>,
         >;
         type PermittedEditOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = ()>;
+        type MaxFeeTiers: Get<u32>;
+        /// The account that receives trading fees by default and holds fees waiting for buy back & burn
+        type TradingFeeCollector: Get<Self::AccountId>;
+        type BuyBackHandler: BuyBackHandler<Self::AccountId, Self::AssetId>;
+        /// The max number of recent trades kept per order book
+        type MaxTradesHistory: Get<u32>;
         type WeightInfo: WeightInfo;
     }
 
@@ -291,6 +311,171 @@ pub mod pallet {
     #[pallet::getter(fn incomplete_expirations_since)]
     pub type IncompleteExpirationsSince<T: Config> = StorageValue<_, T::BlockNumber>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn conditional_orders)]
+    pub type ConditionalOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        Blake2_128Concat,
+        T::OrderId,
+        ConditionalOrder<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn user_conditional_orders)]
+    pub type UserConditionalOrders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        UserOrders<T::OrderId, T::MaxConditionalOrdersPerUser>,
+        ValueQuery,
+    >;
+
+    /// The price of the last executed deal in the order book
+    #[pallet::storage]
+    #[pallet::getter(fn last_trade_price)]
+    pub type LastTradePrice<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        OrderPrice,
+        OptionQuery,
+    >;
+
+    /// The last checked conditional order;
+    /// Weight limit might not allow to check all conditional orders in a block
+    /// so the check is continued from this order in the next block.
+    #[pallet::storage]
+    #[pallet::getter(fn triggers_cursor)]
+    pub type TriggersCursor<T: Config> =
+        StorageValue<_, (OrderBookId<AssetIdOf<T>, T::DEXId>, T::OrderId)>;
+
+    /// Maker & taker fees of the order book
+    #[pallet::storage]
+    #[pallet::getter(fn trading_fees)]
+    pub type OrderBookTradingFees<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        TradingFees,
+        ValueQuery,
+    >;
+
+    /// Fee discount tiers sorted by the min trailing 30-day trading volume
+    #[pallet::storage]
+    #[pallet::getter(fn fee_tiers)]
+    pub type FeeTiers<T: Config> = StorageValue<_, BoundedVec<FeeTier, T::MaxFeeTiers>, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultFeeDestination<T: Config>() -> TradingFeeDestination<T::AccountId, AssetIdOf<T>> {
+        TradingFeeDestination::Account(T::TradingFeeCollector::get())
+    }
+
+    /// Where the collected trading fees go
+    #[pallet::storage]
+    #[pallet::getter(fn fee_destination)]
+    pub type FeeDestination<T: Config> = StorageValue<
+        _,
+        TradingFeeDestination<T::AccountId, AssetIdOf<T>>,
+        ValueQuery,
+        DefaultFeeDestination<T>,
+    >;
+
+    /// Daily trading volumes (in the quote asset) of the user for the last 30 days per DEX.
+    /// Each item is (day number, volume).
+    /// It is bounded by `TRADING_VOLUME_DAYS` items per account and DEX,
+    /// volumes of the users who have not traded for that period are pruned in `on_idle`.
+    #[pallet::storage]
+    #[pallet::getter(fn trading_volumes)]
+    pub type TradingVolumes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::DEXId,
+        BoundedVec<(u32, Balance), ConstU32<TRADING_VOLUME_DAYS>>,
+        ValueQuery,
+    >;
+
+    /// Collected fees waiting for buy back & burn.
+    /// Key is (fee asset, buy back asset)
+    #[pallet::storage]
+    #[pallet::getter(fn fees_to_burn)]
+    pub type FeesToBurn<T: Config> =
+        StorageMap<_, Blake2_128Concat, (AssetIdOf<T>, AssetIdOf<T>), Balance, ValueQuery>;
+
+    /// Maker fees that could not be paid during the deals, they are charged on the next deals of the maker.
+    /// Key is (maker, quote asset)
+    #[pallet::storage]
+    #[pallet::getter(fn maker_fee_debts)]
+    pub type MakerFeeDebts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        AssetIdOf<T>,
+        Balance,
+        ValueQuery,
+    >;
+
+    /// Recent trades of the order book.
+    /// It is a ring buffer of `MaxTradesHistory` trades, the second key is the slot in the buffer.
+    #[pallet::storage]
+    #[pallet::getter(fn trades)]
+    pub type Trades<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        Twox64Concat,
+        u32,
+        Trade<MomentOf<T>>,
+        OptionQuery,
+    >;
+
+    /// The slot for the next trade & the number of stored trades of the order book
+    #[pallet::storage]
+    #[pallet::getter(fn trades_head)]
+    pub type TradesHead<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        OrderBookId<AssetIdOf<T>, T::DEXId>,
+        (u32, u32),
+        ValueQuery,
+    >;
+
+    /// OHLCV candles of the order book.
+    /// Candles of each interval are kept in a ring buffer, the slot is the candle number modulo the retention of the interval.
+    #[pallet::storage]
+    #[pallet::getter(fn candle)]
+    pub type Candles<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (OrderBookId<AssetIdOf<T>, T::DEXId>, CandleInterval),
+        Twox64Concat,
+        u32,
+        Candle<MomentOf<T>>,
+        OptionQuery,
+    >;
+
+    /// The last checked candle;
+    /// Weight limit might not allow to check all candles in a block
+    /// so pruning is continued from this candle in the next block.
+    #[pallet::storage]
+    #[pallet::getter(fn candles_pruning_cursor)]
+    pub type CandlesPruningCursor<T: Config> =
+        StorageValue<_, ((OrderBookId<AssetIdOf<T>, T::DEXId>, CandleInterval), u32)>;
+
+    /// The last checked trading volumes;
+    /// Weight limit might not allow to check all volumes in a block
+    /// so pruning is continued from these volumes in the next block.
+    #[pallet::storage]
+    #[pallet::getter(fn trading_volumes_pruning_cursor)]
+    pub type TradingVolumesPruningCursor<T: Config> = StorageValue<_, (T::AccountId, T::DEXId)>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -403,6 +588,86 @@ pub mod pallet {
             order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
             error: DispatchError,
         },
+
+        /// User placed new conditional order
+        ConditionalOrderPlaced {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            order_id: T::OrderId,
+            owner_id: T::AccountId,
+            side: PriceVariant,
+            kind: ConditionalOrderKind,
+            price_source: TriggerPriceSource,
+            trigger_price: OrderPrice,
+            limit_price: Option<OrderPrice>,
+            amount: OrderVolume,
+        },
+
+        /// User canceled their conditional order
+        ConditionalOrderCanceled {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            order_id: T::OrderId,
+            owner_id: T::AccountId,
+        },
+
+        /// The trigger price is reached and the conditional order is converted into a limit or market order
+        ConditionalOrderTriggered {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            order_id: T::OrderId,
+            owner_id: T::AccountId,
+            price: OrderPrice,
+        },
+
+        /// The conditional order is triggered, but it failed to be executed. The locked liquidity is returned to the owner
+        ConditionalOrderFailed {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            order_id: T::OrderId,
+            owner_id: T::AccountId,
+            error: DispatchError,
+        },
+
+        /// Maker & taker fees of the order book are changed
+        TradingFeesChanged {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            maker_fee_bps: u16,
+            taker_fee_bps: u16,
+        },
+
+        /// Fee discount tiers are changed
+        FeeTiersChanged { tiers: Vec<FeeTier> },
+
+        /// The destination of trading fees is changed
+        TradingFeeDestinationChanged {
+            destination: TradingFeeDestination<T::AccountId, AssetIdOf<T>>,
+        },
+
+        /// Trading fees are collected from the deal
+        TradingFeesCollected {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            amount: OrderVolume,
+        },
+
+        /// The unpaid maker fee debt of the account is changed
+        MakerFeeDebtUpdated {
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            owner_id: T::AccountId,
+            debt: OrderVolume,
+        },
+
+        /// Collected trading fees are exchanged to the buy back asset and burned
+        TradingFeesBurned {
+            asset_id: AssetIdOf<T>,
+            buy_back_asset_id: AssetIdOf<T>,
+            amount: Balance,
+            burned: Balance,
+        },
+
+        /// Collected trading fees failed to be bought back & burned. The fees stay on the collector account
+        TradingFeesBurnFailed {
+            asset_id: AssetIdOf<T>,
+            buy_back_asset_id: AssetIdOf<T>,
+            amount: Balance,
+            error: DispatchError,
+        },
     }
 
     #[pallet::error]
@@ -497,6 +762,22 @@ pub mod pallet {
         ForbiddenStatusToUpdateOrderBook,
         /// Order Book is locked for technical maintenance. Try again later.
         OrderBookIsLocked,
+        /// Conditional order does not exist for this trading pair and order id
+        UnknownConditionalOrder,
+        /// The trigger price of conditional order does not meet the requirements
+        InvalidTriggerPrice,
+        /// User has the max available count of conditional orders in the current order book
+        UserHasMaxCountOfConditionalOrders,
+        /// Fill-or-kill limit order cannot be filled completely at the limit price or better
+        FillOrKillLimitOrderCannotBeFilled,
+        /// Post-only limit order cannot be placed, because it crosses the spread
+        PostOnlyLimitOrderCrossesSpread,
+        /// Trading fee exceeds the max allowed value
+        InvalidTradingFee,
+        /// Fee tiers must be sorted by strictly ascending min volume and discount cannot exceed 100%
+        InvalidFeeTiers,
+        /// Triggered conditional order needs more weight than is available for triggers in a block
+        ConditionalOrderExceedsTriggersWeight,
     }
 
     #[pallet::hooks]
@@ -511,9 +792,34 @@ pub mod pallet {
                 WeightMeter::from_limit(T::MaxAlignmentWeightPerBlock::get());
             Self::service_alignment(&mut alignment_weight_counter);
 
+            // reserve the weight for conditional orders that are checked during the block finalization
+            let triggers_weight = if <ConditionalOrders<T>>::iter_keys().next().is_some() {
+                T::MaxTriggersWeightPerBlock::get()
+            } else {
+                Weight::zero()
+            };
+
             expiration_weight_counter
                 .consumed
                 .saturating_add(alignment_weight_counter.consumed)
+                .saturating_add(triggers_weight)
+                .saturating_add(T::DbWeight::get().reads(1))
+        }
+
+        /// Convert triggered conditional orders into regular orders
+        fn on_finalize(_current_block: T::BlockNumber) {
+            let mut triggers_weight_counter =
+                WeightMeter::from_limit(T::MaxTriggersWeightPerBlock::get());
+            Self::service_triggers(&mut triggers_weight_counter);
+        }
+
+        /// Buy back & burn collected trading fees and prune outdated candles
+        fn on_idle(_current_block: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut weight_counter = WeightMeter::from_limit(remaining_weight);
+            Self::service_fees_burning(&mut weight_counter);
+            Self::service_candles_pruning(&mut weight_counter);
+            Self::service_trading_volumes_pruning(&mut weight_counter);
+            weight_counter.consumed
         }
     }
 
@@ -596,7 +902,10 @@ pub mod pallet {
 
             let is_empty = <LimitOrders<T>>::iter_prefix_values(order_book_id)
                 .next()
-                .is_none();
+                .is_none()
+                && <ConditionalOrders<T>>::iter_prefix_values(order_book_id)
+                    .next()
+                    .is_none();
             ensure!(is_empty, Error::<T>::OrderBookIsNotEmpty);
 
             T::TradingPairSourceManager::disable_source_for_trading_pair(
@@ -608,6 +917,16 @@ pub mod pallet {
 
             Self::deregister_tech_account(order_book_id)?;
             <OrderBooks<T>>::remove(order_book_id);
+            <LastTradePrice<T>>::remove(order_book_id);
+            let _ = <Trades<T>>::clear_prefix(order_book_id, T::MaxTradesHistory::get(), None);
+            <TradesHead<T>>::remove(order_book_id);
+            for interval in CandleInterval::ALL {
+                let _ = <Candles<T>>::clear_prefix(
+                    (order_book_id, interval),
+                    interval.retention(),
+                    None,
+                );
+            }
 
             Self::deposit_event(Event::<T>::OrderBookDeleted { order_book_id });
             Ok(())
@@ -717,52 +1036,15 @@ pub mod pallet {
             side: PriceVariant,
             lifespan: Option<MomentOf<T>>,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            let mut order_book =
-                <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
-            let order_id = order_book.next_order_id();
-            let now = T::Time::now();
-            let current_block = frame_system::Pallet::<T>::block_number();
-            let lifespan = lifespan.unwrap_or(T::MAX_ORDER_LIFESPAN);
-            let amount = if T::AssetInfoProvider::is_non_divisible(&order_book_id.base) {
-                OrderVolume::indivisible(amount)
-            } else {
-                OrderVolume::divisible(amount)
-            };
-            let order = LimitOrder::<T>::new(
-                order_id,
-                who,
-                side,
-                OrderPrice::divisible(price),
+            Self::place_limit_order_with_time_in_force(
+                origin,
+                order_book_id,
+                price,
                 amount,
-                now,
+                side,
                 lifespan,
-                current_block,
-            );
-
-            let mut data = CacheDataLayer::<T>::new();
-
-            let executed_orders_count = order_book.place_limit_order(order, &mut data)?;
-
-            data.commit();
-            <OrderBooks<T>>::insert(order_book_id, order_book);
-
-            // Note: be careful with changing the weight. The fee depends on it,
-            // the market-maker fee is charged for some weight, and the regular fee for none weight
-            let actual_weight = if executed_orders_count == 0 {
-                // if the extrinsic just places the limit order, the weight of the placing is returned
-                Some(<T as Config>::WeightInfo::place_limit_order_without_cross_spread())
-            } else {
-                // if the limit order was converted into market order, then None weight is returned
-                // this weight will be replaced with worst case weight:
-                // exchange_weight() + place_limit_order_without_cross_spread()
-                None
-            };
-
-            Ok(PostDispatchInfo {
-                actual_weight,
-                pays_fee: Pays::Yes,
-            })
+                TimeInForce::GoodTillCancelled,
+            )
         }
 
         #[pallet::call_index(5)]
@@ -832,7 +1114,11 @@ pub mod pallet {
         }
 
         #[pallet::call_index(7)]
-        #[pallet::weight(<T as Config>::WeightInfo::execute_market_order())]
+        // the market order executes up to SOFT_MIN_MAX_RATIO limit orders and records the trade with each of them
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute_market_order()
+                .saturating_add(Pallet::<T>::record_trades_weight(T::SOFT_MIN_MAX_RATIO as u32))
+        )]
         pub fn execute_market_order(
             origin: OriginFor<T>,
             order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
@@ -856,6 +1142,233 @@ pub mod pallet {
             data.commit();
             Ok(())
         }
+
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::place_conditional_order())]
+        pub fn place_conditional_order(
+            origin: OriginFor<T>,
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            side: PriceVariant,
+            kind: ConditionalOrderKind,
+            price_source: TriggerPriceSource,
+            trigger_price: Balance,
+            limit_price: Option<Balance>,
+            amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut order_book =
+                <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
+            let order_id = order_book.next_order_id();
+            let amount = if T::AssetInfoProvider::is_non_divisible(&order_book_id.base) {
+                OrderVolume::indivisible(amount)
+            } else {
+                OrderVolume::divisible(amount)
+            };
+            let order = ConditionalOrder::<T>::new(
+                order_id,
+                who.clone(),
+                side,
+                kind,
+                price_source,
+                OrderPrice::divisible(trigger_price),
+                limit_price.map(OrderPrice::divisible),
+                amount,
+                T::Time::now(),
+            );
+
+            order_book.place_conditional_order(&order)?;
+
+            <UserConditionalOrders<T>>::try_mutate(&who, order_book_id, |user_orders| {
+                user_orders
+                    .try_push(order_id)
+                    .map_err(|_| Error::<T>::UserHasMaxCountOfConditionalOrders)
+            })?;
+            <ConditionalOrders<T>>::insert(order_book_id, order_id, order.clone());
+            <OrderBooks<T>>::insert(order_book_id, order_book);
+
+            Self::deposit_event(Event::<T>::ConditionalOrderPlaced {
+                order_book_id,
+                order_id,
+                owner_id: who,
+                side,
+                kind,
+                price_source,
+                trigger_price: order.trigger_price,
+                limit_price: order.limit_price,
+                amount: order.amount,
+            });
+            Ok(())
+        }
+
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_conditional_order())]
+        pub fn cancel_conditional_order(
+            origin: OriginFor<T>,
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            order_id: T::OrderId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let order = <ConditionalOrders<T>>::get(order_book_id, order_id)
+                .ok_or(Error::<T>::UnknownConditionalOrder)?;
+
+            ensure!(order.owner == who, Error::<T>::Unauthorized);
+
+            let order_book =
+                <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
+
+            order_book.cancel_conditional_order(&order)?;
+            Self::remove_conditional_order(&order_book_id, &order);
+
+            Self::deposit_event(Event::<T>::ConditionalOrderCanceled {
+                order_book_id,
+                order_id,
+                owner_id: who,
+            });
+
+            Ok(PostDispatchInfo {
+                actual_weight: None,
+                pays_fee: Pays::No,
+            })
+        }
+
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_trading_fees())]
+        pub fn set_trading_fees(
+            origin: OriginFor<T>,
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            maker_fee_bps: u16,
+            taker_fee_bps: u16,
+        ) -> DispatchResult {
+            T::PermittedEditOrigin::ensure_origin(origin)?;
+            ensure!(
+                <OrderBooks<T>>::contains_key(order_book_id),
+                Error::<T>::UnknownOrderBook
+            );
+            ensure!(
+                maker_fee_bps <= T::MAX_TRADING_FEE_BPS && taker_fee_bps <= T::MAX_TRADING_FEE_BPS,
+                Error::<T>::InvalidTradingFee
+            );
+
+            <OrderBookTradingFees<T>>::insert(
+                order_book_id,
+                TradingFees {
+                    maker_fee_bps,
+                    taker_fee_bps,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::TradingFeesChanged {
+                order_book_id,
+                maker_fee_bps,
+                taker_fee_bps,
+            });
+            Ok(())
+        }
+
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_fee_tiers())]
+        pub fn set_fee_tiers(origin: OriginFor<T>, tiers: Vec<FeeTier>) -> DispatchResult {
+            T::PermittedEditOrigin::ensure_origin(origin)?;
+            ensure!(
+                tiers
+                    .windows(2)
+                    .all(|pair| pair[0].min_volume < pair[1].min_volume),
+                Error::<T>::InvalidFeeTiers
+            );
+            ensure!(
+                tiers.iter().all(|tier| tier.discount_bps <= BASIS_POINTS),
+                Error::<T>::InvalidFeeTiers
+            );
+
+            let bounded_tiers: BoundedVec<FeeTier, T::MaxFeeTiers> = tiers
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidFeeTiers)?;
+            <FeeTiers<T>>::put(bounded_tiers);
+
+            Self::deposit_event(Event::<T>::FeeTiersChanged { tiers });
+            Ok(())
+        }
+
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_trading_fee_destination())]
+        pub fn set_trading_fee_destination(
+            origin: OriginFor<T>,
+            destination: TradingFeeDestination<T::AccountId, AssetIdOf<T>>,
+        ) -> DispatchResult {
+            T::PermittedEditOrigin::ensure_origin(origin)?;
+            <FeeDestination<T>>::put(destination.clone());
+            Self::deposit_event(Event::<T>::TradingFeeDestinationChanged { destination });
+            Ok(())
+        }
+
+        #[pallet::call_index(13)]
+        // in the worst case the limit order is converted into market order and the exchange occurs
+        #[pallet::weight(Pallet::<T>::exchange_weight())]
+        pub fn place_limit_order_with_time_in_force(
+            origin: OriginFor<T>,
+            order_book_id: OrderBookId<AssetIdOf<T>, T::DEXId>,
+            price: Balance,
+            amount: Balance,
+            side: PriceVariant,
+            lifespan: Option<MomentOf<T>>,
+            time_in_force: TimeInForce,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut order_book =
+                <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
+            let order_id = order_book.next_order_id();
+            let now = T::Time::now();
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let lifespan = lifespan.unwrap_or(T::MAX_ORDER_LIFESPAN);
+            let amount = if T::AssetInfoProvider::is_non_divisible(&order_book_id.base) {
+                OrderVolume::indivisible(amount)
+            } else {
+                OrderVolume::divisible(amount)
+            };
+            let order = LimitOrder::<T>::new(
+                order_id,
+                who,
+                side,
+                OrderPrice::divisible(price),
+                amount,
+                now,
+                lifespan,
+                current_block,
+            );
+
+            let mut data = CacheDataLayer::<T>::new();
+
+            let executed_orders_count =
+                order_book.place_limit_order_with_time_in_force(order, time_in_force, &mut data)?;
+
+            data.commit();
+            <OrderBooks<T>>::insert(order_book_id, order_book);
+
+            // Note: be careful with changing the weight. The fee depends on it,
+            // the market-maker fee is charged for some weight, and the regular fee for none weight
+            let is_maker = match time_in_force {
+                TimeInForce::GoodTillCancelled => executed_orders_count == 0,
+                // post-only limit order never crosses the spread
+                TimeInForce::PostOnly => true,
+                // IOC & FOK limit orders are never placed into the order book
+                TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill => false,
+            };
+            let actual_weight = if is_maker {
+                // if the extrinsic just places the limit order, the weight of the placing is returned
+                Some(<T as Config>::WeightInfo::place_limit_order_without_cross_spread())
+            } else {
+                // if the limit order was converted into market order or executed immediately (IOC & FOK), then None weight is returned
+                // this weight will be replaced with worst case weight:
+                // exchange_weight() + place_limit_order_without_cross_spread()
+                None
+            };
+
+            Ok(PostDispatchInfo {
+                actual_weight,
+                pays_fee: Pays::Yes,
+            })
+        }
     }
 }
 
@@ -1012,6 +1525,19 @@ impl<T: Config> Delegate<T::AccountId, T::AssetId, T::OrderId, T::DEXId, MomentO
                 average_price,
                 to,
             },
+
+            OrderBookEvent::TradingFeesCollected { amount } => Event::<T>::TradingFeesCollected {
+                order_book_id,
+                amount,
+            },
+
+            OrderBookEvent::MakerFeeDebtUpdated { owner_id, debt } => {
+                Event::<T>::MakerFeeDebtUpdated {
+                    order_book_id,
+                    owner_id,
+                    debt,
+                }
+            }
         };
 
         Self::deposit_event(event);
@@ -1049,6 +1575,21 @@ impl<T: Config> Pallet<T> {
         technical::Pallet::<T>::deregister_tech_account_id(tech_account)
     }
 
+    pub fn remove_conditional_order(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        order: &ConditionalOrder<T>,
+    ) {
+        <ConditionalOrders<T>>::remove(order_book_id, order.id);
+        <UserConditionalOrders<T>>::mutate_exists(&order.owner, order_book_id, |user_orders| {
+            if let Some(orders) = user_orders {
+                orders.retain(|x| *x != order.id);
+                if orders.is_empty() {
+                    *user_orders = None;
+                }
+            }
+        });
+    }
+
     pub fn assemble_order_book_id(
         dex_id: T::DEXId,
         input_asset_id: &AssetIdOf<T>,
@@ -1210,6 +1751,300 @@ impl<T: Config> Pallet<T> {
         <OrderBooks<T>>::insert(order_book_id, order_book);
         Self::register_tech_account(*order_book_id)
     }
+
+    /// Returns up to `depth` price levels of bids and asks, both sides are sorted from the best price.
+    /// `None` if there is no such order book.
+    pub fn order_book_depth(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        depth: usize,
+    ) -> Option<(
+        Vec<(OrderPrice, OrderVolume)>,
+        Vec<(OrderPrice, OrderVolume)>,
+    )> {
+        if !<OrderBooks<T>>::contains_key(order_book_id) {
+            return None;
+        }
+
+        let mut data = CacheDataLayer::<T>::new();
+        let bids = data
+            .get_aggregated_bids(order_book_id)
+            .iter()
+            .rev()
+            .take(depth)
+            .map(|(price, volume)| (*price, *volume))
+            .collect();
+        let asks = data
+            .get_aggregated_asks(order_book_id)
+            .iter()
+            .take(depth)
+            .map(|(price, volume)| (*price, *volume))
+            .collect();
+
+        Some((bids, asks))
+    }
+
+    /// Returns the best bid and the best ask with aggregated volumes at these prices.
+    /// `None` if there is no such order book.
+    pub fn best_prices(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+    ) -> Option<(
+        Option<(OrderPrice, OrderVolume)>,
+        Option<(OrderPrice, OrderVolume)>,
+    )> {
+        let order_book = <OrderBooks<T>>::get(order_book_id)?;
+        let mut data = CacheDataLayer::<T>::new();
+        Some((
+            order_book.best_bid(&mut data),
+            order_book.best_ask(&mut data),
+        ))
+    }
+
+    /// Returns open limit orders of the user from all order books.
+    pub fn all_user_limit_orders(
+        account: &T::AccountId,
+    ) -> Vec<(OrderBookId<AssetIdOf<T>, T::DEXId>, LimitOrder<T>)> {
+        let mut data = CacheDataLayer::<T>::new();
+        let mut result = Vec::new();
+        for (order_book_id, order_ids) in data.get_all_user_limit_orders(account) {
+            for order_id in order_ids {
+                if let Ok(order) = data.get_limit_order(&order_book_id, order_id) {
+                    result.push((order_book_id, order));
+                }
+            }
+        }
+        result
+    }
+
+    /// Calculates the result of the market order with `amount` of the base asset
+    /// without execution.
+    pub fn quote_market_order(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        direction: PriceVariant,
+        amount: Balance,
+    ) -> Result<DealInfo<AssetIdOf<T>>, DispatchError> {
+        let order_book = <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
+        let mut data = CacheDataLayer::<T>::new();
+
+        let (input_asset_id, output_asset_id, amount) = match direction {
+            PriceVariant::Buy => (
+                order_book_id.quote,
+                order_book_id.base,
+                QuoteAmount::with_desired_output(amount),
+            ),
+            PriceVariant::Sell => (
+                order_book_id.base,
+                order_book_id.quote,
+                QuoteAmount::with_desired_input(amount),
+            ),
+        };
+
+        order_book.calculate_deal(&input_asset_id, &output_asset_id, amount, &mut data)
+    }
+
+    /// Returns the number of the current day since the Unix epoch
+    pub fn current_day() -> u32 {
+        let millis: u128 = T::Time::now().unique_saturated_into();
+        (millis / MILLISECS_PER_DAY).unique_saturated_into()
+    }
+
+    /// Returns the trading volume of the user in the quote asset for the last 30 days
+    pub fn trailing_volume(account: &T::AccountId, dex_id: &T::DEXId) -> Balance {
+        let first_day = Self::current_day().saturating_sub(TRADING_VOLUME_DAYS - 1);
+        <TradingVolumes<T>>::get(account, dex_id)
+            .iter()
+            .filter(|(day, _)| *day >= first_day)
+            .fold(0, |sum, (_, volume)| sum.saturating_add(*volume))
+    }
+
+    /// Returns the fee discount for the trading `volume` according to the fee tiers
+    pub fn fee_tier_discount(volume: Balance) -> u16 {
+        <FeeTiers<T>>::get()
+            .iter()
+            .rev()
+            .find(|tier| tier.min_volume <= volume)
+            .map(|tier| tier.discount_bps)
+            .unwrap_or_default()
+    }
+
+    /// Returns the fee rate in basis points for the user considering their fee tier
+    pub fn trading_fee_rate(
+        account: &T::AccountId,
+        dex_id: &T::DEXId,
+        role: MarketRole,
+        fees: &TradingFees,
+    ) -> u16 {
+        if fees.is_zero() {
+            return 0;
+        }
+        let discount_bps = Self::fee_tier_discount(Self::trailing_volume(account, dex_id));
+        fees.rate(role, discount_bps)
+    }
+
+    /// Returns the fee from the `volume` with `rate` in basis points
+    pub fn trading_fee(volume: OrderVolume, rate: u16) -> OrderVolume {
+        let fee = volume
+            .balance()
+            .saturating_mul(rate as Balance)
+            .saturating_div(BASIS_POINTS as Balance);
+        volume.copy_divisibility(fee)
+    }
+
+    /// Adds `volume` to the today trading volume of the user and drops the outdated volumes
+    pub fn record_trading_volume(account: &T::AccountId, dex_id: &T::DEXId, volume: &OrderVolume) {
+        if volume.is_zero() {
+            return;
+        }
+        let today = Self::current_day();
+        let first_day = today.saturating_sub(TRADING_VOLUME_DAYS - 1);
+        <TradingVolumes<T>>::mutate(account, dex_id, |volumes| {
+            volumes.retain(|(day, _)| *day >= first_day);
+            match volumes.last_mut() {
+                Some((day, day_volume)) if *day == today => {
+                    *day_volume = day_volume.saturating_add(*volume.balance());
+                }
+                _ => {
+                    // cannot overflow, because outdated volumes have just been removed
+                    let _ = volumes.try_push((today, *volume.balance()));
+                }
+            }
+        });
+    }
+
+    /// Returns the amount to calculate the deal with, so that the deal together with the taker fee
+    /// fits into the desired quote amount.
+    /// The fee is always taken in the quote asset, so only the amounts in the quote asset are adjusted.
+    pub fn amount_without_taker_fee(
+        direction: PriceVariant,
+        amount: QuoteAmount<Balance>,
+        taker_fee_bps: u16,
+    ) -> QuoteAmount<Balance> {
+        if taker_fee_bps == 0 {
+            return amount;
+        }
+        let basis_points = BASIS_POINTS as Balance;
+        let fee_bps = taker_fee_bps.min(BASIS_POINTS - 1) as Balance;
+        match (direction, amount) {
+            // deal + fee = desired input
+            (PriceVariant::Buy, QuoteAmount::WithDesiredInput { desired_amount_in }) => {
+                QuoteAmount::with_desired_input(
+                    desired_amount_in.saturating_mul(basis_points) / (basis_points + fee_bps),
+                )
+            }
+            // deal - fee = desired output
+            (PriceVariant::Sell, QuoteAmount::WithDesiredOutput { desired_amount_out }) => {
+                QuoteAmount::with_desired_output(
+                    desired_amount_out
+                        .saturating_mul(basis_points)
+                        .div_ceil(basis_points - fee_bps),
+                )
+            }
+            _ => amount,
+        }
+    }
+
+    /// Returns input & output amounts of the deal considering the taker `fee` in the quote asset
+    pub fn deal_amounts_with_taker_fee(
+        deal_info: &DealInfo<AssetIdOf<T>>,
+        fee: OrderVolume,
+    ) -> Result<(OrderVolume, OrderVolume), DispatchError> {
+        let input = *deal_info.input_amount.value();
+        let output = *deal_info.output_amount.value();
+        match deal_info.direction {
+            PriceVariant::Buy => Ok((
+                input
+                    .checked_add(&fee)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?,
+                output,
+            )),
+            PriceVariant::Sell => Ok((
+                input,
+                output
+                    .checked_sub(&fee)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?,
+            )),
+        }
+    }
+
+    /// Buys back & burns the collected trading fees while the weight allows
+    pub fn service_fees_burning(weight: &mut WeightMeter) {
+        let collector = T::TradingFeeCollector::get();
+
+        loop {
+            if !weight.check_accrue(<T as Config>::WeightInfo::service_fee_burning()) {
+                return;
+            }
+            // The processed entry is removed, so the first entry is always the next one to process
+            let Some(((asset_id, buy_back_asset_id), amount)) = <FeesToBurn<T>>::iter().next() else {
+                return;
+            };
+            <FeesToBurn<T>>::remove((asset_id, buy_back_asset_id));
+
+            if amount.is_zero() {
+                continue;
+            }
+
+            let result = if asset_id == buy_back_asset_id {
+                assets::Pallet::<T>::burn_from(&asset_id, &collector, &collector, amount)
+                    .map(|_| amount)
+            } else {
+                T::BuyBackHandler::buy_back_and_burn(
+                    &collector,
+                    &asset_id,
+                    &buy_back_asset_id,
+                    amount,
+                )
+            };
+
+            match result {
+                Ok(burned) => Self::deposit_event(Event::<T>::TradingFeesBurned {
+                    asset_id,
+                    buy_back_asset_id,
+                    amount,
+                    burned,
+                }),
+                Err(error) => Self::deposit_event(Event::<T>::TradingFeesBurnFailed {
+                    asset_id,
+                    buy_back_asset_id,
+                    amount,
+                    error,
+                }),
+            }
+        }
+    }
+
+    /// Removes trading volumes of the users who have not traded for the last `TRADING_VOLUME_DAYS`
+    /// while the weight allows.
+    /// Volumes of active users are pruned on each trade, this clears the volumes of inactive ones.
+    pub fn service_trading_volumes_pruning(weight: &mut WeightMeter) {
+        if !weight.can_accrue(<T as Config>::WeightInfo::prune_single_trading_volume()) {
+            return;
+        }
+
+        let first_day = Self::current_day().saturating_sub(TRADING_VOLUME_DAYS - 1);
+
+        let mut last_checked = <TradingVolumesPruningCursor<T>>::take();
+        let volumes = match &last_checked {
+            Some((account, dex_id)) => {
+                <TradingVolumes<T>>::iter_from(<TradingVolumes<T>>::hashed_key_for(account, dex_id))
+            }
+            None => <TradingVolumes<T>>::iter(),
+        };
+
+        for (account, dex_id, days) in volumes {
+            if !weight.check_accrue(<T as Config>::WeightInfo::prune_single_trading_volume()) {
+                // Will later continue from the last checked volumes
+                if let Some(cursor) = last_checked {
+                    <TradingVolumesPruningCursor<T>>::put(cursor);
+                }
+                return;
+            }
+
+            if days.last().map_or(true, |(day, _)| *day < first_day) {
+                <TradingVolumes<T>>::remove(&account, &dex_id);
+            }
+            last_checked = Some((account, dex_id));
+        }
+    }
 }
 
 #[cfg(feature = "private-net")]
@@ -1253,21 +2088,29 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
         let order_book = <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
         let mut data = CacheDataLayer::<T>::new();
 
-        let deal_info =
-            order_book.calculate_deal(input_asset_id, output_asset_id, amount, &mut data)?;
+        let direction = order_book.get_direction(input_asset_id, output_asset_id)?;
+        let taker_fee_bps = <OrderBookTradingFees<T>>::get(order_book_id).taker_fee_bps;
+
+        let deal_info = order_book.calculate_deal(
+            input_asset_id,
+            output_asset_id,
+            Self::amount_without_taker_fee(direction, amount, taker_fee_bps),
+            &mut data,
+        )?;
 
         ensure!(deal_info.is_valid(), Error::<T>::PriceCalculationFailed);
 
-        // order-book doesn't take fee
-        let fee = Balance::zero();
+        // the quote considers the base taker fee without the fee tier discount
+        let fee = Self::trading_fee(deal_info.quote_amount(), taker_fee_bps);
+        let (input_amount, output_amount) = Self::deal_amounts_with_taker_fee(&deal_info, fee)?;
 
         match amount {
             QuoteAmount::WithDesiredInput { .. } => Ok((
-                SwapOutcome::new(*deal_info.output_amount.value().balance(), fee),
+                SwapOutcome::new(*output_amount.balance(), *fee.balance()),
                 Self::quote_weight(),
             )),
             QuoteAmount::WithDesiredOutput { .. } => Ok((
-                SwapOutcome::new(*deal_info.input_amount.value().balance(), fee),
+                SwapOutcome::new(*input_amount.balance(), *fee.balance()),
                 Self::quote_weight(),
             )),
         }
@@ -1288,25 +2131,34 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
         let order_book = <OrderBooks<T>>::get(order_book_id).ok_or(Error::<T>::UnknownOrderBook)?;
         let mut data = CacheDataLayer::<T>::new();
 
+        let direction = order_book.get_direction(input_asset_id, output_asset_id)?;
+        let trading_fees = <OrderBookTradingFees<T>>::get(order_book_id);
+        let taker_fee_bps =
+            Self::trading_fee_rate(sender, dex_id, MarketRole::Taker, &trading_fees);
+
         let deal_info = order_book.calculate_deal(
             input_asset_id,
             output_asset_id,
-            desired_amount.into(),
+            Self::amount_without_taker_fee(direction, desired_amount.into(), taker_fee_bps),
             &mut data,
         )?;
 
         ensure!(deal_info.is_valid(), Error::<T>::PriceCalculationFailed);
 
+        let expected_fee = Self::trading_fee(deal_info.quote_amount(), taker_fee_bps);
+        let (expected_input, expected_output) =
+            Self::deal_amounts_with_taker_fee(&deal_info, expected_fee)?;
+
         match desired_amount {
             SwapAmount::WithDesiredInput { min_amount_out, .. } => {
                 ensure!(
-                    *deal_info.output_amount.value().balance() >= min_amount_out,
+                    *expected_output.balance() >= min_amount_out,
                     Error::<T>::SlippageLimitExceeded
                 );
             }
             SwapAmount::WithDesiredOutput { max_amount_in, .. } => {
                 ensure!(
-                    *deal_info.input_amount.value().balance() <= max_amount_in,
+                    *expected_input.balance() <= max_amount_in,
                     Error::<T>::SlippageLimitExceeded
                 );
             }
@@ -1324,11 +2176,9 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
         let market_order =
             MarketOrder::<T>::new(sender.clone(), direction, order_book_id, amount, to);
 
-        let (input_amount, output_amount, executed_orders_count) =
-            order_book.execute_market_order(market_order, &mut data)?;
-
-        // order-book doesn't take fee
-        let fee = Balance::zero();
+        let (input_amount, output_amount, executed_orders_count, fee) =
+            order_book.execute_market_order_with_fee(market_order, &mut data)?;
+        let fee = *fee.balance();
 
         let result = match desired_amount {
             SwapAmount::WithDesiredInput { min_amount_out, .. } => {
@@ -1349,7 +2199,10 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
 
         data.commit();
 
-        let weight = <T as Config>::WeightInfo::exchange(executed_orders_count as u32);
+        let weight = <T as Config>::WeightInfo::exchange(executed_orders_count as u32)
+            .saturating_add(Pallet::<T>::record_trades_weight(
+                executed_orders_count as u32,
+            ));
 
         Ok((result, weight))
     }
@@ -1387,7 +2240,11 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
             return Err(Error::<T>::NotEnoughLiquidityInOrderBook.into());
         };
 
-        let target_amount = match amount {
+        // the quote considers the base taker fee without the fee tier discount
+        let taker_fee_bps = <OrderBookTradingFees<T>>::get(order_book_id).taker_fee_bps;
+        let deal_amount = Self::amount_without_taker_fee(direction, amount, taker_fee_bps);
+
+        let target_amount = match deal_amount {
             QuoteAmount::WithDesiredInput { desired_amount_in } => match direction {
                 // User wants to swap a known amount of the `quote` asset for the `base` asset.
                 // Necessary to return `base` amount.
@@ -1443,8 +2300,25 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
             Error::<T>::InvalidOrderAmount
         );
 
-        // order-book doesn't take fee
-        let fee = Balance::zero();
+        // the fee is taken in the quote asset
+        let (target_amount, fee) = match (direction, amount) {
+            (PriceVariant::Buy, QuoteAmount::WithDesiredInput { desired_amount_in }) => (
+                target_amount,
+                desired_amount_in.saturating_sub(deal_amount.amount()),
+            ),
+            (PriceVariant::Buy, QuoteAmount::WithDesiredOutput { .. }) => {
+                let fee = Self::trading_fee(target_amount, taker_fee_bps);
+                (target_amount.saturating_add(fee), *fee.balance())
+            }
+            (PriceVariant::Sell, QuoteAmount::WithDesiredInput { .. }) => {
+                let fee = Self::trading_fee(target_amount, taker_fee_bps);
+                (target_amount.saturating_sub(fee), *fee.balance())
+            }
+            (PriceVariant::Sell, QuoteAmount::WithDesiredOutput { desired_amount_out }) => (
+                target_amount,
+                deal_amount.amount().saturating_sub(desired_amount_out),
+            ),
+        };
 
         Ok(SwapOutcome::new(*target_amount.balance(), fee))
     }
@@ -1457,6 +2331,9 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
         // SOFT_MIN_MAX_RATIO is approximately the max number of limit orders
         // that can be executed by one market order
         <T as Config>::WeightInfo::exchange(T::SOFT_MIN_MAX_RATIO.try_into().unwrap())
+            .saturating_add(Pallet::<T>::record_trades_weight(
+                T::SOFT_MIN_MAX_RATIO as u32,
+            ))
     }
 
     fn check_rewards_weight() -> Weight {
@@ -29,9 +29,12 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::{
-    CancelReason, DataLayer, DealInfo, Delegate, Error, ExpirationScheduler, LimitOrder,
-    MarketChange, MarketOrder, MarketRole, OrderAmount, OrderBookEvent, OrderBookId,
-    OrderBookStatus, OrderBookTechStatus, OrderPrice, OrderVolume, Payment,
+    CancelReason, ConditionalOrder, CurrencyLocker, CurrencyUnlocker, DataLayer, DealFees,
+    DealInfo, Delegate, Error, ExpirationScheduler, FeeDestination, FeesToBurn, LastTradePrice,
+    LimitOrder, MakerFeeDebts, MarketChange, MarketOrder, MarketRole, OrderAmount, OrderBookEvent,
+    OrderBookId, OrderBookStatus, OrderBookTechStatus, OrderBookTradingFees, OrderPrice,
+    OrderVolume, Pallet, Payment, TimeInForce, TradingFeeDestination, TradingFees,
+    TriggerPriceSource,
 };
 use assets::AssetIdOf;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -40,7 +43,7 @@ use common::{Balance, PriceVariant};
 use core::fmt::Debug;
 use frame_support::ensure;
 use frame_support::sp_runtime::DispatchError;
-use frame_support::traits::Get;
+use frame_support::traits::{Get, Time};
 use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero};
 use sp_std::cmp::Ordering;
 use sp_std::collections::btree_map::BTreeMap;
@@ -87,12 +90,32 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         self.last_order_id
     }
 
-    /// Tries to place the limit order and returns the count of executed limit orders (if the limit order crosses the spread and was converted into market order).
+    /// Tries to place the GTC limit order and returns the count of executed limit orders (if the limit order crosses the spread and was converted into market order).
     pub fn place_limit_order(
         &self,
         limit_order: LimitOrder<T>,
         data: &mut impl DataLayer<T>,
     ) -> Result<usize, DispatchError> {
+        self.place_limit_order_with_time_in_force(limit_order, TimeInForce::GoodTillCancelled, data)
+    }
+
+    /// Tries to place the limit order according to `time_in_force` and returns the count of executed limit orders.
+    pub fn place_limit_order_with_time_in_force(
+        &self,
+        limit_order: LimitOrder<T>,
+        time_in_force: TimeInForce,
+        data: &mut impl DataLayer<T>,
+    ) -> Result<usize, DispatchError> {
+        match time_in_force {
+            TimeInForce::GoodTillCancelled | TimeInForce::PostOnly => {}
+            TimeInForce::ImmediateOrCancel => {
+                return self.execute_limit_order_immediately(limit_order, false, data);
+            }
+            TimeInForce::FillOrKill => {
+                return self.execute_limit_order_immediately(limit_order, true, data);
+            }
+        }
+
         ensure!(
             self.status == OrderBookStatus::Trade || self.status == OrderBookStatus::PlaceAndCancel,
             Error::<T>::PlacementOfLimitOrdersIsForbidden
@@ -118,6 +141,11 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             }
         };
 
+        ensure!(
+            !(cross_spread && time_in_force == TimeInForce::PostOnly),
+            Error::<T>::PostOnlyLimitOrderCrossesSpread
+        );
+
         let order_id = limit_order.id;
         let owner_id = limit_order.owner.clone();
 
@@ -194,6 +222,80 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         Ok(executed_orders_count)
     }
 
+    /// Executes the limit order against the opposite side of the market up to the limit price.
+    /// The rest of the amount is dropped, or, if `fill_or_kill` is true, the execution fails if the limit order cannot be filled completely.
+    ///
+    /// Returns the count of executed limit orders.
+    fn execute_limit_order_immediately(
+        &self,
+        limit_order: LimitOrder<T>,
+        fill_or_kill: bool,
+        data: &mut impl DataLayer<T>,
+    ) -> Result<usize, DispatchError> {
+        ensure!(
+            self.status == OrderBookStatus::Trade,
+            Error::<T>::TradingIsForbidden
+        );
+
+        self.ensure_limit_order_valid(&limit_order)?;
+
+        let (market_amount, unfilled_amount) = match limit_order.side {
+            PriceVariant::Buy => Self::calculate_market_depth_to_price(
+                limit_order.side.switched(),
+                limit_order.price,
+                limit_order.amount,
+                data.get_aggregated_asks(&self.order_book_id).iter(),
+            ),
+            PriceVariant::Sell => Self::calculate_market_depth_to_price(
+                limit_order.side.switched(),
+                limit_order.price,
+                limit_order.amount,
+                data.get_aggregated_bids(&self.order_book_id).iter().rev(),
+            ),
+        };
+
+        if fill_or_kill {
+            ensure!(
+                unfilled_amount.is_zero(),
+                Error::<T>::FillOrKillLimitOrderCannotBeFilled
+            );
+        }
+
+        if market_amount.is_zero() {
+            return Ok(0);
+        }
+
+        let market_order = MarketOrder::<T>::new(
+            limit_order.owner.clone(),
+            limit_order.side,
+            self.order_book_id,
+            market_amount,
+            None,
+        );
+        let market_change = self.calculate_market_order_impact(market_order, data)?;
+
+        let (Some(deal_amount), Some(average_price)) = (market_change.deal_base_amount(), market_change.average_deal_price()) else {
+            // should never happen
+            return Err(Error::<T>::PriceCalculationFailed.into());
+        };
+
+        let executed_orders_count = market_change.count_of_executed_orders();
+
+        self.apply_market_change(market_change, data)?;
+
+        T::Delegate::emit_event(
+            self.order_book_id,
+            OrderBookEvent::LimitOrderConvertedToMarketOrder {
+                owner_id: limit_order.owner,
+                direction: limit_order.side,
+                amount: OrderAmount::Base(deal_amount),
+                average_price,
+            },
+        );
+
+        Ok(executed_orders_count)
+    }
+
     pub fn cancel_limit_order(
         &self,
         limit_order: LimitOrder<T>,
@@ -231,12 +333,176 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         self.cancel_limit_order_unchecked(limit_order, CancelReason::Expired, data, true)
     }
 
+    /// Validates the conditional order and locks its liquidity until the order is triggered or cancelled.
+    pub fn place_conditional_order(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            self.status == OrderBookStatus::Trade || self.status == OrderBookStatus::PlaceAndCancel,
+            Error::<T>::PlacementOfLimitOrdersIsForbidden
+        );
+
+        self.ensure_conditional_order_valid(conditional_order)?;
+
+        let (asset_id, amount) = self.conditional_order_locked_liquidity(conditional_order)?;
+        T::Locker::lock_liquidity(
+            &conditional_order.owner,
+            self.order_book_id,
+            asset_id,
+            amount,
+        )
+    }
+
+    pub fn cancel_conditional_order(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            self.status == OrderBookStatus::Trade
+                || self.status == OrderBookStatus::PlaceAndCancel
+                || self.status == OrderBookStatus::OnlyCancel,
+            Error::<T>::CancellationOfLimitOrdersIsForbidden
+        );
+
+        self.unlock_conditional_order(conditional_order)
+    }
+
+    /// Returns the liquidity locked by the conditional order to its owner.
+    pub fn unlock_conditional_order(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+    ) -> Result<(), DispatchError> {
+        let (asset_id, amount) = self.conditional_order_locked_liquidity(conditional_order)?;
+        T::Unlocker::unlock_liquidity(
+            &conditional_order.owner,
+            self.order_book_id,
+            asset_id,
+            amount,
+        )
+    }
+
+    /// Converts the triggered conditional order into a limit or market order
+    /// and returns the count of executed limit orders.
+    ///
+    /// The locked liquidity is returned to the owner and then it is used by the new order.
+    pub fn trigger_conditional_order(
+        &self,
+        conditional_order: ConditionalOrder<T>,
+        data: &mut impl DataLayer<T>,
+    ) -> Result<usize, DispatchError> {
+        self.unlock_conditional_order(&conditional_order)?;
+
+        match conditional_order.limit_price {
+            Some(price) => {
+                let limit_order = LimitOrder::<T>::new(
+                    conditional_order.id,
+                    conditional_order.owner,
+                    conditional_order.side,
+                    price,
+                    conditional_order.amount,
+                    T::Time::now(),
+                    T::MAX_ORDER_LIFESPAN,
+                    frame_system::Pallet::<T>::block_number(),
+                );
+                self.place_limit_order(limit_order, data)
+            }
+            None => {
+                let market_order = MarketOrder::<T>::new(
+                    conditional_order.owner,
+                    conditional_order.side,
+                    self.order_book_id,
+                    conditional_order.amount,
+                    None,
+                );
+                let (_, _, executed_orders_count) =
+                    self.execute_market_order(market_order, data)?;
+                Ok(executed_orders_count)
+            }
+        }
+    }
+
+    /// Returns the count of limit orders that would be executed if the conditional order was triggered now.
+    /// Nothing is changed in `data`, it is only used to read the market.
+    pub fn calculate_conditional_order_executed_orders_count(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+        data: &mut impl DataLayer<T>,
+    ) -> Result<usize, DispatchError> {
+        let market_change = match conditional_order.limit_price {
+            Some(price) => {
+                let cross_spread = match conditional_order.side {
+                    PriceVariant::Buy => self
+                        .best_ask(data)
+                        .map_or(false, |(best_ask_price, _)| price >= best_ask_price),
+                    PriceVariant::Sell => self
+                        .best_bid(data)
+                        .map_or(false, |(best_bid_price, _)| price <= best_bid_price),
+                };
+                if !cross_spread {
+                    return Ok(0);
+                }
+                let limit_order = LimitOrder::<T>::new(
+                    conditional_order.id,
+                    conditional_order.owner.clone(),
+                    conditional_order.side,
+                    price,
+                    conditional_order.amount,
+                    T::Time::now(),
+                    T::MAX_ORDER_LIFESPAN,
+                    frame_system::Pallet::<T>::block_number(),
+                );
+                self.cross_spread(limit_order, data)?
+            }
+            None => {
+                let market_order = MarketOrder::<T>::new(
+                    conditional_order.owner.clone(),
+                    conditional_order.side,
+                    self.order_book_id,
+                    conditional_order.amount,
+                    None,
+                );
+                self.calculate_market_order_impact(market_order, data)?
+            }
+        };
+        Ok(market_change.count_of_executed_orders())
+    }
+
+    /// Returns the price that is compared with the trigger price of conditional orders.
+    pub fn trigger_price(
+        &self,
+        source: TriggerPriceSource,
+        side: PriceVariant,
+        data: &mut impl DataLayer<T>,
+    ) -> Option<OrderPrice> {
+        match source {
+            TriggerPriceSource::LastTrade => <LastTradePrice<T>>::get(self.order_book_id),
+            TriggerPriceSource::BestBidAsk => match side {
+                PriceVariant::Buy => self.best_ask(data).map(|(price, _)| price),
+                PriceVariant::Sell => self.best_bid(data).map(|(price, _)| price),
+            },
+        }
+    }
+
     /// Executes market order and returns input & output amounts & count of executed limit orders
     pub fn execute_market_order(
         &self,
         market_order: MarketOrder<T>,
         data: &mut impl DataLayer<T>,
     ) -> Result<(OrderAmount, OrderAmount, usize), DispatchError> {
+        let (input, output, executed_orders_count, _) =
+            self.execute_market_order_with_fee(market_order, data)?;
+        Ok((input, output, executed_orders_count))
+    }
+
+    /// Executes market order and returns input & output amounts, count of executed limit orders
+    /// & the taker fee in the quote asset.
+    /// Input & output amounts consider the taker fee.
+    pub fn execute_market_order_with_fee(
+        &self,
+        market_order: MarketOrder<T>,
+        data: &mut impl DataLayer<T>,
+    ) -> Result<(OrderAmount, OrderAmount, usize, OrderVolume), DispatchError> {
         ensure!(
             self.status == OrderBookStatus::Trade,
             Error::<T>::TradingIsForbidden
@@ -258,6 +524,28 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         };
 
         let executed_orders_count = market_change.count_of_executed_orders();
+        let taker_fee = market_change.fees.taker_fee;
+
+        let (input, output) = match market_order.direction {
+            PriceVariant::Buy => (
+                OrderAmount::Quote(
+                    input
+                        .value()
+                        .checked_add(&taker_fee)
+                        .ok_or(Error::<T>::AmountCalculationFailed)?,
+                ),
+                output,
+            ),
+            PriceVariant::Sell => (
+                input,
+                OrderAmount::Quote(
+                    output
+                        .value()
+                        .checked_sub(&taker_fee)
+                        .ok_or(Error::<T>::AmountCalculationFailed)?,
+                ),
+            ),
+        };
 
         self.apply_market_change(market_change, data)?;
 
@@ -272,7 +560,7 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             },
         );
 
-        Ok((input, output, executed_orders_count))
+        Ok((input, output, executed_orders_count, taker_fee))
     }
 
     pub fn align_limit_orders(
@@ -350,6 +638,7 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             to_force_update: BTreeMap::new(),
             payment,
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         })
     }
 
@@ -390,6 +679,7 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             to_force_update: BTreeMap::new(),
             payment,
             ignore_unschedule_error,
+            fees: DealFees::default(),
         })
     }
 
@@ -433,6 +723,7 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             to_force_update: BTreeMap::new(),
             payment,
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         })
     }
 
@@ -455,6 +746,8 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         let mut limit_orders_to_part_execute = BTreeMap::new();
         let mut limit_orders_to_full_execute = BTreeMap::new();
         let mut payment = Payment::new(self.order_book_id);
+        let mut fees = DealFees::default();
+        let trading_fees = <OrderBookTradingFees<T>>::get(self.order_book_id);
 
         let (maker_out_asset, taker_out_asset) = match direction {
             PriceVariant::Buy => (self.order_book_id.quote, self.order_book_id.base),
@@ -473,13 +766,23 @@ impl<T: crate::Config + Sized> OrderBook<T> {
                     remaining_amount = remaining_amount
                         .checked_sub(&limit_order.amount)
                         .ok_or(Error::<T>::AmountCalculationFailed)?;
+                    let taker_payment = *limit_order.deal_amount(MarketRole::Taker, None)?.value();
                     taker_amount = taker_amount
-                        .checked_add(limit_order.deal_amount(MarketRole::Taker, None)?.value())
+                        .checked_add(&taker_payment)
                         .ok_or(Error::<T>::AmountCalculationFailed)?;
                     let maker_payment = *limit_order.deal_amount(MarketRole::Maker, None)?.value();
                     maker_amount = maker_amount
                         .checked_add(&maker_payment)
                         .ok_or(Error::<T>::AmountCalculationFailed)?;
+                    let maker_payment = self.charge_maker_fee(
+                        &limit_order.owner,
+                        direction,
+                        maker_payment,
+                        taker_payment,
+                        &trading_fees,
+                        &mut payment,
+                        &mut fees,
+                    )?;
                     payment
                         .to_unlock
                         .entry(maker_out_asset)
@@ -493,12 +796,11 @@ impl<T: crate::Config + Sized> OrderBook<T> {
                         break;
                     }
                 } else {
+                    let taker_payment = *limit_order
+                        .deal_amount(MarketRole::Taker, Some(remaining_amount))?
+                        .value();
                     taker_amount = taker_amount
-                        .checked_add(
-                            limit_order
-                                .deal_amount(MarketRole::Taker, Some(remaining_amount))?
-                                .value(),
-                        )
+                        .checked_add(&taker_payment)
                         .ok_or(Error::<T>::AmountCalculationFailed)?;
                     let maker_payment = *limit_order
                         .deal_amount(MarketRole::Maker, Some(remaining_amount))?
@@ -506,6 +808,15 @@ impl<T: crate::Config + Sized> OrderBook<T> {
                     maker_amount = maker_amount
                         .checked_add(&maker_payment)
                         .ok_or(Error::<T>::AmountCalculationFailed)?;
+                    let maker_payment = self.charge_maker_fee(
+                        &limit_order.owner,
+                        direction,
+                        maker_payment,
+                        taker_payment,
+                        &trading_fees,
+                        &mut payment,
+                        &mut fees,
+                    )?;
                     payment
                         .to_unlock
                         .entry(maker_out_asset)
@@ -536,21 +847,32 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             Error::<T>::NotEnoughLiquidityInOrderBook
         );
 
+        let (taker_lock_amount, taker_unlock_amount) = self.charge_taker_fee(
+            &taker,
+            direction,
+            maker_amount,
+            taker_amount,
+            &trading_fees,
+            &mut fees,
+        )?;
+
         payment
             .to_lock
             .entry(maker_out_asset)
             .or_default()
             .entry(taker)
-            .and_modify(|lock_amount| *lock_amount = lock_amount.saturating_add(maker_amount))
-            .or_insert(maker_amount);
+            .and_modify(|lock_amount| *lock_amount = lock_amount.saturating_add(taker_lock_amount))
+            .or_insert(taker_lock_amount);
 
         payment
             .to_unlock
             .entry(taker_out_asset)
             .or_default()
             .entry(receiver)
-            .and_modify(|unlock_amount| *unlock_amount = unlock_amount.saturating_add(taker_amount))
-            .or_insert(taker_amount);
+            .and_modify(|unlock_amount| {
+                *unlock_amount = unlock_amount.saturating_add(taker_unlock_amount)
+            })
+            .or_insert(taker_unlock_amount);
 
         let (deal_input, deal_output) = match direction {
             PriceVariant::Buy => (
@@ -575,9 +897,145 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             to_force_update: BTreeMap::new(),
             payment,
             ignore_unschedule_error: false,
+            fees,
         })
     }
 
+    /// Charges the maker fee in the quote asset for the executed part of the limit order.
+    /// Returns the payment to the maker after the fee.
+    ///
+    /// The fee is charged together with the unpaid fee debt of the maker.
+    /// If the maker sells the base asset, the fee is taken from the quote asset the maker receives.
+    /// If the maker buys the base asset, the fee is taken from the free balance of the quote asset.
+    /// The part of the fee that cannot be paid is recorded as the maker fee debt
+    /// in order not to block the market, the debt is charged on the next deals of the maker.
+    fn charge_maker_fee(
+        &self,
+        maker: &T::AccountId,
+        direction: PriceVariant,
+        maker_payment: OrderVolume,
+        taker_payment: OrderVolume,
+        trading_fees: &TradingFees,
+        payment: &mut Payment<T::AssetId, T::AccountId, T::DEXId>,
+        fees: &mut DealFees<T::AccountId>,
+    ) -> Result<OrderVolume, DispatchError> {
+        if trading_fees.is_zero() {
+            return Ok(maker_payment);
+        }
+
+        let quote_volume = match direction {
+            PriceVariant::Buy => maker_payment,
+            PriceVariant::Sell => taker_payment,
+        };
+        fees.add_volume(maker, quote_volume);
+
+        let rate = Pallet::<T>::trading_fee_rate(
+            maker,
+            &self.order_book_id.dex_id,
+            MarketRole::Maker,
+            trading_fees,
+        );
+        let fee = Pallet::<T>::trading_fee(quote_volume, rate);
+
+        let quote = self.order_book_id.quote;
+        let debt = match fees.maker_fee_debts.get(maker) {
+            Some(debt) => *debt,
+            None => fee.copy_divisibility(<MakerFeeDebts<T>>::get(maker, quote)),
+        };
+        let due = fee.saturating_add(debt);
+
+        let (charged, maker_payment) = match direction {
+            PriceVariant::Buy => {
+                let charged = due.min(maker_payment);
+                let maker_payment = maker_payment
+                    .checked_sub(&charged)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?;
+                (charged, maker_payment)
+            }
+            PriceVariant::Sell => {
+                let already_locked = payment
+                    .to_lock
+                    .get(&quote)
+                    .and_then(|accounts| accounts.get(maker))
+                    .copied()
+                    .unwrap_or_default();
+                let free_balance =
+                    fee.copy_divisibility(T::AssetInfoProvider::free_balance(&quote, maker)?);
+                let charged = due.min(free_balance.saturating_sub(already_locked));
+
+                if !charged.is_zero() {
+                    payment
+                        .to_lock
+                        .entry(quote)
+                        .or_default()
+                        .entry(maker.clone())
+                        .and_modify(|lock_amount| {
+                            *lock_amount = lock_amount.saturating_add(charged)
+                        })
+                        .or_insert(charged);
+                }
+                (charged, maker_payment)
+            }
+        };
+
+        fees.total_fee = fees.total_fee.saturating_add(charged);
+        let new_debt = due
+            .checked_sub(&charged)
+            .ok_or(Error::<T>::AmountCalculationFailed)?;
+        if new_debt != debt {
+            fees.maker_fee_debts.insert(maker.clone(), new_debt);
+        }
+
+        Ok(maker_payment)
+    }
+
+    /// Charges the taker fee in the quote asset.
+    /// Returns the amounts to lock from the taker and to unlock to the receiver considering the fee.
+    fn charge_taker_fee(
+        &self,
+        taker: &T::AccountId,
+        direction: PriceVariant,
+        maker_amount: OrderVolume,
+        taker_amount: OrderVolume,
+        trading_fees: &TradingFees,
+        fees: &mut DealFees<T::AccountId>,
+    ) -> Result<(OrderVolume, OrderVolume), DispatchError> {
+        if trading_fees.is_zero() {
+            return Ok((maker_amount, taker_amount));
+        }
+
+        let quote_volume = match direction {
+            PriceVariant::Buy => maker_amount,
+            PriceVariant::Sell => taker_amount,
+        };
+        fees.add_volume(taker, quote_volume);
+
+        let rate = Pallet::<T>::trading_fee_rate(
+            taker,
+            &self.order_book_id.dex_id,
+            MarketRole::Taker,
+            trading_fees,
+        );
+        let fee = Pallet::<T>::trading_fee(quote_volume, rate);
+        fees.taker_fee = fees.taker_fee.saturating_add(fee);
+        fees.total_fee = fees.total_fee.saturating_add(fee);
+
+        match direction {
+            PriceVariant::Buy => Ok((
+                maker_amount
+                    .checked_add(&fee)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?,
+                taker_amount,
+            )),
+            PriceVariant::Sell => Ok((
+                maker_amount,
+                taker_amount
+                    .checked_sub(&fee)
+                    .ok_or(Error::<T>::AmountCalculationFailed)?,
+            )),
+        }
+    }
+
     pub fn calculate_align_limit_orders_impact(
         &self,
         limit_orders: Vec<LimitOrder<T>>,
@@ -632,6 +1090,7 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             to_force_update: limit_orders_to_force_update,
             payment,
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         })
     }
 
@@ -794,6 +1253,103 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         ))
     }
 
+    /// Transfers the trading fees collected during the deal to the fee destination,
+    /// records the trading volumes of the participants and the changed maker fee debts.
+    fn collect_trading_fees(&self, fees: &DealFees<T::AccountId>) -> Result<(), DispatchError> {
+        if !fees.total_fee.is_zero() {
+            let quote = self.order_book_id.quote;
+            match <FeeDestination<T>>::get() {
+                TradingFeeDestination::Account(account) => {
+                    T::Unlocker::unlock_liquidity(
+                        &account,
+                        self.order_book_id,
+                        &quote,
+                        fees.total_fee,
+                    )?;
+                }
+                TradingFeeDestination::BuyBackAndBurn(buy_back_asset) => {
+                    T::Unlocker::unlock_liquidity(
+                        &T::TradingFeeCollector::get(),
+                        self.order_book_id,
+                        &quote,
+                        fees.total_fee,
+                    )?;
+                    <FeesToBurn<T>>::mutate((quote, buy_back_asset), |amount| {
+                        *amount = amount.saturating_add(*fees.total_fee.balance())
+                    });
+                }
+            }
+
+            T::Delegate::emit_event(
+                self.order_book_id,
+                OrderBookEvent::TradingFeesCollected {
+                    amount: fees.total_fee,
+                },
+            );
+        }
+
+        for (account, volume) in fees.volumes.iter() {
+            Pallet::<T>::record_trading_volume(account, &self.order_book_id.dex_id, volume);
+        }
+
+        for (account, debt) in fees.maker_fee_debts.iter() {
+            if debt.is_zero() {
+                <MakerFeeDebts<T>>::remove(account, self.order_book_id.quote);
+            } else {
+                <MakerFeeDebts<T>>::insert(account, self.order_book_id.quote, *debt.balance());
+            }
+
+            T::Delegate::emit_event(
+                self.order_book_id,
+                OrderBookEvent::MakerFeeDebtUpdated {
+                    owner_id: account.clone(),
+                    debt: *debt,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Records trades with each executed maker from the best price to the worst one
+    fn record_trades(
+        &self,
+        market_change: &MarketChange<T::AccountId, T::AssetId, T::DEXId, T::OrderId, LimitOrder<T>>,
+        is_buy: bool,
+    ) {
+        let direction = if is_buy {
+            PriceVariant::Buy
+        } else {
+            PriceVariant::Sell
+        };
+
+        let mut trades: Vec<(OrderPrice, OrderVolume)> = market_change
+            .to_full_execute
+            .values()
+            .map(|limit_order| (limit_order.price, limit_order.amount))
+            .chain(
+                market_change
+                    .to_part_execute
+                    .values()
+                    .filter_map(|(limit_order, executed)| match executed {
+                        OrderAmount::Base(amount) => Some((limit_order.price, *amount)),
+                        OrderAmount::Quote(amount) => amount
+                            .checked_div(&limit_order.price)
+                            .map(|amount| (limit_order.price, amount)),
+                    }),
+            )
+            .collect();
+
+        match direction {
+            PriceVariant::Buy => trades.sort_by(|(a, _), (b, _)| a.cmp(b)),
+            PriceVariant::Sell => trades.sort_by(|(a, _), (b, _)| b.cmp(a)),
+        }
+
+        for (price, amount) in trades {
+            Pallet::<T>::record_trade(&self.order_book_id, direction, price, amount);
+        }
+    }
+
     pub fn apply_market_change(
         &self,
         market_change: MarketChange<T::AccountId, T::AssetId, T::DEXId, T::OrderId, LimitOrder<T>>,
@@ -803,6 +1359,32 @@ impl<T: crate::Config + Sized> OrderBook<T> {
             .payment
             .execute_all::<T::Locker, T::Unlocker>()?;
 
+        self.collect_trading_fees(&market_change.fees)?;
+
+        // the deal goes from the best price to the worst one, so the worst executed price is the last trade price
+        if let Some(deal_input) = market_change.deal_input {
+            let executed_prices = market_change
+                .to_full_execute
+                .values()
+                .map(|limit_order| limit_order.price)
+                .chain(
+                    market_change
+                        .to_part_execute
+                        .values()
+                        .map(|(limit_order, _)| limit_order.price),
+                );
+            let last_trade_price = if deal_input.is_quote() {
+                executed_prices.max()
+            } else {
+                executed_prices.min()
+            };
+            if let Some(price) = last_trade_price {
+                <LastTradePrice<T>>::insert(self.order_book_id, price);
+            }
+
+            self.record_trades(&market_change, deal_input.is_quote());
+        }
+
         for (limit_order, reason) in market_change.to_cancel.into_values() {
             data.delete_limit_order(&self.order_book_id, limit_order.id)?;
             let unschedule_result = T::Scheduler::unschedule_expiration(
@@ -978,6 +1560,44 @@ impl<T: crate::Config + Sized> OrderBook<T> {
         Ok(())
     }
 
+    fn ensure_conditional_order_valid(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+    ) -> Result<(), DispatchError> {
+        conditional_order.ensure_valid()?;
+        ensure!(
+            conditional_order.trigger_price.balance() % self.tick_size.balance() == 0,
+            Error::<T>::InvalidTriggerPrice
+        );
+        if let Some(limit_price) = conditional_order.limit_price {
+            ensure!(
+                limit_price.balance() % self.tick_size.balance() == 0,
+                Error::<T>::InvalidLimitOrderPrice
+            );
+        }
+        ensure!(
+            self.min_lot_size <= conditional_order.amount
+                && conditional_order.amount <= self.max_lot_size,
+            Error::<T>::InvalidOrderAmount
+        );
+        ensure!(
+            conditional_order.amount.balance() % self.step_lot_size.balance() == 0,
+            Error::<T>::InvalidOrderAmount
+        );
+        Ok(())
+    }
+
+    fn conditional_order_locked_liquidity(
+        &self,
+        conditional_order: &ConditionalOrder<T>,
+    ) -> Result<(&T::AssetId, OrderVolume), DispatchError> {
+        let locked = match conditional_order.locked_amount()? {
+            OrderAmount::Base(amount) => (&self.order_book_id.base, amount),
+            OrderAmount::Quote(amount) => (&self.order_book_id.quote, amount),
+        };
+        Ok(locked)
+    }
+
     fn ensure_market_order_valid(
         &self,
         market_order: &MarketOrder<T>,
@@ -34,15 +34,16 @@
 // Copyright (C) Parity Technologies (UK) Ltd.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::traits::{AlignmentScheduler, ExpirationScheduler};
+use crate::traits::{AlignmentScheduler, ExpirationScheduler, TriggerScheduler};
 use crate::weights::WeightInfo;
 use crate::{
-    AlignmentCursor, CacheDataLayer, Config, DataLayer, Error, Event, ExpirationsAgenda,
-    IncompleteExpirationsSince, LimitOrder, LimitOrders, OrderBookId, OrderBookTechStatus,
-    OrderBooks, Pallet,
+    AlignmentCursor, CacheDataLayer, ConditionalOrder, ConditionalOrders, Config, DataLayer, Error,
+    Event, ExpirationsAgenda, IncompleteExpirationsSince, LimitOrder, LimitOrders, OrderBook,
+    OrderBookId, OrderBookStatus, OrderBookTechStatus, OrderBooks, Pallet, TriggersCursor,
 };
 use assets::AssetIdOf;
 use common::weights::check_accrue_n;
+use frame_support::storage::{with_transaction, TransactionOutcome};
 use frame_support::weights::WeightMeter;
 use sp_runtime::traits::{One, Zero};
 use sp_runtime::{DispatchError, Saturating};
@@ -149,6 +150,123 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Checks the trigger of the conditional order and converts it into a limit or market order if the trigger price is reached.
+    /// The weight of the executed exchange is accrued to `weight`.
+    /// Returns false if the remaining weight is not enough to execute the triggered order,
+    /// in this case the order is kept and should be serviced again later.
+    pub fn service_single_trigger(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        order: ConditionalOrder<T>,
+        weight: &mut WeightMeter,
+    ) -> bool {
+        let Some(order_book) = <OrderBooks<T>>::get(order_book_id) else {
+            debug_assert!(false, "apparently removal of order book did not cleanup conditional orders; \
+                order {:?} is waiting for the trigger but corresponding order book {:?} is not found", order.id, order_book_id);
+            Self::deposit_event(Event::<T>::ConditionalOrderFailed {
+                order_book_id: *order_book_id,
+                order_id: order.id,
+                owner_id: order.owner,
+                error: Error::<T>::UnknownOrderBook.into(),
+            });
+            return true;
+        };
+
+        // conditional orders wait while trading is forbidden
+        if order_book.status != OrderBookStatus::Trade
+            || order_book.tech_status != OrderBookTechStatus::Ready
+        {
+            return true;
+        }
+
+        let mut data = CacheDataLayer::<T>::new();
+        let Some(price) = order_book.trigger_price(order.price_source, order.side, &mut data) else {
+            return true;
+        };
+
+        if !order.is_triggered(&price) {
+            return true;
+        }
+
+        // the exchange weight depends on the count of executed limit orders,
+        // so it is checked before the execution
+        let expected_executed_orders_count = order_book
+            .calculate_conditional_order_executed_orders_count(&order, &mut data)
+            .unwrap_or_default() as u32;
+        let exchange_weight = <T as Config>::WeightInfo::exchange(expected_executed_orders_count)
+            .saturating_add(Self::record_trades_weight(expected_executed_orders_count));
+        let max_exchange_weight = weight.limit.saturating_sub(
+            <T as Config>::WeightInfo::service_triggers_base()
+                .saturating_add(<T as Config>::WeightInfo::service_single_trigger()),
+        );
+        if exchange_weight.any_gt(max_exchange_weight) {
+            // the order cannot be executed even in an empty block, it would stall the triggers forever
+            Self::remove_conditional_order(order_book_id, &order);
+            Self::fail_conditional_order(
+                &order_book,
+                order,
+                Error::<T>::ConditionalOrderExceedsTriggersWeight.into(),
+            );
+            return true;
+        }
+        if !weight.can_accrue(exchange_weight) {
+            return false;
+        }
+
+        Self::remove_conditional_order(order_book_id, &order);
+
+        let result = with_transaction(|| {
+            Self::deposit_event(Event::<T>::ConditionalOrderTriggered {
+                order_book_id: *order_book_id,
+                order_id: order.id,
+                owner_id: order.owner.clone(),
+                price,
+            });
+
+            match order_book.trigger_conditional_order(order.clone(), &mut data) {
+                Ok(executed_orders_count) => {
+                    data.commit();
+                    TransactionOutcome::Commit(Ok(executed_orders_count))
+                }
+                Err(error) => TransactionOutcome::Rollback(Err(error)),
+            }
+        });
+
+        match result {
+            Ok(executed_orders_count) => {
+                weight.defensive_saturating_accrue(
+                    <T as Config>::WeightInfo::exchange(executed_orders_count as u32)
+                        .saturating_add(Self::record_trades_weight(executed_orders_count as u32)),
+                );
+            }
+            Err(error) => {
+                // the order cannot be executed at the current market
+                Self::fail_conditional_order(&order_book, order, error);
+            }
+        }
+        true
+    }
+
+    /// Returns the liquidity locked by the removed conditional order to the owner.
+    fn fail_conditional_order(
+        order_book: &OrderBook<T>,
+        order: ConditionalOrder<T>,
+        error: DispatchError,
+    ) {
+        if let Err(unlock_error) = order_book.unlock_conditional_order(&order) {
+            debug_assert!(
+                false,
+                "unlock of conditional order {:?} resulted in error: {:?}",
+                order.id, unlock_error
+            );
+        }
+        Self::deposit_event(Event::<T>::ConditionalOrderFailed {
+            order_book_id: order_book.order_book_id,
+            order_id: order.id,
+            owner_id: order.owner,
+            error,
+        });
+    }
+
     pub fn get_limit_orders(
         order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
         maybe_cursor: Option<T::OrderId>,
@@ -308,3 +426,44 @@ impl<T: Config> AlignmentScheduler for Pallet<T> {
         }
     }
 }
+
+impl<T: Config> TriggerScheduler for Pallet<T> {
+    fn service_triggers(weight: &mut WeightMeter) {
+        if !weight.check_accrue(<T as Config>::WeightInfo::service_triggers_base()) {
+            return;
+        }
+
+        // the order is triggered only if the weight is enough to execute it at least against one limit order
+        let single_trigger_weight = <T as Config>::WeightInfo::service_single_trigger()
+            .saturating_add(<T as Config>::WeightInfo::exchange(1))
+            .saturating_add(Self::record_trades_weight(1));
+
+        let mut last_serviced = <TriggersCursor<T>>::take();
+        let orders = match last_serviced {
+            Some((order_book_id, order_id)) => <ConditionalOrders<T>>::iter_from(
+                <ConditionalOrders<T>>::hashed_key_for(order_book_id, order_id),
+            ),
+            None => <ConditionalOrders<T>>::iter(),
+        };
+
+        for (order_book_id, order_id, order) in orders {
+            if !weight.can_accrue(single_trigger_weight) {
+                // Will later continue from the last serviced order
+                if let Some(cursor) = last_serviced {
+                    <TriggersCursor<T>>::put(cursor);
+                }
+                return;
+            }
+            weight.defensive_saturating_accrue(<T as Config>::WeightInfo::service_single_trigger());
+
+            if !Self::service_single_trigger(&order_book_id, order, weight) {
+                // Will later continue from this order
+                if let Some(cursor) = last_serviced {
+                    <TriggersCursor<T>>::put(cursor);
+                }
+                return;
+            }
+            last_serviced = Some((order_book_id, order_id));
+        }
+    }
+}
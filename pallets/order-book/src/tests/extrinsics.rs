@@ -31,18 +31,21 @@
 use crate::test_utils::*;
 use assets::AssetIdOf;
 use common::{
-    balance, AssetId32, AssetName, AssetSymbol, Balance, PriceVariant, DEFAULT_BALANCE_PRECISION,
-    ETH, PSWAP, VAL, XOR, XST, XSTUSD,
+    balance, AssetId32, AssetInfoProvider, AssetName, AssetSymbol, Balance, PriceVariant,
+    DEFAULT_BALANCE_PRECISION, ETH, PSWAP, VAL, XOR, XST, XSTUSD,
 };
 use frame_support::error::BadOrigin;
+use frame_support::traits::Hooks;
+use frame_support::weights::{Weight, WeightMeter};
 use frame_support::{assert_err, assert_ok};
 use frame_system::RawOrigin;
 use framenode_chain_spec::ext;
 use framenode_runtime::order_book::{
-    Config, LimitOrder, LimitOrders, MarketRole, OrderBook, OrderBookId, OrderBookStatus,
-    OrderBookTechStatus, OrderPrice, OrderVolume,
+    ConditionalOrderKind, Config, FeeTier, FeesToBurn, LimitOrder, LimitOrders, MarketRole,
+    OrderBook, OrderBookId, OrderBookStatus, OrderBookTechStatus, OrderPrice, OrderVolume,
+    TimeInForce, TradingFeeDestination, TradingFees, TriggerPriceSource, WeightInfo, BASIS_POINTS,
 };
-use framenode_runtime::{Runtime, RuntimeOrigin};
+use framenode_runtime::{AccountId, Runtime, RuntimeOrigin};
 use hex_literal::hex;
 use sp_core::Get;
 use sp_std::collections::btree_map::BTreeMap;
@@ -3363,3 +3366,987 @@ fn should_execute_market_order_with_indivisible_asset() {
         );
     });
 }
+
+#[test]
+fn should_place_conditional_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // sell stop-loss locks base asset
+        let stop_loss_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            ConditionalOrderKind::StopLoss,
+            TriggerPriceSource::BestBidAsk,
+            balance!(9.5),
+            None,
+            balance!(10)
+        ));
+
+        let order = OrderBookPallet::conditional_orders(order_book_id, stop_loss_id).unwrap();
+        assert_eq!(order.owner, caller);
+        assert_eq!(order.side, PriceVariant::Sell);
+        assert_eq!(order.kind, ConditionalOrderKind::StopLoss);
+        assert_eq!(order.trigger_price, balance!(9.5).into());
+        assert_eq!(order.limit_price, None);
+        assert_eq!(order.amount, balance!(10).into());
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance - balance!(10)
+        );
+
+        // buy take-profit locks quote asset at the limit price
+        let take_profit_id = stop_loss_id + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            ConditionalOrderKind::TakeProfit,
+            TriggerPriceSource::LastTrade,
+            balance!(9.7),
+            Some(balance!(9.6)),
+            balance!(10)
+        ));
+
+        let order = OrderBookPallet::conditional_orders(order_book_id, take_profit_id).unwrap();
+        assert_eq!(order.limit_price, Some(balance!(9.6).into()));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(96)
+        );
+
+        assert_eq!(
+            OrderBookPallet::user_conditional_orders(&caller, order_book_id).to_vec(),
+            vec![stop_loss_id, take_profit_id]
+        );
+
+        // conditional orders don't affect the market
+        assert!(OrderBookPallet::limit_orders(order_book_id, stop_loss_id).is_none());
+        assert!(OrderBookPallet::limit_orders(order_book_id, take_profit_id).is_none());
+    });
+}
+
+#[test]
+fn should_not_place_invalid_conditional_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let place = |trigger_price: Balance, limit_price: Option<Balance>, amount: Balance| {
+            OrderBookPallet::place_conditional_order(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                PriceVariant::Sell,
+                ConditionalOrderKind::StopLoss,
+                TriggerPriceSource::BestBidAsk,
+                trigger_price,
+                limit_price,
+                amount,
+            )
+        };
+
+        assert_err!(place(0, None, balance!(10)), E::InvalidTriggerPrice);
+        assert_err!(
+            place(balance!(9.000001), None, balance!(10)),
+            E::InvalidTriggerPrice
+        );
+        assert_err!(
+            place(balance!(9), Some(0), balance!(10)),
+            E::InvalidLimitOrderPrice
+        );
+        assert_err!(
+            place(balance!(9), Some(balance!(8.000001)), balance!(10)),
+            E::InvalidLimitOrderPrice
+        );
+        assert_err!(place(balance!(9), None, 0), E::InvalidOrderAmount);
+        assert_err!(
+            place(balance!(9), None, balance!(0.5)),
+            E::InvalidOrderAmount
+        );
+        assert_err!(
+            place(balance!(9), None, balance!(10000)),
+            E::InvalidOrderAmount
+        );
+        assert_err!(
+            place(balance!(9), None, balance!(10.000001)),
+            E::InvalidOrderAmount
+        );
+
+        assert_ok!(OrderBookPallet::change_orderbook_status(
+            RawOrigin::Root.into(),
+            order_book_id,
+            OrderBookStatus::OnlyCancel
+        ));
+        assert_err!(
+            place(balance!(9), None, balance!(10)),
+            E::PlacementOfLimitOrdersIsForbidden
+        );
+
+        let unknown_order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: PSWAP,
+            quote: XOR,
+        };
+        assert_err!(
+            OrderBookPallet::place_conditional_order(
+                RawOrigin::Signed(caller.clone()).into(),
+                unknown_order_book_id,
+                PriceVariant::Sell,
+                ConditionalOrderKind::StopLoss,
+                TriggerPriceSource::BestBidAsk,
+                balance!(9),
+                None,
+                balance!(10),
+            ),
+            E::UnknownOrderBook
+        );
+    });
+}
+
+#[test]
+fn should_cancel_conditional_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            ConditionalOrderKind::StopLoss,
+            TriggerPriceSource::LastTrade,
+            balance!(12),
+            None,
+            balance!(10)
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(120)
+        );
+
+        assert_err!(
+            OrderBookPallet::cancel_conditional_order(
+                RawOrigin::Signed(accounts::bob::<Runtime>()).into(),
+                order_book_id,
+                order_id
+            ),
+            E::Unauthorized
+        );
+
+        assert_ok!(OrderBookPallet::cancel_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            order_id
+        ));
+
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+        assert!(OrderBookPallet::user_conditional_orders(&caller, order_book_id).is_empty());
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance
+        );
+
+        assert_err!(
+            OrderBookPallet::cancel_conditional_order(
+                RawOrigin::Signed(caller).into(),
+                order_book_id,
+                order_id
+            ),
+            E::UnknownConditionalOrder
+        );
+    });
+}
+
+#[test]
+fn should_not_delete_order_book_with_conditional_orders() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_empty_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            ConditionalOrderKind::TakeProfit,
+            TriggerPriceSource::LastTrade,
+            balance!(12),
+            None,
+            balance!(10)
+        ));
+
+        assert_ok!(OrderBookPallet::change_orderbook_status(
+            RawOrigin::Root.into(),
+            order_book_id,
+            OrderBookStatus::Stop
+        ));
+
+        assert_err!(
+            OrderBookPallet::delete_orderbook(RawOrigin::Root.into(), order_book_id),
+            E::OrderBookIsNotEmpty
+        );
+    });
+}
+
+#[test]
+fn should_place_post_only_limit_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        // crosses the best ask price
+        assert_err!(
+            OrderBookPallet::place_limit_order_with_time_in_force(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                balance!(11),
+                balance!(10),
+                PriceVariant::Buy,
+                None,
+                TimeInForce::PostOnly
+            ),
+            E::PostOnlyLimitOrderCrossesSpread
+        );
+
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        let post_info = OrderBookPallet::place_limit_order_with_time_in_force(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            balance!(10.5),
+            balance!(10),
+            PriceVariant::Buy,
+            None,
+            TimeInForce::PostOnly,
+        )
+        .unwrap();
+
+        // the market maker fee is charged
+        assert!(post_info.actual_weight.is_some());
+
+        let limit_order = OrderBookPallet::limit_orders(order_book_id, order_id).unwrap();
+        assert_eq!(limit_order.owner, caller);
+        assert_eq!(limit_order.price, balance!(10.5).into());
+        assert_eq!(limit_order.amount, balance!(10).into());
+    });
+}
+
+#[test]
+fn should_execute_immediate_or_cancel_limit_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+        let last_order_id = get_last_order_id::<Runtime>(order_book_id).unwrap();
+
+        // only asks with prices 11 and 11.2 are executed, the rest of the amount is dropped
+        let post_info = OrderBookPallet::place_limit_order_with_time_in_force(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            balance!(11.2),
+            balance!(400),
+            PriceVariant::Buy,
+            None,
+            TimeInForce::ImmediateOrCancel,
+        )
+        .unwrap();
+
+        // the regular fee is charged
+        assert!(post_info.actual_weight.is_none());
+
+        let executed_amount = balance!(176.3) + balance!(178.6);
+        let paid_amount = balance!(1939.3) + balance!(2000.32);
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance + executed_amount
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - paid_amount
+        );
+
+        // nothing is placed into the order book
+        assert!(OrderBookPallet::limit_orders(order_book_id, last_order_id + 1).is_none());
+        assert!(OrderBookPallet::user_limit_orders(&caller, order_book_id).is_none());
+        assert_eq!(OrderBookPallet::aggregated_bids(order_book_id).len(), 3);
+        assert_eq!(OrderBookPallet::aggregated_asks(order_book_id).len(), 1);
+
+        // there are no bids that satisfy the limit price
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        assert_ok!(OrderBookPallet::place_limit_order_with_time_in_force(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            balance!(10.5),
+            balance!(10),
+            PriceVariant::Sell,
+            None,
+            TimeInForce::ImmediateOrCancel
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance
+        );
+        assert!(OrderBookPallet::user_limit_orders(&caller, order_book_id).is_none());
+    });
+}
+
+#[test]
+fn should_execute_fill_or_kill_limit_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        // bids with prices 10 and 9.8 have only 308.4 in total
+        assert_err!(
+            OrderBookPallet::place_limit_order_with_time_in_force(
+                RawOrigin::Signed(caller.clone()).into(),
+                order_book_id,
+                balance!(9.8),
+                balance!(400),
+                PriceVariant::Sell,
+                None,
+                TimeInForce::FillOrKill
+            ),
+            E::FillOrKillLimitOrderCannotBeFilled
+        );
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        assert_ok!(OrderBookPallet::place_limit_order_with_time_in_force(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            balance!(9.8),
+            balance!(200),
+            PriceVariant::Sell,
+            None,
+            TimeInForce::FillOrKill
+        ));
+
+        // 168.5 at 10 and 31.5 at 9.8
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance - balance!(200)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance + balance!(1685) + balance!(308.7)
+        );
+        assert!(OrderBookPallet::user_limit_orders(&caller, order_book_id).is_none());
+    });
+}
+
+#[test]
+fn should_not_execute_immediate_limit_order_if_trading_is_forbidden() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::change_orderbook_status(
+            RawOrigin::Root.into(),
+            order_book_id,
+            OrderBookStatus::PlaceAndCancel
+        ));
+
+        for time_in_force in [TimeInForce::ImmediateOrCancel, TimeInForce::FillOrKill] {
+            assert_err!(
+                OrderBookPallet::place_limit_order_with_time_in_force(
+                    RawOrigin::Signed(caller.clone()).into(),
+                    order_book_id,
+                    balance!(11),
+                    balance!(10),
+                    PriceVariant::Buy,
+                    None,
+                    time_in_force
+                ),
+                E::TradingIsForbidden
+            );
+        }
+    });
+}
+
+#[test]
+fn should_set_trading_fees() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        assert_err!(
+            OrderBookPallet::set_trading_fees(RawOrigin::Root.into(), order_book_id, 10, 20),
+            E::UnknownOrderBook
+        );
+
+        create_empty_order_book::<Runtime>(order_book_id);
+
+        assert_err!(
+            OrderBookPallet::set_trading_fees(
+                RawOrigin::Signed(accounts::alice::<Runtime>()).into(),
+                order_book_id,
+                10,
+                20
+            ),
+            BadOrigin
+        );
+
+        let max_fee = <Runtime as Config>::MAX_TRADING_FEE_BPS;
+        assert_err!(
+            OrderBookPallet::set_trading_fees(
+                RawOrigin::Root.into(),
+                order_book_id,
+                max_fee + 1,
+                20
+            ),
+            E::InvalidTradingFee
+        );
+        assert_err!(
+            OrderBookPallet::set_trading_fees(
+                RawOrigin::Root.into(),
+                order_book_id,
+                10,
+                max_fee + 1
+            ),
+            E::InvalidTradingFee
+        );
+
+        assert_eq!(
+            OrderBookPallet::trading_fees(order_book_id),
+            TradingFees::default()
+        );
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            10,
+            max_fee
+        ));
+
+        assert_eq!(
+            OrderBookPallet::trading_fees(order_book_id),
+            TradingFees {
+                maker_fee_bps: 10,
+                taker_fee_bps: max_fee
+            }
+        );
+    });
+}
+
+#[test]
+fn should_set_fee_tiers() {
+    ext().execute_with(|| {
+        let tiers = vec![
+            FeeTier {
+                min_volume: balance!(1000),
+                discount_bps: 1000,
+            },
+            FeeTier {
+                min_volume: balance!(10000),
+                discount_bps: 5000,
+            },
+        ];
+
+        assert_err!(
+            OrderBookPallet::set_fee_tiers(
+                RawOrigin::Signed(accounts::alice::<Runtime>()).into(),
+                tiers.clone()
+            ),
+            BadOrigin
+        );
+
+        let mut wrong_tiers = tiers.clone();
+        wrong_tiers.reverse();
+        assert_err!(
+            OrderBookPallet::set_fee_tiers(RawOrigin::Root.into(), wrong_tiers),
+            E::InvalidFeeTiers
+        );
+
+        let mut wrong_tiers = tiers.clone();
+        wrong_tiers[1].min_volume = wrong_tiers[0].min_volume;
+        assert_err!(
+            OrderBookPallet::set_fee_tiers(RawOrigin::Root.into(), wrong_tiers),
+            E::InvalidFeeTiers
+        );
+
+        let mut wrong_tiers = tiers.clone();
+        wrong_tiers[1].discount_bps = BASIS_POINTS + 1;
+        assert_err!(
+            OrderBookPallet::set_fee_tiers(RawOrigin::Root.into(), wrong_tiers),
+            E::InvalidFeeTiers
+        );
+
+        let max_tiers: u32 = <Runtime as Config>::MaxFeeTiers::get();
+        let too_many_tiers = (0..=max_tiers)
+            .map(|i| FeeTier {
+                min_volume: i as Balance,
+                discount_bps: 0,
+            })
+            .collect();
+        assert_err!(
+            OrderBookPallet::set_fee_tiers(RawOrigin::Root.into(), too_many_tiers),
+            E::InvalidFeeTiers
+        );
+
+        assert_ok!(OrderBookPallet::set_fee_tiers(
+            RawOrigin::Root.into(),
+            tiers.clone()
+        ));
+        assert_eq!(OrderBookPallet::fee_tiers().to_vec(), tiers);
+
+        assert_eq!(OrderBookPallet::fee_tier_discount(balance!(999)), 0);
+        assert_eq!(OrderBookPallet::fee_tier_discount(balance!(1000)), 1000);
+        assert_eq!(OrderBookPallet::fee_tier_discount(balance!(9999)), 1000);
+        assert_eq!(OrderBookPallet::fee_tier_discount(balance!(10000)), 5000);
+        assert_eq!(OrderBookPallet::fee_tier_discount(balance!(100000)), 5000);
+    });
+}
+
+#[test]
+fn should_set_trading_fee_destination() {
+    ext().execute_with(|| {
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        assert_eq!(
+            OrderBookPallet::fee_destination(),
+            TradingFeeDestination::Account(collector)
+        );
+
+        assert_err!(
+            OrderBookPallet::set_trading_fee_destination(
+                RawOrigin::Signed(accounts::alice::<Runtime>()).into(),
+                TradingFeeDestination::BuyBackAndBurn(PSWAP)
+            ),
+            BadOrigin
+        );
+
+        assert_ok!(OrderBookPallet::set_trading_fee_destination(
+            RawOrigin::Root.into(),
+            TradingFeeDestination::BuyBackAndBurn(PSWAP)
+        ));
+        assert_eq!(
+            OrderBookPallet::fee_destination(),
+            TradingFeeDestination::BuyBackAndBurn(PSWAP)
+        );
+    });
+}
+
+#[test]
+fn should_charge_trading_fees_for_buy_market_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            10,
+            20
+        ));
+
+        let caller_base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let caller_quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+        let makers_quote_balance =
+            free_balance::<Runtime>(&order_book_id.quote, &accounts::bob::<Runtime>())
+                + free_balance::<Runtime>(&order_book_id.quote, &accounts::charlie::<Runtime>());
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+
+        // the whole amount is executed at the price 11
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(100)
+        ));
+
+        // deal: 1100, taker fee: 2.2, maker fee: 1.1
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            caller_base_balance + balance!(100)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            caller_quote_balance - balance!(1102.2)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &accounts::bob::<Runtime>())
+                + free_balance::<Runtime>(&order_book_id.quote, &accounts::charlie::<Runtime>()),
+            makers_quote_balance + balance!(1098.9)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(3.3)
+        );
+        assert_eq!(
+            OrderBookPallet::trailing_volume(&caller, &order_book_id.dex_id),
+            balance!(1100)
+        );
+    });
+}
+
+#[test]
+fn should_charge_trading_fees_for_sell_market_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            10,
+            20
+        ));
+
+        let caller_base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let caller_quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+        let makers_quote_balance =
+            free_balance::<Runtime>(&order_book_id.quote, &accounts::bob::<Runtime>())
+                + free_balance::<Runtime>(&order_book_id.quote, &accounts::charlie::<Runtime>());
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+
+        // the whole amount is executed at the price 10
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            balance!(100)
+        ));
+
+        // deal: 1000, taker fee: 2, maker fee: 1 that is taken from the free balance of makers
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            caller_base_balance - balance!(100)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            caller_quote_balance + balance!(998)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &accounts::bob::<Runtime>())
+                + free_balance::<Runtime>(&order_book_id.quote, &accounts::charlie::<Runtime>()),
+            makers_quote_balance - balance!(1)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(3)
+        );
+    });
+}
+
+#[test]
+fn should_record_maker_fee_debt_if_not_enough_free_balance() {
+    ext().execute_with(|| {
+        FrameSystem::set_block_number(1);
+
+        let caller = accounts::alice::<Runtime>();
+        let maker = accounts::bob::<Runtime>();
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            10,
+            20
+        ));
+
+        // the maker has no free balance of the quote asset to pay the fee
+        assert_ok!(Assets::transfer_from(
+            &order_book_id.quote,
+            &maker,
+            &accounts::dave::<Runtime>(),
+            free_balance::<Runtime>(&order_book_id.quote, &maker)
+        ));
+
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+
+        // the whole amount is executed at the price 10 with the bob's limit order
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            balance!(100)
+        ));
+
+        // deal: 1000, taker fee: 2, maker fee: 1 that becomes the debt
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(2)
+        );
+        assert_eq!(
+            OrderBookPallet::maker_fee_debts(&maker, order_book_id.quote),
+            balance!(1)
+        );
+        FrameSystem::assert_has_event(
+            framenode_runtime::order_book::Event::<Runtime>::MakerFeeDebtUpdated {
+                order_book_id,
+                owner_id: maker.clone(),
+                debt: balance!(1).into(),
+            }
+            .into(),
+        );
+
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+
+        // the whole amount is executed at the price 11 with the bob's limit order
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(100)
+        ));
+
+        // deal: 1100, taker fee: 2.2, maker fee: 1.1 and the debt: 1 are taken from the payment
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &maker),
+            balance!(1097.9)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(4.3)
+        );
+        assert_eq!(
+            OrderBookPallet::maker_fee_debts(&maker, order_book_id.quote),
+            0
+        );
+    });
+}
+
+#[test]
+fn should_apply_fee_tier_discount() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            0,
+            20
+        ));
+        assert_ok!(OrderBookPallet::set_fee_tiers(
+            RawOrigin::Root.into(),
+            vec![FeeTier {
+                min_volume: balance!(1000),
+                discount_bps: 5000,
+            }]
+        ));
+
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // no discount, deal: 550, fee: 1.1
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(50)
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(551.1)
+        );
+
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // still no discount, trailing volume is 550, deal: 550, fee: 1.1
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(50)
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(551.1)
+        );
+
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // trailing volume is 1100, discount 50%, deal: 550, fee: 0.55
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(50)
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(550.55)
+        );
+    });
+}
+
+#[test]
+fn should_buy_back_and_burn_trading_fees() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            10,
+            20
+        ));
+        assert_ok!(OrderBookPallet::set_trading_fee_destination(
+            RawOrigin::Root.into(),
+            TradingFeeDestination::BuyBackAndBurn(XOR)
+        ));
+
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+        let total_issuance = Assets::total_issuance(&XOR).unwrap();
+
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(100)
+        ));
+
+        // fees wait on the collector account
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(3.3)
+        );
+        assert_eq!(OrderBookPallet::fees_to_burn((XOR, XOR)), balance!(3.3));
+
+        OrderBookPallet::on_idle(FrameSystem::block_number(), Weight::MAX);
+
+        assert_eq!(OrderBookPallet::fees_to_burn((XOR, XOR)), 0);
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance
+        );
+        assert_eq!(
+            Assets::total_issuance(&XOR).unwrap(),
+            total_issuance - balance!(3.3)
+        );
+    });
+}
+
+#[test]
+fn should_burn_trading_fees_in_bounded_batches() {
+    ext().execute_with(|| {
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+        let total_issuance = Assets::total_issuance(&XOR).unwrap();
+
+        assert_ok!(Assets::mint_unchecked(&XOR, &collector, balance!(10)));
+        FeesToBurn::<Runtime>::insert((XOR, XOR), balance!(4));
+        FeesToBurn::<Runtime>::insert((VAL, XOR), 0);
+
+        // the weight is enough only for one entry
+        let mut weight =
+            WeightMeter::from_limit(<Runtime as Config>::WeightInfo::service_fee_burning());
+        OrderBookPallet::service_fees_burning(&mut weight);
+        assert_eq!(FeesToBurn::<Runtime>::iter().count(), 1);
+
+        OrderBookPallet::on_idle(FrameSystem::block_number(), Weight::MAX);
+        assert_eq!(FeesToBurn::<Runtime>::iter().count(), 0);
+        assert_eq!(
+            Assets::total_issuance(&XOR).unwrap(),
+            total_issuance + balance!(6)
+        );
+    });
+}
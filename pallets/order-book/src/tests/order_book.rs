@@ -37,9 +37,9 @@ use framenode_chain_spec::ext;
 use framenode_runtime::order_book::cache_data_layer::CacheDataLayer;
 use framenode_runtime::order_book::storage_data_layer::StorageDataLayer;
 use framenode_runtime::order_book::{
-    CancelReason, Config, DataLayer, DealInfo, LimitOrder, MarketChange, MarketOrder, MarketRole,
-    OrderAmount, OrderBook, OrderBookId, OrderBookStatus, OrderBookTechStatus, OrderPrice,
-    OrderVolume, Payment,
+    CancelReason, Config, DataLayer, DealFees, DealInfo, LimitOrder, MarketChange, MarketOrder,
+    MarketRole, OrderAmount, OrderBook, OrderBookId, OrderBookStatus, OrderBookTechStatus,
+    OrderPrice, OrderVolume, Payment,
 };
 use framenode_runtime::{Runtime, RuntimeOrigin};
 use sp_core::Get;
@@ -3434,7 +3434,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3490,7 +3491,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3546,7 +3548,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3598,7 +3601,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3652,7 +3656,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3705,7 +3710,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3764,7 +3770,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3825,7 +3832,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3880,7 +3888,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -3937,7 +3946,8 @@ fn should_calculate_market_order_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
     });
@@ -3997,7 +4007,8 @@ fn should_calculate_limit_order_impact() {
                     )]),
                     to_unlock: BTreeMap::new(),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -4023,7 +4034,8 @@ fn should_calculate_limit_order_impact() {
                     )]),
                     to_unlock: BTreeMap::new(),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
     });
@@ -4077,7 +4089,8 @@ fn should_calculate_cancellation_limit_order_impact() {
                         )])
                     )]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -4113,7 +4126,8 @@ fn should_calculate_cancellation_limit_order_impact() {
                         )])
                     )]),
                 },
-                ignore_unschedule_error: true
+                ignore_unschedule_error: true,
+                fees: DealFees::default()
             }
         );
 
@@ -4149,7 +4163,8 @@ fn should_calculate_cancellation_limit_order_impact() {
                         )])
                     )]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -4185,7 +4200,8 @@ fn should_calculate_cancellation_limit_order_impact() {
                         )])
                     )]),
                 },
-                ignore_unschedule_error: true
+                ignore_unschedule_error: true,
+                fees: DealFees::default()
             }
         );
     });
@@ -4264,7 +4280,8 @@ fn should_calculate_cancellation_of_all_limit_orders_impact() {
                         )
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
     });
@@ -4401,6 +4418,7 @@ fn should_calculate_align_limit_orders_impact() {
                     to_unlock: BTreeMap::new(),
                 },
                 ignore_unschedule_error: false,
+                fees: DealFees::default(),
             }
         );
 
@@ -4473,6 +4491,7 @@ fn should_calculate_align_limit_orders_impact() {
                     ]),
                 },
                 ignore_unschedule_error: false,
+                fees: DealFees::default(),
             }
         );
     });
@@ -4632,6 +4651,7 @@ fn should_apply_market_change() {
                 ]),
             },
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         };
 
         // apply market change
@@ -4971,7 +4991,8 @@ fn should_cross_spread() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5023,7 +5044,8 @@ fn should_cross_spread() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5075,7 +5097,8 @@ fn should_cross_spread() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5124,7 +5147,8 @@ fn should_cross_spread() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
     });
@@ -5214,7 +5238,8 @@ fn should_cross_spread_with_small_remaining_amount() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5274,7 +5299,8 @@ fn should_cross_spread_with_small_remaining_amount() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5333,7 +5359,8 @@ fn should_cross_spread_with_small_remaining_amount() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
 
@@ -5393,7 +5420,8 @@ fn should_cross_spread_with_small_remaining_amount() {
                         ),
                     ]),
                 },
-                ignore_unschedule_error: false
+                ignore_unschedule_error: false,
+                fees: DealFees::default()
             }
         );
     });
@@ -34,18 +34,23 @@ use crate::test_utils::*;
 use assets::AssetIdOf;
 use common::prelude::{QuoteAmount, SwapAmount, SwapOutcome};
 use common::{
-    balance, AssetName, AssetSymbol, Balance, LiquiditySource, PriceVariant, VAL, XOR, XSTUSD,
+    balance, AssetName, AssetSymbol, Balance, LiquiditySource, PriceVariant, PSWAP, VAL, XOR,
+    XSTUSD,
 };
-use frame_support::traits::Get;
+use frame_support::dispatch::GetDispatchInfo;
+use frame_support::traits::{Get, Hooks};
+use frame_support::weights::{Weight, WeightMeter};
 use frame_support::{assert_err, assert_ok};
 use frame_system::RawOrigin;
 use framenode_chain_spec::ext;
 use framenode_runtime::order_book::{
-    self, Config, CurrencyLocker, CurrencyUnlocker, ExpirationScheduler, LimitOrder, MarketRole,
-    OrderBook, OrderBookId, OrderBookStatus, OrderPrice, OrderVolume, WeightInfo,
+    self, Candle, CandleInterval, Candles, ConditionalOrderKind, Config, CurrencyLocker,
+    CurrencyUnlocker, ExpirationScheduler, LimitOrder, MarketRole, OrderBook, OrderBookId,
+    OrderBookStatus, OrderPrice, OrderVolume, Trade, TriggerPriceSource, TriggerScheduler,
+    WeightInfo,
 };
-use framenode_runtime::{Runtime, RuntimeOrigin};
-use sp_runtime::traits::UniqueSaturatedInto;
+use framenode_runtime::{AccountId, Runtime, RuntimeOrigin};
+use sp_runtime::traits::{UniqueSaturatedInto, Zero};
 use sp_std::collections::btree_map::BTreeMap;
 
 #[test]
@@ -2009,3 +2014,927 @@ fn should_not_exchange_with_invalid_slippage() {
         );
     });
 }
+
+#[test]
+fn should_trigger_stop_loss_by_best_bid() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            ConditionalOrderKind::StopLoss,
+            TriggerPriceSource::BestBidAsk,
+            balance!(9.9),
+            None,
+            balance!(10)
+        ));
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // best bid is 10, the order is not triggered
+        let current_block = frame_system::Pallet::<Runtime>::block_number();
+        run_to_block(current_block + 1);
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_some());
+
+        // the whole best bid price level is sold, the best bid falls to 9.8
+        assert_ok!(OrderBookPallet::exchange(
+            &accounts::charlie::<Runtime>(),
+            &accounts::charlie::<Runtime>(),
+            &DEX.into(),
+            &VAL,
+            &XOR,
+            SwapAmount::with_desired_input(balance!(168.5), balance!(1685)),
+        ));
+        assert_eq!(
+            OrderBookPallet::last_trade_price(order_book_id),
+            Some(balance!(10).into())
+        );
+
+        run_to_block(current_block + 2);
+
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+        assert!(OrderBookPallet::user_conditional_orders(&caller, order_book_id).is_empty());
+
+        // the order is executed as a market order at 9.8
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance + balance!(98)
+        );
+        assert_eq!(
+            OrderBookPallet::last_trade_price(order_book_id),
+            Some(balance!(9.8).into())
+        );
+    });
+}
+
+#[test]
+fn should_trigger_take_profit_by_last_trade_into_limit_order() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            ConditionalOrderKind::TakeProfit,
+            TriggerPriceSource::LastTrade,
+            balance!(11),
+            Some(balance!(11.5)),
+            balance!(10)
+        ));
+
+        // there are no deals yet
+        let current_block = frame_system::Pallet::<Runtime>::block_number();
+        run_to_block(current_block + 1);
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_some());
+
+        // buy at the best ask price
+        assert_ok!(OrderBookPallet::exchange(
+            &accounts::charlie::<Runtime>(),
+            &accounts::charlie::<Runtime>(),
+            &DEX.into(),
+            &XOR,
+            &VAL,
+            SwapAmount::with_desired_output(balance!(10), balance!(110)),
+        ));
+        assert_eq!(
+            OrderBookPallet::last_trade_price(order_book_id),
+            Some(balance!(11).into())
+        );
+
+        run_to_block(current_block + 2);
+
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+
+        // the limit order is placed with the same id
+        let limit_order = OrderBookPallet::limit_orders(order_book_id, order_id).unwrap();
+        assert_eq!(limit_order.owner, caller);
+        assert_eq!(limit_order.side, PriceVariant::Sell);
+        assert_eq!(limit_order.price, balance!(11.5).into());
+        assert_eq!(limit_order.amount, balance!(10).into());
+    });
+}
+
+#[test]
+fn should_return_liquidity_if_triggered_order_failed() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+
+        // the amount is greater than the asks volume
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            ConditionalOrderKind::StopLoss,
+            TriggerPriceSource::BestBidAsk,
+            balance!(11),
+            None,
+            balance!(700)
+        ));
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(7700)
+        );
+
+        let current_block = frame_system::Pallet::<Runtime>::block_number();
+        run_to_block(current_block + 1);
+
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance
+        );
+
+        // the market is not changed
+        assert_eq!(OrderBookPallet::last_trade_price(order_book_id), None);
+        assert_eq!(OrderBookPallet::aggregated_asks(order_book_id).len(), 3);
+    });
+}
+
+#[test]
+fn should_not_trigger_conditional_order_if_trading_is_forbidden() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+        assert_ok!(OrderBookPallet::place_conditional_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            ConditionalOrderKind::TakeProfit,
+            TriggerPriceSource::BestBidAsk,
+            balance!(10),
+            None,
+            balance!(10)
+        ));
+
+        assert_ok!(OrderBookPallet::change_orderbook_status(
+            RawOrigin::Root.into(),
+            order_book_id,
+            OrderBookStatus::PlaceAndCancel
+        ));
+
+        let current_block = frame_system::Pallet::<Runtime>::block_number();
+        run_to_block(current_block + 1);
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_some());
+
+        assert_ok!(OrderBookPallet::change_orderbook_status(
+            RawOrigin::Root.into(),
+            order_book_id,
+            OrderBookStatus::Trade
+        ));
+
+        run_to_block(current_block + 2);
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+    });
+}
+
+fn place_triggered_conditional_order(
+    order_book_id: OrderBookId<AssetIdOf<Runtime>, DEXId>,
+    amount: Balance,
+) -> u128 {
+    let caller = accounts::alice::<Runtime>();
+    let order_id = get_last_order_id::<Runtime>(order_book_id).unwrap() + 1;
+    assert_ok!(OrderBookPallet::place_conditional_order(
+        RawOrigin::Signed(caller).into(),
+        order_book_id,
+        PriceVariant::Sell,
+        ConditionalOrderKind::TakeProfit,
+        TriggerPriceSource::BestBidAsk,
+        balance!(10),
+        None,
+        amount
+    ));
+    order_id
+}
+
+/// Weight that is enough to trigger a conditional order which executes a single limit order
+fn single_trigger_weight() -> Weight {
+    <Runtime as Config>::WeightInfo::service_triggers_base()
+        .saturating_add(<Runtime as Config>::WeightInfo::service_single_trigger())
+        .saturating_add(<Runtime as Config>::WeightInfo::exchange(1))
+        .saturating_add(OrderBookPallet::record_trades_weight(1))
+}
+
+#[test]
+fn should_postpone_triggered_order_if_weight_is_not_enough() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        // the order is executed against the bid at 10 and one of the bids at 9.8
+        let order_id = place_triggered_conditional_order(order_book_id, balance!(200));
+
+        // the remaining weight is enough to execute only one limit order
+        let two_orders_extra_weight = <Runtime as Config>::WeightInfo::exchange(2)
+            .saturating_add(OrderBookPallet::record_trades_weight(2))
+            .saturating_sub(<Runtime as Config>::WeightInfo::exchange(1))
+            .saturating_sub(OrderBookPallet::record_trades_weight(1));
+        let mut weight = WeightMeter::from_limit(
+            single_trigger_weight().saturating_add(two_orders_extra_weight),
+        );
+        weight.defensive_saturating_accrue(two_orders_extra_weight);
+        OrderBookPallet::service_triggers(&mut weight);
+
+        assert!(weight.consumed.all_lte(weight.limit));
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_some());
+        assert_eq!(OrderBookPallet::last_trade_price(order_book_id), None);
+
+        let mut weight = WeightMeter::from_limit(
+            single_trigger_weight().saturating_add(two_orders_extra_weight),
+        );
+        OrderBookPallet::service_triggers(&mut weight);
+
+        assert!(weight.consumed.all_lte(weight.limit));
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+        assert_eq!(
+            OrderBookPallet::last_trade_price(order_book_id),
+            Some(balance!(9.8).into())
+        );
+    });
+}
+
+#[test]
+fn should_fail_triggered_order_if_it_exceeds_triggers_weight() {
+    ext().execute_with(|| {
+        let caller = accounts::alice::<Runtime>();
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let order_id = place_triggered_conditional_order(order_book_id, balance!(200));
+
+        // even the whole weight is not enough to execute two limit orders
+        let mut weight = WeightMeter::from_limit(single_trigger_weight());
+        OrderBookPallet::service_triggers(&mut weight);
+
+        assert!(weight.consumed.all_lte(weight.limit));
+        assert!(OrderBookPallet::conditional_orders(order_book_id, order_id).is_none());
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance
+        );
+        assert_eq!(OrderBookPallet::last_trade_price(order_book_id), None);
+    });
+}
+
+#[test]
+fn should_return_order_book_depth() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        assert_eq!(OrderBookPallet::order_book_depth(&order_book_id, 10), None);
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+
+        assert_eq!(
+            OrderBookPallet::order_book_depth(&order_book_id, 2),
+            Some((
+                vec![
+                    (balance!(10).into(), balance!(168.5).into()),
+                    (balance!(9.8).into(), balance!(139.9).into())
+                ],
+                vec![
+                    (balance!(11).into(), balance!(176.3).into()),
+                    (balance!(11.2).into(), balance!(178.6).into())
+                ]
+            ))
+        );
+
+        assert_eq!(
+            OrderBookPallet::order_book_depth(&order_book_id, 10),
+            Some((
+                vec![
+                    (balance!(10).into(), balance!(168.5).into()),
+                    (balance!(9.8).into(), balance!(139.9).into()),
+                    (balance!(9.5).into(), balance!(261.3).into())
+                ],
+                vec![
+                    (balance!(11).into(), balance!(176.3).into()),
+                    (balance!(11.2).into(), balance!(178.6).into()),
+                    (balance!(11.5).into(), balance!(255.8).into())
+                ]
+            ))
+        );
+
+        assert_eq!(
+            OrderBookPallet::order_book_depth(&order_book_id, 0),
+            Some((vec![], vec![]))
+        );
+    });
+}
+
+#[test]
+fn should_return_best_prices() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        assert_eq!(OrderBookPallet::best_prices(&order_book_id), None);
+
+        create_empty_order_book::<Runtime>(order_book_id);
+        assert_eq!(
+            OrderBookPallet::best_prices(&order_book_id),
+            Some((None, None))
+        );
+
+        assert_ok!(OrderBookPallet::place_limit_order(
+            RawOrigin::Signed(accounts::alice::<Runtime>()).into(),
+            order_book_id,
+            balance!(10),
+            balance!(100),
+            PriceVariant::Buy,
+            None
+        ));
+        assert_eq!(
+            OrderBookPallet::best_prices(&order_book_id),
+            Some((Some((balance!(10).into(), balance!(100).into())), None))
+        );
+    });
+}
+
+#[test]
+fn should_return_best_prices_of_filled_order_book() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+
+        assert_eq!(
+            OrderBookPallet::best_prices(&order_book_id),
+            Some((
+                Some((balance!(10).into(), balance!(168.5).into())),
+                Some((balance!(11).into(), balance!(176.3).into()))
+            ))
+        );
+    });
+}
+
+#[test]
+fn should_return_all_user_limit_orders() {
+    ext().execute_with(|| {
+        let order_book_id1 = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        let order_book_id2 = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: PSWAP,
+            quote: XOR,
+        };
+
+        assert!(OrderBookPallet::all_user_limit_orders(&accounts::bob::<Runtime>()).is_empty());
+
+        create_and_fill_order_book::<Runtime>(order_book_id1);
+        create_and_fill_order_book::<Runtime>(order_book_id2);
+
+        let mut orders: Vec<_> =
+            OrderBookPallet::all_user_limit_orders(&accounts::bob::<Runtime>())
+                .into_iter()
+                .map(|(order_book_id, order)| {
+                    assert_eq!(order.owner, accounts::bob::<Runtime>());
+                    (order_book_id, order.id)
+                })
+                .collect();
+        orders.sort();
+
+        // Bob owns orders (1, 3, 5, 7, 9, 11) in both order books
+        let mut expected: Vec<_> = [order_book_id1, order_book_id2]
+            .into_iter()
+            .flat_map(|order_book_id| {
+                [1, 3, 5, 7, 9, 11]
+                    .into_iter()
+                    .map(move |order_id| (order_book_id, order_id))
+            })
+            .collect();
+        expected.sort();
+
+        assert_eq!(orders, expected);
+        assert!(OrderBookPallet::all_user_limit_orders(&accounts::alice::<Runtime>()).is_empty());
+    });
+}
+
+#[test]
+fn should_quote_market_order() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        assert_err!(
+            OrderBookPallet::quote_market_order(&order_book_id, PriceVariant::Buy, balance!(200)),
+            E::UnknownOrderBook
+        );
+
+        create_and_fill_order_book::<Runtime>(order_book_id);
+
+        // 176.3 at 11 and 23.7 at 11.2
+        let deal =
+            OrderBookPallet::quote_market_order(&order_book_id, PriceVariant::Buy, balance!(200))
+                .unwrap();
+        assert_eq!(deal.input_asset_id, XOR);
+        assert_eq!(*deal.input_amount.value(), balance!(2204.74).into());
+        assert_eq!(deal.output_asset_id, VAL);
+        assert_eq!(*deal.output_amount.value(), balance!(200).into());
+        assert_eq!(deal.average_price, balance!(11.0237).into());
+
+        // 168.5 at 10 and 31.5 at 9.8
+        let deal =
+            OrderBookPallet::quote_market_order(&order_book_id, PriceVariant::Sell, balance!(200))
+                .unwrap();
+        assert_eq!(deal.input_asset_id, VAL);
+        assert_eq!(*deal.input_amount.value(), balance!(200).into());
+        assert_eq!(deal.output_asset_id, XOR);
+        assert_eq!(*deal.output_amount.value(), balance!(1993.7).into());
+        assert_eq!(deal.average_price, balance!(9.9685).into());
+
+        // not enough liquidity
+        assert_err!(
+            OrderBookPallet::quote_market_order(&order_book_id, PriceVariant::Buy, balance!(1000)),
+            E::NotEnoughLiquidityInOrderBook
+        );
+    });
+}
+
+#[test]
+fn should_quote_and_exchange_with_trading_fee() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+        let caller = accounts::alice::<Runtime>();
+        let collector: AccountId = framenode_runtime::GetTreasuryAccountId::get();
+
+        let _ = create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        assert_ok!(OrderBookPallet::set_trading_fees(
+            RawOrigin::Root.into(),
+            order_book_id,
+            0,
+            20
+        ));
+
+        // quote without impact: the deal is 2994.011976047904191616, the rest is the fee
+        assert_eq!(
+            OrderBookPallet::quote_without_impact(
+                &DEX.into(),
+                &XOR,
+                &VAL,
+                QuoteAmount::with_desired_input(balance!(3000)),
+                false
+            )
+            .unwrap(),
+            SwapOutcome::new(balance!(272.1829), balance!(5.988023952095808384))
+        );
+
+        // quote without impact: deal 2200 + fee 4.4
+        assert_eq!(
+            OrderBookPallet::quote_without_impact(
+                &DEX.into(),
+                &XOR,
+                &VAL,
+                QuoteAmount::with_desired_output(balance!(200)),
+                false
+            )
+            .unwrap(),
+            SwapOutcome::new(balance!(2204.4), balance!(4.4))
+        );
+
+        // quote without impact: deal 2000 - fee 4
+        assert_eq!(
+            OrderBookPallet::quote_without_impact(
+                &DEX.into(),
+                &VAL,
+                &XOR,
+                QuoteAmount::with_desired_input(balance!(200)),
+                false
+            )
+            .unwrap(),
+            SwapOutcome::new(balance!(1996), balance!(4))
+        );
+
+        // deal 2204.74 + fee 4.40948
+        assert_eq!(
+            OrderBookPallet::quote(
+                &DEX.into(),
+                &XOR,
+                &VAL,
+                QuoteAmount::with_desired_output(balance!(200)),
+                false
+            )
+            .unwrap()
+            .0,
+            SwapOutcome::new(balance!(2209.14948), balance!(4.40948))
+        );
+
+        // deal 1993.7 - fee 3.9874
+        assert_eq!(
+            OrderBookPallet::quote(
+                &DEX.into(),
+                &VAL,
+                &XOR,
+                QuoteAmount::with_desired_input(balance!(200)),
+                false
+            )
+            .unwrap()
+            .0,
+            SwapOutcome::new(balance!(1989.7126), balance!(3.9874))
+        );
+
+        // the slippage limit considers the fee
+        assert_err!(
+            OrderBookPallet::exchange(
+                &caller,
+                &caller,
+                &DEX.into(),
+                &XOR,
+                &VAL,
+                SwapAmount::with_desired_output(balance!(200), balance!(2205)),
+            ),
+            E::SlippageLimitExceeded
+        );
+
+        let base_balance = free_balance::<Runtime>(&order_book_id.base, &caller);
+        let quote_balance = free_balance::<Runtime>(&order_book_id.quote, &caller);
+        let collector_balance = free_balance::<Runtime>(&order_book_id.quote, &collector);
+
+        assert_eq!(
+            OrderBookPallet::exchange(
+                &caller,
+                &caller,
+                &DEX.into(),
+                &XOR,
+                &VAL,
+                SwapAmount::with_desired_output(balance!(200), balance!(2210)),
+            )
+            .unwrap()
+            .0,
+            SwapOutcome::new(balance!(2209.14948), balance!(4.40948))
+        );
+
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.base, &caller),
+            base_balance + balance!(200)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &caller),
+            quote_balance - balance!(2209.14948)
+        );
+        assert_eq!(
+            free_balance::<Runtime>(&order_book_id.quote, &collector),
+            collector_balance + balance!(4.40948)
+        );
+    });
+}
+
+#[test]
+fn should_record_trades_and_candles() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+        let caller = accounts::alice::<Runtime>();
+
+        let _ = create_and_fill_order_book::<Runtime>(order_book_id);
+        fill_balance::<Runtime>(caller.clone(), order_book_id);
+
+        let minute_start = 1_699_999_980_000;
+        let hour_start = 1_699_999_200_000;
+        let now = minute_start + 30_000;
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now);
+
+        assert!(OrderBookPallet::recent_trades(&order_book_id, 100).is_empty());
+        assert!(OrderBookPallet::candles(&order_book_id, CandleInterval::Minute, 10).is_empty());
+        assert_eq!(OrderBookPallet::vwap(&order_book_id, 60_000), None);
+
+        // 176.3 at the price 11 and 23.7 at the price 11.2
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller.clone()).into(),
+            order_book_id,
+            PriceVariant::Buy,
+            balance!(200)
+        ));
+
+        let trades = OrderBookPallet::recent_trades(&order_book_id, 100);
+        assert_eq!(
+            trades[0],
+            Trade {
+                price: balance!(11.2).into(),
+                amount: balance!(23.7).into(),
+                direction: PriceVariant::Buy,
+                time: now,
+            }
+        );
+        assert_eq!(trades.last().unwrap().price, balance!(11).into());
+        assert_eq!(
+            trades
+                .iter()
+                .fold(OrderVolume::zero(), |sum, trade| sum + trade.amount),
+            balance!(200).into()
+        );
+        assert!(trades
+            .iter()
+            .all(|trade| trade.direction == PriceVariant::Buy && trade.time == now));
+        assert_eq!(OrderBookPallet::recent_trades(&order_book_id, 1).len(), 1);
+
+        assert_eq!(
+            OrderBookPallet::last_trade_price(order_book_id).unwrap(),
+            balance!(11.2).into()
+        );
+
+        assert_eq!(
+            OrderBookPallet::candles(&order_book_id, CandleInterval::Minute, 10),
+            vec![Candle {
+                start: minute_start,
+                open: balance!(11).into(),
+                high: balance!(11.2).into(),
+                low: balance!(11).into(),
+                close: balance!(11.2).into(),
+                volume: balance!(200).into(),
+                quote_volume: balance!(2204.74).into(),
+            }]
+        );
+        assert_eq!(
+            OrderBookPallet::vwap(&order_book_id, 60_000).unwrap(),
+            balance!(11.0237).into()
+        );
+
+        // the next minute
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now + 60_000);
+
+        assert_ok!(OrderBookPallet::execute_market_order(
+            RawOrigin::Signed(caller).into(),
+            order_book_id,
+            PriceVariant::Sell,
+            balance!(100)
+        ));
+
+        let candles = OrderBookPallet::candles(&order_book_id, CandleInterval::Minute, 10);
+        assert_eq!(candles.len(), 2);
+        assert_eq!(
+            candles[1],
+            Candle {
+                start: minute_start + 60_000,
+                open: balance!(10).into(),
+                high: balance!(10).into(),
+                low: balance!(10).into(),
+                close: balance!(10).into(),
+                volume: balance!(100).into(),
+                quote_volume: balance!(1000).into(),
+            }
+        );
+        assert_eq!(
+            OrderBookPallet::candles(&order_book_id, CandleInterval::Minute, 1),
+            vec![candles[1]]
+        );
+
+        assert_eq!(
+            OrderBookPallet::candles(&order_book_id, CandleInterval::Hour, 10),
+            vec![Candle {
+                start: hour_start,
+                open: balance!(11).into(),
+                high: balance!(11.2).into(),
+                low: balance!(10).into(),
+                close: balance!(10).into(),
+                volume: balance!(300).into(),
+                quote_volume: balance!(3204.74).into(),
+            }]
+        );
+        assert_eq!(
+            OrderBookPallet::vwap(&order_book_id, 60_000).unwrap(),
+            balance!(10).into()
+        );
+        assert_eq!(
+            OrderBookPallet::recent_trades(&order_book_id, 1)[0].direction,
+            PriceVariant::Sell
+        );
+    });
+}
+
+#[test]
+fn should_keep_limited_trade_history() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+        let capacity: u32 = <Runtime as Config>::MaxTradesHistory::get();
+
+        for i in 0..capacity + 5 {
+            OrderBookPallet::record_trade(
+                &order_book_id,
+                PriceVariant::Buy,
+                balance!(10).into(),
+                (i as Balance + 1).into(),
+            );
+        }
+
+        assert_eq!(OrderBookPallet::trades_head(order_book_id), (5, capacity));
+
+        let trades = OrderBookPallet::recent_trades(&order_book_id, capacity + 100);
+        assert_eq!(trades.len(), capacity as usize);
+        assert_eq!(trades[0].amount, ((capacity + 5) as Balance).into());
+        assert_eq!(trades.last().unwrap().amount, 6.into());
+    });
+}
+
+#[test]
+fn should_include_trades_recording_into_market_order_weights() {
+    ext().execute_with(|| {
+        let max_executed_orders = <Runtime as Config>::SOFT_MIN_MAX_RATIO as u32;
+        let record_trades = OrderBookPallet::record_trades_weight(max_executed_orders);
+        assert_eq!(
+            record_trades,
+            <Runtime as Config>::WeightInfo::record_trade()
+                .saturating_mul(max_executed_orders as u64)
+        );
+
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+        let call = order_book::Call::<Runtime>::execute_market_order {
+            order_book_id,
+            direction: PriceVariant::Buy,
+            amount: balance!(10),
+        };
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            <Runtime as Config>::WeightInfo::execute_market_order().saturating_add(record_trades)
+        );
+        assert_eq!(
+            <OrderBookPallet as LiquiditySource<_, _, _, _, _>>::exchange_weight(),
+            <Runtime as Config>::WeightInfo::exchange(max_executed_orders)
+                .saturating_add(record_trades)
+        );
+    });
+}
+
+#[test]
+fn should_prune_outdated_candles() {
+    ext().execute_with(|| {
+        let order_book_id = OrderBookId::<AssetIdOf<Runtime>, DEXId> {
+            dex_id: DEX.into(),
+            base: VAL,
+            quote: XOR,
+        };
+
+        let now = 1_700_000_010_000;
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now);
+
+        OrderBookPallet::record_trade(
+            &order_book_id,
+            PriceVariant::Buy,
+            balance!(10).into(),
+            balance!(100).into(),
+        );
+
+        let count_candles =
+            |interval| Candles::<Runtime>::iter_prefix((order_book_id, interval)).count();
+        assert_eq!(count_candles(CandleInterval::Minute), 1);
+        assert_eq!(count_candles(CandleInterval::Hour), 1);
+        assert_eq!(count_candles(CandleInterval::Day), 1);
+
+        // nothing is outdated yet
+        OrderBookPallet::on_idle(frame_system::Pallet::<Runtime>::block_number(), Weight::MAX);
+        assert_eq!(count_candles(CandleInterval::Minute), 1);
+
+        // minute candles are kept for 1 day
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now + 86_400_000);
+        OrderBookPallet::on_idle(frame_system::Pallet::<Runtime>::block_number(), Weight::MAX);
+
+        assert_eq!(count_candles(CandleInterval::Minute), 0);
+        assert_eq!(count_candles(CandleInterval::Hour), 1);
+        assert_eq!(count_candles(CandleInterval::Day), 1);
+        assert!(OrderBookPallet::candles_pruning_cursor().is_none());
+    });
+}
+
+#[test]
+fn should_prune_outdated_trading_volumes() {
+    ext().execute_with(|| {
+        let alice = accounts::alice::<Runtime>();
+        let bob = accounts::bob::<Runtime>();
+        let dex_id = DEX.into();
+
+        let now = 1_700_000_010_000;
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now);
+
+        OrderBookPallet::record_trading_volume(&alice, &dex_id, &balance!(100).into());
+        OrderBookPallet::record_trading_volume(&bob, &dex_id, &balance!(100).into());
+
+        // nothing is outdated yet
+        OrderBookPallet::on_idle(frame_system::Pallet::<Runtime>::block_number(), Weight::MAX);
+        assert_eq!(OrderBookPallet::trading_volumes(&alice, dex_id).len(), 1);
+        assert_eq!(OrderBookPallet::trading_volumes(&bob, dex_id).len(), 1);
+
+        // volumes are kept for 30 days, bob keeps trading
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(now + 30 * 86_400_000);
+        OrderBookPallet::record_trading_volume(&bob, &dex_id, &balance!(100).into());
+
+        // the weight is enough only for one entry
+        let mut weight =
+            WeightMeter::from_limit(<Runtime as Config>::WeightInfo::prune_single_trading_volume());
+        OrderBookPallet::service_trading_volumes_pruning(&mut weight);
+        assert!(OrderBookPallet::trading_volumes_pruning_cursor().is_some());
+
+        OrderBookPallet::on_idle(frame_system::Pallet::<Runtime>::block_number(), Weight::MAX);
+
+        assert!(!order_book::TradingVolumes::<Runtime>::contains_key(
+            &alice, dex_id
+        ));
+        assert_eq!(
+            OrderBookPallet::trading_volumes(&bob, dex_id).into_inner(),
+            vec![(OrderBookPallet::current_day(), balance!(100))]
+        );
+        assert_eq!(
+            OrderBookPallet::trailing_volume(&bob, &dex_id),
+            balance!(100)
+        );
+        assert!(OrderBookPallet::trading_volumes_pruning_cursor().is_none());
+    });
+}
@@ -34,7 +34,7 @@ use common::{balance, PriceVariant, DAI, VAL, XOR};
 use frame_support::assert_ok;
 use framenode_chain_spec::ext;
 use framenode_runtime::order_book::{
-    CancelReason, Config, DealInfo, LimitOrder, MarketChange, OrderAmount, OrderBookId,
+    CancelReason, Config, DealFees, DealInfo, LimitOrder, MarketChange, OrderAmount, OrderBookId,
     OrderVolume, Payment,
 };
 use framenode_runtime::Runtime;
@@ -871,6 +871,7 @@ fn should_fail_market_change_merge() {
         )]),
         payment,
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     let mut market_change = origin.clone();
@@ -1090,6 +1091,7 @@ fn check_market_change_merge() {
         ]),
         payment: payment.clone(),
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     let different = MarketChange {
@@ -1131,6 +1133,7 @@ fn check_market_change_merge() {
         ]),
         payment: empty_payment.clone(),
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     let mut market_change = origin.clone();
@@ -1208,7 +1211,8 @@ fn check_market_change_merge() {
                 (order_id5, order5_origin.clone()),
             ]),
             payment: payment.clone(),
-            ignore_unschedule_error: false
+            ignore_unschedule_error: false,
+            fees: DealFees::default()
         }
     );
 
@@ -1262,6 +1266,7 @@ fn check_market_change_merge() {
         ]),
         payment: empty_payment.clone(),
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     market_change = origin.clone();
@@ -1328,7 +1333,8 @@ fn check_market_change_merge() {
                 (order_id5, order5_origin),
             ]),
             payment: payment.clone(),
-            ignore_unschedule_error: false
+            ignore_unschedule_error: false,
+            fees: DealFees::default()
         }
     );
 
@@ -1382,6 +1388,7 @@ fn check_market_change_merge() {
         ]),
         payment: empty_payment.clone(),
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     market_change = origin.clone();
@@ -1437,7 +1444,8 @@ fn check_market_change_merge() {
                 (order_id3, order3_other),
             ]),
             payment,
-            ignore_unschedule_error: false
+            ignore_unschedule_error: false,
+            fees: DealFees::default()
         }
     );
 
@@ -1453,6 +1461,7 @@ fn check_market_change_merge() {
         to_force_update: BTreeMap::new(),
         payment: empty_payment,
         ignore_unschedule_error: false,
+        fees: DealFees::default(),
     };
 
     market_change = origin.clone();
@@ -1500,6 +1509,7 @@ fn check_market_change_count_of_executed_orders() {
             to_force_update: BTreeMap::<<Runtime as Config>::OrderId, LimitOrder::<Runtime>>::new(),
             payment: empty_payment.clone(),
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
         .count_of_executed_orders(),
         0
@@ -1536,6 +1546,7 @@ fn check_market_change_count_of_executed_orders() {
             to_force_update: BTreeMap::<<Runtime as Config>::OrderId, LimitOrder::<Runtime>>::new(),
             payment: empty_payment.clone(),
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
         .count_of_executed_orders(),
         1
@@ -1572,6 +1583,7 @@ fn check_market_change_count_of_executed_orders() {
             to_force_update: BTreeMap::<<Runtime as Config>::OrderId, LimitOrder::<Runtime>>::new(),
             payment: empty_payment.clone(),
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
         .count_of_executed_orders(),
         1
@@ -1620,6 +1632,7 @@ fn check_market_change_count_of_executed_orders() {
             to_force_update: BTreeMap::<<Runtime as Config>::OrderId, LimitOrder::<Runtime>>::new(),
             payment: empty_payment.clone(),
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
         .count_of_executed_orders(),
         2
@@ -1714,6 +1727,7 @@ fn check_market_change_count_of_executed_orders() {
             to_force_update: BTreeMap::<<Runtime as Config>::OrderId, LimitOrder::<Runtime>>::new(),
             payment: empty_payment,
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
         .count_of_executed_orders(),
         5
@@ -0,0 +1,199 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::weights::WeightInfo;
+use crate::{
+    Candle, CandleInterval, Candles, CandlesPruningCursor, Config, MomentOf, OrderBookId,
+    OrderPrice, OrderVolume, Pallet, Trade, Trades, TradesHead,
+};
+use assets::AssetIdOf;
+use common::PriceVariant;
+use frame_support::traits::{Get, Time};
+use frame_support::weights::{Weight, WeightMeter};
+use sp_runtime::traits::{CheckedDiv, UniqueSaturatedInto, Zero};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+    /// Weight of recording the trades with `makers_count` executed limit orders
+    pub fn record_trades_weight(makers_count: u32) -> Weight {
+        <T as Config>::WeightInfo::record_trade().saturating_mul(makers_count.into())
+    }
+
+    /// Stores the trade in the ring buffer of recent trades and updates the candles
+    pub fn record_trade(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        direction: PriceVariant,
+        price: OrderPrice,
+        amount: OrderVolume,
+    ) {
+        if amount.is_zero() {
+            return;
+        }
+
+        let now = T::Time::now();
+        let capacity = T::MaxTradesHistory::get();
+        if capacity > 0 {
+            <TradesHead<T>>::mutate(order_book_id, |(next_slot, count)| {
+                <Trades<T>>::insert(
+                    order_book_id,
+                    *next_slot,
+                    Trade {
+                        price,
+                        amount,
+                        direction,
+                        time: now,
+                    },
+                );
+                *next_slot = (*next_slot + 1) % capacity;
+                *count = (*count + 1).min(capacity);
+            });
+        }
+
+        let now_millis: u64 = now.unique_saturated_into();
+        for interval in CandleInterval::ALL {
+            let number = now_millis / interval.millis();
+            let slot = (number % interval.retention() as u64) as u32;
+            let start: MomentOf<T> = (number * interval.millis()).unique_saturated_into();
+
+            <Candles<T>>::mutate((order_book_id, interval), slot, |candle| {
+                if let Some(current) = candle.as_mut().filter(|candle| candle.start == start) {
+                    current.update(price, amount);
+                } else {
+                    // the slot is empty or contains the outdated candle
+                    *candle = Some(Candle::new(start, price, amount));
+                }
+            });
+        }
+    }
+
+    /// Returns up to `limit` recent trades of the order book, the latest trade goes first
+    pub fn recent_trades(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        limit: u32,
+    ) -> Vec<Trade<MomentOf<T>>> {
+        let capacity = T::MaxTradesHistory::get();
+        let (next_slot, count) = <TradesHead<T>>::get(order_book_id);
+        (1..=count.min(limit))
+            .filter_map(|i| {
+                let slot = (next_slot + capacity - i) % capacity;
+                <Trades<T>>::get(order_book_id, slot)
+            })
+            .collect()
+    }
+
+    /// Returns up to `limit` last candles of the order book in chronological order.
+    /// Intervals without trades have no candles.
+    pub fn candles(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        interval: CandleInterval,
+        limit: u32,
+    ) -> Vec<Candle<MomentOf<T>>> {
+        let now_millis: u64 = T::Time::now().unique_saturated_into();
+        let current = now_millis / interval.millis();
+        let count = limit.min(interval.retention()) as u64;
+
+        (0..count.min(current + 1))
+            .rev()
+            .filter_map(|i| {
+                let number = current - i;
+                let slot = (number % interval.retention() as u64) as u32;
+                let start: MomentOf<T> = (number * interval.millis()).unique_saturated_into();
+                <Candles<T>>::get((order_book_id, interval), slot)
+                    .filter(|candle| candle.start == start)
+            })
+            .collect()
+    }
+
+    /// Returns the volume-weighted average price of the trades for the last `period` milliseconds.
+    /// The most precise candles that cover the period are used, so the period is rounded up to the candle interval.
+    pub fn vwap(
+        order_book_id: &OrderBookId<AssetIdOf<T>, T::DEXId>,
+        period: MomentOf<T>,
+    ) -> Option<OrderPrice> {
+        let period: u64 = period.unique_saturated_into();
+        let interval = CandleInterval::ALL
+            .into_iter()
+            .find(|interval| period <= interval.millis() * interval.retention() as u64)
+            .unwrap_or(CandleInterval::Day);
+        let count = period.div_ceil(interval.millis()).max(1) as u32;
+
+        let (volume, quote_volume) = Self::candles(order_book_id, interval, count)
+            .into_iter()
+            .fold(
+                (OrderVolume::zero(), OrderVolume::zero()),
+                |(volume, quote_volume), candle| {
+                    (
+                        volume.saturating_add(candle.volume),
+                        quote_volume.saturating_add(candle.quote_volume),
+                    )
+                },
+            );
+
+        if volume.is_zero() {
+            return None;
+        }
+        quote_volume.checked_div(&volume)
+    }
+
+    /// Removes outdated candles while the weight allows.
+    /// Candles are overwritten by new ones, but if there are no trades in the order book,
+    /// outdated candles stay in the storage until they are pruned.
+    pub fn service_candles_pruning(weight: &mut WeightMeter) {
+        if !weight.can_accrue(<T as Config>::WeightInfo::prune_single_candle()) {
+            return;
+        }
+
+        let now_millis: u64 = T::Time::now().unique_saturated_into();
+
+        let mut last_checked = <CandlesPruningCursor<T>>::take();
+        let candles = match last_checked {
+            Some((key, slot)) => <Candles<T>>::iter_from(<Candles<T>>::hashed_key_for(key, slot)),
+            None => <Candles<T>>::iter(),
+        };
+
+        for ((order_book_id, interval), slot, candle) in candles {
+            if !weight.check_accrue(<T as Config>::WeightInfo::prune_single_candle()) {
+                // Will later continue from the last checked candle
+                if let Some(cursor) = last_checked {
+                    <CandlesPruningCursor<T>>::put(cursor);
+                }
+                return;
+            }
+
+            let start: u64 = candle.start.unique_saturated_into();
+            let end_of_retention =
+                start.saturating_add(interval.millis() * interval.retention() as u64);
+            if end_of_retention <= now_millis {
+                <Candles<T>>::remove((order_book_id, interval), slot);
+            }
+            last_checked = Some(((order_book_id, interval), slot));
+        }
+    }
+}
@@ -245,6 +245,15 @@ pub trait AlignmentScheduler {
     fn service_alignment(weight: &mut WeightMeter);
 }
 
+pub trait TriggerScheduler {
+    /// Check conditional orders and convert the triggered ones into regular orders
+    /// with weight limit to be set by `weight`.
+    ///
+    /// If the weight limit is reached, it should continue where it's left at the
+    /// next block.
+    fn service_triggers(weight: &mut WeightMeter);
+}
+
 pub trait Delegate<AccountId, AssetId, OrderId, DEXId, Moment> {
     fn emit_event(
         order_book_id: OrderBookId<AssetId, DEXId>,
@@ -31,10 +31,10 @@
 use crate::traits::{CurrencyLocker, CurrencyUnlocker};
 use codec::{Decode, Encode, MaxEncodedLen};
 use common::prelude::BalanceUnit;
-use common::{PriceVariant, TradingPair};
+use common::{Balance, PriceVariant, TradingPair};
 use frame_support::sp_runtime::DispatchError;
 use frame_support::{BoundedBTreeMap, BoundedVec};
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, Saturating, Zero};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero};
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::ops::{Add, Sub};
 
@@ -91,6 +91,256 @@ pub enum CancelReason {
     Aligned,
 }
 
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, Default, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TimeInForce {
+    /// The limit order stays in the order book until it is filled, cancelled or expired.
+    /// If the limit order crosses the spread, the crossing part is executed as a market order.
+    #[default]
+    GoodTillCancelled,
+
+    /// The limit order is executed immediately as far as possible at the limit price or better,
+    /// the rest of the amount is dropped.
+    ImmediateOrCancel,
+
+    /// The limit order is executed immediately at the limit price or better only if it can be filled completely,
+    /// otherwise the placement fails.
+    FillOrKill,
+
+    /// The limit order is placed only as a maker order. The placement fails if the limit order crosses the spread.
+    PostOnly,
+}
+
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ConditionalOrderKind {
+    /// Limits the loss: Sell order is triggered when the price falls to the trigger price,
+    /// Buy order is triggered when the price rises to the trigger price
+    StopLoss,
+
+    /// Fixes the profit: Sell order is triggered when the price rises to the trigger price,
+    /// Buy order is triggered when the price falls to the trigger price
+    TakeProfit,
+}
+
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TriggerPriceSource {
+    /// The price of the last executed deal in the order book
+    LastTrade,
+
+    /// The best bid price for Sell order and the best ask price for Buy order
+    BestBidAsk,
+}
+
+/// Basis points in 100%
+pub const BASIS_POINTS: u16 = 10_000;
+
+/// The number of days the trading volume is tracked for fee tiers
+pub const TRADING_VOLUME_DAYS: u32 = 30;
+
+/// Milliseconds in a day, used to split the trading volume into daily buckets
+pub const MILLISECS_PER_DAY: u128 = 86_400_000;
+
+/// Trading fees of the order book in basis points of the deal volume in the quote asset
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, Default, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TradingFees {
+    /// The fee for the limit orders executed by market orders
+    pub maker_fee_bps: u16,
+
+    /// The fee for the market orders and the parts of limit orders that cross the spread
+    pub taker_fee_bps: u16,
+}
+
+impl TradingFees {
+    pub fn is_zero(&self) -> bool {
+        self.maker_fee_bps == 0 && self.taker_fee_bps == 0
+    }
+
+    /// Returns the fee rate for the `role` reduced by the `discount_bps` of the fee tier
+    pub fn rate(&self, role: MarketRole, discount_bps: u16) -> u16 {
+        let rate = match role {
+            MarketRole::Maker => self.maker_fee_bps,
+            MarketRole::Taker => self.taker_fee_bps,
+        };
+        let discount = BASIS_POINTS.saturating_sub(discount_bps.min(BASIS_POINTS));
+        ((rate as u32 * discount as u32) / BASIS_POINTS as u32) as u16
+    }
+}
+
+/// The trading fees discount for accounts that have the trailing 30-day volume of at least `min_volume`
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeeTier {
+    /// The volume in the quote asset
+    pub min_volume: Balance,
+    pub discount_bps: u16,
+}
+
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TradingFeeDestination<AccountId, AssetId> {
+    /// The collected fees are transferred to the account
+    Account(AccountId),
+
+    /// The collected fees are exchanged to the asset and burned
+    BuyBackAndBurn(AssetId),
+}
+
+/// A trade between the taker and one maker. The price is the price of the maker's limit order.
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Trade<Moment> {
+    pub price: OrderPrice,
+
+    /// The traded volume of the base asset
+    pub amount: OrderVolume,
+
+    /// The direction of the taker
+    pub direction: PriceVariant,
+
+    pub time: Moment,
+}
+
+/// The duration of OHLCV candles
+#[derive(
+    Encode,
+    Decode,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Copy,
+    Clone,
+    Debug,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CandleInterval {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl CandleInterval {
+    pub const ALL: [Self; 3] = [Self::Minute, Self::Hour, Self::Day];
+
+    /// The duration of the candle in milliseconds
+    pub fn millis(&self) -> u64 {
+        match self {
+            Self::Minute => 60_000,
+            Self::Hour => 3_600_000,
+            Self::Day => 86_400_000,
+        }
+    }
+
+    /// The number of the last candles that are kept in the storage:
+    /// 1 day of minute candles, 30 days of hour candles and 1 year of day candles.
+    pub fn retention(&self) -> u32 {
+        match self {
+            Self::Minute => 1440,
+            Self::Hour => 720,
+            Self::Day => 365,
+        }
+    }
+}
+
+/// OHLCV candle, `start` is the beginning of the candle interval.
+/// The volume is in the base asset, the quote volume is in the quote asset.
+#[derive(
+    Encode, Decode, PartialEq, Eq, Copy, Clone, Debug, scale_info::TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Candle<Moment> {
+    pub start: Moment,
+    pub open: OrderPrice,
+    pub high: OrderPrice,
+    pub low: OrderPrice,
+    pub close: OrderPrice,
+    pub volume: OrderVolume,
+    pub quote_volume: OrderVolume,
+}
+
+impl<Moment> Candle<Moment> {
+    pub fn new(start: Moment, price: OrderPrice, amount: OrderVolume) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: amount,
+            quote_volume: price.checked_mul(&amount).unwrap_or_default(),
+        }
+    }
+
+    pub fn update(&mut self, price: OrderPrice, amount: OrderVolume) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume = self.volume.saturating_add(amount);
+        self.quote_volume = self
+            .quote_volume
+            .saturating_add(price.checked_mul(&amount).unwrap_or_default());
+    }
+}
+
+/// Trading fees of the deal and volumes of the deal participants, both in the quote asset.
+/// It is empty if the order book doesn't take fees.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct DealFees<AccountId> {
+    /// The fee paid by the taker, it is a part of `total_fee`
+    pub taker_fee: OrderVolume,
+
+    /// The fee paid by the taker and all the makers
+    pub total_fee: OrderVolume,
+
+    /// The traded volumes used to define fee tiers
+    pub volumes: BTreeMap<AccountId, OrderVolume>,
+
+    /// The maker fee debts after the deal, only changed debts are present
+    pub maker_fee_debts: BTreeMap<AccountId, OrderVolume>,
+}
+
+impl<AccountId: Ord + Clone> DealFees<AccountId> {
+    pub fn add_volume(&mut self, account: &AccountId, volume: OrderVolume) {
+        self.volumes
+            .entry(account.clone())
+            .and_modify(|current_volume| *current_volume = current_volume.saturating_add(volume))
+            .or_insert(volume);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.taker_fee = self.taker_fee.saturating_add(other.taker_fee);
+        self.total_fee = self.total_fee.saturating_add(other.total_fee);
+        for (account, volume) in other.volumes.iter() {
+            self.add_volume(account, *volume);
+        }
+        self.maker_fee_debts.extend(
+            other
+                .maker_fee_debts
+                .iter()
+                .map(|(account, debt)| (account.clone(), *debt)),
+        );
+    }
+}
+
 #[derive(
     Encode, Decode, Eq, PartialEq, Clone, Copy, Debug, scale_info::TypeInfo, MaxEncodedLen,
 )]
@@ -392,6 +642,9 @@ pub struct MarketChange<AccountId, AssetId, DEXId, OrderId, LimitOrder> {
 
     pub payment: Payment<AssetId, AccountId, DEXId>,
     pub ignore_unschedule_error: bool,
+
+    /// Trading fees that are already included into `payment`
+    pub fees: DealFees<AccountId>,
 }
 
 impl<AccountId, AssetId, DEXId, OrderId, LimitOrder>
@@ -415,6 +668,7 @@ where
             to_force_update: BTreeMap::new(),
             payment: Payment::new(order_book_id),
             ignore_unschedule_error: false,
+            fees: DealFees::default(),
         }
     }
 
@@ -446,6 +700,8 @@ where
         self.ignore_unschedule_error =
             self.ignore_unschedule_error || other.ignore_unschedule_error;
 
+        self.fees.merge(&other.fees);
+
         Some(())
     }
 
@@ -534,4 +790,13 @@ pub enum OrderBookEvent<AccountId, OrderId, Moment> {
         average_price: OrderPrice,
         to: Option<AccountId>,
     },
+
+    TradingFeesCollected {
+        amount: OrderVolume,
+    },
+
+    MakerFeeDebtUpdated {
+        owner_id: AccountId,
+        debt: OrderVolume,
+    },
 }
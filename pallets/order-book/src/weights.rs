@@ -74,6 +74,17 @@ pub trait WeightInfo {
 	fn service_expiration_base() -> Weight;
 	fn service_expiration_block_base() -> Weight;
 	fn service_single_expiration() -> Weight;
+	fn place_conditional_order() -> Weight;
+	fn cancel_conditional_order() -> Weight;
+	fn service_triggers_base() -> Weight;
+	fn service_single_trigger() -> Weight;
+	fn set_trading_fees() -> Weight;
+	fn set_fee_tiers() -> Weight;
+	fn set_trading_fee_destination() -> Weight;
+	fn service_fee_burning() -> Weight;
+	fn prune_single_candle() -> Weight;
+	fn prune_single_trading_volume() -> Weight;
+	fn record_trade() -> Weight;
 }
 
 /// Weights for order_book using the Substrate node and recommended hardware.
@@ -380,6 +391,168 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Storage: OrderBook OrderBooks (r:1 w:1)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: OrderBook UserConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook UserConditionalOrders (max_values: None, max_size: Some(1110), added: 3585, mode: MaxEncodedLen)
+	/// Storage: OrderBook ConditionalOrders (r:0 w:1)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Assets AssetInfos (r:1 w:0)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	fn place_conditional_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `37377`
+		//  Estimated: `37377`
+		// Minimum execution time: 98_641_000 picoseconds.
+		Weight::from_parts(98_641_000, 37377)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: OrderBook ConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: OrderBook UserConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook UserConditionalOrders (max_values: None, max_size: Some(1110), added: 3585, mode: MaxEncodedLen)
+	fn cancel_conditional_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `12623`
+		//  Estimated: `12623`
+		// Minimum execution time: 84_107_000 picoseconds.
+		Weight::from_parts(84_107_000, 12623)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: OrderBook TriggersCursor (r:1 w:1)
+	/// Proof: OrderBook TriggersCursor (max_values: Some(1), max_size: Some(88), added: 583, mode: MaxEncodedLen)
+	fn service_triggers_base() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `583`
+		//  Estimated: `583`
+		// Minimum execution time: 4_912_000 picoseconds.
+		Weight::from_parts(4_912_000, 583)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook ConditionalOrders (r:1 w:0)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: OrderBook LastTradePrice (r:1 w:0)
+	/// Proof: OrderBook LastTradePrice (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: OrderBook AggregatedBids (r:1 w:0)
+	/// Proof: OrderBook AggregatedBids (max_values: None, max_size: Some(34902), added: 37377, mode: MaxEncodedLen)
+	fn service_single_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `43235`
+		//  Estimated: `43235`
+		// Minimum execution time: 31_486_000 picoseconds.
+		Weight::from_parts(31_486_000, 43235)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+	}
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBookTradingFees (r:0 w:1)
+	/// Proof: OrderBook OrderBookTradingFees (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	fn set_trading_fees() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3703`
+		//  Estimated: `3703`
+		// Minimum execution time: 21_540_000 picoseconds.
+		Weight::from_parts(21_540_000, 3703)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeeTiers (r:0 w:1)
+	/// Proof: OrderBook FeeTiers (max_values: Some(1), max_size: Some(289), added: 784, mode: MaxEncodedLen)
+	fn set_fee_tiers() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_832_000 picoseconds.
+		Weight::from_parts(14_832_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeeDestination (r:0 w:1)
+	/// Proof: OrderBook FeeDestination (max_values: Some(1), max_size: Some(34), added: 529, mode: MaxEncodedLen)
+	fn set_trading_fee_destination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_407_000 picoseconds.
+		Weight::from_parts(12_407_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeesToBurn (r:1 w:1)
+	/// Proof: OrderBook FeesToBurn (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetInfos (r:4 w:1)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	fn service_fee_burning() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `29871`
+		//  Estimated: `29871`
+		// Minimum execution time: 247_318_000 picoseconds.
+		Weight::from_parts(247_318_000, 29871)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: OrderBook Candles (r:1 w:1)
+	/// Proof: OrderBook Candles (max_values: None, max_size: Some(328), added: 2803, mode: MaxEncodedLen)
+	fn prune_single_candle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3793`
+		//  Estimated: `3793`
+		// Minimum execution time: 9_873_000 picoseconds.
+		Weight::from_parts(9_873_000, 3793)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OrderBook TradingVolumes (r:1 w:1)
+	/// Proof: OrderBook TradingVolumes (max_values: None, max_size: Some(671), added: 3146, mode: MaxEncodedLen)
+	fn prune_single_trading_volume() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1014`
+		//  Estimated: `5139`
+		// Minimum execution time: 10_412_000 picoseconds.
+		Weight::from_parts(10_412_000, 5139)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OrderBook TradesHead (r:1 w:1)
+	/// Proof: OrderBook TradesHead (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: OrderBook Candles (r:3 w:3)
+	/// Proof: OrderBook Candles (max_values: None, max_size: Some(328), added: 2803, mode: MaxEncodedLen)
+	/// Storage: OrderBook Trades (r:0 w:1)
+	/// Proof: OrderBook Trades (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	fn record_trade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1022`
+		//  Estimated: `10948`
+		// Minimum execution time: 31_604_000 picoseconds.
+		Weight::from_parts(32_215_000, 10948)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -685,4 +858,166 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Storage: OrderBook OrderBooks (r:1 w:1)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: OrderBook UserConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook UserConditionalOrders (max_values: None, max_size: Some(1110), added: 3585, mode: MaxEncodedLen)
+	/// Storage: OrderBook ConditionalOrders (r:0 w:1)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Assets AssetInfos (r:1 w:0)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	fn place_conditional_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `37377`
+		//  Estimated: `37377`
+		// Minimum execution time: 98_641_000 picoseconds.
+		Weight::from_parts(98_641_000, 37377)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: OrderBook ConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: OrderBook UserConditionalOrders (r:1 w:1)
+	/// Proof: OrderBook UserConditionalOrders (max_values: None, max_size: Some(1110), added: 3585, mode: MaxEncodedLen)
+	fn cancel_conditional_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `12623`
+		//  Estimated: `12623`
+		// Minimum execution time: 84_107_000 picoseconds.
+		Weight::from_parts(84_107_000, 12623)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: OrderBook TriggersCursor (r:1 w:1)
+	/// Proof: OrderBook TriggersCursor (max_values: Some(1), max_size: Some(88), added: 583, mode: MaxEncodedLen)
+	fn service_triggers_base() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `583`
+		//  Estimated: `583`
+		// Minimum execution time: 4_912_000 picoseconds.
+		Weight::from_parts(4_912_000, 583)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook ConditionalOrders (r:1 w:0)
+	/// Proof: OrderBook ConditionalOrders (max_values: None, max_size: Some(247), added: 2722, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: OrderBook LastTradePrice (r:1 w:0)
+	/// Proof: OrderBook LastTradePrice (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: OrderBook AggregatedBids (r:1 w:0)
+	/// Proof: OrderBook AggregatedBids (max_values: None, max_size: Some(34902), added: 37377, mode: MaxEncodedLen)
+	fn service_single_trigger() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `43235`
+		//  Estimated: `43235`
+		// Minimum execution time: 31_486_000 picoseconds.
+		Weight::from_parts(31_486_000, 43235)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+	}
+	/// Storage: OrderBook OrderBooks (r:1 w:0)
+	/// Proof: OrderBook OrderBooks (max_values: None, max_size: Some(238), added: 2713, mode: MaxEncodedLen)
+	/// Storage: OrderBook OrderBookTradingFees (r:0 w:1)
+	/// Proof: OrderBook OrderBookTradingFees (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	fn set_trading_fees() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3703`
+		//  Estimated: `3703`
+		// Minimum execution time: 21_540_000 picoseconds.
+		Weight::from_parts(21_540_000, 3703)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeeTiers (r:0 w:1)
+	/// Proof: OrderBook FeeTiers (max_values: Some(1), max_size: Some(289), added: 784, mode: MaxEncodedLen)
+	fn set_fee_tiers() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_832_000 picoseconds.
+		Weight::from_parts(14_832_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeeDestination (r:0 w:1)
+	/// Proof: OrderBook FeeDestination (max_values: Some(1), max_size: Some(34), added: 529, mode: MaxEncodedLen)
+	fn set_trading_fee_destination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_407_000 picoseconds.
+		Weight::from_parts(12_407_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: OrderBook FeesToBurn (r:1 w:1)
+	/// Proof: OrderBook FeesToBurn (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetInfos (r:4 w:1)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	fn service_fee_burning() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `29871`
+		//  Estimated: `29871`
+		// Minimum execution time: 247_318_000 picoseconds.
+		Weight::from_parts(247_318_000, 29871)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: OrderBook Candles (r:1 w:1)
+	/// Proof: OrderBook Candles (max_values: None, max_size: Some(328), added: 2803, mode: MaxEncodedLen)
+	fn prune_single_candle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3793`
+		//  Estimated: `3793`
+		// Minimum execution time: 9_873_000 picoseconds.
+		Weight::from_parts(9_873_000, 3793)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OrderBook TradingVolumes (r:1 w:1)
+	/// Proof: OrderBook TradingVolumes (max_values: None, max_size: Some(671), added: 3146, mode: MaxEncodedLen)
+	fn prune_single_trading_volume() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1014`
+		//  Estimated: `5139`
+		// Minimum execution time: 10_412_000 picoseconds.
+		Weight::from_parts(10_412_000, 5139)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: OrderBook TradesHead (r:1 w:1)
+	/// Proof: OrderBook TradesHead (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: OrderBook Candles (r:3 w:3)
+	/// Proof: OrderBook Candles (max_values: None, max_size: Some(328), added: 2803, mode: MaxEncodedLen)
+	/// Storage: OrderBook Trades (r:0 w:1)
+	/// Proof: OrderBook Trades (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	fn record_trade() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1022`
+		//  Estimated: `10948`
+		// Minimum execution time: 31_604_000 picoseconds.
+		Weight::from_parts(32_215_000, 10948)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
 }
@@ -38,10 +38,11 @@
 use codec::Decode;
 use common::prelude::{Balance, SwapAmount};
 use common::{
-    balance, AssetInfoProvider, AssetName, AssetSymbol, DEXId, LiquiditySource,
+    balance, fixed, AssetInfoProvider, AssetName, AssetSymbol, DEXId, LiquiditySource,
     TradingPairSourceManager, DEFAULT_BALANCE_PRECISION, DOT, XOR,
 };
 use frame_benchmarking::benchmarks;
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 use hex_literal::hex;
 use pool_xyk::Call;
@@ -54,7 +55,10 @@ use pool_xyk::Pallet as XYKPool;
 #[cfg(test)]
 mod mock;
 pub struct Pallet<T: Config>(pool_xyk::Pallet<T>);
-pub trait Config: pool_xyk::Config {}
+pub trait Config:
+    pool_xyk::Config<RuntimeCall = <Self as frame_system::Config>::RuntimeCall>
+{
+}
 
 pub const DEX: DEXId = DEXId::Polkaswap;
 
@@ -266,6 +270,52 @@ benchmarks! {
         assert!(XYKPool::<T>::properties(asset_xor, asset_dot).is_some())
     }
 
+    set_pool_fee {
+        setup_benchmark::<T>()?;
+        let caller = alice::<T>();
+        let fee = <T as pool_xyk::Config>::GetMaxPoolFee::get();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        DEX.into(),
+        XOR.into(),
+        DOT.into(),
+        fee
+    )
+    verify {
+        assert_eq!(XYKPool::<T>::pool_fees(T::AssetId::from(XOR), T::AssetId::from(DOT)), Some(fee));
+    }
+
+    set_protocol_fee_share {
+    }: _(
+        RawOrigin::Root,
+        fixed!(0.5)
+    )
+    verify {
+        assert_eq!(XYKPool::<T>::protocol_fee_share(), fixed!(0.5));
+    }
+
+    flash_swap {
+        setup_benchmark::<T>()?;
+        let caller = alice::<T>();
+        let initial_dot_balance = Assets::<T>::free_balance(&DOT.into(), &caller).unwrap();
+        let call: <T as frame_system::Config>::RuntimeCall =
+            frame_system::Call::<T>::remark { remark: Vec::new() }.into();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        DEX.into(),
+        XOR.into(),
+        DOT.into(),
+        balance!(1),
+        balance!(10),
+        Box::new(call)
+    )
+    verify {
+        assert_eq!(
+            Into::<u128>::into(Assets::<T>::free_balance(&DOT.into(), &caller).unwrap()),
+            Into::<u128>::into(initial_dot_balance) + balance!(1)
+        );
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::ExtBuilder::default().build(),
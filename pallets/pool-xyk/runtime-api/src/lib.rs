@@ -0,0 +1,70 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use common::{Balance, Fixed};
+use sp_runtime::DispatchError;
+
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PoolFeeInfo {
+    /// Fraction of the swapped amount charged as the pool fee
+    pub fee: Fixed,
+    /// Share of the pool fee transferred to the treasury instead of the pool fee account
+    pub protocol_fee_share: Fixed,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait PoolXykApi<AssetId, DEXId, Moment> where
+        AssetId: Codec,
+        DEXId: Codec,
+        Moment: Codec
+    {
+        /// Time-weighted average amount of `output_asset_id` corresponding to a unit (1) of
+        /// `input_asset_id` over at least the last `period` milliseconds.
+        fn get_twap(
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            period: Moment,
+        ) -> Result<Balance, DispatchError>;
+
+        /// Fee of the pool of `asset_a` and `asset_b` in `dex_id` used by the quotes and swaps,
+        /// `None` if there is no such pool.
+        fn get_pool_fee(
+            dex_id: DEXId,
+            asset_a: AssetId,
+            asset_b: AssetId,
+        ) -> Option<PoolFeeInfo>;
+    }
+}
@@ -90,6 +90,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
             technical::Pallet::<T>::tech_account_id_to_account_id(&self.pool_account)?;
         // Check that pool account is valid.
         Pallet::<T>::is_pool_account_valid_for(self.source.0.asset, &self.pool_account)?;
+        Pallet::<T>::ensure_pool_is_not_locked(&pool_account_repr_sys)?;
 
         // Balance of source account for asset pair.
         let (balance_bs, balance_ts) = if abstract_checking {
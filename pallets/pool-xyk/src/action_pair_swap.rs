@@ -98,6 +98,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
             technical::Pallet::<T>::tech_account_id_to_account_id(&self.pool_account)?;
         // Check that pool account is valid.
         Pallet::<T>::is_pool_account_valid_for(self.source.asset, &self.pool_account)?;
+        Pallet::<T>::ensure_pool_is_not_locked(&pool_account_repr_sys)?;
 
         // Source balance of source account.
         let balance_ss = if abstract_checking {
@@ -135,6 +136,9 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
             _ => (),
         }
 
+        let pool_fee =
+            Pallet::<T>::pool_fee(base_asset_id, &self.source.asset, &self.destination.asset);
+
         // Recommended fee, will be used if fee is not specified or for checking if specified.
         let mut recom_fee: Option<Balance> = None;
 
@@ -145,7 +149,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
                     ensure!(sa > 0, Error::<T>::ZeroValueInAmountParameter);
                     ensure!(ta > 0, Error::<T>::ZeroValueInAmountParameter);
                     let y_out_pair = Pallet::<T>::calc_output_for_exact_input(
-                        T::GetFee::get(),
+                        pool_fee,
                         self.get_fee_from_destination.unwrap(),
                         &balance_st,
                         &balance_tt,
@@ -153,7 +157,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
                         true,
                     )?;
                     let x_in_pair = Pallet::<T>::calc_input_for_exact_output(
-                        T::GetFee::get(),
+                        pool_fee,
                         self.get_fee_from_destination.unwrap(),
                         &balance_st,
                         &balance_tt,
@@ -171,7 +175,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
                     match ta_bnd {
                         Bounds::Min(ta_min) => {
                             let (calculated, fee) = Pallet::<T>::calc_output_for_exact_input(
-                                T::GetFee::get(),
+                                pool_fee,
                                 self.get_fee_from_destination.unwrap(),
                                 &balance_st,
                                 &balance_tt,
@@ -197,7 +201,7 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
                     match sa_bnd {
                         Bounds::Max(sa_max) => {
                             let (calculated, fee) = Pallet::<T>::calc_input_for_exact_output(
-                                T::GetFee::get(),
+                                pool_fee,
                                 self.get_fee_from_destination.unwrap(),
                                 &balance_st,
                                 &balance_tt,
@@ -349,6 +353,7 @@ impl<T: Config> common::SwapAction<AccountIdOf<T>, TechAccountIdOf<T>, AssetIdOf
             let fee_account_repr_sys = technical::Pallet::<T>::tech_account_id_to_account_id(
                 self.fee_account.as_ref().unwrap(),
             )?;
+            let (lp_fee, protocol_fee) = Pallet::<T>::split_protocol_fee(self.fee.unwrap())?;
 
             if self.get_fee_from_destination.unwrap() {
                 technical::Pallet::<T>::transfer_in(
@@ -361,8 +366,20 @@ impl<T: Config> common::SwapAction<AccountIdOf<T>, TechAccountIdOf<T>, AssetIdOf
                     &self.destination.asset,
                     &self.pool_account,
                     &fee_account_repr_sys,
-                    self.fee.unwrap(),
+                    lp_fee,
                 )?;
+                if protocol_fee > 0 {
+                    let treasury_account_repr_sys =
+                        technical::Pallet::<T>::tech_account_id_to_account_id(
+                            &T::TreasuryTechAccountId::get(),
+                        )?;
+                    technical::Pallet::<T>::transfer_out(
+                        &self.destination.asset,
+                        &self.pool_account,
+                        &treasury_account_repr_sys,
+                        protocol_fee,
+                    )?;
+                }
                 technical::Pallet::<T>::transfer_out(
                     &self.destination.asset,
                     &self.pool_account,
@@ -380,8 +397,20 @@ impl<T: Config> common::SwapAction<AccountIdOf<T>, TechAccountIdOf<T>, AssetIdOf
                     &self.source.asset,
                     &source,
                     self.fee_account.as_ref().unwrap(),
-                    self.fee.unwrap(),
+                    lp_fee,
                 )?;
+                if protocol_fee > 0 {
+                    let treasury_account_repr_sys =
+                        technical::Pallet::<T>::tech_account_id_to_account_id(
+                            &T::TreasuryTechAccountId::get(),
+                        )?;
+                    assets::Pallet::<T>::transfer_from(
+                        &self.source.asset,
+                        &source,
+                        &treasury_account_repr_sys,
+                        protocol_fee,
+                    )?;
+                }
                 technical::Pallet::<T>::transfer_out(
                     &self.destination.asset,
                     &self.pool_account,
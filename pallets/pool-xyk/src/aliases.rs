@@ -40,6 +40,8 @@ pub type TechAccountIdOf<T> = <T as technical::Config>::TechAccountId;
 
 pub type DEXIdOf<T> = <T as common::Config>::DEXId;
 
+pub type MomentOf<T> = <T as pallet_timestamp::Config>::Moment;
+
 pub type PolySwapActionStructOf<T> =
     PolySwapAction<AssetIdOf<T>, AccountIdOf<T>, TechAccountIdOf<T>>;
 
@@ -0,0 +1,227 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use frame_support::dispatch::DispatchError;
+use frame_support::ensure;
+use frame_support::traits::Get;
+use sp_core::U256;
+use sp_runtime::traits::Dispatchable;
+
+use common::prelude::{Balance, FixedWrapper};
+use common::{
+    fixed_wrapper, AssetInfoProvider, DexInfoProvider, LiquiditySourceType,
+    LockedLiquiditySourcesManager,
+};
+
+use crate::aliases::{AccountIdOf, AssetIdOf, DEXIdOf, TechAccountIdOf};
+use crate::{Config, Error, FlashSwapLocks, Pallet, Properties};
+
+impl<T: Config> Pallet<T> {
+    /// Transfers `output_amount` of `output_asset_id` from the pool to `who`, dispatches `call`
+    /// on behalf of `who` and then collects from `who` the amount of `input_asset_id` which
+    /// restores the constant product of the pool reserves, plus the pool fee.
+    ///
+    /// Deposits, withdrawals and swaps in the pool are rejected while the call is dispatched.
+    /// The repayment is calculated from the pool reserves after the call, so the assets the call
+    /// has transferred to the pool directly are taken into account.
+    /// Returns the amount of input asset collected from `who`, fee included.
+    pub(crate) fn flash_swap_unchecked(
+        who: &AccountIdOf<T>,
+        dex_id: DEXIdOf<T>,
+        input_asset_id: &AssetIdOf<T>,
+        output_asset_id: &AssetIdOf<T>,
+        output_amount: Balance,
+        max_input_amount: Balance,
+        call: <T as Config>::RuntimeCall,
+    ) -> Result<Balance, DispatchError> {
+        ensure!(output_amount > 0, Error::<T>::ZeroValueInAmountParameter);
+        ensure!(
+            !T::LockedLiquiditySourcesManager::get().contains(&LiquiditySourceType::XYKPool),
+            Error::<T>::LiquiditySourceIsLocked
+        );
+        let base_asset_id = T::DexInfoProvider::get_dex_info(&dex_id)?.base_asset_id;
+        let (_, pool_tech_acc) =
+            Self::tech_account_from_dex_and_asset_pair(dex_id, *input_asset_id, *output_asset_id)?;
+        let target_asset_id = if base_asset_id == *input_asset_id {
+            output_asset_id
+        } else {
+            input_asset_id
+        };
+        ensure!(
+            Properties::<T>::contains_key(&base_asset_id, target_asset_id),
+            Error::<T>::PoolDoesNotExist
+        );
+        let fee_tech_acc = Self::get_fee_account(&pool_tech_acc)?;
+        let pool_acc = technical::Pallet::<T>::tech_account_id_to_account_id(&pool_tech_acc)?;
+        Self::ensure_pool_is_not_locked(&pool_acc)?;
+
+        let reserve_input = assets::Pallet::<T>::free_balance(input_asset_id, &pool_acc)?;
+        let reserve_output = assets::Pallet::<T>::free_balance(output_asset_id, &pool_acc)?;
+        if reserve_input == 0 && reserve_output == 0 {
+            return Err(Error::<T>::PoolIsEmpty.into());
+        } else if reserve_input == 0 || reserve_output == 0 {
+            return Err(Error::<T>::PoolIsInvalid.into());
+        }
+        let invariant = U256::from(reserve_input) * U256::from(reserve_output);
+
+        let get_fee_from_destination =
+            Self::decide_is_fee_from_destination(&base_asset_id, input_asset_id, output_asset_id)?;
+        let fee_fraction = Self::pool_fee(&base_asset_id, input_asset_id, output_asset_id);
+
+        if get_fee_from_destination {
+            let output_fee = Self::flash_swap_fee(output_amount, fee_fraction)?;
+            ensure!(
+                output_amount.saturating_add(output_fee) < reserve_output,
+                Error::<T>::TargetBalanceIsNotLargeEnough
+            );
+            Self::pay_flash_swap_fee_from_pool(
+                output_asset_id,
+                &pool_tech_acc,
+                &fee_tech_acc,
+                output_fee,
+            )?;
+        } else {
+            ensure!(
+                output_amount < reserve_output,
+                Error::<T>::TargetBalanceIsNotLargeEnough
+            );
+        }
+        technical::Pallet::<T>::transfer_out(output_asset_id, &pool_tech_acc, who, output_amount)?;
+
+        // The pool stays locked while the call is dispatched, so the call can't move the
+        // reserves the repayment is calculated from, e.g. by depositing borrowed liquidity.
+        FlashSwapLocks::<T>::insert(&pool_acc, ());
+        let dispatch_result = call.dispatch(frame_system::RawOrigin::Signed(who.clone()).into());
+        FlashSwapLocks::<T>::remove(&pool_acc);
+        dispatch_result.map_err(|e| e.error)?;
+
+        let balance_input = assets::Pallet::<T>::free_balance(input_asset_id, &pool_acc)?;
+        let balance_output = assets::Pallet::<T>::free_balance(output_asset_id, &pool_acc)?;
+        ensure!(
+            balance_output > 0,
+            Error::<T>::PoolBecameInvalidAfterOperation
+        );
+        let balance_output = U256::from(balance_output);
+        // Round up, so that the invariant is never decreased.
+        let required_input = (invariant + balance_output - 1) / balance_output;
+        ensure!(
+            required_input <= U256::from(Balance::MAX),
+            Error::<T>::PoolBecameInvalidAfterOperation
+        );
+        let required_input = required_input.low_u128();
+        let input_to_pool = required_input.saturating_sub(balance_input);
+        let input_fee = if get_fee_from_destination {
+            0
+        } else {
+            Self::flash_swap_fee(required_input.saturating_sub(reserve_input), fee_fraction)?
+        };
+        let total_input = input_to_pool.saturating_add(input_fee);
+        ensure!(
+            total_input <= max_input_amount,
+            Error::<T>::CalculatedValueIsOutOfDesiredBounds
+        );
+        ensure!(
+            assets::Pallet::<T>::free_balance(input_asset_id, who)? >= total_input,
+            Error::<T>::FlashSwapIsNotRepaid
+        );
+
+        if input_to_pool > 0 {
+            technical::Pallet::<T>::transfer_in(
+                input_asset_id,
+                who,
+                &pool_tech_acc,
+                input_to_pool,
+            )?;
+        }
+        if input_fee > 0 {
+            let (lp_fee, protocol_fee) = Self::split_protocol_fee(input_fee)?;
+            technical::Pallet::<T>::transfer_in(input_asset_id, who, &fee_tech_acc, lp_fee)?;
+            if protocol_fee > 0 {
+                let treasury_acc = technical::Pallet::<T>::tech_account_id_to_account_id(
+                    &T::TreasuryTechAccountId::get(),
+                )?;
+                assets::Pallet::<T>::transfer_from(
+                    input_asset_id,
+                    who,
+                    &treasury_acc,
+                    protocol_fee,
+                )?;
+            }
+        }
+
+        let balance_input = assets::Pallet::<T>::free_balance(input_asset_id, &pool_acc)?;
+        let balance_output = assets::Pallet::<T>::free_balance(output_asset_id, &pool_acc)?;
+        ensure!(
+            U256::from(balance_input) * U256::from(balance_output) >= invariant,
+            Error::<T>::FlashSwapIsNotRepaid
+        );
+        Self::update_reserves(
+            &base_asset_id,
+            input_asset_id,
+            output_asset_id,
+            (&balance_input, &balance_output),
+        );
+        Ok(total_input)
+    }
+
+    /// Fee which is charged on top of `amount`, i.e. `amount / (1 - fee) - amount`.
+    fn flash_swap_fee(
+        amount: Balance,
+        fee_fraction: common::Fixed,
+    ) -> Result<Balance, DispatchError> {
+        let amount_with_fee = FixedWrapper::from(amount) / (fixed_wrapper!(1) - fee_fraction);
+        let amount_with_fee = amount_with_fee
+            .try_into_balance()
+            .map_err(|_| Error::<T>::FixedWrapperCalculationFailed)?;
+        Ok(amount_with_fee.saturating_sub(amount))
+    }
+
+    fn pay_flash_swap_fee_from_pool(
+        asset_id: &AssetIdOf<T>,
+        pool_tech_acc: &TechAccountIdOf<T>,
+        fee_tech_acc: &TechAccountIdOf<T>,
+        fee: Balance,
+    ) -> Result<(), DispatchError> {
+        let (lp_fee, protocol_fee) = Self::split_protocol_fee(fee)?;
+        let fee_acc = technical::Pallet::<T>::tech_account_id_to_account_id(fee_tech_acc)?;
+        technical::Pallet::<T>::transfer_out(asset_id, pool_tech_acc, &fee_acc, lp_fee)?;
+        if protocol_fee > 0 {
+            let treasury_acc = technical::Pallet::<T>::tech_account_id_to_account_id(
+                &T::TreasuryTechAccountId::get(),
+            )?;
+            technical::Pallet::<T>::transfer_out(
+                asset_id,
+                pool_tech_acc,
+                &treasury_acc,
+                protocol_fee,
+            )?;
+        }
+        Ok(())
+    }
+}
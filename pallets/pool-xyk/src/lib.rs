@@ -52,7 +52,7 @@ use common::{
 
 mod aliases;
 use aliases::{
-    AccountIdOf, AssetIdOf, DEXIdOf, DepositLiquidityActionOf, PairSwapActionOf,
+    AccountIdOf, AssetIdOf, DEXIdOf, DepositLiquidityActionOf, MomentOf, PairSwapActionOf,
     PolySwapActionStructOf, TechAccountIdOf, TechAssetIdOf, WithdrawLiquidityActionOf,
 };
 use sp_std::collections::btree_set::BTreeSet;
@@ -69,9 +69,13 @@ mod tests;
 #[macro_use]
 mod macros;
 
+mod flash_swap;
 mod math;
+mod twap;
 mod utils;
 
+pub use twap::PriceCumulative;
+
 mod bounds;
 use bounds::*;
 
@@ -170,16 +174,8 @@ impl<T: Config> Pallet<T> {
         fees_account_id: &T::AccountId,
     ) -> DispatchResult {
         let dex_info = T::DexInfoProvider::get_dex_info(dex_id)?;
-        let (sorted_asset_a, sorted_asset_b) = if dex_info.base_asset_id == *asset_a {
-            (asset_a, asset_b)
-        } else if dex_info.base_asset_id == *asset_b {
-            (asset_b, asset_a)
-        } else {
-            let hash_key = common::comm_merkle_op(asset_a, asset_b);
-            let (asset_a_pair, asset_b_pair) =
-                common::sort_with_hash_key(hash_key, (asset_a, &()), (asset_b, &()));
-            (asset_a_pair.0, asset_b_pair.0)
-        };
+        let (sorted_asset_a, sorted_asset_b) =
+            Self::sort_pool_assets(&dex_info.base_asset_id, asset_a, asset_b);
 
         T::TradingPairSourceManager::enable_source_for_trading_pair(
             dex_id,
@@ -195,6 +191,54 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Sorts assets of a pool in the same order as they are stored in `Properties`.
+    fn sort_pool_assets<'a>(
+        base_asset_id: &T::AssetId,
+        asset_a: &'a T::AssetId,
+        asset_b: &'a T::AssetId,
+    ) -> (&'a T::AssetId, &'a T::AssetId) {
+        if base_asset_id == asset_a {
+            (asset_a, asset_b)
+        } else if base_asset_id == asset_b {
+            (asset_b, asset_a)
+        } else {
+            let hash_key = common::comm_merkle_op(asset_a, asset_b);
+            let (asset_a_pair, asset_b_pair) =
+                common::sort_with_hash_key(hash_key, (asset_a, &()), (asset_b, &()));
+            (asset_a_pair.0, asset_b_pair.0)
+        }
+    }
+
+    /// Fee of the pool of `asset_a` and `asset_b`, or the default one if the pool has no own fee.
+    pub fn pool_fee(
+        base_asset_id: &T::AssetId,
+        asset_a: &T::AssetId,
+        asset_b: &T::AssetId,
+    ) -> Fixed {
+        let (sorted_asset_a, sorted_asset_b) =
+            Self::sort_pool_assets(base_asset_id, asset_a, asset_b);
+        PoolFees::<T>::get(sorted_asset_a, sorted_asset_b).unwrap_or_else(T::GetFee::get)
+    }
+
+    /// Fee of the existing pool of `asset_a` and `asset_b` in `dex_id` and the share of it
+    /// transferred to the treasury.
+    pub fn get_pool_fee(
+        dex_id: &T::DEXId,
+        asset_a: &T::AssetId,
+        asset_b: &T::AssetId,
+    ) -> Option<(Fixed, Fixed)> {
+        let base_asset_id = T::DexInfoProvider::get_dex_info(dex_id).ok()?.base_asset_id;
+        let (sorted_asset_a, sorted_asset_b) =
+            Self::sort_pool_assets(&base_asset_id, asset_a, asset_b);
+        if !Properties::<T>::contains_key(sorted_asset_a, sorted_asset_b) {
+            return None;
+        }
+        Some((
+            PoolFees::<T>::get(sorted_asset_a, sorted_asset_b).unwrap_or_else(T::GetFee::get),
+            Self::protocol_fee_share(),
+        ))
+    }
+
     fn update_reserves(
         base_asset_id: &T::AssetId,
         asset_a: &T::AssetId,
@@ -202,9 +246,11 @@ impl<T: Config> Pallet<T> {
         balance_pair: (&Balance, &Balance),
     ) {
         if base_asset_id == asset_a {
+            Self::update_price_cumulative(asset_a, asset_b);
             Reserves::<T>::insert(asset_a, asset_b, (balance_pair.0, balance_pair.1));
             T::OnPoolReservesChanged::reserves_changed(asset_b);
         } else if base_asset_id == asset_b {
+            Self::update_price_cumulative(asset_b, asset_a);
             Reserves::<T>::insert(asset_b, asset_a, (balance_pair.1, balance_pair.0));
             T::OnPoolReservesChanged::reserves_changed(asset_a);
         } else {
@@ -214,6 +260,7 @@ impl<T: Config> Pallet<T> {
                 (asset_a, balance_pair.0),
                 (asset_b, balance_pair.1),
             );
+            Self::update_price_cumulative(pair_u.0, pair_v.0);
             Reserves::<T>::insert(pair_u.0, pair_v.0, (pair_u.1, pair_v.1));
             T::OnPoolReservesChanged::reserves_changed(asset_a);
             T::OnPoolReservesChanged::reserves_changed(asset_b);
@@ -407,11 +454,14 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
             output_asset_id,
         )?;
 
+        let pool_fee =
+            Pallet::<T>::pool_fee(&dex_info.base_asset_id, input_asset_id, output_asset_id);
+
         // Calculate quote.
         match amount {
             QuoteAmount::WithDesiredInput { desired_amount_in } => {
                 let (calculated, fee) = Pallet::<T>::calc_output_for_exact_input(
-                    T::GetFee::get(),
+                    pool_fee,
                     get_fee_from_destination,
                     &reserve_input,
                     &reserve_output,
@@ -422,7 +472,7 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
             }
             QuoteAmount::WithDesiredOutput { desired_amount_out } => {
                 let (calculated, fee) = Pallet::<T>::calc_input_for_exact_output(
-                    T::GetFee::get(),
+                    pool_fee,
                     get_fee_from_destination,
                     &reserve_input,
                     &reserve_output,
@@ -550,7 +600,7 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
 
         let input_price_wrt_output = FixedWrapper::from(reserve_output) / reserve_input;
         let fee_fraction = if deduce_fee {
-            T::GetFee::get()
+            Pallet::<T>::pool_fee(&dex_info.base_asset_id, input_asset_id, output_asset_id)
         } else {
             common::Fixed::default()
         };
@@ -642,11 +692,17 @@ use sp_runtime::traits::Zero;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use common::{AccountIdOf, EnabledSourcesManager, Fixed, GetMarketInfo, OnPoolCreated};
+    use common::{
+        AccountIdOf, EnabledSourcesManager, Fixed, GetMarketInfo, LockedLiquiditySourcesManager,
+        OnPoolCreated,
+    };
+    use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
     use frame_support::pallet_prelude::*;
     use frame_support::traits::StorageVersion;
     use frame_system::pallet_prelude::*;
     use orml_traits::GetByKey;
+    use sp_runtime::traits::Dispatchable;
+    use sp_std::boxed::Box;
 
     // TODO: #395 use AssetInfoProvider instead of assets pallet
     #[pallet::config]
@@ -662,6 +718,11 @@ pub mod pallet {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+        /// The call which is dispatched in the middle of a flash swap.
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo;
+
         //TODO: implement and use + Into<SwapActionOf<T> for this types.
         type PairSwapAction: common::SwapAction<AccountIdOf<Self>, TechAccountIdOf<Self>, AssetIdOf<Self>, Self>
             + Parameter;
@@ -677,13 +738,25 @@ pub mod pallet {
         type TradingPairSourceManager: TradingPairSourceManager<Self::DEXId, Self::AssetId>;
         type DexInfoProvider: DexInfoProvider<Self::DEXId, DEXInfo<Self::AssetId>>;
         type EnabledSourcesManager: EnabledSourcesManager<Self::DEXId, Self::AssetId>;
+        type LockedLiquiditySourcesManager: LockedLiquiditySourcesManager<LiquiditySourceType>;
         type EnsureTradingPairExists: EnsureTradingPairExists<
             Self::DEXId,
             Self::AssetId,
             DispatchError,
         >;
         type XSTMarketInfo: GetMarketInfo<Self::AssetId>;
+        /// Fee of the pools which don't have their own fee
         type GetFee: Get<Fixed>;
+        /// Minimum fee which can be set for a pool
+        type GetMinPoolFee: Get<Fixed>;
+        /// Maximum fee which can be set for a pool
+        type GetMaxPoolFee: Get<Fixed>;
+        /// Technical account which receives the protocol share of the pool fees
+        type TreasuryTechAccountId: Get<TechAccountIdOf<Self>>;
+        /// Maximum number of price observations kept for each pool to calculate TWAP
+        type MaxPriceObservations: Get<u32>;
+        /// Minimum time between two price observations of a pool
+        type PriceObservationPeriod: Get<MomentOf<Self>>;
         type OnPoolCreated: OnPoolCreated<AccountId = AccountIdOf<Self>, DEXId = DEXIdOf<Self>>;
         type OnPoolReservesChanged: OnPoolReservesChanged<Self::AssetId>;
         /// Weight information for extrinsics in this pallet.
@@ -844,10 +917,129 @@ pub mod pallet {
                 let pool_account =
                     technical::Pallet::<T>::tech_account_id_to_account_id(&pool_account)?;
                 T::OnPoolCreated::on_pool_created(fees_ta_repr, dex_id, pool_account)?;
+                let base_asset_id = T::DexInfoProvider::get_dex_info(&dex_id)?.base_asset_id;
+                let (sorted_asset_a, sorted_asset_b) =
+                    Pallet::<T>::sort_pool_assets(&base_asset_id, &asset_a, &asset_b);
+                PoolCreators::<T>::insert(sorted_asset_a, sorted_asset_b, source);
                 Self::deposit_event(Event::PoolIsInitialized(ta_repr));
                 Ok(().into())
             })
         }
+
+        /// Sets the fee of the pool. Can be called by the root or the account which has
+        /// initialized the pool. The fee must be within `GetMinPoolFee` and `GetMaxPoolFee`.
+        ///
+        /// - `dex_id`: ID of the DEX of the pool,
+        /// - `asset_a`: ID of an asset of the pool,
+        /// - `asset_b`: ID of the other asset of the pool,
+        /// - `fee`: the fee fraction of the swapped amounts.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_pool_fee())]
+        pub fn set_pool_fee(
+            origin: OriginFor<T>,
+            dex_id: DEXIdOf<T>,
+            asset_a: AssetIdOf<T>,
+            asset_b: AssetIdOf<T>,
+            fee: Fixed,
+        ) -> DispatchResultWithPostInfo {
+            let base_asset_id = T::DexInfoProvider::get_dex_info(&dex_id)?.base_asset_id;
+            let (base_asset_id, target_asset_id) =
+                Pallet::<T>::sort_pool_assets(&base_asset_id, &asset_a, &asset_b);
+            ensure!(
+                Properties::<T>::contains_key(base_asset_id, target_asset_id),
+                Error::<T>::PoolDoesNotExist
+            );
+            if ensure_root(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(
+                    PoolCreators::<T>::get(base_asset_id, target_asset_id) == Some(who),
+                    DispatchError::BadOrigin
+                );
+            }
+            ensure!(
+                fee >= T::GetMinPoolFee::get() && fee <= T::GetMaxPoolFee::get(),
+                Error::<T>::InvalidPoolFee
+            );
+            PoolFees::<T>::insert(base_asset_id, target_asset_id, fee);
+            Self::deposit_event(Event::PoolFeeUpdated(
+                dex_id,
+                *base_asset_id,
+                *target_asset_id,
+                fee,
+            ));
+            Ok(().into())
+        }
+
+        /// Sets the share of the pool fees which is transferred to the treasury instead of
+        /// the fee accounts of the pools.
+        ///
+        /// - `share`: the share of the fees, from 0 to 1.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_protocol_fee_share())]
+        pub fn set_protocol_fee_share(
+            origin: OriginFor<T>,
+            share: Fixed,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                share >= Fixed::ZERO && share <= Fixed::ONE,
+                Error::<T>::InvalidProtocolFeeShare
+            );
+            ProtocolFeeShare::<T>::put(share);
+            Self::deposit_event(Event::ProtocolFeeShareUpdated(share));
+            Ok(().into())
+        }
+
+        /// Transfers `output_amount` of `output_asset_id` from the pool to the caller,
+        /// dispatches `call` on behalf of the caller and then collects the amount of
+        /// `input_asset_id` which restores the constant product of the pool plus the pool fee.
+        /// The whole operation is reverted if the caller can't repay it.
+        ///
+        /// - `dex_id`: ID of the DEX of the pool,
+        /// - `input_asset_id`: ID of the asset which is paid back to the pool,
+        /// - `output_asset_id`: ID of the asset which is lent by the pool,
+        /// - `output_amount`: the amount of the output asset to lend,
+        /// - `max_input_amount`: the maximum amount of the input asset to pay, fee included,
+        /// - `call`: the call to dispatch with the lent amount.
+        #[pallet::call_index(5)]
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                <T as Config>::WeightInfo::flash_swap().saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+            )
+        })]
+        pub fn flash_swap(
+            origin: OriginFor<T>,
+            dex_id: DEXIdOf<T>,
+            input_asset_id: AssetIdOf<T>,
+            output_asset_id: AssetIdOf<T>,
+            output_amount: Balance,
+            max_input_amount: Balance,
+            call: Box<<T as Config>::RuntimeCall>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let input_amount = common::with_transaction(|| {
+                Pallet::<T>::flash_swap_unchecked(
+                    &who,
+                    dex_id,
+                    &input_asset_id,
+                    &output_asset_id,
+                    output_amount,
+                    max_input_amount,
+                    *call,
+                )
+            })?;
+            Self::deposit_event(Event::FlashSwap(
+                who,
+                dex_id,
+                input_asset_id,
+                output_asset_id,
+                input_amount,
+                output_amount,
+            ));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -855,6 +1047,19 @@ pub mod pallet {
     pub enum Event<T: Config> {
         // New pool for particular pair was initialized. [Reserves Account Id]
         PoolIsInitialized(AccountIdOf<T>),
+        // Fee of the pool was updated. [DEX Id, Base Asset Id, Target Asset Id, Fee]
+        PoolFeeUpdated(DEXIdOf<T>, AssetIdOf<T>, AssetIdOf<T>, Fixed),
+        // Protocol share of the pool fees was updated. [Share]
+        ProtocolFeeShareUpdated(Fixed),
+        // Flash swap was repaid. [Who, DEX Id, Input Asset Id, Output Asset Id, Input Amount, Output Amount]
+        FlashSwap(
+            AccountIdOf<T>,
+            DEXIdOf<T>,
+            AssetIdOf<T>,
+            AssetIdOf<T>,
+            Balance,
+            Balance,
+        ),
     }
 
     #[pallet::error]
@@ -984,6 +1189,22 @@ pub mod pallet {
         NotEnoughLiquidityOutOfFarming,
         /// Cannot create a pool with restricted target asset
         TargetAssetIsRestricted,
+        /// The pool doesn't exist
+        PoolDoesNotExist,
+        /// The pool fee is out of the allowed limits
+        InvalidPoolFee,
+        /// The protocol fee share must be from 0 to 1
+        InvalidProtocolFeeShare,
+        /// TWAP period must be greater than zero
+        InvalidTwapPeriod,
+        /// The pool has no price observations which cover the requested TWAP period
+        NotEnoughPriceObservations,
+        /// The liquidity source is locked
+        LiquiditySourceIsLocked,
+        /// The pool reserves with the fee were not restored after the flash swap
+        FlashSwapIsNotRepaid,
+        /// The pool has lent liquidity in an ongoing flash swap
+        PoolIsLockedByFlashSwap,
     }
 
     /// Updated after last liquidity change operation.
@@ -1041,4 +1262,62 @@ pub mod pallet {
         T::AssetId,
         (T::AccountId, T::AccountId),
     >;
+    /// Fees of particular pools, `GetFee` is used for the pools which are not here.
+    /// Base Asset => Target Asset => Fee
+    #[pallet::storage]
+    #[pallet::getter(fn pool_fees)]
+    pub type PoolFees<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AssetId, Blake2_128Concat, T::AssetId, Fixed>;
+
+    /// Accounts which have initialized particular pools and are allowed to change their fees.
+    /// Base Asset => Target Asset => Creator Account Id
+    #[pallet::storage]
+    #[pallet::getter(fn pool_creators)]
+    pub type PoolCreators<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AssetId,
+        T::AccountId,
+    >;
+
+    /// Share of the pool fees transferred to the treasury.
+    #[pallet::storage]
+    #[pallet::getter(fn protocol_fee_share)]
+    pub type ProtocolFeeShare<T: Config> = StorageValue<_, Fixed, ValueQuery>;
+
+    /// Cumulative prices of the pools as of their last update.
+    /// Base Asset => Target Asset => Cumulative Prices
+    #[pallet::storage]
+    #[pallet::getter(fn price_cumulatives)]
+    pub type PriceCumulatives<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AssetId,
+        PriceCumulative<MomentOf<T>>,
+    >;
+
+    /// Snapshots of the cumulative prices of the pools, made at least `PriceObservationPeriod`
+    /// apart, used as the starting points of TWAP periods. The oldest ones are dropped.
+    /// Base Asset => Target Asset => Observations
+    #[pallet::storage]
+    #[pallet::getter(fn price_observations)]
+    pub type PriceObservations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AssetId,
+        BoundedVec<PriceCumulative<MomentOf<T>>, T::MaxPriceObservations>,
+        ValueQuery,
+    >;
+
+    /// Pools which have lent liquidity in an ongoing flash swap. Deposits, withdrawals and swaps
+    /// in these pools are rejected until the flash swap is repaid.
+    /// Pool Account Id => ()
+    #[pallet::storage]
+    pub type FlashSwapLocks<T: Config> = StorageMap<_, Identity, AccountIdOf<T>, ()>;
 }
@@ -76,6 +76,13 @@ parameter_types! {
     pub const GetBurnUpdateFrequency: BlockNumber = 14400;
     pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
     pub GetFee: Fixed = fixed!(0.003);
+    pub GetMinPoolFee: Fixed = fixed!(0.0005);
+    pub GetMaxPoolFee: Fixed = fixed!(0.01);
+    pub GetTreasuryTechAccountId: TechAccountId = {
+        TechAccountId::from_generic_pair(b"treasury".to_vec(), b"main".to_vec())
+    };
+    pub const MaxPriceObservations: u32 = 3;
+    pub const PriceObservationPeriod: Moment = 60_000;
     pub const MinimumPeriod: u64 = 5;
 }
 
@@ -281,6 +288,7 @@ parameter_types! {
     pub const GetSyntheticBaseBuySellLimit: Balance = balance!(10000000000);
     pub const GetBandRateStalePeriod: Moment = 60*5*1000; // 5 minutes
     pub const GetBandRateStaleBlockPeriod: u64 = 600; // 1 hour
+    pub const GetBandOracleVariant: common::Oracle = common::Oracle::BandChainFeed;
 }
 
 impl band::Config for Runtime {
@@ -291,8 +299,11 @@ impl band::Config for Runtime {
     type Time = Timestamp;
     type GetBandRateStalePeriod = GetBandRateStalePeriod;
     type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetBandOracleVariant;
+    type Currency = Balances;
     type OnSymbolDisabledHook = ();
     type MaxRelaySymbols = frame_support::traits::ConstU32<100>;
+    type MaxRelayers = frame_support::traits::ConstU32<100>;
 }
 
 impl oracle_proxy::Config for Runtime {
@@ -300,6 +311,8 @@ impl oracle_proxy::Config for Runtime {
     type WeightInfo = ();
     type Symbol = <Runtime as band::Config>::Symbol;
     type BandChainOracle = band::Pallet<Runtime>;
+    type AdditionalFeeds = ();
+    type Time = Timestamp;
 }
 
 impl xst::Config for Runtime {
@@ -331,6 +344,7 @@ parameter_type_with_key! {
 impl Config for Runtime {
     const MIN_XOR: Balance = balance!(0.007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = crate::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction = crate::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
     type WithdrawLiquidityAction =
@@ -341,7 +355,13 @@ impl Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
     type EnabledSourcesManager = trading_pair::Pallet<Runtime>;
+    type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type GetFee = GetFee;
+    type GetMinPoolFee = GetMinPoolFee;
+    type GetMaxPoolFee = GetMaxPoolFee;
+    type TreasuryTechAccountId = GetTreasuryTechAccountId;
+    type MaxPriceObservations = MaxPriceObservations;
+    type PriceObservationPeriod = PriceObservationPeriod;
     type OnPoolCreated = PswapDistribution;
     type OnPoolReservesChanged = ();
     type WeightInfo = ();
@@ -32,11 +32,14 @@ use core::str::FromStr;
 
 use common::prelude::{FixedWrapper, QuoteAmount, SwapAmount, SwapOutcome};
 use common::{
-    balance, fixed, AssetInfoProvider, AssetName, AssetSymbol, Balance, LiquiditySource,
-    LiquiditySourceType, Oracle, ToFeeAccount, TradingPairSourceManager, DEFAULT_BALANCE_PRECISION,
+    balance, fixed, AssetInfoProvider, AssetName, AssetSymbol, Balance, GetTwap, LiquiditySource,
+    LiquiditySourceType, LockedLiquiditySourcesManager, Oracle, ToFeeAccount,
+    TradingPairSourceManager, DEFAULT_BALANCE_PRECISION,
 };
 use frame_support::assert_noop;
 use frame_support::assert_ok;
+use frame_support::traits::Get;
+use sp_runtime::DispatchError;
 
 use crate::mock::*;
 use crate::{PoolProviders, TotalIssuances};
@@ -2394,3 +2397,771 @@ fn initialize_pool_with_synthetics() {
         ));
     });
 }
+
+#[test]
+fn set_pool_fee_should_change_swap_fee() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, fee_repr: AccountId| {
+            assert_eq!(PoolXYK::pool_fee(&gt, &gt, &bp), fixed!(0.003));
+            assert_eq!(
+                PoolXYK::get_pool_fee(&dex_id, &gt, &bp),
+                Some((fixed!(0.003), fixed!(0)))
+            );
+            assert_eq!(
+                PoolXYK::get_pool_fee(&dex_id, &GoldenTicket.into(), &RedPepper.into()),
+                None
+            );
+            assert_ok!(PoolXYK::set_pool_fee(
+                RuntimeOrigin::signed(BOB()),
+                dex_id,
+                BlackPepper.into(),
+                GoldenTicket.into(),
+                fixed!(0.01)
+            ));
+            assert_eq!(PoolXYK::pool_fees(&gt, &bp), Some(fixed!(0.01)));
+            assert_eq!(PoolXYK::pool_fee(&gt, &bp, &gt), fixed!(0.01));
+            assert_eq!(
+                PoolXYK::get_pool_fee(&dex_id, &bp, &gt),
+                Some((fixed!(0.01), fixed!(0)))
+            );
+
+            let quote = PoolXYK::quote(
+                &dex_id,
+                &GoldenTicket.into(),
+                &BlackPepper.into(),
+                QuoteAmount::with_desired_input(balance!(33000)),
+                true,
+            )
+            .unwrap();
+            assert_eq!(quote.0.fee, balance!(330));
+
+            assert_ok!(crate::Pallet::<Runtime>::exchange(
+                &ALICE(),
+                &ALICE(),
+                &dex_id,
+                &GoldenTicket.into(),
+                &BlackPepper.into(),
+                SwapAmount::WithDesiredInput {
+                    desired_amount_in: balance!(33000),
+                    min_amount_out: 0,
+                }
+            ));
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap(),
+                balance!(1856000) + quote.0.amount
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap(),
+                balance!(330)
+            );
+        },
+    )]);
+}
+
+#[test]
+fn set_pool_fee_should_check_origin_pool_and_limits() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            assert_noop!(
+                PoolXYK::set_pool_fee(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    GoldenTicket.into(),
+                    BlackPepper.into(),
+                    fixed!(0.01)
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                PoolXYK::set_pool_fee(
+                    RuntimeOrigin::signed(BOB()),
+                    dex_id,
+                    GoldenTicket.into(),
+                    BlackPepper.into(),
+                    fixed!(0.02)
+                ),
+                crate::Error::<Runtime>::InvalidPoolFee
+            );
+            assert_noop!(
+                PoolXYK::set_pool_fee(
+                    RuntimeOrigin::root(),
+                    dex_id,
+                    GoldenTicket.into(),
+                    BlackPepper.into(),
+                    fixed!(0.0001)
+                ),
+                crate::Error::<Runtime>::InvalidPoolFee
+            );
+            assert_noop!(
+                PoolXYK::set_pool_fee(
+                    RuntimeOrigin::root(),
+                    dex_id,
+                    GoldenTicket.into(),
+                    RedPepper.into(),
+                    fixed!(0.01)
+                ),
+                crate::Error::<Runtime>::PoolDoesNotExist
+            );
+            assert_ok!(PoolXYK::set_pool_fee(
+                RuntimeOrigin::root(),
+                dex_id,
+                GoldenTicket.into(),
+                BlackPepper.into(),
+                fixed!(0.0005)
+            ));
+            assert_eq!(PoolXYK::pool_fees(&gt, &bp), Some(fixed!(0.0005)));
+        },
+    )]);
+}
+
+#[test]
+fn protocol_fee_share_should_go_to_treasury() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, _, _, _, _, _, fee_repr: AccountId| {
+            assert_noop!(
+                PoolXYK::set_protocol_fee_share(RuntimeOrigin::signed(BOB()), fixed!(0.5)),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                PoolXYK::set_protocol_fee_share(RuntimeOrigin::root(), fixed!(1.1)),
+                crate::Error::<Runtime>::InvalidProtocolFeeShare
+            );
+            assert_ok!(PoolXYK::set_protocol_fee_share(
+                RuntimeOrigin::root(),
+                fixed!(0.5)
+            ));
+            assert_eq!(
+                PoolXYK::get_pool_fee(&dex_id, &GoldenTicket.into(), &BlackPepper.into()),
+                Some((fixed!(0.003), fixed!(0.5)))
+            );
+
+            let treasury_repr = technical::Pallet::<Runtime>::tech_account_id_to_account_id(
+                &GetTreasuryTechAccountId::get(),
+            )
+            .unwrap();
+
+            // Fee is taken from the source, which is the base asset
+            assert_ok!(crate::Pallet::<Runtime>::exchange(
+                &ALICE(),
+                &ALICE(),
+                &dex_id,
+                &GoldenTicket.into(),
+                &BlackPepper.into(),
+                SwapAmount::WithDesiredInput {
+                    desired_amount_in: balance!(33000),
+                    min_amount_out: 0,
+                }
+            ));
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap(),
+                balance!(507000)
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap(),
+                balance!(49.5)
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &treasury_repr).unwrap(),
+                balance!(49.5)
+            );
+
+            // Fee is taken from the destination, which is the base asset
+            let fee_before = assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap();
+            let outcome = crate::Pallet::<Runtime>::exchange(
+                &ALICE(),
+                &ALICE(),
+                &dex_id,
+                &BlackPepper.into(),
+                &GoldenTicket.into(),
+                SwapAmount::WithDesiredInput {
+                    desired_amount_in: balance!(10000),
+                    min_amount_out: 0,
+                },
+            )
+            .unwrap()
+            .0;
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap() - fee_before,
+                outcome.fee - outcome.fee / 2
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &treasury_repr).unwrap(),
+                balance!(49.5) + outcome.fee / 2
+            );
+        },
+    )]);
+}
+
+fn swap_golden_ticket_for_black_pepper(dex_id: DEXId, amount: Balance) {
+    assert_ok!(crate::Pallet::<Runtime>::exchange(
+        &ALICE(),
+        &ALICE(),
+        &dex_id,
+        &GoldenTicket.into(),
+        &BlackPepper.into(),
+        SwapAmount::WithDesiredInput {
+            desired_amount_in: amount,
+            min_amount_out: 0,
+        }
+    ));
+}
+
+#[test]
+fn twap_should_average_prices_over_time() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            let (reserve_gt, reserve_bp) = PoolXYK::reserves(&gt, &bp);
+            let first_gt_price = (FixedWrapper::from(reserve_bp) / reserve_gt)
+                .try_into_balance()
+                .unwrap();
+            assert_eq!(first_gt_price, balance!(0.4));
+
+            pallet_timestamp::Pallet::<Runtime>::set_timestamp(60_000);
+            swap_golden_ticket_for_black_pepper(dex_id, balance!(33000));
+            let (reserve_gt, reserve_bp) = PoolXYK::reserves(&gt, &bp);
+            let second_gt_price = (FixedWrapper::from(reserve_bp) / reserve_gt)
+                .try_into_balance()
+                .unwrap();
+            let second_bp_price = (FixedWrapper::from(reserve_gt) / reserve_bp)
+                .try_into_balance()
+                .unwrap();
+
+            pallet_timestamp::Pallet::<Runtime>::set_timestamp(120_000);
+            assert_eq!(
+                PoolXYK::get_twap(&gt, &bp, 120_000).unwrap(),
+                (first_gt_price * 60_000 + second_gt_price * 60_000) / 120_000
+            );
+            assert_eq!(
+                PoolXYK::get_twap(&gt, &bp, 60_000).unwrap(),
+                second_gt_price
+            );
+            assert_eq!(
+                PoolXYK::get_twap(&bp, &gt, 60_000).unwrap(),
+                second_bp_price
+            );
+            // The period is extended to the observation made at the pool deposit
+            assert_eq!(
+                PoolXYK::get_twap(&gt, &bp, 90_000).unwrap(),
+                PoolXYK::get_twap(&gt, &bp, 120_000).unwrap()
+            );
+
+            assert_noop!(
+                PoolXYK::get_twap(&gt, &bp, 0),
+                crate::Error::<Runtime>::InvalidTwapPeriod
+            );
+            assert_noop!(
+                PoolXYK::get_twap(&gt, &bp, 120_001),
+                crate::Error::<Runtime>::NotEnoughPriceObservations
+            );
+            assert_noop!(
+                PoolXYK::get_twap(&gt, &RedPepper.into(), 60_000),
+                crate::Error::<Runtime>::PoolDoesNotExist
+            );
+        },
+    )]);
+}
+
+#[test]
+fn twap_observations_should_be_bounded() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            for i in 1..=4 {
+                pallet_timestamp::Pallet::<Runtime>::set_timestamp(i * 60_000);
+                swap_golden_ticket_for_black_pepper(dex_id, balance!(1000));
+            }
+            // Observation is not made earlier than `PriceObservationPeriod` after the last one
+            pallet_timestamp::Pallet::<Runtime>::set_timestamp(250_000);
+            swap_golden_ticket_for_black_pepper(dex_id, balance!(1000));
+
+            let timestamps: Vec<u64> = PoolXYK::price_observations(&gt, &bp)
+                .into_iter()
+                .map(|observation| observation.timestamp)
+                .collect();
+            assert_eq!(timestamps, vec![120_000, 180_000, 240_000]);
+            assert_eq!(
+                PoolXYK::price_cumulatives(&gt, &bp).unwrap().timestamp,
+                250_000
+            );
+
+            assert!(PoolXYK::get_twap(&gt, &bp, 130_000).is_ok());
+            assert_noop!(
+                PoolXYK::get_twap(&gt, &bp, 130_001),
+                crate::Error::<Runtime>::NotEnoughPriceObservations
+            );
+        },
+    )]);
+}
+
+#[test]
+fn twap_should_not_overflow_with_high_price_over_long_period() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |_dex_id, gt, bp, _, _, _, _, _| {
+            // The price of the golden ticket is 10^20 BP, which is 10^38 as a balance
+            let reserve_bp = 10u128.pow(38);
+            crate::Reserves::<Runtime>::insert(&gt, &bp, (balance!(1), reserve_bp));
+            let gt_price = (FixedWrapper::from(reserve_bp) / balance!(1))
+                .try_into_balance()
+                .unwrap();
+            assert_eq!(gt_price, reserve_bp);
+
+            // One year, the cumulative price (~3 * 10^48) exceeds `Balance::MAX`
+            let year = 365 * 24 * 60 * 60 * 1000;
+            pallet_timestamp::Pallet::<Runtime>::set_timestamp(year);
+            assert_eq!(PoolXYK::get_twap(&gt, &bp, year).unwrap(), gt_price);
+            assert_eq!(PoolXYK::get_twap(&gt, &bp, year / 2).unwrap(), gt_price);
+        },
+    )]);
+}
+
+fn pool_invariant(gt: &AssetId, other: &AssetId) -> FixedWrapper {
+    let (reserve_gt, reserve_other) = PoolXYK::reserves(gt, other);
+    FixedWrapper::from(reserve_gt) * reserve_other
+}
+
+fn flash_swap_input_amount() -> Balance {
+    frame_system::Pallet::<Runtime>::events()
+        .into_iter()
+        .rev()
+        .find_map(|record| match record.event {
+            RuntimeEvent::PoolXYK(crate::Event::FlashSwap(_, _, _, _, input_amount, _)) => {
+                Some(input_amount)
+            }
+            _ => None,
+        })
+        .expect("flash swap event is expected")
+}
+
+fn flash_swap_black_pepper_call(output_amount: Balance, call: RuntimeCall) -> RuntimeCall {
+    RuntimeCall::PoolXYK(crate::Call::flash_swap {
+        dex_id: DEX_A_ID,
+        input_asset_id: GoldenTicket.into(),
+        output_asset_id: BlackPepper.into(),
+        output_amount,
+        max_input_amount: balance!(100000),
+        call: Box::new(call),
+    })
+}
+
+fn remark_call() -> RuntimeCall {
+    RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() })
+}
+
+#[test]
+fn flash_swap_should_lend_output_and_collect_input_with_fee() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, repr: AccountId, fee_repr: AccountId| {
+            frame_system::Pallet::<Runtime>::set_block_number(1);
+            let alice_gt = assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap();
+            let alice_bp = assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap();
+            let invariant = pool_invariant(&gt, &bp);
+            let (expected_input, expected_fee) =
+                crate::Pallet::<Runtime>::calc_input_for_exact_output(
+                    GetFee::get(),
+                    false,
+                    &balance!(360000),
+                    &balance!(144000),
+                    &balance!(1000),
+                    true,
+                )
+                .unwrap();
+
+            // The lent amount is needed to make the transfer
+            let transfer = RuntimeCall::Assets(assets::Call::transfer {
+                asset_id: bp,
+                to: CHARLIE(),
+                amount: alice_bp + balance!(1000),
+            });
+            assert_ok!(PoolXYK::flash_swap(
+                RuntimeOrigin::signed(ALICE()),
+                dex_id,
+                gt,
+                bp,
+                balance!(1000),
+                balance!(2600),
+                Box::new(transfer),
+            ));
+
+            let input = flash_swap_input_amount();
+            assert!(distance(input, expected_input) < 100);
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap(),
+                alice_gt - input
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap(),
+                0
+            );
+            assert!(
+                distance(
+                    assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap(),
+                    expected_fee
+                ) < 100
+            );
+            assert_eq!(
+                PoolXYK::reserves(&gt, &bp),
+                (
+                    assets::Pallet::<Runtime>::free_balance(&gt, &repr).unwrap(),
+                    balance!(143000)
+                )
+            );
+            assert!(pool_invariant(&gt, &bp) >= invariant);
+        },
+    )]);
+}
+
+#[test]
+fn flash_swap_should_take_fee_from_base_output() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, fee_repr: AccountId| {
+            frame_system::Pallet::<Runtime>::set_block_number(1);
+            let alice_gt = assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap();
+            let invariant = pool_invariant(&gt, &bp);
+
+            assert_ok!(PoolXYK::flash_swap(
+                RuntimeOrigin::signed(ALICE()),
+                dex_id,
+                bp,
+                gt,
+                balance!(997),
+                balance!(1000),
+                Box::new(remark_call()),
+            ));
+
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap(),
+                alice_gt + balance!(997)
+            );
+            assert!(
+                distance(
+                    assets::Pallet::<Runtime>::free_balance(&gt, &fee_repr).unwrap(),
+                    balance!(3)
+                ) < 100
+            );
+            assert!(distance(PoolXYK::reserves(&gt, &bp).0, balance!(359000)) < 100);
+            assert!(pool_invariant(&gt, &bp) >= invariant);
+        },
+    )]);
+}
+
+#[test]
+fn flash_swap_should_revert_if_not_repaid() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            let alice_gt = assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap();
+            let transfer = RuntimeCall::Assets(assets::Call::transfer {
+                asset_id: gt,
+                to: CHARLIE(),
+                amount: alice_gt,
+            });
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(100000),
+                    Box::new(transfer),
+                ),
+                crate::Error::<Runtime>::FlashSwapIsNotRepaid
+            );
+
+            // Errors of the inner call are propagated
+            let alice_bp = assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap();
+            let transfer = RuntimeCall::Assets(assets::Call::transfer {
+                asset_id: bp,
+                to: CHARLIE(),
+                amount: alice_bp + balance!(1001),
+            });
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(100000),
+                    Box::new(transfer),
+                ),
+                orml_tokens::Error::<Runtime>::BalanceTooLow
+            );
+        },
+    )]);
+}
+
+#[test]
+fn flash_swap_should_check_limits_and_locked_sources() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(2500),
+                    Box::new(remark_call()),
+                ),
+                crate::Error::<Runtime>::CalculatedValueIsOutOfDesiredBounds
+            );
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    0,
+                    balance!(2500),
+                    Box::new(remark_call()),
+                ),
+                crate::Error::<Runtime>::ZeroValueInAmountParameter
+            );
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(144000),
+                    Balance::MAX,
+                    Box::new(remark_call()),
+                ),
+                crate::Error::<Runtime>::TargetBalanceIsNotLargeEnough
+            );
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    RedPepper.into(),
+                    balance!(1000),
+                    balance!(2500),
+                    Box::new(remark_call()),
+                ),
+                crate::Error::<Runtime>::PoolDoesNotExist
+            );
+
+            <trading_pair::Pallet<Runtime> as LockedLiquiditySourcesManager<_>>::append(
+                LiquiditySourceType::XYKPool,
+            );
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(2600),
+                    Box::new(remark_call()),
+                ),
+                crate::Error::<Runtime>::LiquiditySourceIsLocked
+            );
+        },
+    )]);
+}
+
+#[test]
+fn nested_flash_swaps_on_same_pool_should_be_rejected() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            let inner = flash_swap_black_pepper_call(balance!(1000), remark_call());
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(100000),
+                    Box::new(inner),
+                ),
+                crate::Error::<Runtime>::PoolIsLockedByFlashSwap
+            );
+        },
+    )]);
+}
+
+#[test]
+fn flash_swap_should_lock_pool_during_call() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, repr: AccountId, _| {
+            // Depositing the lent liquidity back would inflate the reserves the repayment is
+            // calculated from and let the caller keep the pool tokens for free
+            let deposit = RuntimeCall::PoolXYK(crate::Call::deposit_liquidity {
+                dex_id,
+                input_asset_a: gt,
+                input_asset_b: bp,
+                input_a_desired: balance!(2500),
+                input_b_desired: balance!(1000),
+                input_a_min: balance!(0),
+                input_b_min: balance!(0),
+            });
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(100000),
+                    Box::new(deposit),
+                ),
+                crate::Error::<Runtime>::PoolIsLockedByFlashSwap
+            );
+
+            let withdraw = RuntimeCall::PoolXYK(crate::Call::withdraw_liquidity {
+                dex_id,
+                output_asset_a: gt,
+                output_asset_b: bp,
+                marker_asset_desired: balance!(1000),
+                output_a_min: balance!(0),
+                output_b_min: balance!(0),
+            });
+            assert_noop!(
+                PoolXYK::flash_swap(
+                    RuntimeOrigin::signed(ALICE()),
+                    dex_id,
+                    gt,
+                    bp,
+                    balance!(1000),
+                    balance!(100000),
+                    Box::new(withdraw),
+                ),
+                crate::Error::<Runtime>::PoolIsLockedByFlashSwap
+            );
+
+            crate::FlashSwapLocks::<Runtime>::insert(&repr, ());
+            assert_noop!(
+                crate::Pallet::<Runtime>::exchange(
+                    &ALICE(),
+                    &ALICE(),
+                    &dex_id,
+                    &gt,
+                    &bp,
+                    SwapAmount::with_desired_input(balance!(1000), 0),
+                ),
+                crate::Error::<Runtime>::PoolIsLockedByFlashSwap
+            );
+            crate::FlashSwapLocks::<Runtime>::remove(&repr);
+
+            // The lock is released after the flash swap
+            assert_ok!(PoolXYK::flash_swap(
+                RuntimeOrigin::signed(ALICE()),
+                dex_id,
+                gt,
+                bp,
+                balance!(1000),
+                balance!(100000),
+                Box::new(remark_call()),
+            ));
+            assert!(!crate::FlashSwapLocks::<Runtime>::contains_key(&repr));
+            assert_ok!(crate::Pallet::<Runtime>::exchange(
+                &ALICE(),
+                &ALICE(),
+                &dex_id,
+                &gt,
+                &bp,
+                SwapAmount::with_desired_input(balance!(1000), 0),
+            ));
+        },
+    )]);
+}
+
+#[test]
+fn nested_flash_swaps_on_different_pools_should_be_repaid() {
+    crate::Pallet::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _, _| {
+            let rp: AssetId = RedPepper.into();
+            assert_ok!(assets::Pallet::<Runtime>::register_asset_id(
+                ALICE(),
+                rp,
+                AssetSymbol(b"RP".to_vec()),
+                AssetName(b"Red Pepper".to_vec()),
+                DEFAULT_BALANCE_PRECISION,
+                Balance::from(0u32),
+                true,
+                None,
+                None,
+            ));
+            assert_ok!(trading_pair::Pallet::<Runtime>::register(
+                RuntimeOrigin::signed(BOB()),
+                dex_id,
+                gt,
+                rp
+            ));
+            assert_ok!(PoolXYK::initialize_pool(
+                RuntimeOrigin::signed(BOB()),
+                dex_id,
+                gt,
+                rp
+            ));
+            assert_ok!(PoolXYK::deposit_liquidity(
+                RuntimeOrigin::signed(ALICE()),
+                dex_id,
+                gt,
+                rp,
+                balance!(100000),
+                balance!(50000),
+                balance!(100000),
+                balance!(50000),
+            ));
+            frame_system::Pallet::<Runtime>::set_block_number(1);
+            let alice_gt = assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap();
+            let alice_bp = assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap();
+            let alice_rp = assets::Pallet::<Runtime>::free_balance(&rp, &ALICE()).unwrap();
+            let bp_invariant = pool_invariant(&gt, &bp);
+            let rp_invariant = pool_invariant(&gt, &rp);
+
+            // Red pepper is lent by the inner swap, black pepper is used to repay it
+            let inner = RuntimeCall::PoolXYK(crate::Call::flash_swap {
+                dex_id,
+                input_asset_id: gt,
+                output_asset_id: rp,
+                output_amount: balance!(500),
+                max_input_amount: balance!(100000),
+                call: Box::new(RuntimeCall::Assets(assets::Call::transfer {
+                    asset_id: bp,
+                    to: CHARLIE(),
+                    amount: alice_bp + balance!(1000),
+                })),
+            });
+            assert_ok!(PoolXYK::flash_swap(
+                RuntimeOrigin::signed(ALICE()),
+                dex_id,
+                gt,
+                bp,
+                balance!(1000),
+                balance!(100000),
+                Box::new(inner),
+            ));
+
+            let inputs: Vec<(AssetId, Balance)> = frame_system::Pallet::<Runtime>::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::PoolXYK(crate::Event::FlashSwap(_, _, _, output, input, _)) => {
+                        Some((output, input))
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(inputs.len(), 2);
+            assert_eq!(inputs[0].0, rp);
+            assert_eq!(inputs[1].0, bp);
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&gt, &ALICE()).unwrap(),
+                alice_gt - inputs[0].1 - inputs[1].1
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&rp, &ALICE()).unwrap(),
+                alice_rp + balance!(500)
+            );
+            assert_eq!(
+                assets::Pallet::<Runtime>::free_balance(&bp, &ALICE()).unwrap(),
+                0
+            );
+            assert!(pool_invariant(&gt, &bp) >= bp_invariant);
+            assert!(pool_invariant(&gt, &rp) >= rp_invariant);
+        },
+    )]);
+}
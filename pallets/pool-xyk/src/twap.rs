@@ -0,0 +1,191 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::dispatch::DispatchError;
+use frame_support::ensure;
+use frame_support::traits::Get;
+use sp_core::U256;
+use sp_runtime::traits::{CheckedSub, Saturating, UniqueSaturatedInto, Zero};
+use sp_runtime::RuntimeDebug;
+
+use common::prelude::{Balance, FixedWrapper};
+use common::GetTwap;
+
+use crate::aliases::{AssetIdOf, MomentOf};
+use crate::{Config, Error, Pallet, PriceCumulatives, PriceObservations, Properties, Reserves};
+
+/// Cumulative prices of the pool assets, i.e. sums of the spot prices multiplied by the time
+/// (in milliseconds) they were valid for. The sums are 256-bit, so they don't overflow even if
+/// the price is `Balance::MAX` for 2^128 milliseconds.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct PriceCumulative<Moment> {
+    /// Cumulative price of the base asset in terms of the target asset
+    pub base_price_cumulative: U256,
+    /// Cumulative price of the target asset in terms of the base asset
+    pub target_price_cumulative: U256,
+    /// Time of the last update of the cumulative prices
+    pub timestamp: Moment,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Accumulates the prices defined by the current reserves of the pool up to now.
+    /// Must be called before the reserves are changed.
+    pub(crate) fn update_price_cumulative(
+        base_asset_id: &AssetIdOf<T>,
+        target_asset_id: &AssetIdOf<T>,
+    ) {
+        let now = pallet_timestamp::Pallet::<T>::get();
+        let cumulative = match PriceCumulatives::<T>::get(base_asset_id, target_asset_id) {
+            Some(cumulative) => {
+                if cumulative.timestamp >= now {
+                    return;
+                }
+                Self::price_cumulative_at(base_asset_id, target_asset_id, cumulative, now)
+            }
+            None => PriceCumulative {
+                timestamp: now,
+                ..Default::default()
+            },
+        };
+        PriceCumulatives::<T>::insert(base_asset_id, target_asset_id, cumulative);
+
+        PriceObservations::<T>::mutate(base_asset_id, target_asset_id, |observations| {
+            if let Some(last) = observations.last() {
+                if cumulative.timestamp
+                    < last
+                        .timestamp
+                        .saturating_add(T::PriceObservationPeriod::get())
+                {
+                    return;
+                }
+            }
+            if observations.is_full() {
+                if observations.is_empty() {
+                    return;
+                }
+                observations.remove(0);
+            }
+            let _ = observations.try_push(cumulative);
+        });
+    }
+
+    /// Extrapolates `cumulative` to the moment `now` using the current reserves of the pool.
+    fn price_cumulative_at(
+        base_asset_id: &AssetIdOf<T>,
+        target_asset_id: &AssetIdOf<T>,
+        mut cumulative: PriceCumulative<MomentOf<T>>,
+        now: MomentOf<T>,
+    ) -> PriceCumulative<MomentOf<T>> {
+        let elapsed: Balance = (now - cumulative.timestamp).unique_saturated_into();
+        let elapsed = U256::from(elapsed);
+        let (reserve_base, reserve_target) = Reserves::<T>::get(base_asset_id, target_asset_id);
+        if !elapsed.is_zero() && !reserve_base.is_zero() && !reserve_target.is_zero() {
+            let base_price = (FixedWrapper::from(reserve_target) / reserve_base)
+                .try_into_balance()
+                .unwrap_or(Balance::MAX);
+            let target_price = (FixedWrapper::from(reserve_base) / reserve_target)
+                .try_into_balance()
+                .unwrap_or(Balance::MAX);
+            cumulative.base_price_cumulative = cumulative
+                .base_price_cumulative
+                .saturating_add(U256::from(base_price).saturating_mul(elapsed));
+            cumulative.target_price_cumulative = cumulative
+                .target_price_cumulative
+                .saturating_add(U256::from(target_price).saturating_mul(elapsed));
+        }
+        cumulative.timestamp = now;
+        cumulative
+    }
+
+    /// Returns the time-weighted average price of the base asset (if `base_price` is true) or
+    /// the target asset of the pool over at least the last `period`. The actual period is
+    /// extended to the latest observation made before its start.
+    pub fn pool_twap(
+        base_asset_id: &AssetIdOf<T>,
+        target_asset_id: &AssetIdOf<T>,
+        base_price: bool,
+        period: MomentOf<T>,
+    ) -> Result<Balance, DispatchError> {
+        ensure!(!period.is_zero(), Error::<T>::InvalidTwapPeriod);
+        let now = pallet_timestamp::Pallet::<T>::get();
+        let cumulative = PriceCumulatives::<T>::get(base_asset_id, target_asset_id)
+            .ok_or(Error::<T>::NotEnoughPriceObservations)?;
+        let current = Self::price_cumulative_at(base_asset_id, target_asset_id, cumulative, now);
+        let start = now
+            .checked_sub(&period)
+            .ok_or(Error::<T>::NotEnoughPriceObservations)?;
+        let observation = PriceObservations::<T>::get(base_asset_id, target_asset_id)
+            .into_iter()
+            .rev()
+            .find(|observation| observation.timestamp <= start)
+            .ok_or(Error::<T>::NotEnoughPriceObservations)?;
+        let elapsed: Balance = (current.timestamp - observation.timestamp).unique_saturated_into();
+        let price_cumulative_diff = if base_price {
+            current
+                .base_price_cumulative
+                .saturating_sub(observation.base_price_cumulative)
+        } else {
+            current
+                .target_price_cumulative
+                .saturating_sub(observation.target_price_cumulative)
+        };
+        Ok((price_cumulative_diff / U256::from(elapsed))
+            .try_into()
+            .unwrap_or(Balance::MAX))
+    }
+}
+
+impl<T: Config> GetTwap<AssetIdOf<T>, MomentOf<T>> for Pallet<T> {
+    fn get_twap(
+        input_asset: &AssetIdOf<T>,
+        output_asset: &AssetIdOf<T>,
+        period: MomentOf<T>,
+    ) -> Result<Balance, DispatchError> {
+        if Properties::<T>::contains_key(input_asset, output_asset) {
+            Self::pool_twap(input_asset, output_asset, true, period)
+        } else if Properties::<T>::contains_key(output_asset, input_asset) {
+            Self::pool_twap(output_asset, input_asset, false, period)
+        } else {
+            Err(Error::<T>::PoolDoesNotExist.into())
+        }
+    }
+}
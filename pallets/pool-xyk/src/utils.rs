@@ -34,8 +34,10 @@ use orml_traits::GetByKey;
 
 use crate::aliases::{AssetIdOf, TechAccountIdOf, TechAssetIdOf};
 use crate::bounds::*;
-use crate::{Config, Error, Pallet, PoolProviders, TotalIssuances};
-use common::prelude::{Balance, SwapAmount};
+use crate::{
+    Config, Error, FlashSwapLocks, Pallet, PoolProviders, ProtocolFeeShare, TotalIssuances,
+};
+use common::prelude::{Balance, FixedWrapper, SwapAmount};
 use common::{
     AccountIdOf, DexInfoProvider, ToFeeAccount, ToXykTechUnitFromDEXAndTradingPair, TradingPair,
 };
@@ -86,6 +88,14 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    pub fn ensure_pool_is_not_locked(pool_acc: &AccountIdOf<T>) -> DispatchResult {
+        ensure!(
+            !FlashSwapLocks::<T>::contains_key(pool_acc),
+            Error::<T>::PoolIsLockedByFlashSwap
+        );
+        Ok(())
+    }
+
     pub fn tech_account_from_dex_and_asset_pair(
         dex_id: T::DEXId,
         asset_a: T::AssetId,
@@ -223,4 +233,15 @@ impl<T: Config> Pallet<T> {
             fail!(Error::<T>::BaseAssetIsNotMatchedWithAnyAssetArguments)
         }
     }
+
+    /// Split the swap fee into the part which stays in the pool fee account and the part
+    /// which goes to the treasury according to `ProtocolFeeShare`.
+    pub fn split_protocol_fee(fee: Balance) -> Result<(Balance, Balance), DispatchError> {
+        let share = ProtocolFeeShare::<T>::get();
+        let protocol_fee = (FixedWrapper::from(fee) * share)
+            .try_into_balance()
+            .map_err(|_| Error::<T>::FixedWrapperCalculationFailed)?
+            .min(fee);
+        Ok((fee - protocol_fee, protocol_fee))
+    }
 }
@@ -66,6 +66,9 @@ pub trait WeightInfo {
 	fn deposit_liquidity() -> Weight;
 	fn withdraw_liquidity() -> Weight;
 	fn initialize_pool() -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn flash_swap() -> Weight;
 }
 
 /// Weights for pool_xyk using the Substrate node and recommended hardware.
@@ -81,16 +84,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn swap_pair() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2512`
 		//  Estimated: `32979`
 		// Minimum execution time: 173_943_000 picoseconds.
 		Weight::from_parts(174_938_000, 32979)
-			.saturating_add(T::DbWeight::get().reads(9_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
@@ -134,16 +145,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: PoolXYK PoolProviders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn deposit_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3193`
 		//  Estimated: `50104`
 		// Minimum execution time: 177_847_000 picoseconds.
 		Weight::from_parts(179_671_000, 50104)
-			.saturating_add(T::DbWeight::get().reads(11_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: Assets AssetInfos (r:2 w:0)
 	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
@@ -161,24 +180,30 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: PoolXYK TotalIssuances (max_values: None, max_size: None, mode: Measured)
 	/// Storage: CeresLiquidityLocker LockerData (r:1 w:0)
 	/// Proof Skipped: CeresLiquidityLocker LockerData (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Timestamp Now (r:1 w:0)
-	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: PoolXYK Properties (r:1 w:0)
 	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
 	/// Storage: DemeterFarmingPlatform UserInfos (r:1 w:0)
 	/// Proof Skipped: DemeterFarmingPlatform UserInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn withdraw_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3530`
 		//  Estimated: `70981`
 		// Minimum execution time: 196_381_000 picoseconds.
 		Weight::from_parts(197_605_000, 70981)
-			.saturating_add(T::DbWeight::get().reads(15_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
@@ -196,6 +221,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Farming Pools (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PoolXYK Properties (r:0 w:1)
 	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolCreators (r:0 w:1)
+	/// Proof Skipped: PoolXYK PoolCreators (max_values: None, max_size: None, mode: Measured)
 	fn initialize_pool() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2103`
@@ -203,7 +230,69 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 125_922_000 picoseconds.
 		Weight::from_parts(128_481_000, 39727)
 			.saturating_add(T::DbWeight::get().reads(10_u64))
-			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Properties (r:1 w:0)
+	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolCreators (r:1 w:0)
+	/// Proof Skipped: PoolXYK PoolCreators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolFees (r:0 w:1)
+	/// Proof Skipped: PoolXYK PoolFees (max_values: None, max_size: None, mode: Measured)
+	fn set_pool_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1124`
+		//  Estimated: `11283`
+		// Minimum execution time: 31_504_000 picoseconds.
+		Weight::from_parts(32_268_000, 11283)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: PoolXYK ProtocolFeeShare (r:0 w:1)
+	/// Proof Skipped: PoolXYK ProtocolFeeShare (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_protocol_fee_share() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_113_000 picoseconds.
+		Weight::from_parts(12_562_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingPair LockedLiquiditySources (r:1 w:0)
+	/// Proof Skipped: TradingPair LockedLiquiditySources (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:1 w:0)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:2 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolFees (r:1 w:0)
+	/// Proof Skipped: PoolXYK PoolFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK ProtocolFeeShare (r:1 w:0)
+	/// Proof Skipped: PoolXYK ProtocolFeeShare (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:1)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
+	fn flash_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2912`
+		//  Estimated: `0`
+		// Minimum execution time: 121_846_000 picoseconds.
+		Weight::from_parts(124_310_000, 0)
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 }
 
@@ -219,16 +308,24 @@ impl WeightInfo for () {
 	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn swap_pair() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2512`
 		//  Estimated: `32979`
 		// Minimum execution time: 173_943_000 picoseconds.
 		Weight::from_parts(174_938_000, 32979)
-			.saturating_add(RocksDbWeight::get().reads(9_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
@@ -272,16 +369,24 @@ impl WeightInfo for () {
 	/// Proof Skipped: PoolXYK PoolProviders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn deposit_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3193`
 		//  Estimated: `50104`
 		// Minimum execution time: 177_847_000 picoseconds.
 		Weight::from_parts(179_671_000, 50104)
-			.saturating_add(RocksDbWeight::get().reads(11_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: Assets AssetInfos (r:2 w:0)
 	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
@@ -299,24 +404,30 @@ impl WeightInfo for () {
 	/// Proof Skipped: PoolXYK TotalIssuances (max_values: None, max_size: None, mode: Measured)
 	/// Storage: CeresLiquidityLocker LockerData (r:1 w:0)
 	/// Proof Skipped: CeresLiquidityLocker LockerData (max_values: None, max_size: None, mode: Measured)
-	/// Storage: Timestamp Now (r:1 w:0)
-	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: PoolXYK Properties (r:1 w:0)
 	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
 	/// Storage: DemeterFarmingPlatform UserInfos (r:1 w:0)
 	/// Proof Skipped: DemeterFarmingPlatform UserInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PriceTools PriceInfos (r:1 w:0)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
-	/// Storage: PoolXYK Reserves (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
 	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
 	fn withdraw_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3530`
 		//  Estimated: `70981`
 		// Minimum execution time: 196_381_000 picoseconds.
 		Weight::from_parts(197_605_000, 70981)
-			.saturating_add(RocksDbWeight::get().reads(15_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
@@ -334,6 +445,8 @@ impl WeightInfo for () {
 	/// Proof Skipped: Farming Pools (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PoolXYK Properties (r:0 w:1)
 	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolCreators (r:0 w:1)
+	/// Proof Skipped: PoolXYK PoolCreators (max_values: None, max_size: None, mode: Measured)
 	fn initialize_pool() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2103`
@@ -341,6 +454,68 @@ impl WeightInfo for () {
 		// Minimum execution time: 125_922_000 picoseconds.
 		Weight::from_parts(128_481_000, 39727)
 			.saturating_add(RocksDbWeight::get().reads(10_u64))
-			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Properties (r:1 w:0)
+	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolCreators (r:1 w:0)
+	/// Proof Skipped: PoolXYK PoolCreators (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolFees (r:0 w:1)
+	/// Proof Skipped: PoolXYK PoolFees (max_values: None, max_size: None, mode: Measured)
+	fn set_pool_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1124`
+		//  Estimated: `11283`
+		// Minimum execution time: 31_504_000 picoseconds.
+		Weight::from_parts(32_268_000, 11283)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: PoolXYK ProtocolFeeShare (r:0 w:1)
+	/// Proof Skipped: PoolXYK ProtocolFeeShare (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_protocol_fee_share() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_113_000 picoseconds.
+		Weight::from_parts(12_562_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TradingPair LockedLiquiditySources (r:1 w:0)
+	/// Proof Skipped: TradingPair LockedLiquiditySources (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:1 w:0)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:2 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PoolFees (r:1 w:0)
+	/// Proof Skipped: PoolXYK PoolFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK ProtocolFeeShare (r:1 w:0)
+	/// Proof Skipped: PoolXYK ProtocolFeeShare (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:1)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
+	fn flash_swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2912`
+		//  Estimated: `0`
+		// Minimum execution time: 121_846_000 picoseconds.
+		Weight::from_parts(124_310_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 }
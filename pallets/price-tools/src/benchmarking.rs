@@ -218,6 +218,18 @@ benchmarks! {
             );
         }
     }
+
+    set_averaging_parameters {
+        prepare_secondary_market::<T>(1, 0);
+        let asset = create_asset::<T>(b"asset".to_vec(), 0);
+        let parameters = AveragingParameters {
+            span: crate::AVG_BLOCK_SPAN / 2,
+            ..Default::default()
+        };
+    }: _(RawOrigin::Root, asset, parameters)
+    verify {
+        assert_eq!(crate::AveragingParams::<T>::get(&asset), parameters);
+    }
 }
 
 #[cfg(test)]
@@ -232,4 +244,11 @@ mod tests {
             assert_ok!(Pallet::<Runtime>::test_benchmark_on_initialize());
         });
     }
+
+    #[test]
+    fn test_benchmark_set_averaging_parameters() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Pallet::<Runtime>::test_benchmark_set_averaging_parameters());
+        });
+    }
 }
@@ -66,9 +66,12 @@ use sp_std::convert::TryInto;
 
 pub use pallet::*;
 
-/// Count of blocks to participate in avg value calculation.
+/// Default count of blocks to participate in avg value calculation.
 pub const AVG_BLOCK_SPAN: u32 = 30;
 
+/// Max count of blocks which can be set to participate in avg value calculation.
+pub const MAX_AVG_BLOCK_SPAN: u32 = 1000;
+
 /// Max percentage difference for average value between blocks when price goes down for buy price.
 const MAX_BUY_BLOCK_DEC_AVG_DIFFERENCE: Fixed = fixed_const!(0.00002); // 0.002%
 /// Max percentage difference for average value between blocks when price goes up for buy price.
@@ -81,6 +84,57 @@ const MAX_SELL_BLOCK_INC_AVG_DIFFERENCE: Fixed = fixed_const!(0.00002); // 0.002
 
 pub use weights::WeightInfo;
 
+/// Parameters of the average price calculation of an asset.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, Debug, scale_info::TypeInfo)]
+pub struct AveragingParameters {
+    /// Count of blocks to participate in avg value calculation.
+    pub span: u32,
+    /// Max percentage difference for average value between blocks when price goes up for buy price.
+    pub max_buy_increase: Fixed,
+    /// Max percentage difference for average value between blocks when price goes down for buy price.
+    pub max_buy_decrease: Fixed,
+    /// Max percentage difference for average value between blocks when price goes up for sell price.
+    pub max_sell_increase: Fixed,
+    /// Max percentage difference for average value between blocks when price goes down for sell price.
+    pub max_sell_decrease: Fixed,
+    /// Count of consecutive spot price quote failures after which the spot price history is reset.
+    pub max_failures: u32,
+}
+
+impl Default for AveragingParameters {
+    fn default() -> Self {
+        Self {
+            span: AVG_BLOCK_SPAN,
+            max_buy_increase: MAX_BUY_BLOCK_INC_AVG_DIFFERENCE,
+            max_buy_decrease: MAX_BUY_BLOCK_DEC_AVG_DIFFERENCE,
+            max_sell_increase: MAX_SELL_BLOCK_INC_AVG_DIFFERENCE,
+            max_sell_decrease: MAX_SELL_BLOCK_DEC_AVG_DIFFERENCE,
+            max_failures: AVG_BLOCK_SPAN,
+        }
+    }
+}
+
+impl AveragingParameters {
+    /// Max increase and decrease of the average value between blocks for `price_variant`.
+    pub fn max_differences(&self, price_variant: PriceVariant) -> (Fixed, Fixed) {
+        match price_variant {
+            PriceVariant::Buy => (self.max_buy_increase, self.max_buy_decrease),
+            PriceVariant::Sell => (self.max_sell_increase, self.max_sell_decrease),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let is_valid_difference = |diff: Fixed| diff >= Fixed::ZERO && diff < Fixed::ONE;
+        self.span > 0
+            && self.span <= MAX_AVG_BLOCK_SPAN
+            && self.max_failures > 0
+            && is_valid_difference(self.max_buy_increase)
+            && is_valid_difference(self.max_buy_decrease)
+            && is_valid_difference(self.max_sell_increase)
+            && is_valid_difference(self.max_sell_decrease)
+    }
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Clone, PartialOrd, Ord, Debug, scale_info::TypeInfo)]
 pub struct PriceInfo {
     price_failures: u32,
@@ -102,6 +156,28 @@ impl Default for PriceInfo {
     }
 }
 
+impl PriceInfo {
+    pub fn average_price(&self) -> Balance {
+        self.average_price
+    }
+
+    pub fn last_spot_price(&self) -> Balance {
+        self.last_spot_price
+    }
+
+    pub fn spot_prices_count(&self) -> u32 {
+        self.spot_prices.len() as u32
+    }
+
+    pub fn price_failures(&self) -> u32 {
+        self.price_failures
+    }
+
+    pub fn needs_update(&self) -> bool {
+        self.needs_update
+    }
+}
+
 #[derive(
     Encode, Decode, Eq, PartialEq, Clone, PartialOrd, Ord, Debug, scale_info::TypeInfo, Default,
 )]
@@ -149,7 +225,7 @@ pub mod pallet {
     }
 
     /// The current storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
@@ -168,13 +244,64 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        // no extrinsics
+        /// Sets the parameters of the average price calculation of the asset.
+        /// If the span is decreased, the oldest spot prices are dropped and the average
+        /// price is recalculated from the rest.
+        /// If the span is increased, the parameters are pending and the average over the current
+        /// span is served until the spot price history is long enough for the new span.
+        /// The change of the first average over the new span is bounded by the new max differences.
+        ///
+        /// - `asset_id`: ID of the registered asset,
+        /// - `parameters`: new averaging parameters.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_averaging_parameters())]
+        pub fn set_averaging_parameters(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            parameters: AveragingParameters,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                parameters.is_valid(),
+                Error::<T>::InvalidAveragingParameters
+            );
+            let current_span = AveragingParams::<T>::get(&asset_id).span;
+            let is_pending =
+                PriceInfos::<T>::try_mutate(&asset_id, |opt| -> Result<bool, DispatchError> {
+                    let agg_price_info = opt.as_mut().ok_or(Error::<T>::UnsupportedQuotePath)?;
+                    let mut is_pending = false;
+                    for price_variant in [PriceVariant::Buy, PriceVariant::Sell] {
+                        let price_info = agg_price_info.price_mut_of(price_variant);
+                        if parameters.span > current_span {
+                            is_pending |=
+                                Self::keep_span_history(price_info, parameters.span, current_span)?;
+                        } else {
+                            Self::truncate_to_span(
+                                price_info,
+                                parameters.span,
+                                current_span,
+                                parameters.max_differences(price_variant),
+                            )?;
+                        }
+                    }
+                    Ok(is_pending)
+                })?;
+            if is_pending {
+                PendingAveragingParams::<T>::insert(&asset_id, parameters);
+            } else {
+                AveragingParams::<T>::insert(&asset_id, parameters);
+                PendingAveragingParams::<T>::remove(&asset_id);
+            }
+            Self::deposit_event(Event::AveragingParametersUpdated(asset_id, parameters));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
-    // #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        // no events
+        /// Averaging parameters of the asset were updated. [Asset Id, Parameters]
+        AveragingParametersUpdated(T::AssetId, AveragingParameters),
     }
 
     #[pallet::error]
@@ -194,11 +321,26 @@ pub mod pallet {
         AssetAlreadyRegistered,
         /// Spot price for asset has not changed but info for last spot price is unavailable.
         CantDuplicateLastPrice,
+        /// Averaging span or failure tolerance is out of bounds, or max difference is not in [0, 1).
+        InvalidAveragingParameters,
     }
 
     #[pallet::storage]
     #[pallet::getter(fn price_infos)]
     pub type PriceInfos<T: Config> = StorageMap<_, Identity, T::AssetId, AggregatedPriceInfo>;
+
+    /// Parameters of the average price calculation for each asset.
+    #[pallet::storage]
+    #[pallet::getter(fn averaging_params)]
+    pub type AveragingParams<T: Config> =
+        StorageMap<_, Identity, T::AssetId, AveragingParameters, ValueQuery>;
+
+    /// Parameters with increased span for each asset, which are applied once the spot price
+    /// history is long enough for the new span.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_averaging_params)]
+    pub type PendingAveragingParams<T: Config> =
+        StorageMap<_, Identity, T::AssetId, AveragingParameters>;
 }
 
 impl<T: Config> Pallet<T> {
@@ -244,7 +386,8 @@ impl<T: Config> Pallet<T> {
         asset_id: &T::AssetId,
         price_variant: PriceVariant,
     ) -> Result<Balance, DispatchError> {
-        let avg_count: usize = AVG_BLOCK_SPAN
+        let avg_count: usize = AveragingParams::<T>::get(asset_id)
+            .span
             .try_into()
             .map_err(|_| Error::<T>::FailedToQuoteAveragePrice)?;
 
@@ -253,8 +396,9 @@ impl<T: Config> Pallet<T> {
             .map_or_else(
                 || Err(Error::<T>::UnsupportedQuotePath.into()),
                 |price_info| {
+                    // history may be longer than the span if increased span is pending
                     ensure!(
-                        price_info.spot_prices.len() == avg_count,
+                        price_info.spot_prices.len() >= avg_count,
                         Error::<T>::InsufficientSpotPriceData
                     );
                     Ok(price_info.average_price)
@@ -270,94 +414,216 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResult {
         // reset failure streak for spot prices if needed
         if PriceInfos::<T>::get(asset_id).is_some() {
-            let avg_count: usize = AVG_BLOCK_SPAN
+            let params = AveragingParams::<T>::get(asset_id);
+            let pending_params = PendingAveragingParams::<T>::get(asset_id);
+            let avg_count: usize = params
+                .span
                 .try_into()
                 .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
-            PriceInfos::<T>::mutate(asset_id, |opt| {
-                let val = opt.as_mut().unwrap().price_mut_of(price_variant);
-                // reset failure streak
-                val.price_failures = 0;
-                val.needs_update = false;
-                // spot price history is consistent, normal behavior
-                if val.spot_prices.len() == avg_count {
-                    let old_value = val.spot_prices.pop_front().unwrap();
-
-                    let mut new_avg = Self::replace_in_average(
-                        val.average_price,
-                        old_value,
-                        price,
-                        AVG_BLOCK_SPAN,
-                    )?;
-                    new_avg =
-                        Self::adjust_to_difference(val.average_price, new_avg, price_variant)?;
-                    let adjusted_incoming_price = Self::adjusted_spot_price(
-                        val.average_price,
-                        new_avg,
-                        old_value,
-                        AVG_BLOCK_SPAN,
-                    )?;
-                    val.spot_prices.push_back(adjusted_incoming_price);
-                    val.average_price = new_avg;
-                // spot price history has been recovered/initiated, create initial average value
-                } else if val.spot_prices.len() == avg_count - 1 {
-                    val.spot_prices.push_back(price);
-                    let sum = val
-                        .spot_prices
-                        .iter()
-                        .fold(FixedWrapper::from(0), |a, b| a + *b);
-                    let avg = (sum / balance!(val.spot_prices.len()))
-                        .try_into_balance()
-                        .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
-                    val.average_price = avg;
-                } else {
-                    val.spot_prices.push_back(price);
-                }
-                val.last_spot_price = price;
+            // spot prices for the pending span are collected in advance
+            let history_count: usize = pending_params
+                .map_or(params.span, |pending_params| pending_params.span)
+                .try_into()
+                .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
+            let switched_to_pending =
+                PriceInfos::<T>::mutate(asset_id, |opt| -> Result<bool, DispatchError> {
+                    let agg_price_info = opt.as_mut().unwrap();
+                    let val = agg_price_info.price_mut_of(price_variant);
+                    // reset failure streak
+                    val.price_failures = 0;
+                    val.needs_update = false;
+                    // spot price history is consistent, normal behavior
+                    if val.spot_prices.len() >= avg_count {
+                        // the oldest value in the averaging span
+                        let old_value = val.spot_prices[val.spot_prices.len() - avg_count];
+
+                        let mut new_avg = Self::replace_in_average(
+                            val.average_price,
+                            old_value,
+                            price,
+                            params.span,
+                        )?;
+                        let (max_inc, max_dec) = params.max_differences(price_variant);
+                        new_avg = Self::adjust_to_difference(
+                            val.average_price,
+                            new_avg,
+                            max_inc,
+                            max_dec,
+                        )?;
+                        let adjusted_incoming_price = Self::adjusted_spot_price(
+                            val.average_price,
+                            new_avg,
+                            old_value,
+                            params.span,
+                        )?;
+                        if val.spot_prices.len() >= history_count {
+                            val.spot_prices.pop_front();
+                        }
+                        val.spot_prices.push_back(adjusted_incoming_price);
+                        val.average_price = new_avg;
+                    // spot price history has been recovered/initiated, create initial average value
+                    } else if val.spot_prices.len() == avg_count - 1 {
+                        val.spot_prices.push_back(price);
+                        let sum = val
+                            .spot_prices
+                            .iter()
+                            .fold(FixedWrapper::from(0), |a, b| a + *b);
+                        let avg = (sum / balance!(val.spot_prices.len()))
+                            .try_into_balance()
+                            .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
+                        val.average_price = avg;
+                    } else {
+                        val.spot_prices.push_back(price);
+                    }
+                    val.last_spot_price = price;
 
-                Ok(())
-            })
+                    match pending_params {
+                        Some(pending_params) => {
+                            Self::switch_to_pending_span(agg_price_info, &pending_params)
+                        }
+                        None => Ok(false),
+                    }
+                })?;
+            if switched_to_pending {
+                if let Some(pending_params) = PendingAveragingParams::<T>::take(asset_id) {
+                    AveragingParams::<T>::insert(asset_id, pending_params);
+                }
+            }
+            Ok(())
         } else {
             fail!(Error::<T>::UnsupportedQuotePath);
         }
     }
 
+    /// Recalculate averages over the pending span if both spot price histories are long enough.
+    /// Returns true if the averages are recalculated and the pending parameters are to be applied.
+    fn switch_to_pending_span(
+        agg_price_info: &mut AggregatedPriceInfo,
+        pending_params: &AveragingParameters,
+    ) -> Result<bool, DispatchError> {
+        let span: usize = pending_params
+            .span
+            .try_into()
+            .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
+        if agg_price_info.buy.spot_prices.len() < span
+            || agg_price_info.sell.spot_prices.len() < span
+        {
+            return Ok(false);
+        }
+        for price_variant in [PriceVariant::Buy, PriceVariant::Sell] {
+            // the history is longer than the current span, so the current average is valid
+            Self::recalculate_average(
+                agg_price_info.price_mut_of(price_variant),
+                Some(pending_params.max_differences(price_variant)),
+            )?;
+        }
+        Ok(true)
+    }
+
+    /// Drop the oldest spot prices which don't fit into the increased `span`, the spot prices of
+    /// the current span are kept. Returns true if the average over the current span is valid.
+    fn keep_span_history(
+        price_info: &mut PriceInfo,
+        span: u32,
+        current_span: u32,
+    ) -> Result<bool, DispatchError> {
+        let span: usize = span
+            .try_into()
+            .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+        let current_span: usize = current_span
+            .try_into()
+            .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+        while price_info.spot_prices.len() > span {
+            price_info.spot_prices.pop_front();
+        }
+        Ok(price_info.spot_prices.len() >= current_span)
+    }
+
+    /// Drop the oldest spot prices which don't fit into `span` and recalculate average from the rest.
+    /// If the average over the current span is valid, its change is bounded by `max_differences`.
+    fn truncate_to_span(
+        price_info: &mut PriceInfo,
+        span: u32,
+        current_span: u32,
+        max_differences: (Fixed, Fixed),
+    ) -> DispatchResult {
+        let span: usize = span
+            .try_into()
+            .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+        let current_span: usize = current_span
+            .try_into()
+            .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+        if price_info.spot_prices.len() > span {
+            let is_average_valid = price_info.spot_prices.len() >= current_span;
+            while price_info.spot_prices.len() > span {
+                price_info.spot_prices.pop_front();
+            }
+            Self::recalculate_average(price_info, is_average_valid.then_some(max_differences))?;
+        }
+        Ok(())
+    }
+
+    /// Set average price to the average of the spot prices. If `max_differences` are given, the change
+    /// of the average is bounded by them and the spot prices are scaled to match the bounded average.
+    fn recalculate_average(
+        price_info: &mut PriceInfo,
+        max_differences: Option<(Fixed, Fixed)>,
+    ) -> DispatchResult {
+        let sum = price_info
+            .spot_prices
+            .iter()
+            .fold(FixedWrapper::from(0), |a, b| a + *b);
+        let avg = (sum / balance!(price_info.spot_prices.len()))
+            .try_into_balance()
+            .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+        let adjusted_avg = match max_differences {
+            Some((max_inc, max_dec)) if price_info.average_price > 0 && avg > 0 => {
+                Self::adjust_to_difference(price_info.average_price, avg, max_inc, max_dec)?
+            }
+            _ => avg,
+        };
+        if adjusted_avg != avg {
+            let ratio = FixedWrapper::from(adjusted_avg) / avg;
+            for spot_price in price_info.spot_prices.iter_mut() {
+                *spot_price = (ratio.clone() * *spot_price)
+                    .try_into_balance()
+                    .map_err(|_| Error::<T>::AveragePriceCalculationFailed)?;
+            }
+        }
+        price_info.average_price = adjusted_avg;
+        Ok(())
+    }
+
     /// Register spot price quote failure, continuous failure has to block average price quotation.
     pub fn incoming_spot_price_failure(asset_id: &T::AssetId, price_variant: PriceVariant) {
+        let max_failures = AveragingParams::<T>::get(asset_id).max_failures;
         PriceInfos::<T>::mutate(asset_id, |opt| {
             if let Some(agg_price_info) = opt.as_mut() {
                 let val = agg_price_info.price_mut_of(price_variant);
-                if val.price_failures < AVG_BLOCK_SPAN {
+                if val.price_failures < max_failures {
                     val.price_failures += 1;
-                    if val.price_failures == AVG_BLOCK_SPAN {
-                        val.spot_prices.clear();
-                    }
+                }
+                // failure tolerance may have been decreased while the streak was running
+                if val.price_failures >= max_failures {
+                    val.spot_prices.clear();
                 }
             }
         })
     }
 
     /// Bound `new_avg` value by percentage difference with respect to `old_avg` value. Result will be capped
-    /// by `MAX_BLOCK_AVG_DIFFERENCE` either in positive or negative difference.
+    /// by `max_inc` in positive or by `max_dec` in negative difference.
     pub fn adjust_to_difference(
         old_avg: Balance,
         new_avg: Balance,
-        price_variant: PriceVariant,
+        max_inc: Fixed,
+        max_dec: Fixed,
     ) -> Result<Balance, DispatchError> {
         let mut adjusted_avg = FixedWrapper::from(new_avg);
         let old_avg = FixedWrapper::from(old_avg);
         let diff: Fixed = ((adjusted_avg.clone() - old_avg.clone()) / old_avg.clone())
             .get()
             .map_err(|_| Error::<T>::UpdateAverageWithSpotPriceFailed)?;
-        let (max_inc, max_dec) = match price_variant {
-            PriceVariant::Buy => (
-                MAX_BUY_BLOCK_INC_AVG_DIFFERENCE,
-                MAX_BUY_BLOCK_DEC_AVG_DIFFERENCE,
-            ),
-            PriceVariant::Sell => (
-                MAX_SELL_BLOCK_INC_AVG_DIFFERENCE,
-                MAX_SELL_BLOCK_DEC_AVG_DIFFERENCE,
-            ),
-        };
 
         if diff > max_inc {
             adjusted_avg = old_avg * (fixed_wrapper!(1) + max_inc);
@@ -460,6 +726,7 @@ impl<T: Config> PriceToolsProvider<T::AssetId> for Pallet<T> {
     fn register_asset(asset_id: &T::AssetId) -> DispatchResult {
         if PriceInfos::<T>::get(asset_id).is_none() {
             PriceInfos::<T>::insert(asset_id.clone(), AggregatedPriceInfo::default());
+            AveragingParams::<T>::insert(asset_id.clone(), AveragingParameters::default());
             Ok(())
         } else {
             fail!(Error::<T>::AssetAlreadyRegistered);
@@ -1,10 +1,15 @@
 use frame_support::dispatch::GetStorageVersion;
-use frame_support::traits::StorageVersion;
+use frame_support::log::info;
+use frame_support::traits::{Get, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
 
-use crate::PriceInfos;
-use crate::{AggregatedPriceInfo, Pallet};
+use crate::{AggregatedPriceInfo, AveragingParameters, Pallet};
+use crate::{AveragingParams, PriceInfos};
 use crate::{Config, PriceInfo};
 
+#[cfg(feature = "try-runtime")]
+use sp_std::prelude::Vec;
+
 pub fn migrate<T: Config>() {
     if Pallet::<T>::on_chain_storage_version() < StorageVersion::new(2) {
         PriceInfos::<T>::translate::<PriceInfo, _>(
@@ -18,3 +23,49 @@ pub fn migrate<T: Config>() {
         StorageVersion::new(2).put::<Pallet<T>>()
     }
 }
+
+pub struct MigrateToV3<T>(core::marker::PhantomData<T>);
+
+/// Migration which fills in the averaging parameters, which were hardcoded before, for the
+/// registered assets.
+impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+    fn on_runtime_upgrade() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(3) {
+            info!("Migration to version 3 has already been applied");
+            return T::DbWeight::get().reads(1);
+        }
+        info!("Migrating PriceTools to v3");
+
+        let mut weight = T::DbWeight::get().reads(1);
+        for asset_id in PriceInfos::<T>::iter_keys() {
+            AveragingParams::<T>::insert(asset_id, AveragingParameters::default());
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+        }
+
+        StorageVersion::new(3).put::<Pallet<T>>();
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        frame_support::ensure!(
+            Pallet::<T>::on_chain_storage_version() == StorageVersion::new(2),
+            "must upgrade linearly"
+        );
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+        frame_support::ensure!(
+            Pallet::<T>::on_chain_storage_version() == StorageVersion::new(3),
+            "should be upgraded to version 3"
+        );
+        frame_support::ensure!(
+            PriceInfos::<T>::iter_keys()
+                .all(|asset_id| AveragingParams::<T>::contains_key(asset_id)),
+            "averaging parameters should be set for all assets"
+        );
+        Ok(())
+    }
+}
@@ -31,13 +31,18 @@
 use std::convert::TryInto;
 
 use crate::mock::*;
-use crate::{Error, AVG_BLOCK_SPAN};
-use common::prelude::Balance;
+use crate::{
+    AveragingParameters, AveragingParams, Error, PendingAveragingParams, AVG_BLOCK_SPAN,
+    MAX_AVG_BLOCK_SPAN,
+};
+use common::prelude::{Balance, FixedWrapper};
 use common::{
-    balance, fixed_wrapper, OnPoolReservesChanged, PriceToolsProvider, PriceVariant, DOT, ETH,
-    PSWAP, VAL, XOR,
+    balance, fixed, fixed_wrapper, OnPoolReservesChanged, PriceToolsProvider, PriceVariant, DOT,
+    ETH, PSWAP, VAL, XOR,
 };
-use frame_support::assert_noop;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
 
 fn to_avg<'a, I>(it: I, size: u32) -> Balance
 where
@@ -568,3 +573,317 @@ fn asset_already_registered() {
         );
     });
 }
+
+#[test]
+fn price_info_should_reflect_spot_prices_and_failures() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        PriceTools::register_asset(&ETH).unwrap();
+        let price_info = PriceTools::price_infos(ETH)
+            .unwrap()
+            .price_of(PriceVariant::Buy);
+        assert!(price_info.needs_update());
+        assert_eq!(price_info.spot_prices_count(), 0);
+
+        for _ in 1..=3 {
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Buy).unwrap();
+        }
+        PriceTools::incoming_spot_price_failure(&ETH, PriceVariant::Buy);
+        let agg_price_info = PriceTools::price_infos(ETH).unwrap();
+        let price_info = agg_price_info.clone().price_of(PriceVariant::Buy);
+        assert!(!price_info.needs_update());
+        assert_eq!(price_info.spot_prices_count(), 3);
+        assert_eq!(price_info.last_spot_price(), balance!(10));
+        assert_eq!(price_info.price_failures(), 1);
+        assert_eq!(
+            agg_price_info
+                .price_of(PriceVariant::Sell)
+                .spot_prices_count(),
+            0
+        );
+
+        PriceTools::reserves_changed(&ETH);
+        assert!(PriceTools::price_infos(ETH)
+            .unwrap()
+            .price_of(PriceVariant::Buy)
+            .needs_update());
+    });
+}
+
+#[test]
+fn set_averaging_parameters_should_check_origin_and_bounds() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        PriceTools::register_asset(&ETH).unwrap();
+        let parameters = AveragingParameters::default();
+        assert_noop!(
+            PriceTools::set_averaging_parameters(RuntimeOrigin::signed(alice()), ETH, parameters),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceTools::set_averaging_parameters(RuntimeOrigin::root(), DAI, parameters),
+            Error::<Runtime>::UnsupportedQuotePath
+        );
+        for invalid in [
+            AveragingParameters {
+                span: 0,
+                ..parameters
+            },
+            AveragingParameters {
+                span: MAX_AVG_BLOCK_SPAN + 1,
+                ..parameters
+            },
+            AveragingParameters {
+                max_failures: 0,
+                ..parameters
+            },
+            AveragingParameters {
+                max_buy_increase: fixed!(1),
+                ..parameters
+            },
+            AveragingParameters {
+                max_sell_decrease: fixed!(-0.1),
+                ..parameters
+            },
+        ] {
+            assert_noop!(
+                PriceTools::set_averaging_parameters(RuntimeOrigin::root(), ETH, invalid),
+                Error::<Runtime>::InvalidAveragingParameters
+            );
+        }
+    });
+}
+
+#[test]
+fn decreasing_span_should_recalculate_average() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        PriceTools::register_asset(&ETH).unwrap();
+        assert_eq!(
+            AveragingParams::<Runtime>::get(ETH),
+            AveragingParameters::default()
+        );
+        for i in 1..=AVG_BLOCK_SPAN {
+            PriceTools::incoming_spot_price(&ETH, balance!(i), PriceVariant::Buy).unwrap();
+        }
+        let parameters = AveragingParameters {
+            span: 10,
+            ..Default::default()
+        };
+        assert_ok!(PriceTools::set_averaging_parameters(
+            RuntimeOrigin::root(),
+            ETH,
+            parameters
+        ));
+        frame_system::Pallet::<Runtime>::assert_last_event(
+            crate::Event::<Runtime>::AveragingParametersUpdated(ETH, parameters).into(),
+        );
+        // average of the last 10 prices is 25.5, the change from 15.5 is bounded
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+            (fixed_wrapper!(15.5) * fixed_wrapper!(1.00197)).into_balance()
+        );
+        // sell prices haven't been collected yet
+        assert_noop!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Sell),
+            Error::<Runtime>::InsufficientSpotPriceData
+        );
+        for _ in 1..10 {
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Sell).unwrap();
+        }
+        assert_noop!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Sell),
+            Error::<Runtime>::InsufficientSpotPriceData
+        );
+        PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Sell).unwrap();
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Sell).unwrap(),
+            balance!(10)
+        );
+    });
+}
+
+#[test]
+fn increasing_span_should_keep_current_average_until_history_is_filled() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        PriceTools::register_asset(&ETH).unwrap();
+        for _ in 1..=AVG_BLOCK_SPAN {
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Buy).unwrap();
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Sell).unwrap();
+        }
+        let parameters = AveragingParameters {
+            span: AVG_BLOCK_SPAN + 5,
+            ..Default::default()
+        };
+        assert_ok!(PriceTools::set_averaging_parameters(
+            RuntimeOrigin::root(),
+            ETH,
+            parameters
+        ));
+        assert_eq!(
+            AveragingParams::<Runtime>::get(ETH),
+            AveragingParameters::default()
+        );
+        assert_eq!(
+            PendingAveragingParams::<Runtime>::get(ETH),
+            Some(parameters)
+        );
+
+        // average over the current span is served and bounded as usual
+        let mut expected_avg = balance!(10);
+        for _ in 0..4 {
+            PriceTools::incoming_spot_price(&ETH, balance!(100), PriceVariant::Buy).unwrap();
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Sell).unwrap();
+            expected_avg =
+                (FixedWrapper::from(expected_avg) * fixed_wrapper!(1.00197)).into_balance();
+            assert_eq!(
+                PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+                expected_avg
+            );
+            assert_eq!(
+                PendingAveragingParams::<Runtime>::get(ETH),
+                Some(parameters)
+            );
+        }
+
+        PriceTools::incoming_spot_price(&ETH, balance!(100), PriceVariant::Buy).unwrap();
+        PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Sell).unwrap();
+        assert_eq!(AveragingParams::<Runtime>::get(ETH), parameters);
+        assert_eq!(PendingAveragingParams::<Runtime>::get(ETH), None);
+        // the first average over the new span is lower, its decrease is bounded
+        expected_avg = (FixedWrapper::from(expected_avg) * fixed_wrapper!(1.00197)).into_balance();
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+            (FixedWrapper::from(expected_avg) * fixed_wrapper!(0.99998)).into_balance()
+        );
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Sell).unwrap(),
+            balance!(10)
+        );
+    });
+}
+
+#[test]
+fn increasing_span_without_history_should_apply_immediately() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        PriceTools::register_asset(&ETH).unwrap();
+        let parameters = AveragingParameters {
+            span: AVG_BLOCK_SPAN + 5,
+            ..Default::default()
+        };
+        assert_ok!(PriceTools::set_averaging_parameters(
+            RuntimeOrigin::root(),
+            ETH,
+            parameters
+        ));
+        assert_eq!(AveragingParams::<Runtime>::get(ETH), parameters);
+        assert_eq!(PendingAveragingParams::<Runtime>::get(ETH), None);
+        for _ in 1..AVG_BLOCK_SPAN + 5 {
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Buy).unwrap();
+        }
+        assert_noop!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy),
+            Error::<Runtime>::InsufficientSpotPriceData
+        );
+        PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Buy).unwrap();
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+            balance!(10)
+        );
+    });
+}
+
+#[test]
+fn max_difference_should_be_taken_from_asset_parameters() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        for asset_id in [ETH, DAI].iter() {
+            PriceTools::register_asset(asset_id).unwrap();
+            for _ in 1..=AVG_BLOCK_SPAN {
+                PriceTools::incoming_spot_price(asset_id, balance!(10), PriceVariant::Buy).unwrap();
+            }
+        }
+        assert_ok!(PriceTools::set_averaging_parameters(
+            RuntimeOrigin::root(),
+            ETH,
+            AveragingParameters {
+                max_buy_increase: fixed!(0.5),
+                ..Default::default()
+            }
+        ));
+        for asset_id in [ETH, DAI].iter() {
+            PriceTools::incoming_spot_price(asset_id, balance!(40), PriceVariant::Buy).unwrap();
+        }
+        // (30 * 10 - 10 + 40) / 30 = 11, which is within the increased limit
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+            balance!(11)
+        );
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &DAI.into(), PriceVariant::Buy).unwrap(),
+            (fixed_wrapper!(10) + fixed_wrapper!(10) * fixed_wrapper!(0.00197)).into_balance()
+        );
+    });
+}
+
+#[test]
+fn failure_tolerance_should_be_taken_from_asset_parameters() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        PriceTools::register_asset(&ETH).unwrap();
+        for _ in 1..=AVG_BLOCK_SPAN {
+            PriceTools::incoming_spot_price(&ETH, balance!(10), PriceVariant::Buy).unwrap();
+        }
+        PriceTools::incoming_spot_price_failure(&ETH, PriceVariant::Buy);
+        PriceTools::incoming_spot_price_failure(&ETH, PriceVariant::Buy);
+        assert_eq!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy).unwrap(),
+            balance!(10)
+        );
+
+        // The streak already reaches the new tolerance, so the next failure resets the history
+        assert_ok!(PriceTools::set_averaging_parameters(
+            RuntimeOrigin::root(),
+            ETH,
+            AveragingParameters {
+                max_failures: 2,
+                ..Default::default()
+            }
+        ));
+        PriceTools::incoming_spot_price_failure(&ETH, PriceVariant::Buy);
+        assert_noop!(
+            PriceTools::get_average_price(&XOR.into(), &ETH.into(), PriceVariant::Buy),
+            Error::<Runtime>::InsufficientSpotPriceData
+        );
+    });
+}
+
+#[test]
+fn migration_should_fill_in_averaging_parameters() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        for asset_id in [ETH, DAI].iter() {
+            PriceTools::register_asset(asset_id).unwrap();
+            AveragingParams::<Runtime>::remove(asset_id);
+        }
+        StorageVersion::new(2).put::<PriceTools>();
+
+        crate::migration::MigrateToV3::<Runtime>::on_runtime_upgrade();
+
+        assert_eq!(
+            PriceTools::on_chain_storage_version(),
+            StorageVersion::new(3)
+        );
+        for asset_id in [ETH, DAI].iter() {
+            assert_eq!(
+                AveragingParams::<Runtime>::get(asset_id),
+                AveragingParameters::default()
+            );
+            assert!(AveragingParams::<Runtime>::contains_key(asset_id));
+        }
+        assert!(!AveragingParams::<Runtime>::contains_key(VAL));
+    });
+}
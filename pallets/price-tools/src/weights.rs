@@ -61,6 +61,7 @@ use core::marker::PhantomData;
 /// Weight functions needed for price_tools.
 pub trait WeightInfo {
 	fn on_initialize(a: u32, b: u32, ) -> Weight;
+	fn set_averaging_parameters() -> Weight;
 }
 
 /// Weights for price_tools using the Substrate node and recommended hardware.
@@ -68,6 +69,8 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: PriceTools PriceInfos (r:27 w:26)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PendingAveragingParams (r:20 w:0)
+	/// Proof Skipped: PriceTools PendingAveragingParams (max_values: None, max_size: None, mode: Measured)
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: XSTPool EnabledSynthetics (r:2 w:0)
@@ -95,7 +98,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 105_195
 			.saturating_add(Weight::from_parts(101_632_257, 0).saturating_mul(b.into()))
 			.saturating_add(T::DbWeight::get().reads(18_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(a.into())))
 			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(b.into())))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
@@ -103,12 +106,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 8985).saturating_mul(a.into()))
 			.saturating_add(Weight::from_parts(0, 19614).saturating_mul(b.into()))
 	}
+	/// Storage: PriceTools PriceInfos (r:1 w:1)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools AveragingParams (r:1 w:1)
+	/// Proof Skipped: PriceTools AveragingParams (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PendingAveragingParams (r:0 w:1)
+	/// Proof Skipped: PriceTools PendingAveragingParams (max_values: None, max_size: None, mode: Measured)
+	fn set_averaging_parameters() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1283`
+		//  Estimated: `4748`
+		// Minimum execution time: 31_426_000 picoseconds.
+		Weight::from_parts(32_108_000, 4748)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	/// Storage: PriceTools PriceInfos (r:27 w:26)
 	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PendingAveragingParams (r:20 w:0)
+	/// Proof Skipped: PriceTools PendingAveragingParams (max_values: None, max_size: None, mode: Measured)
 	/// Storage: DEXManager DEXInfos (r:1 w:0)
 	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
 	/// Storage: XSTPool EnabledSynthetics (r:2 w:0)
@@ -136,7 +156,7 @@ impl WeightInfo for () {
 			// Standard Error: 105_195
 			.saturating_add(Weight::from_parts(101_632_257, 0).saturating_mul(b.into()))
 			.saturating_add(RocksDbWeight::get().reads(18_u64))
-			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(a.into())))
 			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(b.into())))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
@@ -144,4 +164,19 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 8985).saturating_mul(a.into()))
 			.saturating_add(Weight::from_parts(0, 19614).saturating_mul(b.into()))
 	}
+	/// Storage: PriceTools PriceInfos (r:1 w:1)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools AveragingParams (r:1 w:1)
+	/// Proof Skipped: PriceTools AveragingParams (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools PendingAveragingParams (r:0 w:1)
+	/// Proof Skipped: PriceTools PendingAveragingParams (max_values: None, max_size: None, mode: Measured)
+	fn set_averaging_parameters() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1283`
+		//  Estimated: `4748`
+		// Minimum execution time: 31_426_000 picoseconds.
+		Weight::from_parts(32_108_000, 4748)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
@@ -0,0 +1,274 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Stable-swap pool module benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use codec::Decode;
+use common::{balance, AssetName, AssetSymbol, DEXId, DEFAULT_BALANCE_PRECISION, XOR};
+use frame_benchmarking::benchmarks;
+use frame_system::{EventRecord, RawOrigin};
+use hex_literal::hex;
+use sp_std::prelude::*;
+
+use assets::Pallet as Assets;
+
+pub const DEX: DEXId = DEXId::Polkaswap;
+const AMPLIFICATION: u128 = 100;
+
+// Support Functions
+fn alice<T: Config>() -> T::AccountId {
+    let bytes = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+    T::AccountId::decode(&mut &bytes[..]).expect("Failed to decode account ID")
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    let events = frame_system::Pallet::<T>::events();
+    let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+    // compare to the last event record
+    let EventRecord { event, .. } = &events[events.len() - 1];
+    assert_eq!(event, &system_event);
+}
+
+/// Registers a new asset and the trading pair with XOR, returns the new asset.
+fn setup_trading_pair<T: Config>() -> T::AssetId {
+    let owner = alice::<T>();
+    frame_system::Pallet::<T>::inc_providers(&owner);
+    let target_asset_id = Assets::<T>::register_from(
+        &owner,
+        AssetSymbol(b"STABLE".to_vec()),
+        AssetName(b"Stable".to_vec()),
+        DEFAULT_BALANCE_PRECISION,
+        balance!(100000),
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+    T::TradingPairSourceManager::register_pair(DEX.into(), XOR.into(), target_asset_id).unwrap();
+    Assets::<T>::mint_unchecked(&XOR.into(), &owner, balance!(100000)).unwrap();
+    target_asset_id
+}
+
+/// Initializes the pool and deposits liquidity, returns the target asset of the pool.
+fn setup_pool<T: Config>() -> T::AssetId {
+    let owner = alice::<T>();
+    let target_asset_id = setup_trading_pair::<T>();
+    Pallet::<T>::initialize_pool(
+        RawOrigin::Root.into(),
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        AMPLIFICATION,
+    )
+    .unwrap();
+    Pallet::<T>::deposit_liquidity(
+        RawOrigin::Signed(owner).into(),
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        balance!(10000),
+        balance!(10000),
+        0,
+    )
+    .unwrap();
+    target_asset_id
+}
+
+benchmarks! {
+    initialize_pool {
+        let target_asset_id = setup_trading_pair::<T>();
+    }: _(
+        RawOrigin::Root,
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        AMPLIFICATION
+    )
+    verify {
+        assert_last_event::<T>(
+            Event::<T>::PoolInitialized(
+                DEX.into(),
+                XOR.into(),
+                target_asset_id,
+                AMPLIFICATION
+            ).into()
+        )
+    }
+
+    deposit_liquidity {
+        let caller = alice::<T>();
+        let target_asset_id = setup_pool::<T>();
+    }: _(
+        RawOrigin::Signed(caller),
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        balance!(1000),
+        balance!(500),
+        0
+    )
+    verify {
+        let pool = Pools::<T>::get(&T::AssetId::from(XOR), &target_asset_id).unwrap();
+        assert_eq!(pool.base_reserve, balance!(11000));
+        assert_eq!(pool.target_reserve, balance!(10500));
+    }
+
+    withdraw_liquidity {
+        let caller = alice::<T>();
+        let target_asset_id = setup_pool::<T>();
+    }: _(
+        RawOrigin::Signed(caller),
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        balance!(1000),
+        0,
+        0
+    )
+    verify {
+        let pool = Pools::<T>::get(&T::AssetId::from(XOR), &target_asset_id).unwrap();
+        assert_eq!(pool.total_shares, balance!(19000));
+    }
+
+    ramp_amplification {
+        let target_asset_id = setup_pool::<T>();
+        let future_block = frame_system::Pallet::<T>::block_number()
+            + T::MinAmplificationRampBlocks::get();
+    }: _(
+        RawOrigin::Root,
+        DEX.into(),
+        XOR.into(),
+        target_asset_id,
+        AMPLIFICATION * 2,
+        future_block
+    )
+    verify {
+        assert_last_event::<T>(
+            Event::<T>::AmplificationRampStarted(
+                DEX.into(),
+                XOR.into(),
+                target_asset_id,
+                AMPLIFICATION,
+                AMPLIFICATION * 2,
+                future_block
+            ).into()
+        )
+    }
+
+    stop_ramp_amplification {
+        let target_asset_id = setup_pool::<T>();
+        let now = frame_system::Pallet::<T>::block_number();
+        let ramp_blocks = T::MinAmplificationRampBlocks::get();
+        Pallet::<T>::ramp_amplification(
+            RawOrigin::Root.into(),
+            DEX.into(),
+            XOR.into(),
+            target_asset_id,
+            AMPLIFICATION * 2,
+            now + ramp_blocks,
+        )
+        .unwrap();
+        frame_system::Pallet::<T>::set_block_number(now + ramp_blocks / 2u32.into());
+        let amplification = Pallet::<T>::amplification(
+            &Pools::<T>::get(&T::AssetId::from(XOR), &target_asset_id).unwrap()
+        );
+    }: _(
+        RawOrigin::Root,
+        DEX.into(),
+        XOR.into(),
+        target_asset_id
+    )
+    verify {
+        assert_last_event::<T>(
+            Event::<T>::AmplificationRampStopped(
+                DEX.into(),
+                XOR.into(),
+                target_asset_id,
+                amplification
+            ).into()
+        )
+    }
+
+    quote {
+        let target_asset_id = setup_pool::<T>();
+    }: {
+        Pallet::<T>::quote(
+            &DEX.into(),
+            &XOR.into(),
+            &target_asset_id,
+            QuoteAmount::with_desired_output(balance!(100)),
+            true,
+        )
+        .unwrap();
+    }
+
+    swap {
+        let caller = alice::<T>();
+        let target_asset_id = setup_pool::<T>();
+    }: {
+        Pallet::<T>::exchange(
+            &caller,
+            &caller,
+            &DEX.into(),
+            &XOR.into(),
+            &target_asset_id,
+            SwapAmount::with_desired_output(balance!(100), balance!(200)),
+        )
+        .unwrap();
+    }
+    verify {
+        let pool = Pools::<T>::get(&T::AssetId::from(XOR), &target_asset_id).unwrap();
+        assert_eq!(pool.target_reserve, balance!(9900));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Runtime};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Pallet::<Runtime>::test_benchmark_initialize_pool());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_deposit_liquidity());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_withdraw_liquidity());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_ramp_amplification());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_stop_ramp_amplification());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_quote());
+            assert_ok!(Pallet::<Runtime>::test_benchmark_swap());
+        });
+    }
+}
@@ -0,0 +1,989 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Stable-swap pools for pegged assets.
+//!
+//! Pools use the StableSwap (Curve) invariant, which gives prices close to 1:1 around the peg
+//! and falls back to constant-product-like behaviour when a pool becomes imbalanced. The
+//! curvature is controlled by the amplification coefficient which is set by governance. Changes
+//! of the amplification coefficient are ramped linearly over a number of blocks, as in Curve,
+//! so that the price doesn't jump and can't be exploited by front-running the change.
+//!
+//! Every pool consists of a base asset and a target asset. If the base asset is a base asset of
+//! the DEX, the pool is registered as a liquidity source of the trading pair and is used by the
+//! router. Pairs of pegged assets without the base asset of the DEX (e.g. DAI/USDT) are supported
+//! as well, such pools are available through `LiquiditySource` directly. Reserves of all pools
+//! are kept on a single technical account and accounted in the pallet storage. Swap fee is charged
+//! in the base asset of the pool and stays in the pool reserves.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use common::prelude::{Balance, FixedWrapper, QuoteAmount, SwapAmount, SwapOutcome};
+use common::{
+    fixed_wrapper, DexInfoProvider, EnsureTradingPairExists, Fixed, LiquiditySource,
+    LiquiditySourceType, RewardReason, TradingPairSourceManager,
+};
+use frame_support::dispatch::DispatchError;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use frame_support::{ensure, RuntimeDebug};
+use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, UniqueSaturatedInto};
+use sp_std::vec::Vec;
+
+pub mod math;
+pub mod weights;
+
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub use weights::WeightInfo;
+
+pub const TECH_ACCOUNT_PREFIX: &[u8] = b"stable-swap-pool";
+pub const TECH_ACCOUNT_RESERVES: &[u8] = b"reserves";
+
+/// Bounds of the amplification coefficient.
+pub const MIN_AMPLIFICATION: u128 = 1;
+pub const MAX_AMPLIFICATION: u128 = 1_000_000;
+/// Maximal factor by which the amplification coefficient can be changed with one ramp.
+pub const MAX_AMPLIFICATION_CHANGE: u128 = 10;
+
+type Technical<T> = technical::Pallet<T>;
+
+pub type PoolInfoOf<T> =
+    PoolInfo<<T as common::Config>::DEXId, <T as frame_system::Config>::BlockNumber>;
+
+/// Amplification coefficient which changes linearly from `initial` at `initial_block`
+/// to `future` at `future_block` and stays constant before and after.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Default, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct AmplificationRamp<BlockNumber> {
+    pub initial: u128,
+    pub future: u128,
+    pub initial_block: BlockNumber,
+    pub future_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> AmplificationRamp<BlockNumber> {
+    /// Amplification coefficient which doesn't change.
+    pub fn constant(amplification: u128, now: BlockNumber) -> Self {
+        Self {
+            initial: amplification,
+            future: amplification,
+            initial_block: now,
+            future_block: now,
+        }
+    }
+
+    /// Returns the amplification coefficient at the block `now`.
+    pub fn value_at(&self, now: BlockNumber) -> u128 {
+        if now >= self.future_block {
+            return self.future;
+        }
+        if now <= self.initial_block {
+            return self.initial;
+        }
+        let elapsed: u128 = (now - self.initial_block).unique_saturated_into();
+        let duration: u128 = (self.future_block - self.initial_block).unique_saturated_into();
+        let change =
+            |from: u128, to: u128| math::mul_div(to - from, elapsed, duration).unwrap_or(to - from);
+        if self.future >= self.initial {
+            self.initial + change(self.initial, self.future)
+        } else {
+            self.initial - change(self.future, self.initial)
+        }
+    }
+}
+
+/// State of a stable-swap pool.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Default, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct PoolInfo<DEXId, BlockNumber> {
+    /// DEX the pool belongs to, the pool is not available in other DEXes.
+    pub dex_id: DEXId,
+    /// Amount of the base asset in the pool.
+    pub base_reserve: Balance,
+    /// Amount of the target asset in the pool.
+    pub target_reserve: Balance,
+    /// Total amount of the liquidity shares issued for the pool.
+    pub total_shares: Balance,
+    /// Amplification coefficient of the invariant.
+    pub amplification: AmplificationRamp<BlockNumber>,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the pool assets ordered as (base, target), whether the input asset is the base one
+    /// and the pool itself. The pool is looked up in both orders of the assets.
+    fn pool_assets(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Result<(T::AssetId, T::AssetId, bool, PoolInfoOf<T>), DispatchError> {
+        let (base_asset_id, target_asset_id, input_is_base, pool) =
+            if let Some(pool) = Pools::<T>::get(input_asset_id, output_asset_id) {
+                (*input_asset_id, *output_asset_id, true, pool)
+            } else if let Some(pool) = Pools::<T>::get(output_asset_id, input_asset_id) {
+                (*output_asset_id, *input_asset_id, false, pool)
+            } else {
+                return Err(Error::<T>::PoolDoesNotExist.into());
+            };
+        ensure!(pool.dex_id == *dex_id, Error::<T>::PoolDoesNotExist);
+        Ok((base_asset_id, target_asset_id, input_is_base, pool))
+    }
+
+    /// Returns the pool of the DEX.
+    fn dex_pool(
+        dex_id: &T::DEXId,
+        base_asset_id: &T::AssetId,
+        target_asset_id: &T::AssetId,
+    ) -> Result<PoolInfoOf<T>, DispatchError> {
+        Pools::<T>::get(base_asset_id, target_asset_id)
+            .filter(|pool| pool.dex_id == *dex_id)
+            .ok_or_else(|| Error::<T>::PoolDoesNotExist.into())
+    }
+
+    /// Returns the current amplification coefficient of the pool.
+    pub fn amplification(pool: &PoolInfoOf<T>) -> u128 {
+        pool.amplification
+            .value_at(frame_system::Pallet::<T>::block_number())
+    }
+
+    fn ensure_amplification_is_valid(amplification: u128) -> Result<(), DispatchError> {
+        ensure!(
+            (MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&amplification),
+            Error::<T>::InvalidAmplification
+        );
+        Ok(())
+    }
+
+    /// `amount * (1 - fee)`
+    fn without_fee(amount: Balance, fee: Fixed) -> Result<Balance, DispatchError> {
+        (FixedWrapper::from(amount) * (fixed_wrapper!(1) - fee))
+            .try_into_balance()
+            .map_err(|_| Error::<T>::CalculationError.into())
+    }
+
+    /// `amount / (1 - fee)`
+    fn with_fee(amount: Balance, fee: Fixed) -> Result<Balance, DispatchError> {
+        (FixedWrapper::from(amount) / (fixed_wrapper!(1) - fee))
+            .try_into_balance()
+            .map_err(|_| Error::<T>::CalculationError.into())
+    }
+
+    /// Calculates the outcome of a swap in the given pool. The amount of the outcome is the
+    /// output amount for the desired input and the input amount for the desired output. The fee
+    /// is charged in the base asset. Rounding is in favour of the pool.
+    pub fn calc_swap(
+        pool: &PoolInfoOf<T>,
+        input_is_base: bool,
+        amount: QuoteAmount<Balance>,
+        deduce_fee: bool,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        let fee = if deduce_fee {
+            T::GetFee::get()
+        } else {
+            Fixed::default()
+        };
+        let (reserve_in, reserve_out) = if input_is_base {
+            (pool.base_reserve, pool.target_reserve)
+        } else {
+            (pool.target_reserve, pool.base_reserve)
+        };
+        ensure!(reserve_in > 0 && reserve_out > 0, Error::<T>::PoolIsEmpty);
+        let amplification = Self::amplification(pool);
+        let d = math::calc_d((reserve_in, reserve_out), amplification)
+            .ok_or(Error::<T>::CalculationError)?;
+
+        match amount {
+            QuoteAmount::WithDesiredInput { desired_amount_in } => {
+                ensure!(desired_amount_in > 0, Error::<T>::ZeroAmount);
+                let input = if input_is_base {
+                    Self::without_fee(desired_amount_in, fee)?
+                } else {
+                    desired_amount_in
+                };
+                let new_reserve_in = reserve_in
+                    .checked_add(input)
+                    .ok_or(Error::<T>::CalculationError)?;
+                let new_reserve_out = math::calc_y(new_reserve_in, d, amplification)
+                    .ok_or(Error::<T>::CalculationError)?;
+                let output = reserve_out
+                    .saturating_sub(new_reserve_out)
+                    .saturating_sub(1);
+                if input_is_base {
+                    Ok(SwapOutcome::new(output, desired_amount_in - input))
+                } else {
+                    let output_without_fee = Self::without_fee(output, fee)?;
+                    Ok(SwapOutcome::new(
+                        output_without_fee,
+                        output - output_without_fee,
+                    ))
+                }
+            }
+            QuoteAmount::WithDesiredOutput { desired_amount_out } => {
+                ensure!(desired_amount_out > 0, Error::<T>::ZeroAmount);
+                let output = if input_is_base {
+                    desired_amount_out
+                } else {
+                    Self::with_fee(desired_amount_out, fee)?
+                };
+                ensure!(output < reserve_out, Error::<T>::NotEnoughReserves);
+                let new_reserve_in = math::calc_y(reserve_out - output, d, amplification)
+                    .ok_or(Error::<T>::CalculationError)?;
+                let input = new_reserve_in.saturating_sub(reserve_in).saturating_add(1);
+                if input_is_base {
+                    let input_with_fee = Self::with_fee(input, fee)?;
+                    Ok(SwapOutcome::new(input_with_fee, input_with_fee - input))
+                } else {
+                    Ok(SwapOutcome::new(input, output - desired_amount_out))
+                }
+            }
+        }
+    }
+
+    /// Calculates the amount of shares minted for the deposit. Deposits which change the ratio
+    /// of the reserves pay a half of the swap fee on the imbalanced part, as in Curve.
+    pub fn calc_deposit_shares(
+        pool: &PoolInfoOf<T>,
+        base_amount: Balance,
+        target_amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        ensure!(base_amount > 0 || target_amount > 0, Error::<T>::ZeroAmount);
+        let amplification = Self::amplification(pool);
+        let new_base_reserve = pool
+            .base_reserve
+            .checked_add(base_amount)
+            .ok_or(Error::<T>::CalculationError)?;
+        let new_target_reserve = pool
+            .target_reserve
+            .checked_add(target_amount)
+            .ok_or(Error::<T>::CalculationError)?;
+        if pool.total_shares == 0 {
+            ensure!(
+                base_amount > 0 && target_amount > 0,
+                Error::<T>::InitialDepositMustContainBothAssets
+            );
+            return math::calc_d((new_base_reserve, new_target_reserve), amplification)
+                .ok_or_else(|| Error::<T>::CalculationError.into());
+        }
+
+        let d0 = math::calc_d((pool.base_reserve, pool.target_reserve), amplification)
+            .ok_or(Error::<T>::CalculationError)?;
+        let d1 = math::calc_d((new_base_reserve, new_target_reserve), amplification)
+            .ok_or(Error::<T>::CalculationError)?;
+        ensure!(d1 > d0, Error::<T>::ZeroAmount);
+
+        let imbalance_fee = FixedWrapper::from(T::GetFee::get()) / fixed_wrapper!(2);
+        let charge_fee = |old_reserve: Balance, new_reserve: Balance| {
+            let ideal_reserve =
+                math::mul_div(d1, old_reserve, d0).ok_or(Error::<T>::CalculationError)?;
+            let fee = (FixedWrapper::from(ideal_reserve.abs_diff(new_reserve))
+                * imbalance_fee.clone())
+            .try_into_balance()
+            .map_err(|_| Error::<T>::CalculationError)?;
+            Ok::<_, Error<T>>(new_reserve.saturating_sub(fee))
+        };
+        let d2 = math::calc_d(
+            (
+                charge_fee(pool.base_reserve, new_base_reserve)?,
+                charge_fee(pool.target_reserve, new_target_reserve)?,
+            ),
+            amplification,
+        )
+        .ok_or(Error::<T>::CalculationError)?;
+        math::mul_div(pool.total_shares, d2.saturating_sub(d0), d0)
+            .ok_or_else(|| Error::<T>::CalculationError.into())
+    }
+
+    /// Calculates the amounts of the base and target assets returned for the burned shares.
+    pub fn calc_withdraw_amounts(
+        pool: &PoolInfoOf<T>,
+        shares: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ensure!(shares > 0, Error::<T>::ZeroAmount);
+        ensure!(shares <= pool.total_shares, Error::<T>::InsufficientShares);
+        let base_amount = math::mul_div(pool.base_reserve, shares, pool.total_shares)
+            .ok_or(Error::<T>::CalculationError)?;
+        let target_amount = math::mul_div(pool.target_reserve, shares, pool.total_shares)
+            .ok_or(Error::<T>::CalculationError)?;
+        Ok((base_amount, target_amount))
+    }
+}
+
+impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, DispatchError>
+    for Pallet<T>
+{
+    fn can_exchange(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> bool {
+        Self::pool_assets(dex_id, input_asset_id, output_asset_id)
+            .map(|(_, _, _, pool)| pool.base_reserve > 0 && pool.target_reserve > 0)
+            .unwrap_or(false)
+    }
+
+    fn quote(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: QuoteAmount<Balance>,
+        deduce_fee: bool,
+    ) -> Result<(SwapOutcome<Balance>, Weight), DispatchError> {
+        let (_, _, input_is_base, pool) =
+            Self::pool_assets(dex_id, input_asset_id, output_asset_id)?;
+        let outcome = Self::calc_swap(&pool, input_is_base, amount, deduce_fee)?;
+        Ok((outcome, Self::quote_weight()))
+    }
+
+    fn exchange(
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Result<(SwapOutcome<Balance>, Weight), DispatchError> {
+        let (base_asset_id, target_asset_id, input_is_base, mut pool) =
+            Self::pool_assets(dex_id, input_asset_id, output_asset_id)?;
+        let outcome = Self::calc_swap(&pool, input_is_base, swap_amount.into(), true)?;
+        let (input_amount, output_amount) = match swap_amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in,
+                min_amount_out,
+            } => {
+                ensure!(
+                    outcome.amount >= min_amount_out,
+                    Error::<T>::SlippageLimitExceeded
+                );
+                (desired_amount_in, outcome.amount)
+            }
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out,
+                max_amount_in,
+            } => {
+                ensure!(
+                    outcome.amount <= max_amount_in,
+                    Error::<T>::SlippageLimitExceeded
+                );
+                (outcome.amount, desired_amount_out)
+            }
+        };
+
+        let tech_account_id = T::GetTechAccountId::get();
+        Technical::<T>::transfer_in(input_asset_id, sender, &tech_account_id, input_amount)?;
+        Technical::<T>::transfer_out(output_asset_id, &tech_account_id, receiver, output_amount)?;
+
+        let (reserve_in, reserve_out) = if input_is_base {
+            (&mut pool.base_reserve, &mut pool.target_reserve)
+        } else {
+            (&mut pool.target_reserve, &mut pool.base_reserve)
+        };
+        *reserve_in = reserve_in
+            .checked_add(input_amount)
+            .ok_or(Error::<T>::CalculationError)?;
+        *reserve_out = reserve_out
+            .checked_sub(output_amount)
+            .ok_or(Error::<T>::NotEnoughReserves)?;
+        Pools::<T>::insert(&base_asset_id, &target_asset_id, pool);
+
+        Ok((outcome, Self::exchange_weight()))
+    }
+
+    fn check_rewards(
+        _target_id: &T::DEXId,
+        _input_asset_id: &T::AssetId,
+        _output_asset_id: &T::AssetId,
+        _input_amount: Balance,
+        _output_amount: Balance,
+    ) -> Result<(Vec<(Balance, T::AssetId, RewardReason)>, Weight), DispatchError> {
+        // Stable-swap pools have no rewards
+        Ok((Vec::new(), Weight::zero()))
+    }
+
+    fn quote_without_impact(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: QuoteAmount<Balance>,
+        deduce_fee: bool,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        let (_, _, input_is_base, pool) =
+            Self::pool_assets(dex_id, input_asset_id, output_asset_id)?;
+        let (reserve_in, reserve_out) = if input_is_base {
+            (pool.base_reserve, pool.target_reserve)
+        } else {
+            (pool.target_reserve, pool.base_reserve)
+        };
+        ensure!(reserve_in > 0 && reserve_out > 0, Error::<T>::PoolIsEmpty);
+        let input_price_wrt_output = FixedWrapper::from(
+            math::calc_spot_price((reserve_in, reserve_out), Self::amplification(&pool))
+                .ok_or(Error::<T>::CalculationError)?,
+        );
+        let fee = if deduce_fee {
+            T::GetFee::get()
+        } else {
+            Fixed::default()
+        };
+        let (amount, fee_amount) = match amount {
+            QuoteAmount::WithDesiredInput { desired_amount_in } => {
+                if input_is_base {
+                    let input = Self::without_fee(desired_amount_in, fee)?;
+                    let output = FixedWrapper::from(input) * input_price_wrt_output;
+                    (output, FixedWrapper::from(desired_amount_in - input))
+                } else {
+                    let output = FixedWrapper::from(desired_amount_in) * input_price_wrt_output;
+                    let output_without_fee = output.clone() * (fixed_wrapper!(1) - fee);
+                    (output_without_fee.clone(), output - output_without_fee)
+                }
+            }
+            QuoteAmount::WithDesiredOutput { desired_amount_out } => {
+                if input_is_base {
+                    let input = FixedWrapper::from(desired_amount_out) / input_price_wrt_output;
+                    let input_with_fee = input.clone() / (fixed_wrapper!(1) - fee);
+                    (input_with_fee.clone(), input_with_fee - input)
+                } else {
+                    let output = Self::with_fee(desired_amount_out, fee)?;
+                    let input = FixedWrapper::from(output) / input_price_wrt_output;
+                    (input, FixedWrapper::from(output - desired_amount_out))
+                }
+            }
+        };
+        Ok(SwapOutcome::new(
+            amount
+                .try_into_balance()
+                .map_err(|_| Error::<T>::CalculationError)?,
+            fee_amount
+                .try_into_balance()
+                .map_err(|_| Error::<T>::CalculationError)?,
+        ))
+    }
+
+    fn quote_weight() -> Weight {
+        <T as Config>::WeightInfo::quote()
+    }
+
+    fn exchange_weight() -> Weight {
+        <T as Config>::WeightInfo::swap()
+    }
+
+    fn check_rewards_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use common::{AccountIdOf, DEXInfo, DexIdOf};
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::StorageVersion;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config + common::Config + assets::Config + technical::Config
+    {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Technical account which holds reserves of all pools.
+        type GetTechAccountId: Get<Self::TechAccountId>;
+        /// Swap fee, charged in the base asset.
+        type GetFee: Get<Fixed>;
+        type EnsureTradingPairExists: EnsureTradingPairExists<
+            Self::DEXId,
+            Self::AssetId,
+            DispatchError,
+        >;
+        type TradingPairSourceManager: TradingPairSourceManager<Self::DEXId, Self::AssetId>;
+        type DexInfoProvider: DexInfoProvider<Self::DEXId, DEXInfo<Self::AssetId>>;
+        /// Minimal number of blocks to ramp the amplification coefficient over.
+        #[pallet::constant]
+        type MinAmplificationRampBlocks: Get<Self::BlockNumber>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Initialize stable-swap pool for the pair of assets.
+        /// Can be only called by root.
+        ///
+        /// If the base asset is a base asset of the DEX, the trading pair must exist. Otherwise
+        /// the pool is a pair of pegged assets, its trading pair without the DEX base assets is
+        /// registered if needed. In both cases the pool is enabled as a liquidity source of the
+        /// trading pair, so the router can use it. The pool is available only in the given DEX.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset of the pool, the swap fee is charged in it.
+        /// - `target_asset_id`: target asset of the pool.
+        /// - `amplification`: amplification coefficient of the invariant.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::initialize_pool())]
+        pub fn initialize_pool(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+            amplification: u128,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            Self::ensure_amplification_is_valid(amplification)?;
+            ensure!(
+                base_asset_id != target_asset_id,
+                Error::<T>::IdenticalAssets
+            );
+            ensure!(
+                !Pools::<T>::contains_key(&base_asset_id, &target_asset_id)
+                    && !Pools::<T>::contains_key(&target_asset_id, &base_asset_id),
+                Error::<T>::PoolAlreadyExists
+            );
+            let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
+            let is_dex_base_asset = |asset_id: &T::AssetId| {
+                *asset_id == dex_info.base_asset_id || *asset_id == dex_info.synthetic_base_asset_id
+            };
+            ensure!(
+                !is_dex_base_asset(&target_asset_id),
+                Error::<T>::DexBaseAssetMustBePoolBaseAsset
+            );
+            Technical::<T>::register_tech_account_id_if_not_exist(&T::GetTechAccountId::get())?;
+            let (pair_base_asset_id, pair_target_asset_id) = if is_dex_base_asset(&base_asset_id) {
+                T::EnsureTradingPairExists::ensure_trading_pair_exists(
+                    &dex_id,
+                    &base_asset_id,
+                    &target_asset_id,
+                )?;
+                (base_asset_id, target_asset_id)
+            } else if T::EnsureTradingPairExists::ensure_trading_pair_exists(
+                &dex_id,
+                &target_asset_id,
+                &base_asset_id,
+            )
+            .is_ok()
+            {
+                // the pair of pegged assets is already registered in the other order
+                (target_asset_id, base_asset_id)
+            } else {
+                if T::EnsureTradingPairExists::ensure_trading_pair_exists(
+                    &dex_id,
+                    &base_asset_id,
+                    &target_asset_id,
+                )
+                .is_err()
+                {
+                    T::TradingPairSourceManager::register_non_base_pair(
+                        dex_id,
+                        base_asset_id,
+                        target_asset_id,
+                    )?;
+                }
+                (base_asset_id, target_asset_id)
+            };
+            T::TradingPairSourceManager::enable_source_for_trading_pair(
+                &dex_id,
+                &pair_base_asset_id,
+                &pair_target_asset_id,
+                LiquiditySourceType::StableSwapPool,
+            )?;
+            Pools::<T>::insert(
+                &base_asset_id,
+                &target_asset_id,
+                PoolInfo {
+                    dex_id,
+                    base_reserve: 0,
+                    target_reserve: 0,
+                    total_shares: 0,
+                    amplification: AmplificationRamp::constant(
+                        amplification,
+                        frame_system::Pallet::<T>::block_number(),
+                    ),
+                },
+            );
+            Self::deposit_event(Event::PoolInitialized(
+                dex_id,
+                base_asset_id,
+                target_asset_id,
+                amplification,
+            ));
+            Ok(().into())
+        }
+
+        /// Add liquidity to the pool. Any proportion of the assets is allowed except for the first
+        /// deposit which must contain both assets.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset of the pool.
+        /// - `target_asset_id`: target asset of the pool.
+        /// - `base_amount`: amount of the base asset to deposit.
+        /// - `target_amount`: amount of the target asset to deposit.
+        /// - `min_shares`: minimal amount of the liquidity shares to receive.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit_liquidity())]
+        pub fn deposit_liquidity(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+            base_amount: Balance,
+            target_amount: Balance,
+            min_shares: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut pool = Self::dex_pool(&dex_id, &base_asset_id, &target_asset_id)?;
+            let shares = Self::calc_deposit_shares(&pool, base_amount, target_amount)?;
+            ensure!(shares > 0, Error::<T>::ZeroAmount);
+            ensure!(shares >= min_shares, Error::<T>::SlippageLimitExceeded);
+
+            let tech_account_id = T::GetTechAccountId::get();
+            if base_amount > 0 {
+                Technical::<T>::transfer_in(&base_asset_id, &who, &tech_account_id, base_amount)?;
+            }
+            if target_amount > 0 {
+                Technical::<T>::transfer_in(
+                    &target_asset_id,
+                    &who,
+                    &tech_account_id,
+                    target_amount,
+                )?;
+            }
+
+            pool.base_reserve = pool
+                .base_reserve
+                .checked_add(base_amount)
+                .ok_or(Error::<T>::CalculationError)?;
+            pool.target_reserve = pool
+                .target_reserve
+                .checked_add(target_amount)
+                .ok_or(Error::<T>::CalculationError)?;
+            pool.total_shares = pool
+                .total_shares
+                .checked_add(shares)
+                .ok_or(Error::<T>::CalculationError)?;
+            Pools::<T>::insert(&base_asset_id, &target_asset_id, pool);
+            LiquidityProviders::<T>::mutate((&base_asset_id, &target_asset_id), &who, |value| {
+                *value = value.saturating_add(shares)
+            });
+
+            Self::deposit_event(Event::LiquidityDeposited(
+                who,
+                dex_id,
+                base_asset_id,
+                target_asset_id,
+                base_amount,
+                target_amount,
+                shares,
+            ));
+            Ok(().into())
+        }
+
+        /// Burn liquidity shares and receive the proportional part of the pool reserves.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset of the pool.
+        /// - `target_asset_id`: target asset of the pool.
+        /// - `shares`: amount of the liquidity shares to burn.
+        /// - `min_base_amount`: minimal amount of the base asset to receive.
+        /// - `min_target_amount`: minimal amount of the target asset to receive.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_liquidity())]
+        pub fn withdraw_liquidity(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+            shares: Balance,
+            min_base_amount: Balance,
+            min_target_amount: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut pool = Self::dex_pool(&dex_id, &base_asset_id, &target_asset_id)?;
+            let provider_shares =
+                LiquidityProviders::<T>::get((&base_asset_id, &target_asset_id), &who);
+            ensure!(shares <= provider_shares, Error::<T>::InsufficientShares);
+            let (base_amount, target_amount) = Self::calc_withdraw_amounts(&pool, shares)?;
+            ensure!(
+                base_amount >= min_base_amount && target_amount >= min_target_amount,
+                Error::<T>::SlippageLimitExceeded
+            );
+
+            let tech_account_id = T::GetTechAccountId::get();
+            if base_amount > 0 {
+                Technical::<T>::transfer_out(&base_asset_id, &tech_account_id, &who, base_amount)?;
+            }
+            if target_amount > 0 {
+                Technical::<T>::transfer_out(
+                    &target_asset_id,
+                    &tech_account_id,
+                    &who,
+                    target_amount,
+                )?;
+            }
+
+            pool.base_reserve -= base_amount;
+            pool.target_reserve -= target_amount;
+            pool.total_shares -= shares;
+            Pools::<T>::insert(&base_asset_id, &target_asset_id, pool);
+            if provider_shares == shares {
+                LiquidityProviders::<T>::remove((&base_asset_id, &target_asset_id), &who);
+            } else {
+                LiquidityProviders::<T>::insert(
+                    (&base_asset_id, &target_asset_id),
+                    &who,
+                    provider_shares - shares,
+                );
+            }
+
+            Self::deposit_event(Event::LiquidityWithdrawn(
+                who,
+                dex_id,
+                base_asset_id,
+                target_asset_id,
+                base_amount,
+                target_amount,
+                shares,
+            ));
+            Ok(().into())
+        }
+
+        /// Start changing amplification coefficient of the pool. The coefficient changes linearly
+        /// from the current value to `future_amplification` until `future_block`.
+        /// An ongoing ramp is replaced by the new one starting from the current value.
+        /// Can be only called by root.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset of the pool.
+        /// - `target_asset_id`: target asset of the pool.
+        /// - `future_amplification`: amplification coefficient at the end of the ramp, it can
+        /// differ from the current one at most by `MAX_AMPLIFICATION_CHANGE` times.
+        /// - `future_block`: block at which the ramp ends, it must be at least
+        /// `MinAmplificationRampBlocks` after the current block.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::ramp_amplification())]
+        pub fn ramp_amplification(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+            future_amplification: u128,
+            future_block: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            Self::ensure_amplification_is_valid(future_amplification)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                future_block >= now.saturating_add(T::MinAmplificationRampBlocks::get()),
+                Error::<T>::AmplificationRampTooShort
+            );
+            let current_amplification =
+                Pools::<T>::try_mutate(&base_asset_id, &target_asset_id, |pool| {
+                    let pool = pool
+                        .as_mut()
+                        .filter(|pool| pool.dex_id == dex_id)
+                        .ok_or(Error::<T>::PoolDoesNotExist)?;
+                    let current_amplification = pool.amplification.value_at(now);
+                    ensure!(
+                        future_amplification
+                            <= current_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE)
+                            && future_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE)
+                                >= current_amplification,
+                        Error::<T>::AmplificationChangeTooLarge
+                    );
+                    pool.amplification = AmplificationRamp {
+                        initial: current_amplification,
+                        future: future_amplification,
+                        initial_block: now,
+                        future_block,
+                    };
+                    Ok::<_, Error<T>>(current_amplification)
+                })?;
+            Self::deposit_event(Event::AmplificationRampStarted(
+                dex_id,
+                base_asset_id,
+                target_asset_id,
+                current_amplification,
+                future_amplification,
+                future_block,
+            ));
+            Ok(().into())
+        }
+
+        /// Stop changing amplification coefficient of the pool, the current value is kept.
+        /// Can be only called by root.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset of the pool.
+        /// - `target_asset_id`: target asset of the pool.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::stop_ramp_amplification())]
+        pub fn stop_ramp_amplification(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let amplification = Pools::<T>::try_mutate(&base_asset_id, &target_asset_id, |pool| {
+                let pool = pool
+                    .as_mut()
+                    .filter(|pool| pool.dex_id == dex_id)
+                    .ok_or(Error::<T>::PoolDoesNotExist)?;
+                let amplification = pool.amplification.value_at(now);
+                pool.amplification = AmplificationRamp::constant(amplification, now);
+                Ok::<_, Error<T>>(amplification)
+            })?;
+            Self::deposit_event(Event::AmplificationRampStopped(
+                dex_id,
+                base_asset_id,
+                target_asset_id,
+                amplification,
+            ));
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Pool has been initialized. [DEX Id, Base Asset Id, Target Asset Id, Amplification]
+        PoolInitialized(DexIdOf<T>, T::AssetId, T::AssetId, u128),
+        /// Liquidity has been deposited.
+        /// [Who, DEX Id, Base Asset Id, Target Asset Id, Base Amount, Target Amount, Shares]
+        LiquidityDeposited(
+            AccountIdOf<T>,
+            DexIdOf<T>,
+            T::AssetId,
+            T::AssetId,
+            Balance,
+            Balance,
+            Balance,
+        ),
+        /// Liquidity has been withdrawn.
+        /// [Who, DEX Id, Base Asset Id, Target Asset Id, Base Amount, Target Amount, Shares]
+        LiquidityWithdrawn(
+            AccountIdOf<T>,
+            DexIdOf<T>,
+            T::AssetId,
+            T::AssetId,
+            Balance,
+            Balance,
+            Balance,
+        ),
+        /// Amplification coefficient has started to change.
+        /// [DEX Id, Base Asset Id, Target Asset Id, Initial Amplification, Future Amplification,
+        /// Future Block]
+        AmplificationRampStarted(
+            DexIdOf<T>,
+            T::AssetId,
+            T::AssetId,
+            u128,
+            u128,
+            T::BlockNumber,
+        ),
+        /// Amplification coefficient has stopped changing.
+        /// [DEX Id, Base Asset Id, Target Asset Id, Amplification]
+        AmplificationRampStopped(DexIdOf<T>, T::AssetId, T::AssetId, u128),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Pool for the trading pair already exists.
+        PoolAlreadyExists,
+        /// Pool for the trading pair does not exist.
+        PoolDoesNotExist,
+        /// Pool has no liquidity.
+        PoolIsEmpty,
+        /// Amplification coefficient is out of the allowed bounds.
+        InvalidAmplification,
+        /// Base and target assets of the pool are the same.
+        IdenticalAssets,
+        /// Base asset of the DEX can be only the base asset of the pool.
+        DexBaseAssetMustBePoolBaseAsset,
+        /// Amplification coefficient must be ramped over more blocks.
+        AmplificationRampTooShort,
+        /// Amplification coefficient is changed by too large factor.
+        AmplificationChangeTooLarge,
+        /// The first deposit to the pool must contain both assets.
+        InitialDepositMustContainBothAssets,
+        /// Amount must be greater than zero.
+        ZeroAmount,
+        /// Account doesn't have enough liquidity shares.
+        InsufficientShares,
+        /// Pool doesn't have enough reserves for the swap.
+        NotEnoughReserves,
+        /// Outcome of the operation is worse than the specified limit.
+        SlippageLimitExceeded,
+        /// Error during calculations of the invariant or amounts.
+        CalculationError,
+    }
+
+    /// Stable-swap pools, there is at most one pool for a pair of assets among all DEXes.
+    /// [Base Asset Id, Target Asset Id] => Pool
+    #[pallet::storage]
+    #[pallet::getter(fn pools)]
+    pub type Pools<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AssetId,
+        PoolInfoOf<T>,
+        OptionQuery,
+    >;
+
+    /// Liquidity shares of the providers. [(Base Asset Id, Target Asset Id), Account Id] => Shares
+    #[pallet::storage]
+    #[pallet::getter(fn liquidity_providers)]
+    pub type LiquidityProviders<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (T::AssetId, T::AssetId),
+        Blake2_128Concat,
+        T::AccountId,
+        Balance,
+        ValueQuery,
+    >;
+}
@@ -0,0 +1,207 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! StableSwap invariant for two-asset pools:
+//!
+//! `A * n^n * (x + y) + D = A * D * n^n + D^(n + 1) / (n^n * x * y)`, where `n = 2`,
+//! `x` and `y` are the reserves, `A` is the amplification coefficient and `D` is the total
+//! amount of the assets when they have equal prices.
+//!
+//! All calculations are made in `U256` to avoid overflows of the intermediate values.
+
+use common::Balance;
+use sp_core::U256;
+
+/// Number of assets in a pool.
+const N: u128 = 2;
+/// Maximum number of the Newton's method iterations.
+const MAX_ITERATIONS: usize = 255;
+/// Precision of the prices returned by [`calc_spot_price`].
+const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+fn to_balance(value: U256) -> Option<Balance> {
+    if value > U256::from(Balance::MAX) {
+        None
+    } else {
+        Some(value.low_u128())
+    }
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// `A * n^n`
+fn ann(amplification: u128) -> Option<U256> {
+    U256::from(amplification).checked_mul(U256::from(N * N))
+}
+
+/// Calculates `a * b / c` without intermediate overflow.
+pub fn mul_div(a: Balance, b: Balance, c: Balance) -> Option<Balance> {
+    let value = U256::from(a)
+        .checked_mul(U256::from(b))?
+        .checked_div(U256::from(c))?;
+    to_balance(value)
+}
+
+/// Calculates the invariant `D` for the given reserves.
+pub fn calc_d(reserves: (Balance, Balance), amplification: u128) -> Option<Balance> {
+    let (x, y) = (U256::from(reserves.0), U256::from(reserves.1));
+    let sum = x + y;
+    if sum.is_zero() {
+        return Some(0);
+    }
+    if x.is_zero() || y.is_zero() {
+        return None;
+    }
+    let n = U256::from(N);
+    let ann = ann(amplification)?;
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // D^(n + 1) / (n^n * x * y)
+        let d_p = d.checked_mul(d)?.checked_div(x * n)?;
+        let d_p = d_p.checked_mul(d)?.checked_div(y * n)?;
+        let d_prev = d;
+        let numerator = ann
+            .checked_mul(sum)?
+            .checked_add(d_p.checked_mul(n)?)?
+            .checked_mul(d)?;
+        let denominator = (ann - 1)
+            .checked_mul(d)?
+            .checked_add(d_p.checked_mul(n + 1)?)?;
+        d = numerator.checked_div(denominator)?;
+        if abs_diff(d, d_prev) <= U256::one() {
+            return to_balance(d);
+        }
+    }
+    None
+}
+
+/// Calculates the reserve of one asset which keeps the invariant `d` when the reserve of
+/// the other asset is `x`.
+pub fn calc_y(x: Balance, d: Balance, amplification: u128) -> Option<Balance> {
+    let (x, d) = (U256::from(x), U256::from(d));
+    if x.is_zero() || d.is_zero() {
+        return None;
+    }
+    let n = U256::from(N);
+    let ann = ann(amplification)?;
+    // D^(n + 1) / (n^n * x * A * n^n)
+    let c = d.checked_mul(d)?.checked_div(x * n)?;
+    let c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+    let b = x.checked_add(d.checked_div(ann)?)?;
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = y
+            .checked_mul(n)?
+            .checked_add(b)?
+            .checked_sub(d)
+            .filter(|denominator| !denominator.is_zero())?;
+        y = numerator.checked_div(denominator)?;
+        if abs_diff(y, y_prev) <= U256::one() {
+            return to_balance(y);
+        }
+    }
+    None
+}
+
+/// Calculates the marginal price of the asset with reserve `x` in terms of the asset with
+/// reserve `y`, i.e. `-dy/dx` along the invariant curve, with 18 decimals precision.
+pub fn calc_spot_price(reserves: (Balance, Balance), amplification: u128) -> Option<Balance> {
+    let d = U256::from(calc_d(reserves, amplification)?);
+    let (x, y) = (U256::from(reserves.0), U256::from(reserves.1));
+    let precision = U256::from(PRICE_PRECISION);
+    let ann = ann(amplification)?.checked_mul(precision)?;
+    // D / x and D / y scaled by the precision
+    let d_x = d.checked_mul(precision)?.checked_div(x)?;
+    let d_y = d.checked_mul(precision)?.checked_div(y)?;
+    // D^3 / (4 * x^2 * y) and D^3 / (4 * x * y^2) scaled by the precision
+    let term_x = d_x.checked_mul(d_x)?.checked_mul(d_y)? / precision / precision / 4;
+    let term_y = d_x.checked_mul(d_y)?.checked_mul(d_y)? / precision / precision / 4;
+    let price = ann
+        .checked_add(term_x)?
+        .checked_mul(precision)?
+        .checked_div(ann.checked_add(term_y)?)?;
+    to_balance(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::balance;
+
+    #[test]
+    fn invariant_of_balanced_pool_is_sum_of_reserves() {
+        assert_eq!(
+            calc_d((balance!(1000), balance!(1000)), 100),
+            Some(balance!(2000))
+        );
+        assert_eq!(calc_d((0, 0), 100), Some(0));
+        assert_eq!(calc_d((balance!(1000), 0), 100), None);
+    }
+
+    #[test]
+    fn mul_div_does_not_overflow() {
+        assert_eq!(
+            mul_div(Balance::MAX, balance!(2), balance!(4)),
+            Some(Balance::MAX / 2)
+        );
+        assert_eq!(mul_div(Balance::MAX, 2, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    fn calc_y_keeps_invariant() {
+        let reserves = (balance!(1000), balance!(3000));
+        let d = calc_d(reserves, 100).unwrap();
+        let y = calc_y(reserves.0, d, 100).unwrap();
+        assert!(y.abs_diff(reserves.1) <= 2);
+        let x = calc_y(reserves.1, d, 100).unwrap();
+        assert!(x.abs_diff(reserves.0) <= 2);
+    }
+
+    #[test]
+    fn spot_price_of_balanced_pool_is_one() {
+        assert_eq!(
+            calc_spot_price((balance!(1000), balance!(1000)), 100),
+            Some(balance!(1))
+        );
+        let price = calc_spot_price((balance!(1000), balance!(3000)), 100).unwrap();
+        assert!(price > balance!(1) && price < balance!(1.01));
+        let price = calc_spot_price((balance!(3000), balance!(1000)), 100).unwrap();
+        assert!(price < balance!(1) && price > balance!(0.99));
+    }
+}
@@ -0,0 +1,681 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::mock::*;
+use crate::{Error, Event, Pools};
+use common::prelude::{QuoteAmount, SwapAmount};
+use common::{
+    balance, AssetInfoProvider, DEXId, LiquiditySource, LiquiditySourceType,
+    TradingPairSourceManager, DAI, USDT, XSTUSD,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+type Assets = assets::Pallet<Runtime>;
+
+const AMPLIFICATION: u128 = 100;
+
+fn init_pool() {
+    assert_ok!(StableSwapPool::initialize_pool(
+        RuntimeOrigin::root(),
+        DEX_ID,
+        XSTUSD,
+        DAI,
+        AMPLIFICATION
+    ));
+}
+
+fn init_pool_with_liquidity() {
+    init_pool();
+    assert_ok!(StableSwapPool::deposit_liquidity(
+        RuntimeOrigin::signed(ALICE),
+        DEX_ID,
+        XSTUSD,
+        DAI,
+        balance!(10000),
+        balance!(10000),
+        balance!(20000)
+    ));
+}
+
+#[test]
+fn initialize_pool_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::signed(ALICE),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                AMPLIFICATION
+            ),
+            DispatchError::BadOrigin
+        );
+        init_pool();
+        System::assert_last_event(
+            Event::<Runtime>::PoolInitialized(DEX_ID, XSTUSD, DAI, AMPLIFICATION).into(),
+        );
+        assert!(TradingPair::is_source_enabled_for_trading_pair(
+            &DEX_ID,
+            &XSTUSD,
+            &DAI,
+            LiquiditySourceType::StableSwapPool
+        )
+        .unwrap());
+        assert_eq!(
+            StableSwapPool::amplification(&StableSwapPool::pools(XSTUSD, DAI).unwrap()),
+            AMPLIFICATION
+        );
+        // Pool without liquidity can't be used for exchange
+        assert!(!StableSwapPool::can_exchange(&DEX_ID, &XSTUSD, &DAI));
+
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                AMPLIFICATION
+            ),
+            Error::<Runtime>::PoolAlreadyExists
+        );
+        assert_noop!(
+            StableSwapPool::initialize_pool(RuntimeOrigin::root(), DEX_ID, XSTUSD, USDT, 0),
+            Error::<Runtime>::InvalidAmplification
+        );
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                common::XOR,
+                AMPLIFICATION
+            ),
+            trading_pair::Error::<Runtime>::TradingPairDoesntExist
+        );
+    });
+}
+
+#[test]
+fn deposit_and_withdraw_liquidity_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool();
+        assert_noop!(
+            StableSwapPool::deposit_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                balance!(10000),
+                0,
+                0
+            ),
+            Error::<Runtime>::InitialDepositMustContainBothAssets
+        );
+        assert_noop!(
+            StableSwapPool::deposit_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                balance!(10000),
+                balance!(10000),
+                balance!(20001)
+            ),
+            Error::<Runtime>::SlippageLimitExceeded
+        );
+        assert_ok!(StableSwapPool::deposit_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            balance!(10000),
+            balance!(10000),
+            balance!(20000)
+        ));
+        assert_eq!(
+            StableSwapPool::liquidity_providers((XSTUSD, DAI), ALICE),
+            balance!(20000)
+        );
+
+        // Balanced deposit receives proportional shares without a fee
+        assert_ok!(StableSwapPool::deposit_liquidity(
+            RuntimeOrigin::signed(BOB),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            balance!(1000),
+            balance!(1000),
+            0
+        ));
+        let bob_shares = StableSwapPool::liquidity_providers((XSTUSD, DAI), BOB);
+        assert!(bob_shares.abs_diff(balance!(2000)) <= 10);
+        let pool = StableSwapPool::pools(XSTUSD, DAI).unwrap();
+        assert_eq!(pool.base_reserve, balance!(11000));
+        assert_eq!(pool.target_reserve, balance!(11000));
+        assert_eq!(pool.total_shares, balance!(20000) + bob_shares);
+
+        assert_noop!(
+            StableSwapPool::withdraw_liquidity(
+                RuntimeOrigin::signed(BOB),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                bob_shares + 1,
+                0,
+                0
+            ),
+            Error::<Runtime>::InsufficientShares
+        );
+        assert_ok!(StableSwapPool::withdraw_liquidity(
+            RuntimeOrigin::signed(BOB),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            bob_shares,
+            balance!(999.99),
+            balance!(999.99)
+        ));
+        assert_eq!(StableSwapPool::liquidity_providers((XSTUSD, DAI), BOB), 0);
+        assert!(Assets::free_balance(&XSTUSD, &BOB).unwrap() <= balance!(10000));
+        assert!(Assets::free_balance(&XSTUSD, &BOB).unwrap() > balance!(9999.99));
+        assert_eq!(
+            StableSwapPool::pools(XSTUSD, DAI).unwrap().total_shares,
+            balance!(20000)
+        );
+    });
+}
+
+#[test]
+fn imbalanced_deposit_should_pay_fee() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        assert_ok!(StableSwapPool::deposit_liquidity(
+            RuntimeOrigin::signed(BOB),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            balance!(2000),
+            0,
+            0
+        ));
+        let bob_shares = StableSwapPool::liquidity_providers((XSTUSD, DAI), BOB);
+        assert!(bob_shares < balance!(2000));
+        assert!(bob_shares > balance!(1990));
+
+        // Withdrawing the shares right away doesn't give back more than deposited
+        assert_ok!(StableSwapPool::withdraw_liquidity(
+            RuntimeOrigin::signed(BOB),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            bob_shares,
+            0,
+            0
+        ));
+        let xstusd_received = Assets::free_balance(&XSTUSD, &BOB).unwrap() - balance!(8000);
+        let dai_received = Assets::free_balance(&DAI, &BOB).unwrap() - balance!(10000);
+        assert!(xstusd_received + dai_received < balance!(2000));
+    });
+}
+
+#[test]
+fn swap_should_have_low_slippage_near_peg() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        assert!(StableSwapPool::can_exchange(&DEX_ID, &XSTUSD, &DAI));
+        assert!(StableSwapPool::can_exchange(&DEX_ID, &DAI, &XSTUSD));
+        assert!(!StableSwapPool::can_exchange(&DEX_ID, &DAI, &USDT));
+
+        let (outcome, _) = StableSwapPool::quote(
+            &DEX_ID,
+            &XSTUSD,
+            &DAI,
+            QuoteAmount::with_desired_input(balance!(1000)),
+            true,
+        )
+        .unwrap();
+        assert_eq!(outcome.fee, balance!(0.4));
+        // Constant product pool would give ~908.7 here
+        assert!(outcome.amount > balance!(999));
+        assert!(outcome.amount < balance!(999.6));
+
+        assert_noop!(
+            StableSwapPool::exchange(
+                &BOB,
+                &BOB,
+                &DEX_ID,
+                &XSTUSD,
+                &DAI,
+                SwapAmount::with_desired_input(balance!(1000), outcome.amount + 1),
+            ),
+            Error::<Runtime>::SlippageLimitExceeded
+        );
+        assert_ok!(StableSwapPool::exchange(
+            &BOB,
+            &BOB,
+            &DEX_ID,
+            &XSTUSD,
+            &DAI,
+            SwapAmount::with_desired_input(balance!(1000), outcome.amount),
+        ));
+        assert_eq!(Assets::free_balance(&XSTUSD, &BOB).unwrap(), balance!(9000));
+        assert_eq!(
+            Assets::free_balance(&DAI, &BOB).unwrap(),
+            balance!(10000) + outcome.amount
+        );
+        let pool = StableSwapPool::pools(XSTUSD, DAI).unwrap();
+        assert_eq!(pool.base_reserve, balance!(11000));
+        assert_eq!(pool.target_reserve, balance!(10000) - outcome.amount);
+    });
+}
+
+#[test]
+fn quotes_should_be_consistent() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        for (input, output) in [(XSTUSD, DAI), (DAI, XSTUSD)] {
+            let (with_input, _) = StableSwapPool::quote(
+                &DEX_ID,
+                &input,
+                &output,
+                QuoteAmount::with_desired_input(balance!(500)),
+                true,
+            )
+            .unwrap();
+            let (with_output, _) = StableSwapPool::quote(
+                &DEX_ID,
+                &input,
+                &output,
+                QuoteAmount::with_desired_output(with_input.amount),
+                true,
+            )
+            .unwrap();
+            assert!(with_output.amount.abs_diff(balance!(500)) < balance!(0.000001));
+            assert!(with_output.fee.abs_diff(with_input.fee) < balance!(0.000001));
+
+            let without_impact = StableSwapPool::quote_without_impact(
+                &DEX_ID,
+                &input,
+                &output,
+                QuoteAmount::with_desired_input(balance!(500)),
+                true,
+            )
+            .unwrap();
+            assert_eq!(without_impact.amount, balance!(499.8));
+            assert_eq!(without_impact.fee, balance!(0.2));
+            assert!(with_input.amount < without_impact.amount);
+        }
+
+        assert_noop!(
+            StableSwapPool::quote(
+                &DEX_ID,
+                &XSTUSD,
+                &DAI,
+                QuoteAmount::with_desired_output(balance!(10000)),
+                true,
+            ),
+            Error::<Runtime>::NotEnoughReserves
+        );
+    });
+}
+
+#[test]
+fn initialize_pool_of_non_base_assets_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            StableSwapPool::initialize_pool(RuntimeOrigin::root(), DEX_ID, DAI, DAI, AMPLIFICATION),
+            Error::<Runtime>::IdenticalAssets
+        );
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                DAI,
+                XSTUSD,
+                AMPLIFICATION
+            ),
+            Error::<Runtime>::DexBaseAssetMustBePoolBaseAsset
+        );
+        assert_ok!(StableSwapPool::initialize_pool(
+            RuntimeOrigin::root(),
+            DEX_ID,
+            DAI,
+            USDT,
+            AMPLIFICATION
+        ));
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                USDT,
+                DAI,
+                AMPLIFICATION
+            ),
+            Error::<Runtime>::PoolAlreadyExists
+        );
+        // The trading pair of the assets is registered, so the pool is a source of the router
+        assert!(TradingPair::is_source_enabled_for_trading_pair(
+            &DEX_ID,
+            &DAI,
+            &USDT,
+            LiquiditySourceType::StableSwapPool
+        )
+        .unwrap());
+        assert_eq!(
+            TradingPair::list_asset_neighbours(&DEX_ID, &DAI),
+            vec![USDT]
+        );
+        assert_eq!(
+            TradingPair::list_asset_neighbours(&DEX_ID, &USDT),
+            vec![DAI]
+        );
+
+        assert_ok!(StableSwapPool::deposit_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            DEX_ID,
+            DAI,
+            USDT,
+            balance!(10000),
+            balance!(10000),
+            balance!(20000)
+        ));
+        assert!(StableSwapPool::can_exchange(&DEX_ID, &DAI, &USDT));
+        assert!(StableSwapPool::can_exchange(&DEX_ID, &USDT, &DAI));
+
+        // The fee is charged in the base asset of the pool
+        let (outcome, _) = StableSwapPool::quote(
+            &DEX_ID,
+            &USDT,
+            &DAI,
+            QuoteAmount::with_desired_input(balance!(1000)),
+            true,
+        )
+        .unwrap();
+        assert!(outcome.amount > balance!(999));
+        assert!(outcome.amount < balance!(999.6));
+        assert_ok!(StableSwapPool::exchange(
+            &BOB,
+            &BOB,
+            &DEX_ID,
+            &DAI,
+            &USDT,
+            SwapAmount::with_desired_input(balance!(1000), 0),
+        ));
+        assert_eq!(Assets::free_balance(&DAI, &BOB).unwrap(), balance!(9000));
+        let pool = StableSwapPool::pools(DAI, USDT).unwrap();
+        assert_eq!(pool.base_reserve, balance!(11000));
+        assert_eq!(
+            pool.target_reserve,
+            balance!(10000) - Assets::free_balance(&USDT, &BOB).unwrap()
+        );
+    });
+}
+
+#[test]
+fn pool_should_be_available_only_in_its_dex() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        assert!(StableSwapPool::can_exchange(&DEX_ID, &XSTUSD, &DAI));
+        assert!(!StableSwapPool::can_exchange(
+            &DEXId::Polkaswap,
+            &XSTUSD,
+            &DAI
+        ));
+        assert_noop!(
+            StableSwapPool::quote(
+                &DEXId::Polkaswap,
+                &XSTUSD,
+                &DAI,
+                QuoteAmount::with_desired_input(balance!(100)),
+                true,
+            ),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        assert_noop!(
+            StableSwapPool::deposit_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                DEXId::Polkaswap,
+                XSTUSD,
+                DAI,
+                balance!(100),
+                balance!(100),
+                0
+            ),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        assert_noop!(
+            StableSwapPool::withdraw_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                DEXId::Polkaswap,
+                XSTUSD,
+                DAI,
+                balance!(100),
+                0,
+                0
+            ),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        // A pool of the same assets can't be created in another DEX
+        assert_noop!(
+            StableSwapPool::initialize_pool(
+                RuntimeOrigin::root(),
+                DEXId::Polkaswap,
+                DAI,
+                XSTUSD,
+                AMPLIFICATION
+            ),
+            Error::<Runtime>::PoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn ramp_amplification_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        let quote = || {
+            StableSwapPool::quote(
+                &DEX_ID,
+                &XSTUSD,
+                &DAI,
+                QuoteAmount::with_desired_input(balance!(5000)),
+                false,
+            )
+            .unwrap()
+            .0
+            .amount
+        };
+        let before = quote();
+
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::signed(ALICE),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                1000,
+                101
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                crate::MAX_AMPLIFICATION + 1,
+                101
+            ),
+            Error::<Runtime>::InvalidAmplification
+        );
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                USDT,
+                1000,
+                101
+            ),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                1000,
+                100
+            ),
+            Error::<Runtime>::AmplificationRampTooShort
+        );
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                AMPLIFICATION * crate::MAX_AMPLIFICATION_CHANGE + 1,
+                101
+            ),
+            Error::<Runtime>::AmplificationChangeTooLarge
+        );
+        assert_noop!(
+            StableSwapPool::ramp_amplification(
+                RuntimeOrigin::root(),
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                AMPLIFICATION / crate::MAX_AMPLIFICATION_CHANGE - 1,
+                101
+            ),
+            Error::<Runtime>::AmplificationChangeTooLarge
+        );
+
+        assert_ok!(StableSwapPool::ramp_amplification(
+            RuntimeOrigin::root(),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            1000,
+            101
+        ));
+        System::assert_last_event(
+            Event::<Runtime>::AmplificationRampStarted(
+                DEX_ID,
+                XSTUSD,
+                DAI,
+                AMPLIFICATION,
+                1000,
+                101,
+            )
+            .into(),
+        );
+        let pool = || Pools::<Runtime>::get(XSTUSD, DAI).unwrap();
+
+        // The coefficient doesn't jump
+        assert_eq!(StableSwapPool::amplification(&pool()), AMPLIFICATION);
+        assert_eq!(quote(), before);
+
+        // The coefficient changes linearly
+        System::set_block_number(51);
+        assert_eq!(StableSwapPool::amplification(&pool()), 550);
+        let in_the_middle = quote();
+        assert!(in_the_middle > before);
+
+        System::set_block_number(101);
+        assert_eq!(StableSwapPool::amplification(&pool()), 1000);
+        System::set_block_number(200);
+        assert_eq!(StableSwapPool::amplification(&pool()), 1000);
+
+        // Higher amplification means lower slippage
+        assert!(quote() > in_the_middle);
+
+        // Decreasing ramp
+        assert_ok!(StableSwapPool::ramp_amplification(
+            RuntimeOrigin::root(),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            AMPLIFICATION,
+            400
+        ));
+        System::set_block_number(300);
+        assert_eq!(StableSwapPool::amplification(&pool()), 550);
+    });
+}
+
+#[test]
+fn stop_ramp_amplification_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        init_pool_with_liquidity();
+        assert_ok!(StableSwapPool::ramp_amplification(
+            RuntimeOrigin::root(),
+            DEX_ID,
+            XSTUSD,
+            DAI,
+            1000,
+            101
+        ));
+        System::set_block_number(51);
+
+        assert_noop!(
+            StableSwapPool::stop_ramp_amplification(
+                RuntimeOrigin::signed(ALICE),
+                DEX_ID,
+                XSTUSD,
+                DAI
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            StableSwapPool::stop_ramp_amplification(RuntimeOrigin::root(), DEX_ID, XSTUSD, USDT),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        assert_ok!(StableSwapPool::stop_ramp_amplification(
+            RuntimeOrigin::root(),
+            DEX_ID,
+            XSTUSD,
+            DAI
+        ));
+        System::assert_last_event(
+            Event::<Runtime>::AmplificationRampStopped(DEX_ID, XSTUSD, DAI, 550).into(),
+        );
+
+        System::set_block_number(101);
+        assert_eq!(
+            StableSwapPool::amplification(&Pools::<Runtime>::get(XSTUSD, DAI).unwrap()),
+            550
+        );
+    });
+}
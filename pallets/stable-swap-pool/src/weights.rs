@@ -0,0 +1,299 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Autogenerated weights for stable_swap_pool
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-02-13, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `1d7f88616439`, CPU: `Intel(R) Xeon(R) Platinum 8275CL CPU @ 3.00GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
+
+// Executed Command:
+// /usr/local/bin/framenode
+// benchmark
+// pallet
+// --chain=local
+// --steps=50
+// --repeat=20
+// --pallet=stable_swap_pool
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --header=./misc/file_header.txt
+// --template=./misc/pallet-weight-template.hbs
+// --output=./pallets/stable-swap-pool/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for stable_swap_pool.
+pub trait WeightInfo {
+	fn initialize_pool() -> Weight;
+	fn deposit_liquidity() -> Weight;
+	fn withdraw_liquidity() -> Weight;
+	fn ramp_amplification() -> Weight;
+	fn stop_ramp_amplification() -> Weight;
+	fn quote() -> Weight;
+	fn swap() -> Weight;
+}
+
+/// Weights for stable_swap_pool using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: DEXManager DEXInfos (r:4 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:4 w:1)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair AssetNeighbours (r:2 w:2)
+	/// Proof Skipped: TradingPair AssetNeighbours (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetOwners (r:2 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool Pools (r:2 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:1)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn initialize_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1974`
+		//  Estimated: `31264`
+		// Minimum execution time: 84_108_000 picoseconds.
+		Weight::from_parts(85_512_000, 31264)
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool LiquidityProviders (r:1 w:1)
+	/// Proof Skipped: StableSwapPool LiquidityProviders (max_values: None, max_size: None, mode: Measured)
+	fn deposit_liquidity() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2280`
+		//  Estimated: `29340`
+		// Minimum execution time: 121_508_000 picoseconds.
+		Weight::from_parts(124_113_000, 29340)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool LiquidityProviders (r:1 w:1)
+	/// Proof Skipped: StableSwapPool LiquidityProviders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn withdraw_liquidity() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `29472`
+		// Minimum execution time: 117_902_000 picoseconds.
+		Weight::from_parts(120_654_000, 29472)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn ramp_amplification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `561`
+		//  Estimated: `3036`
+		// Minimum execution time: 18_671_000 picoseconds.
+		Weight::from_parts(19_203_000, 3036)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn stop_ramp_amplification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `593`
+		//  Estimated: `3068`
+		// Minimum execution time: 17_904_000 picoseconds.
+		Weight::from_parts(18_412_000, 3068)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:2 w:0)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn quote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `873`
+		//  Estimated: `6823`
+		// Minimum execution time: 31_547_000 picoseconds.
+		Weight::from_parts(32_459_000, 6823)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:2 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2180`
+		//  Estimated: `26580`
+		// Minimum execution time: 112_341_000 picoseconds.
+		Weight::from_parts(115_027_000, 26580)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: DEXManager DEXInfos (r:4 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:4 w:1)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair AssetNeighbours (r:2 w:2)
+	/// Proof Skipped: TradingPair AssetNeighbours (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetOwners (r:2 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool Pools (r:2 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:1)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn initialize_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1974`
+		//  Estimated: `31264`
+		// Minimum execution time: 84_108_000 picoseconds.
+		Weight::from_parts(85_512_000, 31264)
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool LiquidityProviders (r:1 w:1)
+	/// Proof Skipped: StableSwapPool LiquidityProviders (max_values: None, max_size: None, mode: Measured)
+	fn deposit_liquidity() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2280`
+		//  Estimated: `29340`
+		// Minimum execution time: 121_508_000 picoseconds.
+		Weight::from_parts(124_113_000, 29340)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: StableSwapPool LiquidityProviders (r:1 w:1)
+	/// Proof Skipped: StableSwapPool LiquidityProviders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn withdraw_liquidity() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `29472`
+		// Minimum execution time: 117_902_000 picoseconds.
+		Weight::from_parts(120_654_000, 29472)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn ramp_amplification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `561`
+		//  Estimated: `3036`
+		// Minimum execution time: 18_671_000 picoseconds.
+		Weight::from_parts(19_203_000, 3036)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:1 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn stop_ramp_amplification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `593`
+		//  Estimated: `3068`
+		// Minimum execution time: 17_904_000 picoseconds.
+		Weight::from_parts(18_412_000, 3068)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:2 w:0)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	fn quote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `873`
+		//  Estimated: `6823`
+		// Minimum execution time: 31_547_000 picoseconds.
+		Weight::from_parts(32_459_000, 6823)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// Storage: StableSwapPool Pools (r:2 w:1)
+	/// Proof Skipped: StableSwapPool Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof Skipped: Tokens Accounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof Skipped: System Account (max_values: None, max_size: None, mode: Measured)
+	fn swap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2180`
+		//  Estimated: `26580`
+		// Minimum execution time: 112_341_000 picoseconds.
+		Weight::from_parts(115_027_000, 26580)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+}
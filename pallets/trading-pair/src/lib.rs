@@ -61,6 +61,9 @@ mod tests;
 pub type TradingPair<T> = common::prelude::TradingPair<<T as assets::Config>::AssetId>;
 type Assets<T> = assets::Pallet<T>;
 
+/// Maximum number of trading pairs without the base assets of the DEX for a single asset.
+pub const MAX_ASSET_NEIGHBOURS: u32 = 8;
+
 pub use weights::WeightInfo;
 
 impl<T: Config> EnsureTradingPairExists<T::DEXId, T::AssetId, DispatchError> for Pallet<T> {
@@ -192,6 +195,18 @@ impl<T: Config> TradingPairSourceManager<T::DEXId, T::AssetId> for Pallet<T> {
     ) -> Result<(), DispatchError> {
         Self::register_pair(dex_id, base_asset_id, target_asset_id)
     }
+
+    fn register_non_base_pair(
+        dex_id: T::DEXId,
+        base_asset_id: T::AssetId,
+        target_asset_id: T::AssetId,
+    ) -> Result<(), DispatchError> {
+        Self::register_non_base_pair(dex_id, base_asset_id, target_asset_id)
+    }
+
+    fn list_asset_neighbours(dex_id: &T::DEXId, asset_id: &T::AssetId) -> Vec<T::AssetId> {
+        AssetNeighbours::<T>::get(dex_id, asset_id).into_inner()
+    }
 }
 
 impl<T: Config> Pallet<T> {
@@ -231,6 +246,64 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    /// Registers trading pair of two assets, neither of which is a base asset of the DEX,
+    /// e.g. a pair of two stablecoins. The assets are added to `AssetNeighbours` of each other,
+    /// so that the pair can be found by routing. The pair can be registered only in one order
+    /// of the assets.
+    pub fn register_non_base_pair(
+        dex_id: T::DEXId,
+        base_asset_id: T::AssetId,
+        target_asset_id: T::AssetId,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            base_asset_id != target_asset_id,
+            Error::<T>::IdenticalAssetIds
+        );
+
+        let dex_info = T::DexInfoProvider::get_dex_info(&dex_id)?;
+        let is_dex_base_asset = |asset_id: &T::AssetId| {
+            *asset_id == dex_info.base_asset_id || *asset_id == dex_info.synthetic_base_asset_id
+        };
+        ensure!(
+            !is_dex_base_asset(&base_asset_id) && !is_dex_base_asset(&target_asset_id),
+            Error::<T>::DexBaseAssetInNonBasePair
+        );
+        Assets::<T>::ensure_asset_exists(&base_asset_id)?;
+        Assets::<T>::ensure_asset_exists(&target_asset_id)?;
+
+        let trading_pair = TradingPair::<T> {
+            base_asset_id,
+            target_asset_id,
+        };
+        let reversed_pair = TradingPair::<T> {
+            base_asset_id: target_asset_id,
+            target_asset_id: base_asset_id,
+        };
+        ensure!(
+            Self::enabled_sources(&dex_id, &trading_pair).is_none()
+                && Self::enabled_sources(&dex_id, &reversed_pair).is_none(),
+            Error::<T>::TradingPairExists
+        );
+        let with_neighbour = |asset_id: &T::AssetId, neighbour: T::AssetId| {
+            let mut neighbours = AssetNeighbours::<T>::get(&dex_id, asset_id);
+            neighbours
+                .try_push(neighbour)
+                .map_err(|_| Error::<T>::TooManyAssetNeighbours)?;
+            Ok::<_, Error<T>>(neighbours)
+        };
+        let base_neighbours = with_neighbour(&base_asset_id, target_asset_id)?;
+        let target_neighbours = with_neighbour(&target_asset_id, base_asset_id)?;
+        AssetNeighbours::<T>::insert(&dex_id, &base_asset_id, base_neighbours);
+        AssetNeighbours::<T>::insert(&dex_id, &target_asset_id, target_neighbours);
+        EnabledSources::<T>::insert(
+            &dex_id,
+            &trading_pair,
+            BTreeSet::<LiquiditySourceType>::new(),
+        );
+        Self::deposit_event(Event::TradingPairStored(dex_id, trading_pair));
+        Ok(())
+    }
+
     pub fn list_trading_pairs(dex_id: &T::DEXId) -> Result<Vec<TradingPair<T>>, DispatchError> {
         T::DexInfoProvider::ensure_dex_exists(dex_id)?;
         Ok(EnabledSources::<T>::iter_prefix(dex_id)
@@ -324,6 +397,10 @@ pub mod pallet {
         IdenticalAssetIds,
         /// Trading pair is not registered for given DEXId.
         TradingPairDoesntExist,
+        /// Trading pair without the base asset contains a base asset of the DEX.
+        DexBaseAssetInNonBasePair,
+        /// The asset has too many trading pairs without the base asset.
+        TooManyAssetNeighbours,
     }
 
     #[pallet::storage]
@@ -337,6 +414,20 @@ pub mod pallet {
         BTreeSet<LiquiditySourceType>,
     >;
 
+    /// Assets paired with the asset by trading pairs without the base assets of the DEX.
+    /// Pairs with the base assets aren't listed, every asset can be exchanged via them.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_neighbours)]
+    pub type AssetNeighbours<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::DEXId,
+        Blake2_128Concat,
+        T::AssetId,
+        BoundedVec<T::AssetId, ConstU32<MAX_ASSET_NEIGHBOURS>>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     pub type LockedLiquiditySources<T: Config> =
         StorageValue<_, Vec<LiquiditySourceType>, ValueQuery>;
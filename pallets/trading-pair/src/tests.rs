@@ -29,10 +29,10 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
-use crate::{Error, Pallet};
+use crate::{AssetNeighbours, Error, Pallet, MAX_ASSET_NEIGHBOURS};
 use common::{
-    EnsureTradingPairExists, LiquiditySourceType, TradingPair, TradingPairSourceManager, DOT, KSM,
-    XOR, XSTUSD,
+    AssetId32, EnsureTradingPairExists, LiquiditySourceType, TradingPair, TradingPairSourceManager,
+    DOT, KSM, XOR, XST, XSTUSD,
 };
 use frame_support::assert_noop;
 use frame_support::assert_ok;
@@ -560,3 +560,75 @@ fn should_fail_with_nonexistent_dex() {
         );
     });
 }
+
+#[test]
+fn should_register_non_base_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            TradingPairPallet::register(RuntimeOrigin::signed(ALICE), DEX_ID, DOT, KSM),
+            Error::<Runtime>::ForbiddenBaseAssetId
+        );
+        for (base_asset_id, target_asset_id) in [(XOR, DOT), (DOT, XOR), (KSM, XST)] {
+            assert_noop!(
+                TradingPairPallet::register_non_base_pair(DEX_ID, base_asset_id, target_asset_id),
+                Error::<Runtime>::DexBaseAssetInNonBasePair
+            );
+        }
+
+        assert_ok!(TradingPairPallet::register_non_base_pair(DEX_ID, DOT, KSM));
+        assert_ok!(TradingPairPallet::ensure_trading_pair_exists(
+            &DEX_ID, &DOT, &KSM
+        ));
+        assert_eq!(
+            TradingPairPallet::list_asset_neighbours(&DEX_ID, &DOT),
+            vec![KSM]
+        );
+        assert_eq!(
+            TradingPairPallet::list_asset_neighbours(&DEX_ID, &KSM),
+            vec![DOT]
+        );
+        assert!(TradingPairPallet::list_asset_neighbours(&DEX_ID, &XOR).is_empty());
+        assert!(TradingPairPallet::list_asset_neighbours(&1, &DOT).is_empty());
+
+        for (base_asset_id, target_asset_id) in [(DOT, KSM), (KSM, DOT)] {
+            assert_noop!(
+                TradingPairPallet::register_non_base_pair(DEX_ID, base_asset_id, target_asset_id),
+                Error::<Runtime>::TradingPairExists
+            );
+        }
+
+        // pairs with the base asset aren't listed as neighbours
+        assert_ok!(TradingPairPallet::register(
+            RuntimeOrigin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(
+            TradingPairPallet::list_asset_neighbours(&DEX_ID, &DOT),
+            vec![KSM]
+        );
+    });
+}
+
+#[test]
+fn should_not_register_non_base_pair_with_too_many_neighbours() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        AssetNeighbours::<Runtime>::mutate(DEX_ID, KSM, |neighbours| {
+            for i in 0..MAX_ASSET_NEIGHBOURS {
+                neighbours
+                    .try_push(AssetId32::from_bytes([i as u8 + 1; 32]))
+                    .unwrap();
+            }
+        });
+
+        for (base_asset_id, target_asset_id) in [(DOT, KSM), (KSM, DOT)] {
+            assert_noop!(
+                TradingPairPallet::register_non_base_pair(DEX_ID, base_asset_id, target_asset_id),
+                Error::<Runtime>::TooManyAssetNeighbours
+            );
+        }
+    });
+}
@@ -36,7 +36,17 @@ use frame_benchmarking::benchmarks;
 use frame_support::sp_runtime::FixedU128;
 use frame_system::RawOrigin;
 
-use crate::{Config, Pallet};
+use crate::{Config, DynamicMultiplierParams, Pallet};
+
+fn dynamic_multiplier_params() -> DynamicMultiplierParams {
+    DynamicMultiplierParams {
+        target_fee: common::balance!(0.001),
+        congestion_factor: FixedU128::from(1),
+        min_multiplier: FixedU128::from(1),
+        max_multiplier: FixedU128::from(10),
+        update_period: 1,
+    }
+}
 
 benchmarks! {
     update_multiplier {
@@ -46,5 +56,50 @@ benchmarks! {
         assert_eq!(crate::Multiplier::<T>::get(), new_multiplier);
     }
 
+    add_fee_asset {
+        let asset_id = T::ValId::get();
+    }: _(RawOrigin::Root, asset_id)
+    verify {
+        assert!(crate::FeeAssets::<T>::contains_key(asset_id));
+    }
+
+    remove_fee_asset {
+        let asset_id = T::ValId::get();
+        crate::FeeAssets::<T>::insert(asset_id, ());
+    }: _(RawOrigin::Root, asset_id)
+    verify {
+        assert!(!crate::FeeAssets::<T>::contains_key(asset_id));
+    }
+
+    set_dynamic_multiplier {
+        let params = dynamic_multiplier_params();
+    }: _(RawOrigin::Root, Some(params.clone()))
+    verify {
+        assert_eq!(crate::DynamicMultiplier::<T>::get(), Some(params));
+    }
+
+    adjust_multiplier {
+        let params = dynamic_multiplier_params();
+        crate::DynamicMultiplier::<T>::put(params.clone());
+    }: {
+        Pallet::<T>::adjust_multiplier(&params);
+    }
+
+    exchange_fee_asset {
+        let caller = frame_benchmarking::account::<T::AccountId>("caller", 0, 0);
+        let asset_id = T::ValId::get();
+        let fee = common::balance!(0.0007);
+        crate::FeeAssets::<T>::insert(asset_id, ());
+        let amount = Pallet::<T>::fee_in_asset(&asset_id, fee).unwrap();
+        assets::Pallet::<T>::mint_unchecked(
+            &asset_id,
+            &T::GetTechnicalAccountId::get(),
+            amount,
+        )
+        .unwrap();
+    }: {
+        assert!(Pallet::<T>::exchange_fee_asset(&caller, &asset_id, amount, fee).is_some());
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::build(), crate::mock::Runtime);
 }
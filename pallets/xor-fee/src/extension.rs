@@ -43,13 +43,25 @@ use sp_runtime::{
     DispatchResult,
 };
 
-use crate::{BalanceOf, Config, CustomFeeDetailsOf, LiquidityInfo};
+use common::Balance;
+
+use crate::{BalanceOf, Config, CustomFeeDetailsOf, LiquidityInfo, WeightInfo};
+
+/// Asset chosen by the signer to pay the transaction fee in instead of XOR
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Debug, TypeInfo)]
+pub struct FeeAsset<AssetId> {
+    /// Asset exchanged for XOR, must be allowed for fee payment
+    pub asset_id: AssetId,
+    /// Maximum amount of the asset to be withdrawn, protects the signer from slippage
+    pub max_amount_in: Balance,
+}
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct ChargeTransactionPayment<T: Config> {
     #[codec(compact)]
     tip: BalanceOf<T>,
+    fee_asset: Option<FeeAsset<T::AssetId>>,
 }
 
 impl<T: Config> From<u128> for ChargeTransactionPayment<T>
@@ -57,7 +69,10 @@ where
     BalanceOf<T>: From<u128>,
 {
     fn from(value: u128) -> Self {
-        Self { tip: value.into() }
+        Self {
+            tip: value.into(),
+            fee_asset: None,
+        }
     }
 }
 
@@ -65,6 +80,7 @@ impl<T: Config> Debug for ChargeTransactionPayment<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("XorFeeChargeTransactionPayment")
             .field(&self.tip)
+            .field(&self.fee_asset)
             .finish()
     }
 }
@@ -76,6 +92,7 @@ where
     fn default() -> Self {
         ChargeTransactionPayment {
             tip: Default::default(),
+            fee_asset: None,
         }
     }
 }
@@ -92,6 +109,15 @@ where
         Default::default()
     }
 
+    /// Pay the fee in `asset_id` withdrawing at most `max_amount_in` of it
+    pub fn with_fee_asset(mut self, asset_id: T::AssetId, max_amount_in: Balance) -> Self {
+        self.fee_asset = Some(FeeAsset {
+            asset_id,
+            max_amount_in,
+        });
+        self
+    }
+
     #[allow(clippy::type_complexity)] // This function can only be called in this module
     fn withdraw_fee(
         &self,
@@ -109,7 +135,20 @@ where
     > {
         let tip = self.tip;
         let (fee, fee_details) = crate::Pallet::<T>::compute_fee(len as u32, call, info, tip);
-        let liquidity_info = T::OnChargeTransaction::withdraw_fee(who, call, info, fee, tip)?;
+        let paid_in_asset = match &self.fee_asset {
+            Some(fee_asset) => crate::Pallet::<T>::withdraw_fee_in_asset(
+                who,
+                call,
+                fee.into(),
+                &fee_asset.asset_id,
+                fee_asset.max_amount_in,
+            )?,
+            None => None,
+        };
+        let liquidity_info = match paid_in_asset {
+            Some(liquidity_info) => liquidity_info,
+            None => T::OnChargeTransaction::withdraw_fee(who, call, info, fee, tip)?,
+        };
         Ok((fee, liquidity_info, fee_details))
     }
 }
@@ -166,6 +205,13 @@ where
         len: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
         let (_, liquidity_info, fee_kind) = self.withdraw_fee(who, call, info, len)?;
+        if matches!(liquidity_info, LiquidityInfo::PaidInAsset(..)) {
+            // The fee asset is exchanged for XOR after the call is dispatched
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                <T as Config>::WeightInfo::exchange_fee_asset(),
+                info.class,
+            );
+        }
         Ok((self.tip, who.clone(), liquidity_info, fee_kind))
     }
 
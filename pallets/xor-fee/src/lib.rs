@@ -30,12 +30,15 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use common::prelude::SwapAmount;
 use common::{
-    Balance, BuyBackHandler, LiquidityProxyTrait, LiquiditySourceFilter, LiquiditySourceType,
-    OnValBurned, ReferrerAccountProvider,
+    AssetInfoProvider, Balance, BuyBackHandler, LiquidityProxyTrait, LiquiditySourceFilter,
+    LiquiditySourceType, OnValBurned, PriceToolsProvider, PriceVariant, ReferrerAccountProvider,
+};
+use frame_support::dispatch::{
+    DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo,
 };
-use frame_support::dispatch::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo};
 use frame_support::log::error;
 use frame_support::pallet_prelude::InvalidTransaction;
 use frame_support::traits::{Currency, ExistenceRequirement, Get, Imbalance, WithdrawReasons};
@@ -47,13 +50,16 @@ use pallet_transaction_payment as ptp;
 use pallet_transaction_payment::{
     FeeDetails, InclusionFee, OnChargeTransaction, RuntimeDispatchInfo,
 };
+use scale_info::TypeInfo;
 use smallvec::smallvec;
 use sp_arithmetic::FixedPointOperand;
 use sp_runtime::traits::{
-    DispatchInfoOf, Dispatchable, Extrinsic as ExtrinsicT, PostDispatchInfoOf, SaturatedConversion,
-    Saturating, UniqueSaturatedInto, Zero,
+    DispatchInfoOf, Dispatchable, Extrinsic as ExtrinsicT, One, PostDispatchInfoOf,
+    SaturatedConversion, Saturating, UniqueSaturatedInto, Zero,
+};
+use sp_runtime::{
+    DispatchError, DispatchResult, FixedPointNumber, FixedU128, Perbill, Percent, RuntimeDebug,
 };
-use sp_runtime::{DispatchError, DispatchResult, FixedPointNumber, FixedU128, Perbill, Percent};
 use sp_staking::SessionIndex;
 use sp_std::vec::Vec;
 
@@ -71,6 +77,10 @@ mod tests;
 pub const TECH_ACCOUNT_PREFIX: &[u8] = b"xor-fee";
 pub const TECH_ACCOUNT_MAIN: &[u8] = b"main";
 
+/// Custom errors for fee payment validation, InvalidTransaction::Custom(u8)
+pub const VALIDATION_ERROR_FEE_ASSET_NOT_ALLOWED: u8 = 1;
+pub const VALIDATION_ERROR_FEE_ASSET_SLIPPAGE: u8 = 2;
+
 type NegativeImbalanceOf<T> = <<T as Config>::XorCurrency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
@@ -83,6 +93,30 @@ type BalanceOf<T> =
 type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
 type Assets<T> = assets::Pallet<T>;
 
+/// Parameters of the automatic adjustment of the weight to fee multiplier
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DynamicMultiplierParams {
+    /// Fee of the reference transaction in the reference asset (USD) to be targeted
+    pub target_fee: Balance,
+    /// Fee increase at the full block, e.g. 1 doubles the fee when the block is full
+    pub congestion_factor: FixedU128,
+    /// Lower bound of the multiplier
+    pub min_multiplier: FixedU128,
+    /// Upper bound of the multiplier
+    pub max_multiplier: FixedU128,
+    /// The multiplier is recalculated every `update_period` blocks
+    pub update_period: u32,
+}
+
+impl DynamicMultiplierParams {
+    pub fn is_valid(&self) -> bool {
+        !self.target_fee.is_zero()
+            && !self.min_multiplier.is_zero()
+            && self.min_multiplier <= self.max_multiplier
+            && self.update_period > 0
+    }
+}
+
 // #[cfg_attr(test, derive(PartialEq))]
 pub enum LiquidityInfo<T: Config> {
     /// Fees operate as normal
@@ -91,6 +125,9 @@ pub enum LiquidityInfo<T: Config> {
     Postponed(T::AccountId),
     /// The fee should not be paid
     NotPaid,
+    /// The fee is paid in another asset which is held by the pallet until the actual fee is known.
+    /// [Fee payer, Asset Id, Amount of the asset, Fee in XOR the amount was quoted for]
+    PaidInAsset(T::AccountId, T::AssetId, Balance, Balance),
 }
 
 impl<T: Config> sp_std::fmt::Debug for LiquidityInfo<T> {
@@ -105,6 +142,13 @@ impl<T: Config> sp_std::fmt::Debug for LiquidityInfo<T> {
             LiquidityInfo::NotPaid => {
                 write!(f, "NotPaid")
             }
+            LiquidityInfo::PaidInAsset(account_id, asset_id, amount, fee) => {
+                write!(
+                    f,
+                    "PaidInAsset({:?}, {:?}, {:?}, {:?})",
+                    account_id, asset_id, amount, fee
+                )
+            }
         }
     }
 }
@@ -116,6 +160,10 @@ impl<T: Config> PartialEq for LiquidityInfo<T> {
                 (a1 == a2) && b1.as_ref().map(|b| b.peek()) == b2.as_ref().map(|b| b.peek())
             }
             (LiquidityInfo::Postponed(a1), LiquidityInfo::Postponed(a2)) => a1 == a2,
+            (
+                LiquidityInfo::PaidInAsset(a1, b1, c1, d1),
+                LiquidityInfo::PaidInAsset(a2, b2, c2, d2),
+            ) => a1 == a2 && b1 == b2 && c1 == c2 && d1 == d2,
             _ => false,
         }
     }
@@ -178,8 +226,17 @@ where
         tip: BalanceOf<T>,
         already_withdrawn: Self::LiquidityInfo,
     ) -> Result<(), TransactionValidityError> {
+        // XOR is refunded only if it was withdrawn from the fee source,
+        // fees paid in other assets are refunded in that asset
+        let mut refund_xor = true;
         let (fee_source, withdrawn) = match already_withdrawn {
             LiquidityInfo::Paid(a, b) => (a, b),
+            LiquidityInfo::PaidInAsset(fee_source, asset_id, amount, _) => {
+                refund_xor = false;
+                let withdrawn =
+                    Self::exchange_fee_asset(&fee_source, &asset_id, amount, corrected_fee.into());
+                (fee_source, withdrawn)
+            }
             LiquidityInfo::Postponed(fee_source) => {
                 let withdraw_reason = if tip.is_zero() {
                     WithdrawReasons::TRANSACTION_PAYMENT
@@ -203,7 +260,11 @@ where
             // Refund behavior is fully defined by CustomFee type or
             // by default transaction payment pallet implementation if
             // call is not subject for custom fee
-            let refund_amount = paid.peek().saturating_sub(corrected_fee);
+            let refund_amount = if refund_xor {
+                paid.peek().saturating_sub(corrected_fee)
+            } else {
+                Zero::zero()
+            };
 
             // Refund to the the account that paid the fees. If this fails, the
             // account might have dropped below the existential balance. In
@@ -578,9 +639,215 @@ where
         let info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(unchecked_extrinsic);
         Self::compute_fee_details(len, call, &info, 0u32.into()).0
     }
+
+    /// Returns the amount of `asset_id` to be withdrawn to pay the fee of the extrinsic
+    /// or `None` if the fee can't be paid in this asset
+    pub fn query_fee_in_asset<Extrinsic: ExtrinsicT + GetDispatchInfo>(
+        unchecked_extrinsic: &Extrinsic,
+        call: &CallOf<T>,
+        len: u32,
+        asset_id: &T::AssetId,
+    ) -> Option<Balance> {
+        let info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(unchecked_extrinsic);
+        let fee = Self::compute_fee(len, call, &info, 0u32.into()).0;
+        Self::fee_in_asset(asset_id, fee.into())
+    }
 }
 
 impl<T: Config> Pallet<T> {
+    fn should_adjust_multiplier(
+        block_number: T::BlockNumber,
+        params: &DynamicMultiplierParams,
+    ) -> bool {
+        (block_number % params.update_period.into()).is_zero()
+    }
+
+    /// Calculate the multiplier targeting the fee of the reference transaction
+    /// in the reference asset, raised by the normal dispatch class fullness of the block
+    pub fn calculate_multiplier(
+        params: &DynamicMultiplierParams,
+    ) -> Result<FixedU128, DispatchError> {
+        let xor_price = T::PriceTools::get_average_price(
+            &T::XorId::get(),
+            &T::GetReferenceAssetId::get(),
+            PriceVariant::Sell,
+        )?;
+        let reference_fee =
+            FixedU128::from_inner(xor_price).saturating_mul_int(T::ReferenceFee::get());
+        let base = FixedU128::checked_from_rational(params.target_fee, reference_fee)
+            .ok_or(Error::<T>::MultiplierCalculationFailed)?;
+
+        let block_weights = T::BlockWeights::get();
+        let max_weight = block_weights
+            .get(DispatchClass::Normal)
+            .max_total
+            .unwrap_or(block_weights.max_block);
+        let block_weight = *frame_system::Pallet::<T>::block_weight().get(DispatchClass::Normal);
+        let fullness =
+            FixedU128::checked_from_rational(block_weight.ref_time(), max_weight.ref_time())
+                .unwrap_or_default()
+                .min(FixedU128::one());
+        let congestion =
+            FixedU128::one().saturating_add(params.congestion_factor.saturating_mul(fullness));
+
+        Ok(base
+            .saturating_mul(congestion)
+            .max(params.min_multiplier)
+            .min(params.max_multiplier))
+    }
+
+    fn adjust_multiplier(params: &DynamicMultiplierParams) {
+        match Self::calculate_multiplier(params) {
+            Ok(multiplier) => {
+                Multiplier::<T>::put(multiplier);
+                Self::deposit_event(Event::WeightToFeeMultiplierAdjusted(multiplier));
+            }
+            Err(e) => {
+                error!("failed to adjust xor fee multiplier, e: {:?}", e);
+            }
+        }
+    }
+
+    /// Returns the amount of `asset_id` to be exchanged for `fee` XOR
+    /// or `None` if the asset is not allowed for fee payment or has no average price.
+    ///
+    /// The amount is priced with the average price of price tools, so that the transaction
+    /// validation doesn't run the liquidity proxy. The actual exchange happens after dispatch.
+    pub fn fee_in_asset(asset_id: &T::AssetId, fee: Balance) -> Option<Balance> {
+        if *asset_id == T::XorId::get() {
+            return Some(fee);
+        }
+        if !FeeAssets::<T>::contains_key(asset_id) {
+            return None;
+        }
+        if fee.is_zero() {
+            return Some(0);
+        }
+        let xor_price =
+            T::PriceTools::get_average_price(&T::XorId::get(), asset_id, PriceVariant::Buy).ok()?;
+        FixedU128::from_inner(xor_price).checked_mul_int(fee)
+    }
+
+    /// Withdraw the fee in `asset_id` instead of XOR.
+    ///
+    /// The asset is held by the pallet technical account until the actual fee is known.
+    /// Returns `None` if the fee should be withdrawn in XOR as usual.
+    pub fn withdraw_fee_in_asset(
+        who: &T::AccountId,
+        call: &CallOf<T>,
+        fee: Balance,
+        asset_id: &T::AssetId,
+        max_amount_in: Balance,
+    ) -> Result<Option<LiquidityInfo<T>>, TransactionValidityError> {
+        if *asset_id == T::XorId::get()
+            || fee.is_zero()
+            || !T::CustomFees::should_be_paid(who, call)
+            || T::CustomFees::get_fee_source(who, call, fee) != *who
+        {
+            return Ok(None);
+        }
+        let amount_in = Self::fee_in_asset(asset_id, fee).ok_or(InvalidTransaction::Custom(
+            VALIDATION_ERROR_FEE_ASSET_NOT_ALLOWED,
+        ))?;
+        if amount_in > max_amount_in {
+            return Err(InvalidTransaction::Custom(VALIDATION_ERROR_FEE_ASSET_SLIPPAGE).into());
+        }
+        Assets::<T>::transfer_from(asset_id, who, &T::GetTechnicalAccountId::get(), amount_in)
+            .map_err(|_| InvalidTransaction::Payment)?;
+        Ok(Some(LiquidityInfo::PaidInAsset(
+            who.clone(),
+            *asset_id,
+            amount_in,
+            fee,
+        )))
+    }
+
+    /// Exchange the fee paid in `asset_id` for exactly `corrected_fee` XOR spending at most the
+    /// withheld `amount` and refund the rest of the asset.
+    ///
+    /// If the exchange fails the fee is withdrawn in XOR from `who` instead and the whole asset
+    /// amount is refunded. If that fails too, the asset is kept by the pallet technical account.
+    /// Returns the withdrawn XOR or `None` if no XOR was withdrawn.
+    fn exchange_fee_asset(
+        who: &T::AccountId,
+        asset_id: &T::AssetId,
+        amount: Balance,
+        corrected_fee: Balance,
+    ) -> Option<NegativeImbalanceOf<T>> {
+        let tech_account_id = T::GetTechnicalAccountId::get();
+        if corrected_fee.is_zero() {
+            Self::refund_fee_asset(who, asset_id, amount);
+            return None;
+        }
+        let result = common::with_transaction(|| {
+            let outcome = T::LiquidityProxy::exchange(
+                T::DEXIdValue::get(),
+                &tech_account_id,
+                &tech_account_id,
+                asset_id,
+                &T::XorId::get(),
+                SwapAmount::with_desired_output(corrected_fee, amount),
+                LiquiditySourceFilter::empty(T::DEXIdValue::get()),
+            )?;
+            let imbalance = T::XorCurrency::withdraw(
+                &tech_account_id,
+                BalanceOf::<T>::saturated_from(corrected_fee),
+                WithdrawReasons::TRANSACTION_PAYMENT,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            Ok::<_, DispatchError>((outcome.amount, imbalance))
+        });
+        match result {
+            Ok((used_amount, imbalance)) => {
+                Self::refund_fee_asset(who, asset_id, amount.saturating_sub(used_amount));
+                Self::deposit_event(Event::FeeWithdrawnInAsset(
+                    who.clone(),
+                    *asset_id,
+                    used_amount,
+                ));
+                Some(imbalance)
+            }
+            Err(e) => {
+                error!(
+                    "failed to exchange fee asset {:?} to xor, withdrawing xor, e: {:?}",
+                    asset_id, e
+                );
+                match T::XorCurrency::withdraw(
+                    who,
+                    BalanceOf::<T>::saturated_from(corrected_fee),
+                    WithdrawReasons::TRANSACTION_PAYMENT,
+                    ExistenceRequirement::KeepAlive,
+                ) {
+                    Ok(imbalance) => {
+                        Self::refund_fee_asset(who, asset_id, amount);
+                        Some(imbalance)
+                    }
+                    Err(e) => {
+                        error!(
+                            "failed to withdraw xor fee, keeping {} of fee asset {:?}, e: {:?}",
+                            amount, asset_id, e
+                        );
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn refund_fee_asset(who: &T::AccountId, asset_id: &T::AssetId, amount: Balance) {
+        if amount.is_zero() {
+            return;
+        }
+        if let Err(e) =
+            Assets::<T>::transfer_from(asset_id, &T::GetTechnicalAccountId::get(), who, amount)
+        {
+            error!(
+                "failed to refund {} of fee asset {:?}, e: {:?}",
+                amount, asset_id, e
+            );
+        }
+    }
+
     pub fn remint(xor_to_val: Balance) -> Result<(), DispatchError> {
         let tech_account_id = <T as Config>::GetTechnicalAccountId::get();
         let xor = T::XorId::get();
@@ -689,6 +956,12 @@ pub mod pallet {
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
         type WithdrawFee: WithdrawFee<Self>;
+        /// Average prices used by the dynamic multiplier
+        type PriceTools: PriceToolsProvider<Self::AssetId>;
+        /// Asset the dynamic multiplier targets the fee in, e.g. DAI
+        type GetReferenceAssetId: Get<Self::AssetId>;
+        /// Fee in XOR of the reference transaction with the multiplier equal to 1
+        type ReferenceFee: Get<Balance>;
     }
 
     /// The current storage version.
@@ -701,11 +974,30 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: T::BlockNumber) -> Weight {
+            match DynamicMultiplier::<T>::get() {
+                Some(params) if Self::should_adjust_multiplier(block_number, &params) => {
+                    <T as Config>::WeightInfo::adjust_multiplier()
+                }
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
+
+        fn on_finalize(block_number: T::BlockNumber) {
+            if let Some(params) = DynamicMultiplier::<T>::get() {
+                if Self::should_adjust_multiplier(block_number, &params) {
+                    Self::adjust_multiplier(&params);
+                }
+            }
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Update the multiplier for weight -> fee conversion.
+        /// The multiplier can't be updated manually while the dynamic multiplier is enabled,
+        /// disable it with `set_dynamic_multiplier` first.
         // TODO: benchmark on reference hardware
         // 0 is passed because argument is unused and no need to
         // do unnecessary conversions
@@ -716,10 +1008,71 @@ pub mod pallet {
             new_multiplier: FixedU128,
         ) -> DispatchResultWithPostInfo {
             ensure_root(origin)?;
+            ensure!(
+                DynamicMultiplier::<T>::get().is_none(),
+                Error::<T>::DynamicMultiplierIsEnabled
+            );
             <Multiplier<T>>::put(new_multiplier);
             Self::deposit_event(Event::WeightToFeeMultiplierUpdated(new_multiplier));
             Ok(().into())
         }
+
+        /// Allow paying transaction fees in the asset.
+        ///
+        /// - `origin`: the root account,
+        /// - `asset_id`: the asset to be exchanged for XOR on fee payment.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_fee_asset())]
+        pub fn add_fee_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(asset_id != T::XorId::get(), Error::<T>::FeeAssetIsXor);
+            assets::Pallet::<T>::ensure_asset_exists(&asset_id)?;
+            ensure!(
+                !FeeAssets::<T>::contains_key(asset_id),
+                Error::<T>::FeeAssetAlreadyAdded
+            );
+            FeeAssets::<T>::insert(asset_id, ());
+            Self::deposit_event(Event::FeeAssetAdded(asset_id));
+            Ok(())
+        }
+
+        /// Disallow paying transaction fees in the asset.
+        ///
+        /// - `origin`: the root account,
+        /// - `asset_id`: the asset previously allowed for fee payment.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_fee_asset())]
+        pub fn remove_fee_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                FeeAssets::<T>::take(asset_id).is_some(),
+                Error::<T>::FeeAssetNotFound
+            );
+            Self::deposit_event(Event::FeeAssetRemoved(asset_id));
+            Ok(())
+        }
+
+        /// Enable the automatic adjustment of the multiplier or disable it if `params` is `None`.
+        ///
+        /// - `origin`: the root account,
+        /// - `params`: target fee, congestion factor, bounds and period of the adjustment.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_dynamic_multiplier())]
+        pub fn set_dynamic_multiplier(
+            origin: OriginFor<T>,
+            params: Option<DynamicMultiplierParams>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(params) = &params {
+                ensure!(
+                    params.is_valid(),
+                    Error::<T>::InvalidDynamicMultiplierParams
+                );
+            }
+            DynamicMultiplier::<T>::set(params.clone());
+            Self::deposit_event(Event::DynamicMultiplierUpdated(params));
+            Ok(())
+        }
     }
 
     #[pallet::event]
@@ -732,6 +1085,33 @@ pub mod pallet {
         /// New multiplier for weight to fee conversion is set
         /// (*1_000_000_000_000_000_000). [New value]
         WeightToFeeMultiplierUpdated(FixedU128),
+        /// Fee has been paid in the asset exchanged for XOR.
+        /// [Account Id to withdraw from, Asset Id, Amount of the asset]
+        FeeWithdrawnInAsset(AccountIdOf<T>, T::AssetId, Balance),
+        /// The asset is allowed for fee payment. [Asset Id]
+        FeeAssetAdded(T::AssetId),
+        /// The asset is no longer allowed for fee payment. [Asset Id]
+        FeeAssetRemoved(T::AssetId),
+        /// The multiplier is adjusted automatically. [New value]
+        WeightToFeeMultiplierAdjusted(FixedU128),
+        /// The automatic adjustment of the multiplier is updated, `None` if disabled. [Parameters]
+        DynamicMultiplierUpdated(Option<DynamicMultiplierParams>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// XOR can't be added as a fee asset, fees are paid in XOR by default
+        FeeAssetIsXor,
+        /// The asset is already allowed for fee payment
+        FeeAssetAlreadyAdded,
+        /// The asset is not allowed for fee payment
+        FeeAssetNotFound,
+        /// Bounds, period or target fee of the dynamic multiplier are invalid
+        InvalidDynamicMultiplierParams,
+        /// The multiplier can't be calculated from the reference price
+        MultiplierCalculationFailed,
+        /// The multiplier can't be updated manually while the dynamic multiplier is enabled
+        DynamicMultiplierIsEnabled,
     }
 
     /// The amount of XOR to be reminted and exchanged for VAL at the end of the session
@@ -749,6 +1129,16 @@ pub mod pallet {
     #[pallet::getter(fn multiplier)]
     pub type Multiplier<T> = StorageValue<_, FixedU128, ValueQuery, DefaultForFeeMultiplier<T>>;
 
+    /// Parameters of the automatic multiplier adjustment, the multiplier is set manually if `None`
+    #[pallet::storage]
+    #[pallet::getter(fn dynamic_multiplier)]
+    pub type DynamicMultiplier<T> = StorageValue<_, DynamicMultiplierParams, OptionQuery>;
+
+    /// Assets allowed by governance to pay transaction fees in
+    #[pallet::storage]
+    #[pallet::getter(fn fee_assets)]
+    pub type FeeAssets<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
+
     // This affects `base_fee` and `weight_fee`. `length_fee` is too small
     // in comparison to them, so we should be fine multiplying only this parts.
     impl<T: Config> WeightToFeePolynomial for Pallet<T> {
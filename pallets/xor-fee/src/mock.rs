@@ -35,8 +35,8 @@ use common::mock::ExistentialDeposits;
 use common::prelude::{Balance, BlockLength, FixedWrapper, QuoteAmount, SwapAmount, SwapOutcome};
 use common::{
     self, balance, Amount, AssetId32, AssetName, AssetSymbol, LiquidityProxyTrait,
-    LiquiditySourceFilter, LiquiditySourceType, OnValBurned, ReferrerAccountProvider, PSWAP, TBCD,
-    VAL, XOR,
+    LiquiditySourceFilter, LiquiditySourceType, OnValBurned, PriceToolsProvider, PriceVariant,
+    ReferrerAccountProvider, DAI, PSWAP, TBCD, VAL, XOR,
 };
 
 use currencies::BasicCurrencyAdapter;
@@ -52,8 +52,9 @@ use permissions::{Scope, BURN, MINT};
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
-use sp_runtime::{AccountId32, DispatchError, Percent};
+use sp_runtime::{AccountId32, DispatchError, DispatchResult, Percent};
 use sp_staking::SessionIndex;
+use std::cell::RefCell;
 use traits::MultiCurrency;
 
 pub use crate::{self as xor_fee, Config, Pallet};
@@ -86,6 +87,8 @@ parameter_types! {
     pub const ExistentialDeposit: u32 = 0;
     pub const XorId: AssetId = XOR;
     pub const ValId: AssetId = VAL;
+    pub const GetReferenceAssetId: AssetId = DAI;
+    pub const ReferenceFee: Balance = balance!(0.0007);
     pub const DEXIdValue: DEXId = common::DEXId::Polkaswap;
     pub GetXorFeeAccountId: AccountId = account_from_str("xor-fee");
     pub GetParliamentAccountId: AccountId = account_from_str("sora-parliament");
@@ -318,6 +321,46 @@ impl Config for Runtime {
     type BuyBackHandler = ();
     type ReferrerAccountProvider = MockReferrerAccountProvider;
     type WeightInfo = ();
+    type PriceTools = MockPriceTools;
+    type GetReferenceAssetId = GetReferenceAssetId;
+    type ReferenceFee = ReferenceFee;
+}
+
+thread_local! {
+    static XOR_PRICE: RefCell<Option<Balance>> = RefCell::new(Some(balance!(0.5)));
+    static VAL_PRICE: RefCell<Balance> = RefCell::new(balance!(3.1));
+}
+
+pub struct MockPriceTools;
+
+impl MockPriceTools {
+    /// Sets XOR average price in DAI, `None` if the price is not available
+    pub fn set_xor_price(price: Option<Balance>) {
+        XOR_PRICE.with(|xor_price| *xor_price.borrow_mut() = price);
+    }
+}
+
+impl PriceToolsProvider<AssetId> for MockPriceTools {
+    /// Returns XOR price set with `set_xor_price`, $0.5 by default,
+    /// and XOR price in VAL set with `MockLiquidityProxy::set_val_price`
+    fn get_average_price(
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        _price_variant: PriceVariant,
+    ) -> Result<Balance, DispatchError> {
+        match (input_asset_id, output_asset_id) {
+            (&XOR, &DAI) => XOR_PRICE
+                .with(|xor_price| *xor_price.borrow())
+                .ok_or(DispatchError::CannotLookup),
+            (&XOR, &VAL) => Ok(VAL_PRICE.with(|val_price| *val_price.borrow())),
+            _ => Err(DispatchError::CannotLookup),
+        }
+    }
+
+    /// Method not used
+    fn register_asset(_asset_id: &AssetId) -> DispatchResult {
+        unimplemented!()
+    }
 }
 
 pub struct MockReferrerAccountProvider;
@@ -361,10 +404,15 @@ impl pallet_session::historical::SessionManager<AccountId, ()> for MockSessionMa
 pub struct MockLiquidityProxy;
 
 impl MockLiquidityProxy {
+    /// Sets VAL price in XOR, 3.1 by default
+    pub fn set_val_price(price: Balance) {
+        VAL_PRICE.with(|val_price| *val_price.borrow_mut() = price);
+    }
+
     fn mock_price(asset_id: &AssetId) -> Balance {
         match asset_id {
             &XOR => balance!(1.0),
-            &VAL => balance!(3.1),
+            &VAL => VAL_PRICE.with(|val_price| *val_price.borrow()),
             &PSWAP => balance!(13),
             _ => balance!(2.5),
         }
@@ -422,6 +470,22 @@ impl LiquidityProxyTrait<DEXId, AccountId, AssetId> for MockLiquidityProxy {
         amount: SwapAmount<Balance>,
         _filter: LiquiditySourceFilter<DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        let outcome =
+            Self::exchange_inner(None, None, input_asset_id, output_asset_id, amount.into())?;
+        match amount {
+            SwapAmount::WithDesiredInput { min_amount_out, .. } => {
+                frame_support::ensure!(
+                    outcome.amount >= min_amount_out,
+                    DispatchError::Other("slippage")
+                );
+            }
+            SwapAmount::WithDesiredOutput { max_amount_in, .. } => {
+                frame_support::ensure!(
+                    outcome.amount <= max_amount_in,
+                    DispatchError::Other("slippage")
+                );
+            }
+        }
         Self::exchange_inner(
             Some(sender),
             Some(receiver),
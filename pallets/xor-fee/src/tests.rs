@@ -32,15 +32,20 @@
 #![allow(clippy::all)]
 
 use crate::extension::ChargeTransactionPayment;
-use crate::{mock::*, LiquidityInfo, XorToVal};
-use common::balance;
+use crate::{
+    mock::*, DynamicMultiplier, DynamicMultiplierParams, Error, Event, FeeAssets, LiquidityInfo,
+    WeightInfo, XorToVal, VALIDATION_ERROR_FEE_ASSET_NOT_ALLOWED,
+    VALIDATION_ERROR_FEE_ASSET_SLIPPAGE,
+};
+use common::{balance, AssetInfoProvider, TBCD, VAL, XOR};
 
 use common::mock::{alice, bob};
+use frame_support::dispatch::DispatchClass;
 use frame_support::error::BadOrigin;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Get, Hooks};
 use frame_support::weights::{Weight, WeightToFee};
 use frame_support::{assert_noop, assert_ok};
-use sp_runtime::traits::SignedExtension;
+use sp_runtime::traits::{SignedExtension, Zero};
 use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
 use sp_runtime::{FixedPointNumber, FixedU128};
 
@@ -497,3 +502,404 @@ fn it_works_referrer_refund() {
         assert_eq!(XorToVal::<Runtime>::get(), balance!(0.00035));
     });
 }
+
+fn transfer_call() -> RuntimeCall {
+    RuntimeCall::Assets(assets::Call::transfer {
+        to: bob(),
+        asset_id: VAL,
+        amount: 10,
+    })
+}
+
+fn setup_fee_asset() {
+    set_weight_to_fee_multiplier(1);
+    assert_ok!(XorFee::add_fee_asset(RuntimeOrigin::root(), VAL));
+    assert_ok!(Assets::mint_to(
+        &VAL,
+        &GetXorFeeAccountId::get(),
+        &alice(),
+        balance!(10)
+    ));
+}
+
+#[test]
+fn fee_assets_are_managed_by_root() {
+    ExtBuilder::build().execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        assert_noop!(
+            XorFee::add_fee_asset(RuntimeOrigin::signed(alice()), VAL),
+            BadOrigin
+        );
+        assert_noop!(
+            XorFee::add_fee_asset(RuntimeOrigin::root(), XOR),
+            Error::<Runtime>::FeeAssetIsXor
+        );
+        assert_noop!(
+            XorFee::add_fee_asset(RuntimeOrigin::root(), common::PSWAP),
+            assets::Error::<Runtime>::AssetIdNotExists
+        );
+        assert_ok!(XorFee::add_fee_asset(RuntimeOrigin::root(), VAL));
+        System::assert_last_event(Event::<Runtime>::FeeAssetAdded(VAL).into());
+        assert!(FeeAssets::<Runtime>::contains_key(VAL));
+        assert_noop!(
+            XorFee::add_fee_asset(RuntimeOrigin::root(), VAL),
+            Error::<Runtime>::FeeAssetAlreadyAdded
+        );
+
+        assert_noop!(
+            XorFee::remove_fee_asset(RuntimeOrigin::signed(alice()), VAL),
+            BadOrigin
+        );
+        assert_ok!(XorFee::remove_fee_asset(RuntimeOrigin::root(), VAL));
+        System::assert_last_event(Event::<Runtime>::FeeAssetRemoved(VAL).into());
+        assert!(!FeeAssets::<Runtime>::contains_key(VAL));
+        assert_noop!(
+            XorFee::remove_fee_asset(RuntimeOrigin::root(), VAL),
+            Error::<Runtime>::FeeAssetNotFound
+        );
+    });
+}
+
+#[test]
+fn fee_in_asset_is_quoted() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(XorFee::fee_in_asset(&XOR, balance!(1)), Some(balance!(1)));
+        assert_eq!(XorFee::fee_in_asset(&VAL, balance!(1)), None);
+        assert_ok!(XorFee::add_fee_asset(RuntimeOrigin::root(), VAL));
+        assert_eq!(XorFee::fee_in_asset(&VAL, 0), Some(0));
+        let amount = XorFee::fee_in_asset(&VAL, balance!(1)).unwrap();
+        assert!(amount > balance!(3.0999) && amount < balance!(3.1001));
+    });
+}
+
+#[test]
+fn fee_in_asset_uses_average_price() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(XorFee::add_fee_asset(RuntimeOrigin::root(), VAL));
+        assert_ok!(XorFee::add_fee_asset(RuntimeOrigin::root(), TBCD));
+        MockLiquidityProxy::set_val_price(balance!(6.2));
+        assert_eq!(XorFee::fee_in_asset(&VAL, balance!(1)), Some(balance!(6.2)));
+        // TBCD can be exchanged but has no average price
+        assert_eq!(XorFee::fee_in_asset(&TBCD, balance!(1)), None);
+    });
+}
+
+#[test]
+fn fee_paid_in_asset_works() {
+    ExtBuilder::build().execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        setup_fee_asset();
+        let who = alice();
+        let amount_in = XorFee::fee_in_asset(&VAL, balance!(0.0007)).unwrap();
+        let pre = ChargeTransactionPayment::<Runtime>::new()
+            .with_fee_asset(VAL, balance!(1))
+            .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100)
+            .unwrap();
+        assert_eq!(
+            pre,
+            (
+                0,
+                who.clone(),
+                LiquidityInfo::<Runtime>::PaidInAsset(
+                    who.clone(),
+                    VAL,
+                    amount_in,
+                    balance!(0.0007)
+                ),
+                Some(balance!(0.0007)),
+            )
+        );
+        assert_eq!(
+            Assets::free_balance(&VAL, &who).unwrap(),
+            balance!(10) - amount_in
+        );
+        // the exchange is accounted in the block weight
+        assert_eq!(
+            *System::block_weight().get(DispatchClass::Normal),
+            <() as WeightInfo>::exchange_fee_asset()
+        );
+        ChargeTransactionPayment::<Runtime>::post_dispatch(
+            Some(pre),
+            &info_from_weight(100.into()),
+            &post_info_from_weight(100.into()),
+            100,
+            &Ok(()),
+        )
+        .unwrap();
+        System::assert_has_event(
+            Event::<Runtime>::FeeWithdrawnInAsset(who.clone(), VAL, amount_in).into(),
+        );
+        assert_eq!(
+            Assets::free_balance(&VAL, &who).unwrap(),
+            balance!(10) - amount_in
+        );
+        assert_eq!(Balances::usable_balance_for_fees(&who), 0);
+        // the fee is exchanged for XOR at the mock price, allow rounding error
+        assert!(XorToVal::<Runtime>::get().abs_diff(balance!(0.00035)) <= 1);
+    });
+}
+
+#[test]
+fn fee_paid_in_asset_is_refunded() {
+    ExtBuilder::build().execute_with(|| {
+        setup_fee_asset();
+        let who = alice();
+        let pre = ChargeTransactionPayment::<Runtime>::new()
+            .with_fee_asset(VAL, balance!(1))
+            .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100)
+            .unwrap();
+        assert!(Assets::free_balance(&VAL, &who).unwrap() < balance!(10));
+        ChargeTransactionPayment::<Runtime>::post_dispatch(
+            Some(pre),
+            &info_from_weight(100.into()),
+            &post_info_pays_no(),
+            100,
+            &Ok(()),
+        )
+        .unwrap();
+        assert_eq!(Assets::free_balance(&VAL, &who).unwrap(), balance!(10));
+        assert_eq!(XorToVal::<Runtime>::get(), 0);
+    });
+}
+
+#[test]
+fn fee_paid_in_asset_falls_back_to_xor() {
+    ExtBuilder::build().execute_with(|| {
+        setup_fee_asset();
+        let who = alice();
+        let _ = Balances::deposit_creating(&who, balance!(1));
+        let amount_in = XorFee::fee_in_asset(&VAL, balance!(0.0007)).unwrap();
+        let pre = ChargeTransactionPayment::<Runtime>::new()
+            .with_fee_asset(VAL, balance!(1))
+            .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100)
+            .unwrap();
+        assert_eq!(
+            Assets::free_balance(&VAL, &who).unwrap(),
+            balance!(10) - amount_in
+        );
+        // The withheld amount is no longer enough to buy the fee
+        MockLiquidityProxy::set_val_price(balance!(6.2));
+        ChargeTransactionPayment::<Runtime>::post_dispatch(
+            Some(pre),
+            &info_from_weight(100.into()),
+            &post_info_from_weight(100.into()),
+            100,
+            &Ok(()),
+        )
+        .unwrap();
+        assert_eq!(Assets::free_balance(&VAL, &who).unwrap(), balance!(10));
+        assert_eq!(
+            Balances::usable_balance_for_fees(&who),
+            balance!(1) - balance!(0.0007)
+        );
+        assert_eq!(XorToVal::<Runtime>::get(), balance!(0.00035));
+    });
+}
+
+#[test]
+fn fee_paid_in_asset_is_kept_if_not_exchanged() {
+    ExtBuilder::build().execute_with(|| {
+        setup_fee_asset();
+        let who = alice();
+        let amount_in = XorFee::fee_in_asset(&VAL, balance!(0.0007)).unwrap();
+        let pre = ChargeTransactionPayment::<Runtime>::new()
+            .with_fee_asset(VAL, balance!(1))
+            .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100)
+            .unwrap();
+        MockLiquidityProxy::set_val_price(balance!(6.2));
+        ChargeTransactionPayment::<Runtime>::post_dispatch(
+            Some(pre),
+            &info_from_weight(100.into()),
+            &post_info_from_weight(100.into()),
+            100,
+            &Ok(()),
+        )
+        .unwrap();
+        assert_eq!(
+            Assets::free_balance(&VAL, &who).unwrap(),
+            balance!(10) - amount_in
+        );
+        assert_eq!(
+            Assets::free_balance(&VAL, &GetXorFeeAccountId::get()).unwrap(),
+            amount_in
+        );
+        assert_eq!(XorToVal::<Runtime>::get(), 0);
+    });
+}
+
+#[test]
+fn fee_paid_in_asset_fails() {
+    ExtBuilder::build().execute_with(|| {
+        set_weight_to_fee_multiplier(1);
+        let who = alice();
+        assert_noop!(
+            ChargeTransactionPayment::<Runtime>::new()
+                .with_fee_asset(VAL, balance!(1))
+                .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100),
+            TransactionValidityError::Invalid(InvalidTransaction::Custom(
+                VALIDATION_ERROR_FEE_ASSET_NOT_ALLOWED
+            ))
+        );
+        setup_fee_asset();
+        assert_noop!(
+            ChargeTransactionPayment::<Runtime>::new()
+                .with_fee_asset(VAL, balance!(0.002))
+                .pre_dispatch(&who, &transfer_call(), &info_from_weight(100.into()), 100),
+            TransactionValidityError::Invalid(InvalidTransaction::Custom(
+                VALIDATION_ERROR_FEE_ASSET_SLIPPAGE
+            ))
+        );
+        assert_noop!(
+            ChargeTransactionPayment::<Runtime>::new()
+                .with_fee_asset(VAL, balance!(1))
+                .pre_dispatch(&bob(), &transfer_call(), &info_from_weight(100.into()), 100),
+            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+        );
+    });
+}
+
+fn dynamic_multiplier_params() -> DynamicMultiplierParams {
+    DynamicMultiplierParams {
+        target_fee: balance!(0.0007),
+        congestion_factor: FixedU128::from(1),
+        min_multiplier: FixedU128::from(1),
+        max_multiplier: FixedU128::from(10),
+        update_period: 2,
+    }
+}
+
+#[test]
+fn dynamic_multiplier_is_set_by_root() {
+    ExtBuilder::build().execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        let params = dynamic_multiplier_params();
+        assert_noop!(
+            XorFee::set_dynamic_multiplier(RuntimeOrigin::signed(alice()), Some(params.clone())),
+            BadOrigin
+        );
+        for invalid_params in [
+            DynamicMultiplierParams {
+                target_fee: 0,
+                ..params.clone()
+            },
+            DynamicMultiplierParams {
+                min_multiplier: FixedU128::zero(),
+                ..params.clone()
+            },
+            DynamicMultiplierParams {
+                max_multiplier: FixedU128::from_float(0.5),
+                ..params.clone()
+            },
+            DynamicMultiplierParams {
+                update_period: 0,
+                ..params.clone()
+            },
+        ] {
+            assert_noop!(
+                XorFee::set_dynamic_multiplier(RuntimeOrigin::root(), Some(invalid_params)),
+                Error::<Runtime>::InvalidDynamicMultiplierParams
+            );
+        }
+
+        assert_ok!(XorFee::set_dynamic_multiplier(
+            RuntimeOrigin::root(),
+            Some(params.clone())
+        ));
+        assert_eq!(DynamicMultiplier::<Runtime>::get(), Some(params.clone()));
+        System::assert_last_event(Event::<Runtime>::DynamicMultiplierUpdated(Some(params)).into());
+
+        assert_ok!(XorFee::set_dynamic_multiplier(RuntimeOrigin::root(), None));
+        assert_eq!(DynamicMultiplier::<Runtime>::get(), None);
+        System::assert_last_event(Event::<Runtime>::DynamicMultiplierUpdated(None).into());
+    });
+}
+
+#[test]
+fn multiplier_is_not_updated_manually_while_dynamic_multiplier_is_enabled() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(XorFee::set_dynamic_multiplier(
+            RuntimeOrigin::root(),
+            Some(dynamic_multiplier_params())
+        ));
+        assert_noop!(
+            XorFee::update_multiplier(RuntimeOrigin::root(), FixedU128::from(3)),
+            Error::<Runtime>::DynamicMultiplierIsEnabled
+        );
+
+        assert_ok!(XorFee::set_dynamic_multiplier(RuntimeOrigin::root(), None));
+        assert_ok!(XorFee::update_multiplier(
+            RuntimeOrigin::root(),
+            FixedU128::from(3)
+        ));
+        assert_eq!(XorFee::multiplier(), FixedU128::from(3));
+    });
+}
+
+#[test]
+fn dynamic_multiplier_targets_reference_fee() {
+    ExtBuilder::build().execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        set_weight_to_fee_multiplier(1);
+        assert_ok!(XorFee::set_dynamic_multiplier(
+            RuntimeOrigin::root(),
+            Some(dynamic_multiplier_params())
+        ));
+
+        // adjusted only once per period
+        assert_eq!(
+            XorFee::on_initialize(1),
+            <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+        );
+        XorFee::on_finalize(1);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(1));
+
+        // XOR costs $0.5, so the reference fee of 0.0007 XOR is doubled to cost $0.0007
+        assert_eq!(
+            XorFee::on_initialize(2),
+            <() as WeightInfo>::adjust_multiplier()
+        );
+        XorFee::on_finalize(2);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(2));
+        System::assert_last_event(
+            Event::<Runtime>::WeightToFeeMultiplierAdjusted(FixedU128::from(2)).into(),
+        );
+
+        // the fee is doubled once more at the full block
+        let max_weight = <Runtime as frame_system::Config>::BlockWeights::get()
+            .get(DispatchClass::Normal)
+            .max_total
+            .unwrap();
+        frame_system::Pallet::<Runtime>::register_extra_weight_unchecked(
+            max_weight,
+            DispatchClass::Normal,
+        );
+        XorFee::on_finalize(4);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(4));
+    });
+}
+
+#[test]
+fn dynamic_multiplier_is_bounded() {
+    ExtBuilder::build().execute_with(|| {
+        frame_system::Pallet::<Runtime>::set_block_number(1);
+        assert_ok!(XorFee::set_dynamic_multiplier(
+            RuntimeOrigin::root(),
+            Some(dynamic_multiplier_params())
+        ));
+
+        MockPriceTools::set_xor_price(Some(balance!(0.01)));
+        XorFee::on_finalize(2);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(10));
+
+        MockPriceTools::set_xor_price(Some(balance!(100)));
+        XorFee::on_finalize(4);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(1));
+
+        // the multiplier is kept if the price is not available
+        set_weight_to_fee_multiplier(3);
+        MockPriceTools::set_xor_price(None);
+        XorFee::on_finalize(6);
+        assert_eq!(XorFee::multiplier(), FixedU128::from(3));
+        MockPriceTools::set_xor_price(Some(balance!(0.5)));
+    });
+}
@@ -61,33 +61,220 @@ use core::marker::PhantomData;
 /// Weight functions needed for xor_fee.
 pub trait WeightInfo {
 	fn update_multiplier() -> Weight;
+	fn add_fee_asset() -> Weight;
+	fn remove_fee_asset() -> Weight;
+	fn set_dynamic_multiplier() -> Weight;
+	fn adjust_multiplier() -> Weight;
+	fn exchange_fee_asset() -> Weight;
 }
 
 /// Weights for xor_fee using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: XorFee DynamicMultiplier (r:1 w:0)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: XorFee Multiplier (r:0 w:1)
 	/// Proof Skipped: XorFee Multiplier (max_values: Some(1), max_size: None, mode: Measured)
 	fn update_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `571`
+		// Minimum execution time: 12_904_000 picoseconds.
+		Weight::from_parts(13_382_000, 571)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XorFee FeeAssets (r:1 w:1)
+	/// Proof Skipped: XorFee FeeAssets (max_values: None, max_size: None, mode: Measured)
+	fn add_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1104`
+		//  Estimated: `7158`
+		// Minimum execution time: 24_512_000 picoseconds.
+		Weight::from_parts(25_301_000, 7158)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: XorFee FeeAssets (r:1 w:1)
+	/// Proof Skipped: XorFee FeeAssets (max_values: None, max_size: None, mode: Measured)
+	fn remove_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `132`
+		//  Estimated: `2607`
+		// Minimum execution time: 16_218_000 picoseconds.
+		Weight::from_parts(16_804_000, 2607)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: XorFee DynamicMultiplier (r:0 w:1)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_dynamic_multiplier() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
-		// Minimum execution time: 10_327_000 picoseconds.
-		Weight::from_parts(10_920_000, 0)
+		// Minimum execution time: 12_014_000 picoseconds.
+		Weight::from_parts(12_581_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: XorFee DynamicMultiplier (r:1 w:0)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PriceTools PriceInfos (r:1 w:0)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools AveragingParameters (r:1 w:0)
+	/// Proof Skipped: PriceTools AveragingParameters (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System BlockWeight (r:1 w:0)
+	/// Proof: System BlockWeight (max_values: Some(1), max_size: Some(48), added: 543, mode: MaxEncodedLen)
+	/// Storage: XorFee Multiplier (r:0 w:1)
+	/// Proof Skipped: XorFee Multiplier (max_values: Some(1), max_size: None, mode: Measured)
+	fn adjust_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1386`
+		//  Estimated: `12318`
+		// Minimum execution time: 41_703_000 picoseconds.
+		Weight::from_parts(42_960_000, 12318)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair LockedLiquiditySources (r:1 w:0)
+	/// Proof Skipped: TradingPair LockedLiquiditySources (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:1 w:0)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Properties (r:1 w:0)
+	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:2 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetInfos (r:2 w:0)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:3 w:3)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn exchange_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3310`
+		//  Estimated: `0`
+		// Minimum execution time: 241_502_000 picoseconds.
+		Weight::from_parts(246_857_000, 0)
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	/// Storage: XorFee DynamicMultiplier (r:1 w:0)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: XorFee Multiplier (r:0 w:1)
 	/// Proof Skipped: XorFee Multiplier (max_values: Some(1), max_size: None, mode: Measured)
 	fn update_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `571`
+		// Minimum execution time: 12_904_000 picoseconds.
+		Weight::from_parts(13_382_000, 571)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets AssetOwners (r:1 w:0)
+	/// Proof Skipped: Assets AssetOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XorFee FeeAssets (r:1 w:1)
+	/// Proof Skipped: XorFee FeeAssets (max_values: None, max_size: None, mode: Measured)
+	fn add_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1104`
+		//  Estimated: `7158`
+		// Minimum execution time: 24_512_000 picoseconds.
+		Weight::from_parts(25_301_000, 7158)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: XorFee FeeAssets (r:1 w:1)
+	/// Proof Skipped: XorFee FeeAssets (max_values: None, max_size: None, mode: Measured)
+	fn remove_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `132`
+		//  Estimated: `2607`
+		// Minimum execution time: 16_218_000 picoseconds.
+		Weight::from_parts(16_804_000, 2607)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: XorFee DynamicMultiplier (r:0 w:1)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_dynamic_multiplier() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
-		// Minimum execution time: 10_327_000 picoseconds.
-		Weight::from_parts(10_920_000, 0)
+		// Minimum execution time: 12_014_000 picoseconds.
+		Weight::from_parts(12_581_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: XorFee DynamicMultiplier (r:1 w:0)
+	/// Proof Skipped: XorFee DynamicMultiplier (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PriceTools PriceInfos (r:1 w:0)
+	/// Proof Skipped: PriceTools PriceInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PriceTools AveragingParameters (r:1 w:0)
+	/// Proof Skipped: PriceTools AveragingParameters (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System BlockWeight (r:1 w:0)
+	/// Proof: System BlockWeight (max_values: Some(1), max_size: Some(48), added: 543, mode: MaxEncodedLen)
+	/// Storage: XorFee Multiplier (r:0 w:1)
+	/// Proof Skipped: XorFee Multiplier (max_values: Some(1), max_size: None, mode: Measured)
+	fn adjust_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1386`
+		//  Estimated: `12318`
+		// Minimum execution time: 41_703_000 picoseconds.
+		Weight::from_parts(42_960_000, 12318)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DEXManager DEXInfos (r:1 w:0)
+	/// Proof Skipped: DEXManager DEXInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: TradingPair LockedLiquiditySources (r:1 w:0)
+	/// Proof Skipped: TradingPair LockedLiquiditySources (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: TradingPair EnabledSources (r:1 w:0)
+	/// Proof Skipped: TradingPair EnabledSources (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Properties (r:1 w:0)
+	/// Proof Skipped: PoolXYK Properties (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK Reserves (r:1 w:1)
+	/// Proof Skipped: PoolXYK Reserves (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK FlashSwapLocks (r:1 w:0)
+	/// Proof Skipped: PoolXYK FlashSwapLocks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:2 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets AssetInfos (r:2 w:0)
+	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:3 w:3)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: PoolXYK PriceCumulatives (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceCumulatives (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PoolXYK PriceObservations (r:1 w:1)
+	/// Proof Skipped: PoolXYK PriceObservations (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn exchange_fee_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3310`
+		//  Estimated: `0`
+		// Minimum execution time: 241_502_000 picoseconds.
+		Weight::from_parts(246_857_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
 }
@@ -96,6 +96,7 @@ parameter_types! {
     pub const MinimumPeriod: u64 = 5;
     pub const GetBandRateStalePeriod: Moment = 60*5*1000; // 5 minutes
     pub const GetBandRateStaleBlockPeriod: u64 = 600;
+    pub const GetBandOracleVariant: common::Oracle = common::Oracle::BandChainFeed;
     pub GetXSTPoolPermissionedTechAccountId: TechAccountId = {
         let tech_account_id = TechAccountId::from_generic_pair(
             crate::TECH_ACCOUNT_PREFIX.to_vec(),
@@ -207,7 +208,10 @@ impl band::Config for Runtime {
     type Time = Timestamp;
     type OnSymbolDisabledHook = crate::Pallet<Runtime>;
     type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetBandOracleVariant;
+    type Currency = Balances;
     type MaxRelaySymbols = frame_support::traits::ConstU32<100>;
+    type MaxRelayers = frame_support::traits::ConstU32<100>;
 }
 
 impl oracle_proxy::Config for Runtime {
@@ -215,6 +219,8 @@ impl oracle_proxy::Config for Runtime {
     type WeightInfo = ();
     type Symbol = <Runtime as band::Config>::Symbol;
     type BandChainOracle = band::Pallet<Runtime>;
+    type AdditionalFeeds = ();
+    type Time = Timestamp;
 }
 
 impl tokens::Config for Runtime {
@@ -282,6 +288,7 @@ impl dex_api::Config for Runtime {
     type MulticollateralBondingCurvePool = ();
     type DexInfoProvider = ();
     type OrderBook = ();
+    type StableSwapPool = ();
     type WeightInfo = ();
 }
 
@@ -346,6 +353,7 @@ impl demeter_farming_platform::Config for Runtime {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.0007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -357,7 +365,13 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
     type EnabledSourcesManager = trading_pair::Pallet<Runtime>;
+    type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type GetFee = GetXykFee;
+    type GetMinPoolFee = ();
+    type GetMaxPoolFee = ();
+    type TreasuryTechAccountId = ();
+    type MaxPriceObservations = ();
+    type PriceObservationPeriod = ();
     type OnPoolCreated = PswapDistribution;
     type OnPoolReservesChanged = ();
     type WeightInfo = ();
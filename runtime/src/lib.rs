@@ -259,10 +259,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("sora-substrate"),
     impl_name: create_runtime_str!("sora-substrate"),
     authoring_version: 1,
-    spec_version: 72,
+    spec_version: 73,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 72,
+    transaction_version: 73,
     state_version: 0,
 };
 
@@ -984,6 +984,11 @@ impl technical::Config for Runtime {
 
 parameter_types! {
     pub GetFee: Fixed = fixed!(0.003);
+    pub GetMinPoolFee: Fixed = fixed!(0.0005);
+    pub GetMaxPoolFee: Fixed = fixed!(0.01);
+    pub const GetMaxPriceObservations: u32 = 48;
+    // 30 minutes, so TWAP is available for periods up to one day
+    pub const GetPriceObservationPeriod: Moment = 30 * 60 * 1000;
 }
 
 parameter_type_with_key! {
@@ -999,6 +1004,7 @@ parameter_type_with_key! {
 impl pool_xyk::Config for Runtime {
     const MIN_XOR: Balance = balance!(0.0007);
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type PairSwapAction = pool_xyk::PairSwapAction<AssetId, AccountId, TechAccountId>;
     type DepositLiquidityAction =
         pool_xyk::DepositLiquidityAction<AssetId, AccountId, TechAccountId>;
@@ -1010,7 +1016,13 @@ impl pool_xyk::Config for Runtime {
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
     type EnabledSourcesManager = trading_pair::Pallet<Runtime>;
+    type LockedLiquiditySourcesManager = trading_pair::Pallet<Runtime>;
     type GetFee = GetFee;
+    type GetMinPoolFee = GetMinPoolFee;
+    type GetMaxPoolFee = GetMaxPoolFee;
+    type TreasuryTechAccountId = GetTreasuryTechAccountId;
+    type MaxPriceObservations = GetMaxPriceObservations;
+    type PriceObservationPeriod = GetPriceObservationPeriod;
     type OnPoolCreated = (PswapDistribution, Farming);
     type OnPoolReservesChanged = PriceTools;
     type WeightInfo = pool_xyk::weights::SubstrateWeight<Runtime>;
@@ -1018,6 +1030,29 @@ impl pool_xyk::Config for Runtime {
     type GetTradingPairRestrictedFlag = GetTradingPairRestrictedFlag;
 }
 
+parameter_types! {
+    pub GetStableSwapPoolTechAccountId: TechAccountId = {
+        let tech_account_id = TechAccountId::from_generic_pair(
+            stable_swap_pool::TECH_ACCOUNT_PREFIX.to_vec(),
+            stable_swap_pool::TECH_ACCOUNT_RESERVES.to_vec(),
+        );
+        tech_account_id
+    };
+    pub GetStableSwapPoolFee: Fixed = fixed!(0.0004);
+    pub const StableSwapPoolMinAmplificationRampBlocks: BlockNumber = DAYS;
+}
+
+impl stable_swap_pool::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type GetTechAccountId = GetStableSwapPoolTechAccountId;
+    type GetFee = GetStableSwapPoolFee;
+    type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
+    type TradingPairSourceManager = trading_pair::Pallet<Runtime>;
+    type DexInfoProvider = dex_manager::Pallet<Runtime>;
+    type MinAmplificationRampBlocks = StableSwapPoolMinAmplificationRampBlocks;
+    type WeightInfo = stable_swap_pool::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
     pub GetLiquidityProxyTechAccountId: TechAccountId = {
         // TODO(Harrm): why pswap_distribution?
@@ -1073,6 +1108,9 @@ impl liquidity_proxy::Config for Runtime {
         EnsureRoot<AccountId>,
     >;
     type MaxAdditionalDataLength = MaxAdditionalDataLength;
+    type MaxRouteHops = ConstU32<4>;
+    type MaxRoutePaths = ConstU32<8>;
+    type Time = Timestamp;
 }
 
 impl mock_liquidity_source::Config<mock_liquidity_source::Instance1> for Runtime {
@@ -1118,6 +1156,7 @@ impl dex_api::Config for Runtime {
     type XSTPool = xst::Pallet<Runtime>;
     type DexInfoProvider = dex_manager::Pallet<Runtime>;
     type OrderBook = order_book::Pallet<Runtime>;
+    type StableSwapPool = stable_swap_pool::Pallet<Runtime>;
 
     type WeightInfo = dex_api::weights::SubstrateWeight<Runtime>;
 }
@@ -1252,6 +1291,7 @@ where
 
 parameter_types! {
     pub const DEXIdValue: DEXId = 0;
+    pub const XorFeeReferenceFee: Balance = SMALL_FEE;
 }
 
 impl xor_fee::Config for Runtime {
@@ -1276,6 +1316,9 @@ impl xor_fee::Config for Runtime {
     type BuyBackHandler = liquidity_proxy::LiquidityProxyBuyBackHandler<Runtime, GetBuyBackDexId>;
     type WeightInfo = xor_fee::weights::SubstrateWeight<Runtime>;
     type WithdrawFee = xor_fee_impls::WithdrawFee;
+    type PriceTools = PriceTools;
+    type GetReferenceAssetId = GetReferenceAssetId;
+    type ReferenceFee = XorFeeReferenceFee;
 }
 
 pub struct ConstantFeeMultiplier;
@@ -1880,12 +1923,17 @@ impl oracle_proxy::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = oracle_proxy::weights::SubstrateWeight<Runtime>;
     type BandChainOracle = band::Pallet<Runtime>;
+    type AdditionalFeeds = (band::Pallet<Runtime, SecondaryBandInstance>,);
+    type Time = Timestamp;
 }
 
 parameter_types! {
     pub const GetBandRateStalePeriod: Moment = 60*5*1000; // 5 minutes
     pub const GetBandRateStaleBlockPeriod: u32 = 600; // 1 hour in blocks
+    pub const GetBandOracleVariant: common::Oracle = common::Oracle::BandChainFeed;
+    pub const GetSecondaryBandOracleVariant: common::Oracle = common::Oracle::Feed(0);
     pub const BandMaxRelaySymbols: u32 = 100;
+    pub const BandMaxRelayers: u32 = 100;
 }
 
 impl band::Config for Runtime {
@@ -1896,8 +1944,31 @@ impl band::Config for Runtime {
     type Time = Timestamp;
     type GetBandRateStalePeriod = GetBandRateStalePeriod;
     type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetBandOracleVariant;
+    type Currency = Balances;
     type OnSymbolDisabledHook = xst::Pallet<Runtime>;
     type MaxRelaySymbols = BandMaxRelaySymbols;
+    type MaxRelayers = BandMaxRelayers;
+}
+
+/// Band feed relayed by the second set of relayers, it is the first additional feed of the
+/// oracle proxy.
+pub type SecondaryBandInstance = band::Instance1;
+
+impl band::Config<SecondaryBandInstance> for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Symbol = Symbol;
+    type WeightInfo = band::weights::SubstrateWeight<Runtime>;
+    type OnNewSymbolsRelayedHook = oracle_proxy::Pallet<Runtime>;
+    type Time = Timestamp;
+    type GetBandRateStalePeriod = GetBandRateStalePeriod;
+    type GetBandRateStaleBlockPeriod = GetBandRateStaleBlockPeriod;
+    type OracleVariant = GetSecondaryBandOracleVariant;
+    type Currency = Balances;
+    // symbols stay available in the oracle proxy through the other feeds
+    type OnSymbolDisabledHook = ();
+    type MaxRelaySymbols = BandMaxRelaySymbols;
+    type MaxRelayers = BandMaxRelayers;
 }
 
 parameter_types! {
@@ -1947,6 +2018,21 @@ parameter_types! {
     // pub KensetsuOffchainWorkerTxLongevity: TransactionLongevity = 100;
     // TODO set 100 for release
     pub KensetsuOffchainWorkerTxLongevity: TransactionLongevity = 5;
+
+    // Collateral auction starts with 20% premium to the reference price
+    pub KensetsuAuctionStartPriceMultiplier: sp_runtime::FixedU128 =
+        sp_runtime::FixedU128::from_rational(12, 10);
+    // Collateral auction price decreases by 1% every block
+    pub const KensetsuAuctionPriceDecay: Perbill = Perbill::from_percent(1);
+    // 1 hour = 600 blocks, auction is restarted after that
+    pub const KensetsuAuctionDuration: BlockNumber = 600;
+    // Auction is closed after 1 day of restarts, unsold collateral is kept by protocol
+    pub const KensetsuMaxAuctionRestarts: u32 = 24;
+    // Oracle prices older than 5 minutes are not used, in seconds
+    pub const KensetsuMaxOraclePriceAge: u64 = 5 * 60;
+    // Savings rate is limited by 20% per year, rate per second
+    pub KensetsuMaxSavingsRate: sp_runtime::FixedU128 =
+        sp_runtime::FixedU128::from_inner(6_341_958_396);
 }
 
 #[cfg(feature = "wip")] // kensetsu
@@ -1956,12 +2042,23 @@ impl kensetsu::Config for Runtime {
     type TreasuryTechAccount = KensetsuTreasuryTechAccountId;
     type KusdAssetId = KusdAssetId;
     type PriceTools = PriceTools;
+    type Symbol = Symbol;
+    type Oracle = OracleProxy;
     type LiquidityProxy = LiquidityProxy;
     type MaxCdpsPerOwner = ConstU32<100>;
     type MaxRiskManagementTeamSize = ConstU32<100>;
     type AccrueInterestPeriod = AccrueInterestPeriod;
+    type AuctionStartPriceMultiplier = KensetsuAuctionStartPriceMultiplier;
+    type AuctionPriceDecay = KensetsuAuctionPriceDecay;
+    type AuctionDuration = KensetsuAuctionDuration;
     type UnsignedPriority = KensetsuOffchainWorkerTxPriority;
     type UnsignedLongevity = KensetsuOffchainWorkerTxLongevity;
+    type OffchainWorkerMaxCdpsPerBlock = ConstU32<100>;
+    type MaxBasketCollaterals = ConstU32<10>;
+    type MaxOraclePriceAge = KensetsuMaxOraclePriceAge;
+    type MaxAuctionsPerBlock = ConstU32<20>;
+    type MaxAuctionRestarts = KensetsuMaxAuctionRestarts;
+    type MaxSavingsRate = KensetsuMaxSavingsRate;
     type WeightInfo = kensetsu::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1969,6 +2066,7 @@ parameter_types! {
     // small value for test environment in order to check postponing expirations
     pub ExpirationsSchedulerMaxWeight: Weight = Perbill::from_percent(15) * BlockWeights::get().max_block;
     pub AlignmentSchedulerMaxWeight: Weight = Perbill::from_percent(35) * BlockWeights::get().max_block;
+    pub TriggersSchedulerMaxWeight: Weight = Perbill::from_percent(15) * BlockWeights::get().max_block;
 }
 
 impl order_book::Config for Runtime {
@@ -1977,6 +2075,7 @@ impl order_book::Config for Runtime {
     const MILLISECS_PER_BLOCK: Moment = MILLISECS_PER_BLOCK;
     const SOFT_MIN_MAX_RATIO: usize = 1000;
     const HARD_MIN_MAX_RATIO: usize = 4000;
+    const MAX_TRADING_FEE_BPS: u16 = 1000; // 10%
     type RuntimeEvent = RuntimeEvent;
     type OrderId = u128;
     type Locker = OrderBook;
@@ -1998,6 +2097,8 @@ impl order_book::Config for Runtime {
     type MaxExpiringOrdersPerBlock = ConstU32<1024>;
     type MaxExpirationWeightPerBlock = ExpirationsSchedulerMaxWeight;
     type MaxAlignmentWeightPerBlock = AlignmentSchedulerMaxWeight;
+    type MaxConditionalOrdersPerUser = ConstU32<256>;
+    type MaxTriggersWeightPerBlock = TriggersSchedulerMaxWeight;
     type EnsureTradingPairExists = TradingPair;
     type TradingPairSourceManager = TradingPair;
     type AssetInfoProvider = Assets;
@@ -2015,6 +2116,10 @@ impl order_book::Config for Runtime {
         pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
         EnsureRoot<AccountId>,
     >;
+    type MaxFeeTiers = ConstU32<16>;
+    type TradingFeeCollector = GetTreasuryAccountId;
+    type BuyBackHandler = liquidity_proxy::LiquidityProxyBuyBackHandler<Runtime, GetBuyBackDexId>;
+    type MaxTradesHistory = ConstU32<1024>;
     type WeightInfo = order_book::weights::SubstrateWeight<Runtime>;
 }
 
@@ -2391,7 +2496,7 @@ construct_runtime! {
         Identity: pallet_identity::{Pallet, Call, Storage, Event<T>} = 41,
         Farming: farming::{Pallet, Storage} = 42,
         XSTPool: xst::{Pallet, Call, Storage, Config<T>, Event<T>} = 43,
-        PriceTools: price_tools::{Pallet, Storage, Event<T>} = 44,
+        PriceTools: price_tools::{Pallet, Call, Storage, Event<T>} = 44,
         CeresStaking: ceres_staking::{Pallet, Call, Storage, Event<T>} = 45,
         CeresLiquidityLocker: ceres_liquidity_locker::{Pallet, Call, Storage, Event<T>} = 46,
         CeresTokenLocker: ceres_token_locker::{Pallet, Call, Storage, Event<T>} = 47,
@@ -2403,9 +2508,11 @@ construct_runtime! {
         ElectionProviderMultiPhase: pallet_election_provider_multi_phase::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 52,
         Band: band::{Pallet, Call, Storage, Event<T>} = 53,
         OracleProxy: oracle_proxy::{Pallet, Call, Storage, Event<T>} = 54,
+        BandSecondary: band::<Instance1>::{Pallet, Call, Storage, Event<T>} = 60,
         HermesGovernancePlatform: hermes_governance_platform::{Pallet, Call, Storage, Event<T>} = 55,
         Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>} = 56,
         OrderBook: order_book::{Pallet, Call, Storage, Event<T>} = 57,
+        StableSwapPool: stable_swap_pool::{Pallet, Call, Storage, Event<T>} = 59,
 
         #[cfg(feature = "wip")] // kensetsu
         Kensetsu: kensetsu::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 58,
@@ -2614,6 +2721,17 @@ impl_runtime_apis! {
         }
     }
 
+    impl xor_fee_runtime_api::XorFeeAPI<Block, AssetId> for Runtime {
+        fn quote_fee_in_asset(
+            uxt: <Block as BlockT>::Extrinsic,
+            len: u32,
+            asset_id: AssetId,
+        ) -> Option<BalanceWrapper> {
+            let call = &uxt.function;
+            XorFee::query_fee_in_asset(&uxt, call, len, &asset_id).map(|amount| amount.into())
+        }
+    }
+
     impl dex_manager_runtime_api::DEXManagerAPI<Block, DEXId> for Runtime {
         fn list_dex_ids() -> Vec<DEXId> {
             DEXManager::list_dex_ids()
@@ -2773,6 +2891,14 @@ impl_runtime_apis! {
         fn get_asset_content_src(asset_id: AssetId) -> Option<ContentSource> {
             Assets::get_asset_content_src(&asset_id)
         }
+
+        fn allowance(asset_id: AssetId, owner: AccountId, spender: AccountId) -> Option<assets_runtime_api::BalanceInfo<Balance>> {
+            Assets::spendable_allowance(&asset_id, &owner, &spender).map(|balance|
+                assets_runtime_api::BalanceInfo::<Balance> {
+                    balance,
+                }
+            )
+        }
     }
 
     impl
@@ -2854,6 +2980,41 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(feature = "wip")] // kensetsu
+    impl kensetsu_runtime_api::KensetsuAPI<Block, AccountId, AssetId> for Runtime {
+        fn get_cdp_info(
+            cdp_id: u128,
+        ) -> Result<Option<kensetsu_runtime_api::CdpInfo<AccountId, AssetId>>, DispatchError> {
+            let cdp = match Kensetsu::cdp(cdp_id) {
+                Some(cdp) => cdp,
+                None => return Ok(None),
+            };
+            let collaterals = Kensetsu::cdp_collaterals(cdp_id, &cdp)
+                .into_iter()
+                .map(|(asset_id, amount)| kensetsu_runtime_api::CdpCollateral { asset_id, amount })
+                .collect();
+            Ok(Kensetsu::get_cdp_health(cdp_id)?.map(|health| kensetsu_runtime_api::CdpInfo {
+                owner: cdp.owner,
+                collateral_asset_id: cdp.collateral_asset_id,
+                collateral_amount: cdp.collateral_amount,
+                collaterals,
+                collateral_value: health.collateral_value,
+                debt: health.debt,
+                collateral_ratio: health.collateral_ratio.map(|ratio| ratio.into_inner()),
+                liquidation_price: health.liquidation_price.map(|price| price.into_inner()),
+                max_borrow: health.max_borrow,
+            }))
+        }
+
+        fn list_owner_cdps(owner: AccountId) -> Result<Vec<u128>, DispatchError> {
+            Kensetsu::get_account_cdp_ids(&owner)
+        }
+
+        fn list_unsafe_cdps(collateral_asset_id: AssetId) -> Result<Vec<u128>, DispatchError> {
+            Ok(Kensetsu::get_unsafe_cdp_ids(&collateral_asset_id))
+        }
+    }
+
     impl liquidity_proxy_runtime_api::LiquidityProxyAPI<
         Block,
         DEXId,
@@ -2884,18 +3045,29 @@ impl_runtime_apis! {
                 LiquiditySourceFilter::with_mode(dex_id, filter_mode, selected_source_types),
                 false,
                 true,
-            ).ok().map(|(quote_info, _)| liquidity_proxy_runtime_api::SwapOutcomeInfo::<Balance, AssetId> {
-                amount: quote_info.outcome.amount,
-                amount_without_impact: quote_info.amount_without_impact.unwrap_or(0),
-                fee: quote_info.outcome.fee,
-                rewards: quote_info.rewards.into_iter()
-                                .map(|(amount, currency, reason)| liquidity_proxy_runtime_api::RewardsInfo::<Balance, AssetId> {
-                                    amount,
-                                    currency,
-                                    reason
-                                }).collect(),
-                route: quote_info.path
-                })
+            ).ok().map(|(quote_info, _)| {
+                let hops = quote_info.path.windows(2)
+                    .zip(quote_info.path_amounts.windows(2))
+                    .map(|(assets, amounts)| liquidity_proxy_runtime_api::SwapHopInfo::<Balance, AssetId> {
+                        input_asset_id: assets[0],
+                        output_asset_id: assets[1],
+                        input_amount: amounts[0],
+                        output_amount: amounts[1],
+                    }).collect();
+                liquidity_proxy_runtime_api::SwapOutcomeInfo::<Balance, AssetId> {
+                    amount: quote_info.outcome.amount,
+                    amount_without_impact: quote_info.amount_without_impact.unwrap_or(0),
+                    fee: quote_info.outcome.fee,
+                    rewards: quote_info.rewards.into_iter()
+                                    .map(|(amount, currency, reason)| liquidity_proxy_runtime_api::RewardsInfo::<Balance, AssetId> {
+                                        amount,
+                                        currency,
+                                        reason
+                                    }).collect(),
+                    route: quote_info.path,
+                    hops,
+                }
+            })
         }
 
         fn is_path_available(
@@ -2931,7 +3103,8 @@ impl_runtime_apis! {
             match rate_wrapped {
                 Ok(rate) => Ok(rate.map(|rate| oracle_proxy_runtime_api::RateInfo{
                     value: rate.value,
-                    last_updated: rate.last_updated
+                    last_updated: rate.last_updated,
+                    sources: rate.sources,
                 })),
                 Err(e) => Err(e)
             }
@@ -2944,6 +3117,134 @@ impl_runtime_apis! {
         }
     }
 
+    impl order_book_runtime_api::OrderBookAPI<
+        Block,
+        order_book::OrderBookId<AssetId, DEXId>,
+        AccountId,
+        u128,
+        Moment,
+        BlockNumber,
+    > for Runtime {
+        fn order_book_depth(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+            depth: u32,
+        ) -> Option<order_book_runtime_api::OrderBookDepthInfo> {
+            let into_level = |(price, volume): (order_book::OrderPrice, order_book::OrderVolume)| {
+                order_book_runtime_api::PriceLevelInfo {
+                    price,
+                    volume,
+                }
+            };
+            OrderBook::order_book_depth(&order_book_id, depth as usize).map(|(bids, asks)| {
+                order_book_runtime_api::OrderBookDepthInfo {
+                    bids: bids.into_iter().map(into_level).collect(),
+                    asks: asks.into_iter().map(into_level).collect(),
+                }
+            })
+        }
+
+        fn best_prices(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+        ) -> Option<order_book_runtime_api::BestPricesInfo> {
+            let into_level = |(price, volume): (order_book::OrderPrice, order_book::OrderVolume)| {
+                order_book_runtime_api::PriceLevelInfo {
+                    price,
+                    volume,
+                }
+            };
+            OrderBook::best_prices(&order_book_id).map(|(best_bid, best_ask)| {
+                order_book_runtime_api::BestPricesInfo {
+                    best_bid: best_bid.map(into_level),
+                    best_ask: best_ask.map(into_level),
+                }
+            })
+        }
+
+        fn user_limit_orders(
+            account_id: AccountId,
+        ) -> Vec<order_book_runtime_api::LimitOrderInfo<order_book::OrderBookId<AssetId, DEXId>, u128, Moment, BlockNumber>> {
+            OrderBook::all_user_limit_orders(&account_id)
+                .into_iter()
+                .map(|(order_book_id, order)| order_book_runtime_api::LimitOrderInfo {
+                    order_book_id,
+                    id: order.id,
+                    side: order.side,
+                    price: order.price,
+                    original_amount: order.original_amount,
+                    amount: order.amount,
+                    time: order.time,
+                    lifespan: order.lifespan,
+                    expires_at: order.expires_at,
+                })
+                .collect()
+        }
+
+        fn quote_market_order(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+            direction: common::PriceVariant,
+            amount: BalanceWrapper,
+        ) -> Result<order_book_runtime_api::MarketOrderQuoteInfo, DispatchError> {
+            OrderBook::quote_market_order(&order_book_id, direction, amount.0)
+                .map(|deal| order_book_runtime_api::MarketOrderQuoteInfo {
+                    input_amount: *deal.input_amount.value(),
+                    output_amount: *deal.output_amount.value(),
+                    average_price: deal.average_price,
+                })
+        }
+
+        fn last_trade_price(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+        ) -> Option<common::prelude::BalanceUnit> {
+            OrderBook::last_trade_price(order_book_id)
+        }
+
+        fn vwap(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+            period: Moment,
+        ) -> Option<common::prelude::BalanceUnit> {
+            OrderBook::vwap(&order_book_id, period)
+        }
+
+        fn candles(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+            interval: order_book_runtime_api::CandleIntervalInfo,
+            limit: u32,
+        ) -> Vec<order_book_runtime_api::CandleInfo<Moment>> {
+            let interval = match interval {
+                order_book_runtime_api::CandleIntervalInfo::Minute => order_book::CandleInterval::Minute,
+                order_book_runtime_api::CandleIntervalInfo::Hour => order_book::CandleInterval::Hour,
+                order_book_runtime_api::CandleIntervalInfo::Day => order_book::CandleInterval::Day,
+            };
+            OrderBook::candles(&order_book_id, interval, limit)
+                .into_iter()
+                .map(|candle| order_book_runtime_api::CandleInfo {
+                    start: candle.start,
+                    open: candle.open,
+                    high: candle.high,
+                    low: candle.low,
+                    close: candle.close,
+                    volume: candle.volume,
+                    quote_volume: candle.quote_volume,
+                })
+                .collect()
+        }
+
+        fn recent_trades(
+            order_book_id: order_book::OrderBookId<AssetId, DEXId>,
+            limit: u32,
+        ) -> Vec<order_book_runtime_api::TradeInfo<Moment>> {
+            OrderBook::recent_trades(&order_book_id, limit)
+                .into_iter()
+                .map(|trade| order_book_runtime_api::TradeInfo {
+                    price: trade.price,
+                    amount: trade.amount,
+                    direction: trade.direction,
+                    time: trade.time,
+                })
+                .collect()
+        }
+    }
+
     impl pswap_distribution_runtime_api::PswapDistributionAPI<
         Block,
         AccountId,
@@ -3206,6 +3507,7 @@ impl_runtime_apis! {
             list_benchmark!(list, extra, xst, XSTPoolBench::<Runtime>);
             list_benchmark!(list, extra, oracle_proxy, OracleProxy);
             list_benchmark!(list, extra, order_book, OrderBookBench::<Runtime>);
+            list_benchmark!(list, extra, stable_swap_pool, StableSwapPool);
 
             // Trustless bridge
             #[cfg(feature = "wip")] // EVM bridge
@@ -3308,6 +3610,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, hermes_governance_platform, HermesGovernancePlatform);
             add_benchmark!(params, batches, oracle_proxy, OracleProxy);
             add_benchmark!(params, batches, order_book, OrderBookBench::<Runtime>);
+            add_benchmark!(params, batches, stable_swap_pool, StableSwapPool);
 
             // Trustless bridge
             #[cfg(feature = "wip")] // EVM bridge
@@ -3364,6 +3667,61 @@ impl_runtime_apis! {
         }
     }
 
+    impl pool_xyk_runtime_api::PoolXykApi<Block, AssetId, DEXId, Moment> for Runtime {
+        fn get_twap(
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            period: Moment,
+        ) -> Result<Balance, DispatchError> {
+            <PoolXYK as common::GetTwap<AssetId, Moment>>::get_twap(
+                &input_asset_id,
+                &output_asset_id,
+                period,
+            )
+        }
+
+        fn get_pool_fee(
+            dex_id: DEXId,
+            asset_a: AssetId,
+            asset_b: AssetId,
+        ) -> Option<pool_xyk_runtime_api::PoolFeeInfo> {
+            PoolXYK::get_pool_fee(&dex_id, &asset_a, &asset_b).map(|(fee, protocol_fee_share)| {
+                pool_xyk_runtime_api::PoolFeeInfo {
+                    fee,
+                    protocol_fee_share,
+                }
+            })
+        }
+    }
+
+    impl price_tools_runtime_api::PriceToolsAPI<Block, AssetId> for Runtime {
+        fn get_average_price(
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            price_variant: common::PriceVariant,
+        ) -> Option<BalanceWrapper> {
+            PriceTools::get_average_price(&input_asset_id, &output_asset_id, price_variant)
+                .ok()
+                .map(|price| price.into())
+        }
+
+        fn get_price_info(asset_id: AssetId) -> Option<price_tools_runtime_api::AggregatedPriceInfo> {
+            let to_price_info = |price_info: price_tools::PriceInfo| price_tools_runtime_api::PriceInfo {
+                average_price: price_info.average_price(),
+                last_spot_price: price_info.last_spot_price(),
+                spot_prices_count: price_info.spot_prices_count(),
+                price_failures: price_info.price_failures(),
+                needs_update: price_info.needs_update(),
+            };
+            PriceTools::price_infos(asset_id).map(|agg_price_info| {
+                price_tools_runtime_api::AggregatedPriceInfo {
+                    buy: to_price_info(agg_price_info.clone().price_of(common::PriceVariant::Buy)),
+                    sell: to_price_info(agg_price_info.price_of(common::PriceVariant::Sell)),
+                }
+            })
+        }
+    }
+
     #[cfg(feature = "try-runtime")]
     impl frame_try_runtime::TryRuntime<Block> for Runtime {
         fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
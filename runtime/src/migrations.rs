@@ -28,4 +28,12 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-pub type Migrations = ();
+#[cfg(not(feature = "wip"))]
+pub type Migrations = (price_tools::migration::MigrateToV3<crate::Runtime>,);
+
+#[cfg(feature = "wip")] // kensetsu
+pub type Migrations = (
+    price_tools::migration::MigrateToV3<crate::Runtime>,
+    kensetsu::migrations::v1::MigrateToV1<crate::Runtime>,
+    kensetsu::migrations::v2::MigrateToV2<crate::Runtime>,
+);
@@ -46,7 +46,12 @@ impl RuntimeCall {
             }
             Self::LiquidityProxy(liquidity_proxy::Call::swap { .. })
             | Self::LiquidityProxy(liquidity_proxy::Call::swap_transfer { .. })
-            | Self::LiquidityProxy(liquidity_proxy::Call::swap_transfer_batch { .. }) => 1,
+            | Self::LiquidityProxy(liquidity_proxy::Call::swap_transfer_batch { .. })
+            | Self::LiquidityProxy(liquidity_proxy::Call::swap_transfer_with_options { .. })
+            | Self::LiquidityProxy(liquidity_proxy::Call::swap_transfer_batch_with_options {
+                ..
+            }) => 1,
+            Self::PoolXYK(pool_xyk::Call::flash_swap { call, .. }) => 1 + call.swap_count(),
             _ => 0,
         }
     }
@@ -98,7 +103,10 @@ impl CustomFees {
             RuntimeCall::LiquidityProxy(liquidity_proxy::Call::swap_transfer_batch {
                 swap_batches,
                 ..
-            }) => Some(
+            })
+            | RuntimeCall::LiquidityProxy(
+                liquidity_proxy::Call::swap_transfer_batch_with_options { swap_batches, .. },
+            ) => Some(
                 swap_batches
                     .iter()
                     .map(|x| x.receivers.len() as Balance)
@@ -125,6 +133,7 @@ impl CustomFees {
             | RuntimeCall::Staking(pallet_staking::Call::payout_stakers { .. })
             | RuntimeCall::TradingPair(..)
             | RuntimeCall::Band(..)
+            | RuntimeCall::BandSecondary(..)
             | RuntimeCall::Referrals(..)
             | RuntimeCall::OrderBook(..) => Some(SMALL_FEE),
             _ => None,
@@ -137,7 +146,7 @@ pub enum CustomFeeDetails {
     /// Regular call with custom fee without any additional logic
     Regular(Balance),
 
-    /// OrderBook::place_limit_order custom fee depends on limit order lifetime
+    /// OrderBook::place_limit_order & OrderBook::place_limit_order_with_time_in_force custom fee depends on limit order lifetime
     LimitOrderLifetime(Option<Moment>),
 }
 
@@ -151,9 +160,11 @@ impl xor_fee::ApplyCustomFees<RuntimeCall, AccountId> for CustomFees {
         let fee = Self::base_fee(call)?;
 
         let details = match call {
-            RuntimeCall::OrderBook(order_book::Call::place_limit_order { lifespan, .. }) => {
-                CustomFeeDetails::LimitOrderLifetime(*lifespan)
-            }
+            RuntimeCall::OrderBook(order_book::Call::place_limit_order { lifespan, .. })
+            | RuntimeCall::OrderBook(order_book::Call::place_limit_order_with_time_in_force {
+                lifespan,
+                ..
+            }) => CustomFeeDetails::LimitOrderLifetime(*lifespan),
             _ => CustomFeeDetails::Regular(fee),
         };
 
@@ -496,4 +507,89 @@ mod tests {
             .into(),
         );
     }
+
+    #[test]
+    fn swap_transfer_with_options_in_batch_should_fail() {
+        test_swap_in_batch(
+            liquidity_proxy::Call::swap_transfer_with_options {
+                receiver: From::from([1; 32]),
+                dex_id: 0,
+                input_asset_id: VAL,
+                output_asset_id: XOR,
+                swap_amount: common::prelude::SwapAmount::WithDesiredInput {
+                    desired_amount_in: crate::balance!(100),
+                    min_amount_out: crate::balance!(100),
+                },
+                selected_source_types: vec![],
+                filter_mode: common::FilterMode::Disabled,
+                options: Default::default(),
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn swap_transfer_batch_with_options_fee_depends_on_receivers() {
+        let receivers = vec![
+            liquidity_proxy::BatchReceiverInfo::new(From::from([1; 32]), crate::balance!(1)),
+            liquidity_proxy::BatchReceiverInfo::new(From::from([2; 32]), crate::balance!(1)),
+        ];
+        let call =
+            RuntimeCall::LiquidityProxy(liquidity_proxy::Call::swap_transfer_batch_with_options {
+                swap_batches: vec![liquidity_proxy::SwapBatchInfo {
+                    outcome_asset_id: VAL,
+                    outcome_asset_reuse: 0,
+                    dex_id: 0,
+                    receivers,
+                }],
+                input_asset_id: XOR,
+                max_input_amount: crate::balance!(100),
+                selected_source_types: vec![],
+                filter_mode: common::FilterMode::Disabled,
+                options: Default::default(),
+            });
+
+        assert_eq!(call.swap_count(), 1);
+        assert_eq!(CustomFees::base_fee(&call), Some(2 * SMALL_FEE));
+    }
+
+    #[test]
+    fn swap_in_flash_swap_should_fail() {
+        let flash_swap = |call: RuntimeCall| -> RuntimeCall {
+            pool_xyk::Call::flash_swap {
+                dex_id: 0,
+                input_asset_id: XOR,
+                output_asset_id: VAL,
+                output_amount: crate::balance!(100),
+                max_input_amount: crate::balance!(100),
+                call: Box::new(call),
+            }
+            .into()
+        };
+        let remark: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+        let swap: RuntimeCall = liquidity_proxy::Call::swap {
+            dex_id: 0,
+            input_asset_id: VAL,
+            output_asset_id: XOR,
+            swap_amount: common::prelude::SwapAmount::WithDesiredInput {
+                desired_amount_in: crate::balance!(100),
+                min_amount_out: crate::balance!(100),
+            },
+            selected_source_types: vec![],
+            filter_mode: common::FilterMode::Disabled,
+        }
+        .into();
+
+        let call = flash_swap(remark.clone());
+        assert_eq!(call.swap_count(), 1);
+        assert!(crate::BaseCallFilter::contains(&call));
+
+        let call = flash_swap(swap);
+        assert_eq!(call.swap_count(), 2);
+        assert!(!crate::BaseCallFilter::contains(&call));
+
+        let call = flash_swap(flash_swap(remark));
+        assert_eq!(call.swap_count(), 2);
+        assert!(!crate::BaseCallFilter::contains(&call));
+    }
 }